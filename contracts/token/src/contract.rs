@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    entry_point, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult,
 };
 
 use cw2::set_contract_version;
@@ -7,15 +8,20 @@ use cw20_base::contract::{create_accounts, execute as cw20_execute, query as cw2
 use cw20_base::msg::{ExecuteMsg, QueryMsg};
 use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO};
 use cw20_base::ContractError;
+use cw_storage_plus::Item;
 
 use astroport::asset::addr_validate_to_lower;
-use astroport::token::{InstantiateMsg, MigrateMsg};
+use astroport::factory::UpdateAddr;
+use astroport::token::{InstantiateMsg, MigrateMsg, TransferHookMsg, TRANSFER_HOOK_REPLY_ID};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "astroport-token";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The contract notified when tokens change owner
+const TRANSFER_HOOK: Item<Addr> = Item::new("transfer_hook");
+
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 /// Returns the default object of type [`Response`] if the operation was successful,
@@ -68,9 +74,27 @@ pub fn instantiate(
 
     TOKEN_INFO.save(deps.storage, &data)?;
 
+    if let Some(transfer_hook) = msg.transfer_hook {
+        TRANSFER_HOOK.save(
+            deps.storage,
+            &addr_validate_to_lower(deps.api, &transfer_hook)?,
+        )?;
+    }
+
     Ok(Response::default())
 }
 
+/// ## Description
+/// Executes the cw20 base message and, if a transfer hook is set, notifies it about the tokens
+/// which changed owner, were minted or were burned.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is the object of type [`ExecuteMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -78,7 +102,119 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    cw20_execute(deps, env, info, msg)
+    let notification = match TRANSFER_HOOK.may_load(deps.storage)? {
+        Some(hook) => hook_notification(deps.as_ref(), &info, &msg, &hook)?
+            .map(|notification| (hook, notification)),
+        None => None,
+    };
+
+    let response = cw20_execute(deps, env, info, msg)?;
+
+    match notification {
+        Some((hook, notification)) => {
+            Ok(response.add_submessage(notification.into_sub_msg(hook.to_string())?))
+        }
+        None => Ok(response),
+    }
+}
+
+/// ## Description
+/// Returns the [`TransferHookMsg`] of the message, or [`None`] if it doesn't move tokens or the
+/// hook is one of the parties, since the hook handles the operations it takes part in itself.
+/// The parties are validated, so they are compared with the hook in their normalized form.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is the object of type [`ExecuteMsg`].
+///
+/// * **hook** is the object of type [`Addr`]. The contract to notify.
+fn hook_notification(
+    deps: Deps,
+    info: &MessageInfo,
+    msg: &ExecuteMsg,
+    hook: &Addr,
+) -> StdResult<Option<TransferHookMsg>> {
+    let validate = |addr: &str| -> StdResult<String> {
+        Ok(addr_validate_to_lower(deps.api, addr)?.to_string())
+    };
+    let notification = match msg {
+        ExecuteMsg::Transfer { recipient, amount }
+        | ExecuteMsg::Send {
+            contract: recipient,
+            amount,
+            ..
+        } => TransferHookMsg::TransferNotification {
+            sender: info.sender.to_string(),
+            recipient: validate(recipient)?,
+            amount: *amount,
+        },
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        }
+        | ExecuteMsg::SendFrom {
+            owner,
+            contract: recipient,
+            amount,
+            ..
+        } => TransferHookMsg::TransferNotification {
+            sender: validate(owner)?,
+            recipient: validate(recipient)?,
+            amount: *amount,
+        },
+        ExecuteMsg::Mint { recipient, amount } => TransferHookMsg::MintNotification {
+            recipient: validate(recipient)?,
+            amount: *amount,
+        },
+        ExecuteMsg::Burn { amount } => TransferHookMsg::BurnNotification {
+            owner: info.sender.to_string(),
+            amount: *amount,
+        },
+        ExecuteMsg::BurnFrom { owner, amount } => TransferHookMsg::BurnNotification {
+            owner: validate(owner)?,
+            amount: *amount,
+        },
+        _ => return Ok(None),
+    };
+
+    let (parties, amount) = match &notification {
+        TransferHookMsg::TransferNotification {
+            sender,
+            recipient,
+            amount,
+        } => (vec![sender, recipient], amount),
+        TransferHookMsg::MintNotification { recipient, amount } => (vec![recipient], amount),
+        TransferHookMsg::BurnNotification { owner, amount } => (vec![owner], amount),
+    };
+    if amount.is_zero() || parties.iter().any(|party| *party == hook.as_str()) {
+        return Ok(None);
+    }
+    Ok(Some(notification))
+}
+
+/// ## Description
+/// Ignores the failures of transfer hook calls, so a rejecting hook doesn't revert the token
+/// operation. The failure is reported in the `transfer_hook_error` attribute.
+/// ## Params
+/// * **_deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **msg** is the object of type [`Reply`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    match msg.id {
+        TRANSFER_HOOK_REPLY_ID => {
+            Ok(Response::new().add_attribute("transfer_hook_error", msg.result.unwrap_err()))
+        }
+        _ => Err(StdError::generic_err(format!(
+            "Unknown reply id {}",
+            msg.id
+        ))),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -87,14 +223,23 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 /// ## Description
-/// Used for migration of contract. Returns the default object of type [`Response`].
+/// Used for migration of contract. Updates or removes the transfer hook if specified, e.g. after
+/// the generator moved to a new address. Returns the default object of type [`Response`].
 /// ## Params
-/// * **_deps** is the object of type [`DepsMut`].
+/// * **deps** is the object of type [`DepsMut`].
 ///
 /// * **_env** is the object of type [`Env`].
 ///
-/// * **_msg** is the object of type [`MigrateMsg`].
+/// * **msg** is the object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg.transfer_hook {
+        Some(UpdateAddr::Set(transfer_hook)) => TRANSFER_HOOK.save(
+            deps.storage,
+            &addr_validate_to_lower(deps.api, &transfer_hook)?,
+        )?,
+        Some(UpdateAddr::Remove {}) => TRANSFER_HOOK.remove(deps.storage),
+        None => {}
+    }
     Ok(Response::default())
 }
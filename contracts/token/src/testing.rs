@@ -0,0 +1,163 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{ContractResult, DepsMut, Reply, ReplyOn, SubMsg, Uint128};
+use cw20::{Cw20Coin, MinterResponse};
+use cw20_base::msg::ExecuteMsg;
+
+use astroport::factory::UpdateAddr;
+use astroport::token::{
+    InstantiateMsg, MigrateMsg, TransferHookMsg, TRANSFER_HOOK_GAS_LIMIT, TRANSFER_HOOK_REPLY_ID,
+};
+
+use crate::contract::{execute, instantiate, migrate, reply};
+
+fn instantiate_token(deps: DepsMut, transfer_hook: Option<String>) {
+    let msg = InstantiateMsg {
+        name: "Astroport LP token".to_string(),
+        symbol: "uLP".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+            address: "user0000".to_string(),
+            amount: Uint128::new(1000),
+        }],
+        mint: Some(MinterResponse {
+            minter: "pair0000".to_string(),
+            cap: None,
+        }),
+        transfer_hook,
+    };
+    instantiate(deps, mock_env(), mock_info("pair0000", &[]), msg).unwrap();
+}
+
+fn hook_sub_msg(notification: TransferHookMsg) -> SubMsg {
+    SubMsg {
+        id: TRANSFER_HOOK_REPLY_ID,
+        msg: notification
+            .into_cosmos_msg("generator0000".to_string())
+            .unwrap(),
+        gas_limit: Some(TRANSFER_HOOK_GAS_LIMIT),
+        reply_on: ReplyOn::Error,
+    }
+}
+
+#[test]
+fn transfer_hook_notifications() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), Some("generator0000".to_string()));
+
+    let transfer = ExecuteMsg::Transfer {
+        recipient: "user0001".to_string(),
+        amount: Uint128::new(100),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user0000", &[]),
+        transfer,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![hook_sub_msg(TransferHookMsg::TransferNotification {
+            sender: "user0000".to_string(),
+            recipient: "user0001".to_string(),
+            amount: Uint128::new(100),
+        })]
+    );
+
+    // transfers to the hook are handled by the hook itself
+    let deposit = ExecuteMsg::Transfer {
+        recipient: "generator0000".to_string(),
+        amount: Uint128::new(100),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user0000", &[]),
+        deposit,
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let mint = ExecuteMsg::Mint {
+        recipient: "user0001".to_string(),
+        amount: Uint128::new(50),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("pair0000", &[]), mint).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![hook_sub_msg(TransferHookMsg::MintNotification {
+            recipient: "user0001".to_string(),
+            amount: Uint128::new(50),
+        })]
+    );
+
+    let burn = ExecuteMsg::Burn {
+        amount: Uint128::new(30),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("user0001", &[]), burn).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![hook_sub_msg(TransferHookMsg::BurnNotification {
+            owner: "user0001".to_string(),
+            amount: Uint128::new(30),
+        })]
+    );
+
+    // a failing hook doesn't revert the token operation
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: TRANSFER_HOOK_REPLY_ID,
+            result: ContractResult::Err("Unknown pool".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "Unknown pool");
+}
+
+#[test]
+fn migrate_transfer_hook() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), Some("generator0000".to_string()));
+
+    let msg = MigrateMsg {
+        transfer_hook: Some(UpdateAddr::Set("generator0001".to_string())),
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    let transfer = ExecuteMsg::Transfer {
+        recipient: "user0001".to_string(),
+        amount: Uint128::new(100),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user0000", &[]),
+        transfer.clone(),
+    )
+    .unwrap();
+    let notification = TransferHookMsg::TransferNotification {
+        sender: "user0000".to_string(),
+        recipient: "user0001".to_string(),
+        amount: Uint128::new(100),
+    };
+    assert_eq!(
+        res.messages[0].msg,
+        notification
+            .into_cosmos_msg("generator0001".to_string())
+            .unwrap()
+    );
+
+    let msg = MigrateMsg {
+        transfer_hook: Some(UpdateAddr::Remove {}),
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user0000", &[]),
+        transfer,
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+}
@@ -0,0 +1,315 @@
+use crate::staking::Cw20HookMsg as StakingCw20HookMsg;
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdError, StdResult, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+
+/// The maximum number of campaigns returned by a single query
+pub const MAX_CAMPAIGNS_LIMIT: u32 = 30;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the ASTRO token contract which is airdropped
+    pub astro_token: String,
+    /// the staking contract used by claims with `claim_and_stake`
+    pub staking_contract: String,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
+    /// template.
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Claims the airdrop of the sender in a campaign. The proof is verified with
+    /// [`verify_merkle_proof`] against the merkle root of the campaign
+    Claim {
+        /// the identifier of the campaign
+        campaign_id: u64,
+        /// the airdropped amount of the sender
+        amount: Uint128,
+        /// the hex encoded hashes of the merkle proof
+        proof: Vec<String>,
+        /// whether the ASTRO is staked for the sender and delivered as xASTRO, see
+        /// [`claim_and_stake_msg`]
+        #[serde(default)]
+        claim_and_stake: bool,
+    },
+    /// ## Description
+    /// Sends the unclaimed ASTRO of an expired campaign to the recipient
+    /// ## Executor
+    /// Only owner can execute it
+    Clawback {
+        /// the identifier of the campaign
+        campaign_id: u64,
+        /// the recipient of the unclaimed ASTRO
+        recipient: String,
+    },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the staking contract used by claims with `claim_and_stake`
+        staking_contract: Option<String>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Registers a campaign funded with the received ASTRO. Only the owner can send it
+    RegisterCampaign {
+        /// the hex encoded merkle root of the (address, amount) leaves, see [`merkle_leaf`]
+        merkle_root: String,
+        /// the timestamp after which claims are rejected and the rest can be clawed back
+        expiry: u64,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns a campaign in a [`CampaignResponse`] object
+    Campaign { campaign_id: u64 },
+    /// Returns a list of campaigns in a [`CampaignsResponse`] object
+    Campaigns {
+        start_after: Option<u64>,
+        /// the number of items to be read, at most [`MAX_CAMPAIGNS_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// Returns whether the address claimed its airdrop in a campaign in a [`IsClaimedResponse`] object
+    IsClaimed { campaign_id: u64, address: String },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the ASTRO token contract which is airdropped
+    pub astro_token: Addr,
+    /// the staking contract used by claims with `claim_and_stake`
+    pub staking_contract: Addr,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignResponse {
+    /// the identifier of the campaign
+    pub id: u64,
+    /// the hex encoded merkle root of the campaign
+    pub merkle_root: String,
+    /// the timestamp after which claims are rejected
+    pub expiry: u64,
+    /// the ASTRO funding the campaign
+    pub total_amount: Uint128,
+    /// the ASTRO claimed so far
+    pub claimed_amount: Uint128,
+    /// whether the unclaimed ASTRO was clawed back
+    pub clawed_back: bool,
+}
+
+impl CampaignResponse {
+    /// ## Description
+    /// Returns an [`Err`] if the campaign expired or doesn't hold enough ASTRO for the claim.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **amount** is the claimed amount.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn assert_claimable(&self, amount: Uint128, time: u64) -> StdResult<()> {
+        if time > self.expiry || self.clawed_back {
+            return Err(StdError::generic_err(format!(
+                "Campaign {} expired at {}",
+                self.id, self.expiry
+            )));
+        }
+        if self.claimed_amount.checked_add(amount)? > self.total_amount {
+            return Err(StdError::generic_err(format!(
+                "Campaign {} doesn't hold enough ASTRO for the claim",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the unclaimed ASTRO which can be clawed back, or an [`Err`] if the campaign has not
+    /// expired yet or was already clawed back.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn clawback_amount(&self, time: u64) -> StdResult<Uint128> {
+        if time <= self.expiry {
+            return Err(StdError::generic_err(format!(
+                "Campaign {} can be clawed back after {}",
+                self.id, self.expiry
+            )));
+        }
+        if self.clawed_back {
+            return Err(StdError::generic_err(format!(
+                "Campaign {} was already clawed back",
+                self.id
+            )));
+        }
+        Ok(self.total_amount.checked_sub(self.claimed_amount)?)
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignsResponse {
+    pub campaigns: Vec<CampaignResponse>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns the merkle leaf of an airdrop, the SHA-256 hash of the address followed by the amount
+/// as 16 big-endian bytes. The fixed width amount keeps distinct address and amount pairs from
+/// hashing to the same leaf.
+/// ## Params
+/// * **address** is the recipient of the airdrop.
+///
+/// * **amount** is the airdropped amount.
+pub fn merkle_leaf(address: &str, amount: Uint128) -> [u8; 32] {
+    Sha256::new()
+        .chain(address.as_bytes())
+        .chain(amount.u128().to_be_bytes())
+        .finalize()
+        .into()
+}
+
+/// ## Description
+/// Decodes a hex encoded merkle root or proof hash. Returns an [`Err`] if it is not 32 bytes.
+/// ## Params
+/// * **hash** is the hex encoded hash.
+pub fn decode_merkle_hash(hash: &str) -> StdResult<[u8; 32]> {
+    let bytes = hex::decode(hash)
+        .map_err(|_| StdError::generic_err(format!("Invalid hex hash {}", hash)))?;
+    bytes
+        .try_into()
+        .map_err(|_| StdError::generic_err(format!("Hash {} must be 32 bytes", hash)))
+}
+
+/// ## Description
+/// Returns an [`Err`] if the proof doesn't lead from the leaf of the airdrop to the merkle root.
+/// The hashes of each level are hashed in ascending byte order, so the proof doesn't encode the
+/// position of the leaf.
+/// ## Params
+/// * **merkle_root** is the hex encoded merkle root of the campaign.
+///
+/// * **address** is the recipient of the airdrop.
+///
+/// * **amount** is the airdropped amount.
+///
+/// * **proof** are the hex encoded hashes of the merkle proof.
+pub fn verify_merkle_proof(
+    merkle_root: &str,
+    address: &str,
+    amount: Uint128,
+    proof: &[String],
+) -> StdResult<()> {
+    let root = decode_merkle_hash(merkle_root)?;
+    let mut hash = merkle_leaf(address, amount);
+    for sibling in proof {
+        let sibling = decode_merkle_hash(sibling)?;
+        let (first, second) = if hash <= sibling {
+            (hash, sibling)
+        } else {
+            (sibling, hash)
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(first);
+        hasher.update(second);
+        hash = hasher.finalize().into();
+    }
+    if hash != root {
+        return Err(StdError::generic_err("Invalid merkle proof"));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the message which stakes the claimed ASTRO with [`StakingCw20HookMsg::Enter`] and
+/// mints the xASTRO to the recipient.
+/// ## Params
+/// * **astro_token** is the ASTRO token contract.
+///
+/// * **staking_contract** is the staking contract.
+///
+/// * **recipient** is the claimer who receives the xASTRO.
+///
+/// * **amount** is the claimed amount.
+pub fn claim_and_stake_msg(
+    astro_token: &Addr,
+    staking_contract: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: astro_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: staking_contract.to_string(),
+            amount,
+            msg: to_binary(&StakingCw20HookMsg::Enter {
+                lock_duration: None,
+                receiver: Some(recipient.to_string()),
+            })?,
+        })?,
+        funds: vec![],
+    }))
+}
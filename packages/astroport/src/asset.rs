@@ -1,14 +1,19 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
 
+use crate::chain::NATIVE_TAX_ENABLED;
 use crate::factory::PairType;
-use crate::querier::{query_balance, query_token_balance, query_token_symbol};
+use crate::querier::{
+    query_balance, query_token_balance, query_token_symbol, query_transfer_allowed,
+};
 use cosmwasm_std::{
     to_binary, Addr, Api, BankMsg, Coin, CosmosMsg, Decimal, MessageInfo, QuerierWrapper, StdError,
     StdResult, Uint128, WasmMsg,
 };
-use cw20::Cw20ExecuteMsg;
+use cw20::{Cw20CoinVerified, Cw20ExecuteMsg};
 use terra_cosmwasm::TerraQuerier;
 
 /// ## Description
@@ -21,16 +26,192 @@ pub struct Asset {
     pub amount: Uint128,
 }
 
+/// Formats the amount followed by the bare denom or contract address, which is not parsed back
+/// by [`FromStr`]. The parseable form is [`Asset::to_canonical_string`].
 impl fmt::Display for Asset {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}{}", self.amount, self.info)
     }
 }
 
+impl FromStr for Asset {
+    type Err = StdError;
+
+    /// ## Description
+    /// Parses an asset from its canonical string form, e.g. `1000native:uusd` or `1000cw20:terra1...`.
+    /// See [`Asset::to_canonical_string`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split_at = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        if split_at == 0 {
+            return Err(StdError::parse_err(
+                "Asset",
+                format!("Asset {} should start with an amount", s),
+            ));
+        }
+
+        let (amount, info) = s.split_at(split_at);
+        Ok(Asset {
+            info: info.parse()?,
+            amount: Uint128::from_str(amount)?,
+        })
+    }
+}
+
+impl From<Coin> for Asset {
+    fn from(coin: Coin) -> Self {
+        Asset {
+            info: AssetInfo::NativeToken { denom: coin.denom },
+            amount: coin.amount,
+        }
+    }
+}
+
+impl From<&Coin> for Asset {
+    fn from(coin: &Coin) -> Self {
+        coin.clone().into()
+    }
+}
+
+impl From<Cw20CoinVerified> for Asset {
+    fn from(coin: Cw20CoinVerified) -> Self {
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: coin.address,
+            },
+            amount: coin.amount,
+        }
+    }
+}
+
+impl TryFrom<Asset> for Coin {
+    type Error = StdError;
+
+    /// ## Description
+    /// Converts a native asset into a [`Coin`]. Returns an [`Err`] for CW20 tokens.
+    fn try_from(asset: Asset) -> Result<Self, Self::Error> {
+        match asset.info {
+            AssetInfo::NativeToken { denom } => Ok(Coin {
+                denom,
+                amount: asset.amount,
+            }),
+            AssetInfo::Token { contract_addr } => Err(StdError::generic_err(format!(
+                "Asset {} is not a native coin",
+                contract_addr
+            ))),
+        }
+    }
+}
+
+impl TryFrom<&Asset> for Coin {
+    type Error = StdError;
+
+    fn try_from(asset: &Asset) -> Result<Self, Self::Error> {
+        Coin::try_from(asset.clone())
+    }
+}
+
+impl TryFrom<Asset> for Cw20CoinVerified {
+    type Error = StdError;
+
+    /// ## Description
+    /// Converts a CW20 asset into a [`Cw20CoinVerified`]. Returns an [`Err`] for native coins.
+    fn try_from(asset: Asset) -> Result<Self, Self::Error> {
+        match asset.info {
+            AssetInfo::Token { contract_addr } => Ok(Cw20CoinVerified {
+                address: contract_addr,
+                amount: asset.amount,
+            }),
+            AssetInfo::NativeToken { denom } => Err(StdError::generic_err(format!(
+                "Asset {} is not a CW20 token",
+                denom
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "cw-asset")]
+impl From<cw_asset::AssetInfo> for AssetInfo {
+    fn from(info: cw_asset::AssetInfo) -> Self {
+        match info {
+            cw_asset::AssetInfo::Cw20(contract_addr) => AssetInfo::Token { contract_addr },
+            cw_asset::AssetInfo::Native(denom) => AssetInfo::NativeToken { denom },
+        }
+    }
+}
+
+#[cfg(feature = "cw-asset")]
+impl From<AssetInfo> for cw_asset::AssetInfo {
+    fn from(info: AssetInfo) -> Self {
+        match info {
+            AssetInfo::Token { contract_addr } => cw_asset::AssetInfo::Cw20(contract_addr),
+            AssetInfo::NativeToken { denom } => cw_asset::AssetInfo::Native(denom),
+        }
+    }
+}
+
+#[cfg(feature = "cw-asset")]
+impl From<cw_asset::Asset> for Asset {
+    fn from(asset: cw_asset::Asset) -> Self {
+        Asset {
+            info: asset.info.into(),
+            amount: asset.amount,
+        }
+    }
+}
+
+#[cfg(feature = "cw-asset")]
+impl From<Asset> for cw_asset::Asset {
+    fn from(asset: Asset) -> Self {
+        cw_asset::Asset {
+            info: asset.info.into(),
+            amount: asset.amount,
+        }
+    }
+}
+
+#[cfg(feature = "proto")]
+impl TryFrom<cosmos_sdk_proto::cosmos::base::v1beta1::Coin> for Asset {
+    type Error = StdError;
+
+    /// ## Description
+    /// Converts a protobuf coin into a native asset. Returns an [`Err`] if the amount is not a
+    /// valid [`Uint128`].
+    fn try_from(coin: cosmos_sdk_proto::cosmos::base::v1beta1::Coin) -> Result<Self, Self::Error> {
+        Ok(Asset {
+            amount: Uint128::from_str(&coin.amount)?,
+            info: AssetInfo::NativeToken { denom: coin.denom },
+        })
+    }
+}
+
+#[cfg(feature = "proto")]
+impl TryFrom<Asset> for cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+    type Error = StdError;
+
+    /// ## Description
+    /// Converts a native asset into a protobuf coin. Returns an [`Err`] for CW20 tokens.
+    fn try_from(asset: Asset) -> Result<Self, Self::Error> {
+        let coin = Coin::try_from(asset)?;
+        Ok(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        })
+    }
+}
+
 /// the decimal fraction
 static DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
 
 impl Asset {
+    /// ## Description
+    /// Returns the canonical string form of the asset, e.g. `1000native:uusd`, which can be parsed
+    /// back with [`FromStr`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn to_canonical_string(&self) -> String {
+        format!("{}{}", self.amount, self.info.to_canonical_string())
+    }
+
     /// ## Description
     /// Returns true if token is native token. Otherwise returns false.
     /// ## Params
@@ -40,12 +221,16 @@ impl Asset {
     }
 
     /// ## Description
-    /// Calculates and returns computed tax for native token. For other tokens returns zero.
+    /// Calculates and returns computed tax for native token. For other tokens, or on chains without
+    /// a native tax (see [`NATIVE_TAX_ENABLED`]), returns zero.
     /// ## Params
     /// * **self** is the type of the caller object.
     ///
     /// * **querier** is the object of type [`QuerierWrapper`]
     pub fn compute_tax(&self, querier: &QuerierWrapper) -> StdResult<Uint128> {
+        if !NATIVE_TAX_ENABLED {
+            return Ok(Uint128::zero());
+        }
         let amount = self.amount;
         if let AssetInfo::NativeToken { denom } = &self.info {
             let terra_querier = TerraQuerier::new(querier);
@@ -113,6 +298,44 @@ impl Asset {
         }
     }
 
+    /// ## Description
+    /// Returns a message of type [`CosmosMsg`] like [`Asset::into_msg`], but probes the transfer
+    /// restriction registry first if the denom is restricted (see [`is_restricted_denom`]).
+    /// Returns an [`Err`] naming the denom and the reason if the registry rejects the transfer.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **querier** is the object of type [`QuerierWrapper`]
+    ///
+    /// * **registry** is an [`Option`] field with the transfer restriction registry contract.
+    ///
+    /// * **sender** is the address the funds are sent from.
+    ///
+    /// * **recipient** is the address where the funds will be sent.
+    pub fn into_checked_msg(
+        self,
+        querier: &QuerierWrapper,
+        registry: Option<Addr>,
+        sender: Addr,
+        recipient: Addr,
+    ) -> StdResult<CosmosMsg> {
+        if let (AssetInfo::NativeToken { denom }, Some(registry)) = (&self.info, registry) {
+            if is_restricted_denom(denom) {
+                let res = query_transfer_allowed(
+                    querier,
+                    registry,
+                    denom.clone(),
+                    sender,
+                    recipient.clone(),
+                    self.amount,
+                )?;
+                res.assert_allowed(denom)?;
+            }
+        }
+
+        self.into_msg(querier, recipient)
+    }
+
     /// ## Description
     /// Approves the amount of native tokens. Returns [`Ok`] if successful, otherwise returns [`Err`].
     /// ## Params
@@ -161,6 +384,13 @@ pub enum AssetInfo {
     NativeToken { denom: String },
 }
 
+/// the prefix of the canonical string form of a native token
+const NATIVE_TOKEN_PREFIX: &str = "native:";
+/// the prefix of the canonical string form of a CW20 token
+const TOKEN_PREFIX: &str = "cw20:";
+
+/// Formats the bare denom or contract address, which is used for storage keys and is not parsed
+/// back by [`FromStr`]. The parseable form is [`AssetInfo::to_canonical_string`].
 impl fmt::Display for AssetInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -170,7 +400,96 @@ impl fmt::Display for AssetInfo {
     }
 }
 
+impl FromStr for AssetInfo {
+    type Err = StdError;
+
+    /// ## Description
+    /// Parses an asset info from its canonical string form, e.g. `native:uusd` or `cw20:terra1...`.
+    /// See [`AssetInfo::to_canonical_string`]. The address of a token is not validated, use [`AssetInfo::check`] for that.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(denom) = s.strip_prefix(NATIVE_TOKEN_PREFIX) {
+            if !denom.is_empty() {
+                return Ok(AssetInfo::NativeToken {
+                    denom: denom.to_string(),
+                });
+            }
+        } else if let Some(contract_addr) = s.strip_prefix(TOKEN_PREFIX) {
+            if !contract_addr.is_empty() {
+                return Ok(AssetInfo::Token {
+                    contract_addr: Addr::unchecked(contract_addr),
+                });
+            }
+        }
+
+        Err(StdError::parse_err(
+            "AssetInfo",
+            format!(
+                "Asset info {} should be in the form {}<denom> or {}<contract_addr>",
+                s, NATIVE_TOKEN_PREFIX, TOKEN_PREFIX
+            ),
+        ))
+    }
+}
+
+/// ## Description
+/// This trait describes types which have a canonical string form.
+pub trait CanonicalString: FromStr<Err = StdError> {
+    fn to_canonical_string(&self) -> String;
+}
+
+impl CanonicalString for Asset {
+    fn to_canonical_string(&self) -> String {
+        Asset::to_canonical_string(self)
+    }
+}
+
+impl CanonicalString for AssetInfo {
+    fn to_canonical_string(&self) -> String {
+        AssetInfo::to_canonical_string(self)
+    }
+}
+
+/// ## Description
+/// Serde helpers for message fields which accept an [`Asset`] or [`AssetInfo`] in the canonical
+/// string form. Use them with `#[serde(with = "astroport::asset::string_form")]` and
+/// `#[schemars(with = "String")]`.
+pub mod string_form {
+    use super::CanonicalString;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: CanonicalString,
+        S: Serializer,
+    {
+        serializer.serialize_str(&value.to_canonical_string())
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: CanonicalString,
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        T::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
 impl AssetInfo {
+    /// ## Description
+    /// Returns the canonical string form of the asset info, e.g. `native:uusd` or `cw20:terra1...`,
+    /// which can be parsed back with [`FromStr`]. Unlike [`fmt::Display`] the form is unambiguous,
+    /// whereas [`fmt::Display`] keeps the bare denom or address because it is used for storage keys.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn to_canonical_string(&self) -> String {
+        match self {
+            AssetInfo::NativeToken { denom } => format!("{}{}", NATIVE_TOKEN_PREFIX, denom),
+            AssetInfo::Token { contract_addr } => format!("{}{}", TOKEN_PREFIX, contract_addr),
+        }
+    }
+
     /// ## Description
     /// Returns true if the caller is a native token. Otherwise returns false.
     /// ## Params
@@ -240,6 +559,7 @@ impl AssetInfo {
 
     /// ## Description
     /// Returns [`Ok`] if the token of type [`AssetInfo`] is in lowercase and valid. Otherwise returns [`Err`].
+    /// Native denoms are validated with [`validate_native_denom`].
     /// ## Params
     /// * **self** is the type of the caller object.
     ///
@@ -249,14 +569,7 @@ impl AssetInfo {
             AssetInfo::Token { contract_addr } => {
                 addr_validate_to_lower(api, contract_addr.as_str())?;
             }
-            AssetInfo::NativeToken { denom } => {
-                if denom != &denom.to_lowercase() {
-                    return Err(StdError::generic_err(format!(
-                        "Native token denom {} should be lowercase",
-                        denom
-                    )));
-                }
-            }
+            AssetInfo::NativeToken { denom } => validate_native_denom(api, denom)?,
         }
         Ok(())
     }
@@ -274,9 +587,90 @@ pub struct PairInfo {
     pub liquidity_token: Addr,
     /// the type of pair available in [`PairType`]
     pub pair_type: PairType,
+    /// the features supported by the pair, e.g. [`CAPABILITY_SWAP_EXACT_OUT`]
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// the project-provided labels of the pair, see [`PairMetadata`]
+    #[serde(default)]
+    pub metadata: Option<PairMetadata>,
+    /// whether the pair was marked as official by governance
+    #[serde(default)]
+    pub official: bool,
+}
+
+/// The maximum length of the pair metadata name
+pub const PAIR_NAME_MAX_LENGTH: usize = 64;
+/// The maximum length of the pair metadata description
+pub const PAIR_DESCRIPTION_MAX_LENGTH: usize = 512;
+/// The maximum length of the pair metadata url
+pub const PAIR_URL_MAX_LENGTH: usize = 256;
+
+/// ## Description
+/// This structure describes the optional labels which the creator of a pair can attach to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PairMetadata {
+    /// the display name of the pair
+    pub name: Option<String>,
+    /// the description of the project behind the pair
+    pub description: Option<String>,
+    /// the project website, must start with `https://`
+    pub url: Option<String>,
+}
+
+impl PairMetadata {
+    /// ## Description
+    /// Returns an [`Err`] if any of the fields is empty or too long, or if the url is not a https url.
+    /// ## Params
+    /// * **self** is the type of the caller object
+    pub fn validate(&self) -> StdResult<()> {
+        let fields = [
+            ("name", &self.name, PAIR_NAME_MAX_LENGTH),
+            (
+                "description",
+                &self.description,
+                PAIR_DESCRIPTION_MAX_LENGTH,
+            ),
+            ("url", &self.url, PAIR_URL_MAX_LENGTH),
+        ];
+        for (field, value, max_length) in fields.iter() {
+            if let Some(value) = value {
+                if value.trim().is_empty() || value.len() > *max_length {
+                    return Err(StdError::generic_err(format!(
+                        "Pair metadata {} must be between 1 and {} characters",
+                        field, max_length
+                    )));
+                }
+            }
+        }
+        if let Some(url) = &self.url {
+            if !url.starts_with("https://") || url.chars().any(char::is_whitespace) {
+                return Err(StdError::generic_err(
+                    "Pair metadata url must be a https url",
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
+/// The pair supports swaps specifying the exact ask amount
+pub const CAPABILITY_SWAP_EXACT_OUT: &str = "swap_exact_out";
+/// The pair supports flash loans
+pub const CAPABILITY_FLASH_LOAN: &str = "flash_loan";
+/// The pair supports providing liquidity with a single asset
+pub const CAPABILITY_SINGLE_SIDED: &str = "single_sided";
+
 impl PairInfo {
+    /// ## Description
+    /// Returns true if the pair supports the specified capability.
+    /// ## Params
+    /// * **self** is the type of the caller object
+    ///
+    /// * **capability** is the name of the capability, e.g. [`CAPABILITY_FLASH_LOAN`].
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+
     /// ## Description
     /// Returns balance for each asset in the pool.
     /// ## Params
@@ -319,7 +713,58 @@ pub fn addr_validate_to_lower(api: &dyn Api, addr: &str) -> StdResult<Addr> {
     api.addr_validate(addr)
 }
 
+/// ## Description
+/// Validates an address with [`addr_validate_to_lower`] and names the field in the error, so a
+/// failing instantiate or execute message points to the malformed input.
+/// ## Params
+/// * **api** is a object of type [`Api`]
+///
+/// * **field** is the name of the validated field.
+///
+/// * **addr** is the address to validate.
+pub fn addr_field_validate(api: &dyn Api, field: &str, addr: &str) -> StdResult<Addr> {
+    addr_validate_to_lower(api, addr)
+        .map_err(|e| StdError::generic_err(format!("Invalid {}: {}", field, e)))
+}
+
+/// ## Description
+/// Validates an optional address with [`addr_field_validate`].
+/// ## Params
+/// * **api** is a object of type [`Api`]
+///
+/// * **field** is the name of the validated field.
+///
+/// * **addr** is the optional address to validate.
+pub fn addr_opt_validate(
+    api: &dyn Api,
+    field: &str,
+    addr: &Option<String>,
+) -> StdResult<Option<Addr>> {
+    addr.as_ref()
+        .map(|addr| addr_field_validate(api, field, addr))
+        .transpose()
+}
+
+/// ## Description
+/// Validates a list of addresses with [`addr_field_validate`]. The error names the index of the
+/// first malformed address.
+/// ## Params
+/// * **api** is a object of type [`Api`]
+///
+/// * **field** is the name of the validated field.
+///
+/// * **addrs** are the addresses to validate.
+pub fn validate_addresses(api: &dyn Api, field: &str, addrs: &[String]) -> StdResult<Vec<Addr>> {
+    addrs
+        .iter()
+        .enumerate()
+        .map(|(i, addr)| addr_field_validate(api, &format!("{}[{}]", field, i), addr))
+        .collect()
+}
+
 const TOKEN_SYMBOL_MAX_LENGTH: usize = 4;
+/// The symbol used for LP tokens whose assets give no usable symbol
+const DEFAULT_LP_TOKEN_SYMBOL: &str = "uLP";
 
 /// ## Description
 /// Returns formatted liquidity token name
@@ -333,21 +778,207 @@ pub fn format_lp_token_name(
 ) -> StdResult<String> {
     let mut short_symbols: Vec<String> = vec![];
     for asset_info in asset_infos {
-        let short_symbol: String;
-        match asset_info {
+        let short_symbol: String = match asset_info {
             AssetInfo::NativeToken { denom } => {
-                short_symbol = denom.chars().take(TOKEN_SYMBOL_MAX_LENGTH).collect();
+                denom.chars().take(TOKEN_SYMBOL_MAX_LENGTH).collect()
             }
             AssetInfo::Token { contract_addr } => {
                 let token_symbol = query_token_symbol(querier, contract_addr)?;
-                short_symbol = token_symbol.chars().take(TOKEN_SYMBOL_MAX_LENGTH).collect();
+                token_symbol.chars().take(TOKEN_SYMBOL_MAX_LENGTH).collect()
             }
-        }
+        };
         short_symbols.push(short_symbol);
     }
     Ok(format!("{}-{}-LP", short_symbols[0], short_symbols[1]).to_uppercase())
 }
 
+/// ## Description
+/// Returns the liquidity token symbol derived from the asset symbols and the pair type, e.g.
+/// `MAPPUUSDLP` for an xyk pair or `MAPPUUSDSLP` for a stable pair. Only letters of the asset
+/// symbols are kept, so the symbol always satisfies the CW20 restrictions.
+/// ## Params
+/// * **asset_infos** is array with two items the type of [`AssetInfo`].
+///
+/// * **pair_type** is the type of pair available in [`PairType`].
+///
+/// * **querier** is the object of type [`QuerierWrapper`].
+pub fn format_lp_token_symbol(
+    asset_infos: [AssetInfo; 2],
+    pair_type: &PairType,
+    querier: &QuerierWrapper,
+) -> StdResult<String> {
+    let mut symbol = String::new();
+    for asset_info in asset_infos {
+        let asset_symbol = match asset_info {
+            AssetInfo::NativeToken { denom } => denom,
+            AssetInfo::Token { contract_addr } => query_token_symbol(querier, contract_addr)?,
+        };
+        symbol.extend(
+            asset_symbol
+                .chars()
+                .filter(|c| c.is_ascii_alphabetic())
+                .take(TOKEN_SYMBOL_MAX_LENGTH),
+        );
+    }
+    if symbol.is_empty() {
+        return Ok(DEFAULT_LP_TOKEN_SYMBOL.to_string());
+    }
+
+    let suffix = match pair_type {
+        PairType::Xyk {} => "LP",
+        PairType::Stable {} => "SLP",
+        PairType::Custom(_) => "CLP",
+    };
+    // at least one letter and a two letter suffix meet the minimum CW20 symbol length
+    symbol.push_str(suffix);
+    Ok(symbol.to_uppercase())
+}
+
+/// The prefix of tokenfactory denoms which can have transfer hooks
+pub const TOKENFACTORY_DENOM_PREFIX: &str = "factory/";
+
+/// The prefix of IBC denoms
+pub const IBC_DENOM_PREFIX: &str = "ibc/";
+/// The number of hex characters of the hash of an IBC denom
+pub const IBC_DENOM_HASH_LENGTH: usize = 64;
+/// The minimum length of a native denom
+pub const NATIVE_DENOM_MIN_LENGTH: usize = 3;
+/// The maximum length of a native denom
+pub const NATIVE_DENOM_MAX_LENGTH: usize = 128;
+/// The maximum length of the subdenom of a tokenfactory denom
+pub const TOKENFACTORY_SUBDENOM_MAX_LENGTH: usize = 44;
+
+/// ## Description
+/// Returns [`Ok`] if the native denom is well formed. IBC denoms must be `ibc/` followed by an
+/// uppercase hex hash, tokenfactory denoms must be `factory/{creator}/{subdenom}` with a valid
+/// creator address, other denoms must be lowercase.
+/// ## Params
+/// * **api** is a object of type [`Api`].
+///
+/// * **denom** is the native denom.
+pub fn validate_native_denom(api: &dyn Api, denom: &str) -> StdResult<()> {
+    let invalid = |reason: &str| {
+        Err(StdError::generic_err(format!(
+            "Invalid native denom {}: {}",
+            denom, reason
+        )))
+    };
+    if denom.len() < NATIVE_DENOM_MIN_LENGTH || denom.len() > NATIVE_DENOM_MAX_LENGTH {
+        return invalid(&format!(
+            "length must be between {} and {}",
+            NATIVE_DENOM_MIN_LENGTH, NATIVE_DENOM_MAX_LENGTH
+        ));
+    }
+    if let Some(hash) = denom.strip_prefix(IBC_DENOM_PREFIX) {
+        if hash.len() != IBC_DENOM_HASH_LENGTH
+            || !hash
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+        {
+            return invalid(&format!(
+                "the hash must be {} uppercase hex characters",
+                IBC_DENOM_HASH_LENGTH
+            ));
+        }
+        return Ok(());
+    }
+    if denom != denom.to_lowercase() {
+        return Err(StdError::generic_err(format!(
+            "Native token denom {} should be lowercase",
+            denom
+        )));
+    }
+    if let Some(rest) = denom.strip_prefix(TOKENFACTORY_DENOM_PREFIX) {
+        let (creator, subdenom) = match rest.split_once('/') {
+            Some(parts) => parts,
+            None => return invalid("tokenfactory denoms must be factory/{creator}/{subdenom}"),
+        };
+        api.addr_validate(creator)?;
+        if subdenom.is_empty() || subdenom.len() > TOKENFACTORY_SUBDENOM_MAX_LENGTH {
+            return invalid(&format!(
+                "the subdenom length must be between 1 and {}",
+                TOKENFACTORY_SUBDENOM_MAX_LENGTH
+            ));
+        }
+        return Ok(());
+    }
+    let mut chars = denom.chars();
+    if !matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        || !chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+    {
+        return invalid("only letters, digits and /:._- are allowed, starting with a letter");
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the canonical form of a native denom which passes [`validate_native_denom`]: the hash
+/// of IBC denoms is uppercased, other denoms are lowercased.
+/// ## Params
+/// * **denom** is the native denom.
+pub fn normalize_native_denom(denom: &str) -> String {
+    match denom.get(..IBC_DENOM_PREFIX.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(IBC_DENOM_PREFIX) => format!(
+            "{}{}",
+            IBC_DENOM_PREFIX,
+            denom[IBC_DENOM_PREFIX.len()..].to_uppercase()
+        ),
+        _ => denom.to_lowercase(),
+    }
+}
+
+/// ## Description
+/// Returns true if transfers of the native denom can be restricted by hooks and have to be
+/// probed in the transfer restriction registry before building transfer messages.
+/// ## Params
+/// * **denom** is the native denom.
+pub fn is_restricted_denom(denom: &str) -> bool {
+    denom.starts_with(TOKENFACTORY_DENOM_PREFIX)
+}
+
+/// ## Description
+/// This structure describes the query messages of a transfer restriction registry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferRestrictionQueryMsg {
+    /// Returns whether the transfer is allowed in a [`TransferAllowedResponse`] object
+    TransferAllowed {
+        denom: String,
+        sender: String,
+        recipient: String,
+        amount: Uint128,
+    },
+}
+
+/// ## Description
+/// This structure describes the response to the transfer allowed query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferAllowedResponse {
+    /// whether the transfer is allowed
+    pub allowed: bool,
+    /// the reason of the restriction
+    pub reason: Option<String>,
+}
+
+impl TransferAllowedResponse {
+    /// ## Description
+    /// Returns an [`Err`] naming the denom and the reason if the transfer is not allowed.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **denom** is the native denom of the transfer.
+    pub fn assert_allowed(&self, denom: &str) -> StdResult<()> {
+        if self.allowed {
+            return Ok(());
+        }
+        Err(StdError::generic_err(format!(
+            "Transfer of {} is restricted: {}",
+            denom,
+            self.reason.as_deref().unwrap_or("rejected by the registry")
+        )))
+    }
+}
+
 pub fn native_asset(denom: String, amount: Uint128) -> Asset {
     Asset {
         info: AssetInfo::NativeToken { denom },
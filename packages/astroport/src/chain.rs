@@ -0,0 +1,35 @@
+#[cfg(all(feature = "injective", feature = "sei"))]
+compile_error!("Features `injective` and `sei` are mutually exclusive");
+
+/// The name of the chain the contracts are built for
+#[cfg(not(any(feature = "injective", feature = "sei")))]
+pub const CHAIN_NAME: &str = "terra";
+/// The name of the chain the contracts are built for
+#[cfg(feature = "injective")]
+pub const CHAIN_NAME: &str = "injective";
+/// The name of the chain the contracts are built for
+#[cfg(feature = "sei")]
+pub const CHAIN_NAME: &str = "sei";
+
+/// The average block time of the chain in milliseconds
+#[cfg(not(any(feature = "injective", feature = "sei")))]
+pub const BLOCK_TIME_MS: u64 = 6_000;
+/// The average block time of the chain in milliseconds
+#[cfg(feature = "injective")]
+pub const BLOCK_TIME_MS: u64 = 1_000;
+/// The average block time of the chain in milliseconds
+#[cfg(feature = "sei")]
+pub const BLOCK_TIME_MS: u64 = 400;
+
+/// Whether the chain charges a tax on native token transfers which contracts must deduct
+pub const NATIVE_TAX_ENABLED: bool = cfg!(not(any(feature = "injective", feature = "sei")));
+
+/// The Stargate query path of the parameters of the tokenfactory module
+#[cfg(not(any(feature = "injective", feature = "sei")))]
+pub const TOKENFACTORY_PARAMS_PATH: &str = "/osmosis.tokenfactory.v1beta1.Query/Params";
+/// The Stargate query path of the parameters of the tokenfactory module
+#[cfg(feature = "injective")]
+pub const TOKENFACTORY_PARAMS_PATH: &str = "/injective.tokenfactory.v1beta1.Query/Params";
+/// The Stargate query path of the parameters of the tokenfactory module
+#[cfg(feature = "sei")]
+pub const TOKENFACTORY_PARAMS_PATH: &str = "/seiprotocol.seichain.tokenfactory.Query/Params";
@@ -0,0 +1,216 @@
+use crate::asset::{Asset, AssetInfo};
+use crate::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, SimulatedHop, SwapOperation,
+    MAX_SWAP_OPERATIONS,
+};
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, StdError, StdResult, Uint128, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract. The contract exposes
+/// Uniswap V2 like swap messages and forwards them to the Astroport router.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the router contract which executes the swaps
+    pub router: String,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
+    /// template.
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Swaps the native coins sent with the message along the path, see [`path_to_operations`]
+    SwapExactTokensForTokens {
+        /// the offered amount, it must match the sent coins
+        amount_in: Uint128,
+        /// the minimum amount of the last asset of the path
+        amount_out_min: Uint128,
+        /// the assets traversed by the swap, starting with the offer asset
+        path: Vec<AssetInfo>,
+        /// the recipient of the last asset of the path
+        to: String,
+        /// the block time after which the swap is rejected
+        deadline: u64,
+    },
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Swaps the received tokens along the path, the offered amount is the received amount
+    SwapExactTokensForTokens {
+        /// the minimum amount of the last asset of the path
+        amount_out_min: Uint128,
+        /// the assets traversed by the swap, starting with the offer asset
+        path: Vec<AssetInfo>,
+        /// the recipient of the last asset of the path
+        to: String,
+        /// the block time after which the swap is rejected
+        deadline: u64,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns the amounts of every asset of the path for a swap in an [`AmountsResponse`] object
+    GetAmountsOut {
+        /// the offered amount of the first asset of the path
+        amount_in: Uint128,
+        /// the assets traversed by the swap, starting with the offer asset
+        path: Vec<AssetInfo>,
+    },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// the router contract which executes the swaps
+    pub router: Addr,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AmountsResponse {
+    /// the amounts of the path assets, starting with the offered amount
+    pub amounts: Vec<Uint128>,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns the router operations swapping along the path, one [`SwapOperation::AstroSwap`] per
+/// pair of consecutive assets. Returns an [`Err`] if the path has less than two assets, exceeds
+/// [`MAX_SWAP_OPERATIONS`] hops or contains the same asset twice in a row.
+/// ## Params
+/// * **path** are the assets traversed by the swap, starting with the offer asset.
+pub fn path_to_operations(path: &[AssetInfo]) -> StdResult<Vec<SwapOperation>> {
+    if path.len() < 2 {
+        return Err(StdError::generic_err(
+            "The path must contain at least two assets",
+        ));
+    }
+    if path.len() - 1 > MAX_SWAP_OPERATIONS {
+        return Err(StdError::generic_err(format!(
+            "The path exceeds the maximum of {} swaps",
+            MAX_SWAP_OPERATIONS
+        )));
+    }
+    path.windows(2)
+        .enumerate()
+        .map(|(index, assets)| {
+            if assets[0].equal(&assets[1]) {
+                return Err(StdError::generic_err(format!(
+                    "Path index {}: the asset {} is repeated",
+                    index + 1,
+                    assets[1]
+                )));
+            }
+            Ok(SwapOperation::AstroSwap {
+                offer_asset_info: assets[0].clone(),
+                ask_asset_info: assets[1].clone(),
+            })
+        })
+        .collect()
+}
+
+/// ## Description
+/// Returns the message which executes a swap along the path with the router. Tokens are sent to
+/// the router with its CW20 hook, native coins are attached to the router message.
+/// ## Params
+/// * **router** is the router contract.
+///
+/// * **offer_asset** is the offered asset, the first asset of the path.
+///
+/// * **path** are the assets traversed by the swap.
+///
+/// * **amount_out_min** is the minimum amount of the last asset of the path.
+///
+/// * **to** is the recipient of the last asset of the path.
+///
+/// * **deadline** is the block time after which the swap is rejected.
+pub fn router_swap_msg(
+    router: &Addr,
+    offer_asset: Asset,
+    path: &[AssetInfo],
+    amount_out_min: Uint128,
+    to: &Addr,
+    deadline: u64,
+) -> StdResult<CosmosMsg> {
+    let operations = path_to_operations(path)?;
+    if !offer_asset.info.equal(&path[0]) {
+        return Err(StdError::generic_err(
+            "The path must start with the offered asset",
+        ));
+    }
+    let minimum_receive = Some(amount_out_min);
+
+    match offer_asset.info {
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: router.to_string(),
+                amount: offer_asset.amount,
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive,
+                    to: Some(to.to_string()),
+                    recipients: vec![],
+                    allow_partial: false,
+                    deadline: Some(deadline),
+                    client_id: None,
+                })?,
+            })?,
+            funds: vec![],
+        })),
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: router.to_string(),
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations,
+                minimum_receive,
+                to: Some(to.clone()),
+                recipients: vec![],
+                allow_partial: false,
+                deadline: Some(deadline),
+                client_id: None,
+            })?,
+            funds: vec![Coin {
+                denom,
+                amount: offer_asset.amount,
+            }],
+        })),
+    }
+}
+
+/// ## Description
+/// Returns the amounts of every asset of the path from the simulated hops of the router,
+/// starting with the offered amount.
+/// ## Params
+/// * **amount_in** is the offered amount.
+///
+/// * **hops** are the hops simulated by the router in the order of the path.
+pub fn amounts_out(amount_in: Uint128, hops: &[SimulatedHop]) -> Vec<Uint128> {
+    std::iter::once(amount_in)
+        .chain(hops.iter().map(|hop| hop.return_amount))
+        .collect()
+}
@@ -1,5 +1,9 @@
-use crate::asset::{AssetInfo, PairInfo};
-use cosmwasm_std::{Addr, Binary};
+use crate::asset::{addr_field_validate, Asset, AssetInfo, PairInfo, PairMetadata};
+use crate::pair::PairFees;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, StdError, StdResult,
+    Uint128, WasmMsg,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result};
@@ -50,6 +54,208 @@ pub struct PairConfig {
     /// We disable pair configs instead of removing them. If it is disabled, new pairs cannot be
     /// created, but existing ones can still obtain proper settings, such as fee amounts
     pub is_disabled: Option<bool>,
+    /// the number of blocks between reserve checkpoints stored by pairs of this type,
+    /// checkpoints are disabled if not set
+    pub reserves_checkpoint_interval: Option<u64>,
+    /// the maximum spread users of pairs of this type can allow,
+    /// [`crate::pair::MAX_ALLOWED_SLIPPAGE`] if not set
+    #[serde(default)]
+    pub max_allowed_spread: Option<Decimal>,
+    /// the asset in which pairs of this type charge the commission
+    #[serde(default)]
+    pub commission_mode: CommissionMode,
+    /// whether anyone can create pairs of this type, otherwise only the owner can
+    #[serde(default)]
+    pub permissionless: bool,
+    /// whether new pairs of this type are registered in the generator with a zero allocation
+    /// point once they are instantiated
+    #[serde(default)]
+    pub register_in_generator: bool,
+    /// whether new pairs of this type are registered in the oracle factory once they are
+    /// instantiated
+    #[serde(default)]
+    pub register_in_oracle: bool,
+    /// whether the LP tokens of new pairs of this type notify the generator when they change owner
+    #[serde(default)]
+    pub lp_transfer_hook: bool,
+    /// the bounds of the swap fee of stable pairs of this type with a dynamic fee curve
+    #[serde(default)]
+    pub dynamic_fee_bounds: Option<DynamicFeeBounds>,
+    /// whether pairs of this type issue a tokenfactory denom as LP token, so their creation
+    /// requires the tokenfactory creation fee, see [`tokenfactory_creation_funds`]
+    #[serde(default)]
+    pub tokenfactory_lp: bool,
+}
+
+/// ## Description
+/// This structure describes the parameters of the tokenfactory module of the chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct TokenFactoryParams {
+    /// the fee charged for creating a denom
+    pub denom_creation_fee: Vec<Coin>,
+}
+
+impl TokenFactoryParams {
+    /// ## Description
+    /// Decodes the protobuf encoded response of the tokenfactory `Params` query, see
+    /// [`crate::chain::TOKENFACTORY_PARAMS_PATH`]. Only the creation fee (field 1 of the params)
+    /// is read, the other fields are skipped.
+    /// ## Params
+    /// * **data** is the protobuf encoded `QueryParamsResponse`.
+    pub fn decode(data: &[u8]) -> StdResult<Self> {
+        let mut params = TokenFactoryParams::default();
+        // the response wraps the params in its field 1
+        for (field, value) in decode_proto_fields(data)? {
+            if field != 1 {
+                continue;
+            }
+            for (field, value) in decode_proto_fields(value)? {
+                if field != 1 {
+                    continue;
+                }
+                let mut coin = Coin::new(0, "");
+                for (field, value) in decode_proto_fields(value)? {
+                    let value = std::str::from_utf8(value)
+                        .map_err(|_| StdError::generic_err("Invalid tokenfactory creation fee"))?;
+                    match field {
+                        1 => coin.denom = value.to_string(),
+                        2 => coin.amount = value.parse()?,
+                        _ => {}
+                    }
+                }
+                params.denom_creation_fee.push(coin);
+            }
+        }
+        Ok(params)
+    }
+}
+
+/// ## Description
+/// Returns the length-delimited fields of a protobuf message as (field number, bytes) pairs,
+/// skipping the fields of the other wire types.
+fn decode_proto_fields(mut data: &[u8]) -> StdResult<Vec<(u64, &[u8])>> {
+    fn read_varint(data: &mut &[u8]) -> StdResult<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) = data
+                .split_first()
+                .ok_or_else(|| StdError::generic_err("Truncated protobuf varint"))?;
+            *data = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(StdError::generic_err("Invalid protobuf varint"))
+    }
+
+    let mut fields = vec![];
+    while !data.is_empty() {
+        let key = read_varint(&mut data)?;
+        let skip = match key & 0x7 {
+            0 => {
+                read_varint(&mut data)?;
+                0
+            }
+            1 => 8,
+            2 => {
+                let len = read_varint(&mut data)? as usize;
+                if len > data.len() {
+                    return Err(StdError::generic_err("Truncated protobuf field"));
+                }
+                fields.push((key >> 3, &data[..len]));
+                len
+            }
+            5 => 4,
+            _ => return Err(StdError::generic_err("Unsupported protobuf wire type")),
+        };
+        if skip > data.len() {
+            return Err(StdError::generic_err("Truncated protobuf field"));
+        }
+        data = &data[skip..];
+    }
+    Ok(fields)
+}
+
+/// ## Description
+/// Returns the funds attached to the pair instantiation for paying the tokenfactory creation fee
+/// of its LP token. Returns an [`Err`] if the funds sent to the factory don't cover the fee, so
+/// the pair creation fails up front instead of inside the pair's instantiate reply.
+/// ## Params
+/// * **funds** are the funds sent with the [`ExecuteMsg::CreatePair`] message.
+///
+/// * **params** are the tokenfactory parameters of the chain.
+pub fn tokenfactory_creation_funds(
+    funds: &[Coin],
+    params: &TokenFactoryParams,
+) -> StdResult<Vec<Coin>> {
+    for fee in params
+        .denom_creation_fee
+        .iter()
+        .filter(|fee| !fee.amount.is_zero())
+    {
+        let provided = funds
+            .iter()
+            .filter(|coin| coin.denom == fee.denom)
+            .map(|coin| coin.amount)
+            .sum::<Uint128>();
+        if provided < fee.amount {
+            return Err(StdError::generic_err(format!(
+                "Creating a tokenfactory LP token requires a fee of {}, provided {}{}",
+                fee, provided, fee.denom
+            )));
+        }
+    }
+    Ok(params
+        .denom_creation_fee
+        .iter()
+        .filter(|fee| !fee.amount.is_zero())
+        .cloned()
+        .collect())
+}
+
+/// ## Description
+/// This structure describes the bounds of the swap fee computed by the dynamic fee curve of a
+/// stable pair, see [`crate::pair::DynamicFeeParams`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub struct DynamicFeeBounds {
+    /// the minimum swap fee in bps
+    pub min_fee_bps: u16,
+    /// the maximum swap fee in bps
+    pub max_fee_bps: u16,
+}
+
+impl DynamicFeeBounds {
+    /// ## Description
+    /// Returns an [`Err`] if the minimum exceeds the maximum or the maximum exceeds 10000 bps.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if self.min_fee_bps > self.max_fee_bps || self.max_fee_bps > 10_000 {
+            return Err(StdError::generic_err(
+                "Dynamic fee bounds must satisfy min_fee_bps <= max_fee_bps <= 10000",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This enum describes the asset in which the commission of a swap is charged.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommissionMode {
+    /// The commission is deducted from the returned ask asset
+    AskAsset {},
+    /// The commission is deducted from the offer asset before the swap, useful when the ask
+    /// asset is illiquid or can't be transferred to the maker
+    OfferAsset {},
+}
+
+impl Default for CommissionMode {
+    fn default() -> Self {
+        CommissionMode::AskAsset {}
+    }
 }
 
 impl PairConfig {
@@ -60,8 +266,146 @@ impl PairConfig {
     pub fn valid_fee_bps(&self) -> bool {
         self.total_fee_bps <= 10_000 && self.maker_fee_bps <= 10_000
     }
+
+    /// ## Description
+    /// Returns the fees which are pushed to pairs of the config.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    pub fn fees(&self) -> PairFees {
+        PairFees {
+            total_fee_bps: self.total_fee_bps,
+            maker_fee_bps: self.maker_fee_bps,
+        }
+    }
+
+    /// ## Description
+    /// Returns the registration messages dispatched once a pair of this config is instantiated.
+    /// The generator and the oracle factory receive a [`PairCreationHookMsg::OnPairCreated`].
+    /// Returns an [`Err`] if a registration is enabled but its contract is not set.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    ///
+    /// * **pair_info** is the information about the instantiated pair.
+    ///
+    /// * **generator** is an [`Option`] field with the generator contract address.
+    ///
+    /// * **oracle_factory** is an [`Option`] field with the oracle factory contract address.
+    pub fn registration_msgs(
+        &self,
+        pair_info: &PairInfo,
+        generator: Option<&Addr>,
+        oracle_factory: Option<&Addr>,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let targets = [
+            (self.register_in_generator, generator, "generator"),
+            (self.register_in_oracle, oracle_factory, "oracle factory"),
+        ];
+        let mut msgs = vec![];
+        for (enabled, contract, name) in targets {
+            if !enabled {
+                continue;
+            }
+            let contract = contract.ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "Pair config {} registers pairs in the {}, but it is not set",
+                    self.pair_type, name
+                ))
+            })?;
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: to_binary(&PairCreationHookMsg::OnPairCreated {
+                    pair_info: pair_info.clone(),
+                })?,
+                funds: vec![],
+            }));
+        }
+        Ok(msgs)
+    }
+
+    /// ## Description
+    /// This method is used to check the maximum allowed spread.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    pub fn valid_max_allowed_spread(&self) -> bool {
+        self.max_allowed_spread
+            .map(|spread| spread <= Decimal::one())
+            .unwrap_or(true)
+    }
+
+    /// ## Description
+    /// Returns an [`Err`] if the config is disabled or if the sender is not the owner and
+    /// the config does not allow permissionless pair creation.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    ///
+    /// * **is_owner** is whether the sender of [`ExecuteMsg::CreatePair`] is the factory owner.
+    pub fn assert_creation_allowed(&self, is_owner: bool) -> StdResult<()> {
+        if self.is_disabled.unwrap_or(false) {
+            return Err(StdError::generic_err(format!(
+                "Pair config {} is disabled",
+                self.pair_type
+            )));
+        }
+        if !is_owner && !self.permissionless {
+            return Err(StdError::generic_err(format!(
+                "Only the owner can create {} pairs",
+                self.pair_type
+            )));
+        }
+        Ok(())
+    }
 }
 
+/// ## Description
+/// Returns the storage key of the pair for the specified assets. The key does not depend on the
+/// order of the assets and on the case of denoms and addresses, so `[A, B]` and `[b, a]` map to
+/// the same pair. Each asset is prefixed with its length as two big-endian bytes, so distinct
+/// pairs can't concatenate to the same key.
+/// ## Params
+/// * **asset_infos** is array with two items the type of [`AssetInfo`].
+pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
+    let mut keys = [
+        asset_infos[0].to_string().to_lowercase(),
+        asset_infos[1].to_string().to_lowercase(),
+    ];
+    keys.sort();
+    keys.iter()
+        .flat_map(|key| {
+            let len = (key.len() as u16).to_be_bytes();
+            len.iter()
+                .chain(key.as_bytes())
+                .copied()
+                .collect::<Vec<u8>>()
+        })
+        .collect()
+}
+
+/// ## Description
+/// Returns an [`Err`] if both asset infos describe the same asset according to [`pair_key`].
+/// ## Params
+/// * **asset_infos** is array with two items the type of [`AssetInfo`].
+pub fn assert_distinct_assets(asset_infos: &[AssetInfo; 2]) -> StdResult<()> {
+    if asset_infos[0].to_string().to_lowercase() == asset_infos[1].to_string().to_lowercase() {
+        return Err(StdError::generic_err("Doubling assets in asset infos"));
+    }
+    Ok(())
+}
+
+/// The maximum number of contracts notified about created pairs
+pub const MAX_PAIR_CREATION_HOOKS: usize = 10;
+/// The maximum number of operations in a single [`ExecuteMsg::BatchUpdate`]
+pub const MAX_BATCH_OPS: usize = 20;
+/// The maximum number of pairs returned by a single [`QueryMsg::PoolsDetailed`] query
+pub const MAX_POOLS_DETAILED_LIMIT: u32 = 10;
+/// The maximum number of assets in a single [`QueryMsg::AssetsMetadata`] query
+pub const MAX_ASSETS_METADATA: usize = 20;
+/// The maximum number of pairs updated by a single fee push
+pub const MAX_PUSH_FEES_LIMIT: u32 = 30;
+/// The maximum number of pairs checked by a single [`QueryMsg::InconsistentPairs`] query
+pub const MAX_INCONSISTENT_PAIRS_LIMIT: u32 = 10;
+/// The version of the [`ConfigExport`] format
+pub const CONFIG_EXPORT_VERSION: u32 = 2;
+
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -78,6 +422,64 @@ pub struct InstantiateMsg {
     pub owner: String,
     /// cw1 whitelist contract code id used to store 3rd party rewards in pools
     pub whitelist_code_id: u64,
+    /// the contract which is notified about every swap with a [`VolumeTrackerMsg`]
+    pub volume_tracker: Option<String>,
+    /// the address which can pause operations of pairs besides the owner
+    pub guardian: Option<String>,
+    /// the limits of permissionless pair creation, unlimited if not set
+    #[serde(default)]
+    pub creation_rate_limit: Option<CreationRateLimit>,
+    /// the oracle factory new pairs are registered in, see [`PairConfig::register_in_oracle`]
+    #[serde(default)]
+    pub oracle_factory: Option<String>,
+}
+
+/// ## Description
+/// This structure describes the limits of pair creation by addresses which are neither the owner
+/// nor whitelisted deployers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreationRateLimit {
+    /// the number of seconds an address has to wait between creating two pairs
+    pub cooldown: u64,
+    /// the maximum number of pairs created by limited addresses in a single block
+    pub max_per_block: u32,
+}
+
+impl CreationRateLimit {
+    /// ## Description
+    /// Returns an [`Err`] if the sender created a pair less than [`CreationRateLimit::cooldown`]
+    /// seconds ago or the per-block cap is reached.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **last_creation** is the time of the last pair created by the sender, if any.
+    ///
+    /// * **created_in_block** is the number of pairs created by limited addresses in the current block.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn assert_allowed(
+        &self,
+        last_creation: Option<u64>,
+        created_in_block: u32,
+        time: u64,
+    ) -> StdResult<()> {
+        if let Some(last_creation) = last_creation {
+            let next_creation = last_creation.saturating_add(self.cooldown);
+            if time < next_creation {
+                return Err(StdError::generic_err(format!(
+                    "Pair creation is rate limited until {}",
+                    next_creation
+                )));
+            }
+        }
+        if created_in_block >= self.max_per_block {
+            return Err(StdError::generic_err(format!(
+                "At most {} pairs can be created per block",
+                self.max_per_block
+            )));
+        }
+        Ok(())
+    }
 }
 
 /// ## Description
@@ -95,26 +497,160 @@ pub enum ExecuteMsg {
         generator_address: Option<String>,
         /// cw1 whitelist contract code id used to store 3rd party rewards in pools
         whitelist_code_id: Option<u64>,
+        /// the contract which is notified about every swap
+        volume_tracker: Option<UpdateAddr>,
+        /// the address which can pause operations of pairs besides the owner
+        guardian: Option<UpdateAddr>,
+        /// the oracle factory new pairs are registered in
+        oracle_factory: Option<UpdateAddr>,
     },
     /// UpdatePairConfig updates configs of pair
     UpdatePairConfig {
         /// new [`PairConfig`] settings for pair
         config: PairConfig,
+        /// whether the new fees are pushed to the first [`MAX_PUSH_FEES_LIMIT`] existing pairs of
+        /// the type with [`crate::pair::ExecuteMsg::UpdateFees`], the remaining pairs are updated
+        /// with [`ExecuteMsg::PushPairFees`]
+        #[serde(default)]
+        push_fees: bool,
     },
-    /// CreatePair instantiates pair contract
+    /// UpdateMakerFeeShare changes the share of the total fee sent to the maker for pairs of the
+    /// type, leaving the rest of the pair config untouched
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateMakerFeeShare {
+        /// the type of pairs to update
+        pair_type: PairType,
+        /// the new share of the total fee sent to the maker in bps, at most 10000
+        maker_fee_bps: u16,
+        /// whether the new fees are pushed to the first [`MAX_PUSH_FEES_LIMIT`] existing pairs of
+        /// the type, the remaining pairs are updated with [`ExecuteMsg::PushPairFees`]
+        #[serde(default)]
+        push_fees: bool,
+    },
+    /// PushPairFees pushes the fees of a pair config to existing pairs of the type
+    /// ## Executor
+    /// Only owner can execute it
+    PushPairFees {
+        /// the type of pairs to update
+        pair_type: PairType,
+        /// the pair contract address after which the pairs are updated
+        start_after: Option<String>,
+        /// the number of pairs to update, at most [`MAX_PUSH_FEES_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// SetFeeDiscountPolicy sets the router whose fee discounts the pairs honor and the maximum
+    /// discount
+    /// ## Executor
+    /// Only owner can execute it
+    SetFeeDiscountPolicy {
+        /// the router contract, removing it disables the discounts
+        router: UpdateAddr,
+        /// the maximum fee discount in bps
+        max_discount_bps: u16,
+    },
+    /// CreatePair instantiates pair contract. Pairs whose config has `tokenfactory_lp` set
+    /// require the tokenfactory creation fee in the funds, see [`tokenfactory_creation_funds`]
+    /// ## Executor
+    /// Anyone can execute it for pair configs with [`PairConfig::permissionless`] set, otherwise
+    /// only owner can execute it. Senders other than the owner and whitelisted deployers are
+    /// subject to the [`CreationRateLimit`]
     CreatePair {
         /// the type of pair available in [`PairType`]
         pair_type: PairType,
-        /// the type of asset infos available in [`AssetInfo`]
+        /// the type of asset infos available in [`AssetInfo`], both are validated with
+        /// [`AssetInfo::check`]
         asset_infos: [AssetInfo; 2],
         /// Optional binary serialised parameters for custom pool types
         init_params: Option<Binary>,
+        /// Optional labels of the pair which can later be updated by the sender
+        #[serde(default)]
+        metadata: Option<PairMetadata>,
     },
-    /// Deregister removes a previously created pair
+    /// Deregister removes a previously created pair from the registry. The pair contract itself
+    /// is left intact, so its liquidity providers can still withdraw
+    /// ## Executor
+    /// Only owner can execute it
     Deregister {
         /// the type of asset infos available in [`AssetInfo`]
         asset_infos: [AssetInfo; 2],
     },
+    /// SyncPair queries the pair info of a registered pair and repairs its registry entry if the
+    /// asset infos, the LP token or the pair type diverged, e.g. after a pair migration, see
+    /// [`sync_pair_info`]
+    /// ## Executor
+    /// Only owner can execute it
+    SyncPair {
+        /// the pair contract address
+        pair_contract: String,
+    },
+    /// RegisterPairCreationHook subscribes a contract to notifications about created pairs.
+    /// The contract receives a [`PairCreationHookMsg`] each time a new pair is created.
+    /// ## Executor
+    /// Only owner can execute it
+    RegisterPairCreationHook {
+        /// the contract to notify
+        contract: String,
+    },
+    /// DeregisterPairCreationHook unsubscribes a contract from notifications about created pairs
+    /// ## Executor
+    /// Only owner can execute it
+    DeregisterPairCreationHook {
+        /// the contract to unsubscribe
+        contract: String,
+    },
+    /// SetCreationRateLimit sets or removes the limits of pair creation
+    /// ## Executor
+    /// Only owner can execute it
+    SetCreationRateLimit {
+        /// the new limits, [`None`] removes them
+        limit: Option<CreationRateLimit>,
+    },
+    /// UpdateDeployers adds or removes deployers exempted from the pair creation limits
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateDeployers {
+        /// the deployers to exempt
+        add: Vec<String>,
+        /// the deployers to limit again
+        remove: Vec<String>,
+    },
+    /// BatchUpdate executes several owner operations atomically, either all of them are applied
+    /// or none
+    /// ## Executor
+    /// Only owner can execute it
+    BatchUpdate {
+        /// the operations executed in order, at most [`MAX_BATCH_OPS`]
+        ops: Vec<FactoryOp>,
+    },
+    /// ImportConfig applies a [`ConfigExport`] blob returned by [`QueryMsg::ExportConfig`] of
+    /// another factory. Only allowed while no pairs were created.
+    /// ## Executor
+    /// Only owner can execute it
+    ImportConfig {
+        /// the binary serialized [`ConfigExport`]
+        blob: Binary,
+    },
+    /// SetPairFeeOverride sets or removes the fees of a single pair which replace the fees of its
+    /// pair config, and pushes the resulting fees to the pair with
+    /// [`crate::pair::ExecuteMsg::UpdateFees`]
+    /// ## Executor
+    /// Only owner can execute it
+    SetPairFeeOverride {
+        /// the pair contract address
+        pair_contract: String,
+        /// the fees of the pair, [`None`] falls back to the fees of the pair config
+        fees: Option<PairFees>,
+    },
+    /// Freeze blocks pair creation and config changes during a migration to a new factory, see
+    /// [`ExecuteMsg::assert_allowed_when_frozen`]. Queries and existing pairs keep working
+    /// ## Executor
+    /// Only owner can execute it
+    Freeze {},
+    /// Unfreeze lifts the freeze set by [`ExecuteMsg::Freeze`]
+    /// ## Executor
+    /// Only owner can execute it
+    Unfreeze {},
     /// ProposeNewOwner creates an offer for a new owner. The validity period of the offer is set in the `expires_in` variable.
     ProposeNewOwner {
         /// contract address that used for controls settings for factory, pools and tokenomics contracts
@@ -128,6 +664,92 @@ pub enum ExecuteMsg {
     ClaimOwnership {},
 }
 
+impl ExecuteMsg {
+    /// ## Description
+    /// Returns an [`Err`] if the message creates pairs or changes the config while the factory is
+    /// frozen. Freezing, unfreezing and ownership changes stay allowed, so the factory can be
+    /// handed over during a migration.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **frozen** is whether the factory is frozen.
+    pub fn assert_allowed_when_frozen(&self, frozen: bool) -> StdResult<()> {
+        let allowed = matches!(
+            self,
+            ExecuteMsg::Freeze {}
+                | ExecuteMsg::Unfreeze {}
+                | ExecuteMsg::ProposeNewOwner { .. }
+                | ExecuteMsg::DropOwnershipProposal {}
+                | ExecuteMsg::ClaimOwnership {}
+        );
+        if frozen && !allowed {
+            return Err(StdError::generic_err(
+                "The factory is frozen for a migration",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This enum describes the operations of a [`ExecuteMsg::BatchUpdate`]. Each operation behaves
+/// like the execute message of the same name.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FactoryOp {
+    /// Updates relevant code IDs and addresses
+    UpdateConfig {
+        /// CW20 token contract code identifier
+        token_code_id: Option<u64>,
+        /// contract address to send fees to
+        fee_address: Option<String>,
+        /// contract address that used for auto_stake from pools
+        generator_address: Option<String>,
+        /// cw1 whitelist contract code id used to store 3rd party rewards in pools
+        whitelist_code_id: Option<u64>,
+        /// the contract which is notified about every swap
+        volume_tracker: Option<UpdateAddr>,
+        /// the address which can pause operations of pairs besides the owner
+        guardian: Option<UpdateAddr>,
+        /// the oracle factory new pairs are registered in
+        oracle_factory: Option<UpdateAddr>,
+    },
+    /// Updates configs of pair
+    UpdatePairConfig {
+        /// new [`PairConfig`] settings for pair
+        config: PairConfig,
+        /// whether the new fees are pushed to existing pairs of the type, see
+        /// [`ExecuteMsg::UpdatePairConfig`]
+        #[serde(default)]
+        push_fees: bool,
+    },
+    /// Removes a previously created pair
+    Deregister {
+        /// the type of asset infos available in [`AssetInfo`]
+        asset_infos: [AssetInfo; 2],
+    },
+    /// ## Description
+    /// SyncPair queries the pair info of a registered pair and repairs its registry entry if the
+    /// asset infos, the LP token or the pair type diverged, e.g. after a pair migration, see
+    /// [`sync_pair_info`]
+    /// ## Executor
+    /// Only owner can execute it
+    SyncPair {
+        /// the pair contract address
+        pair_contract: String,
+    },
+    /// Subscribes a contract to notifications about created pairs
+    RegisterPairCreationHook {
+        /// the contract to notify
+        contract: String,
+    },
+    /// Unsubscribes a contract from notifications about created pairs
+    DeregisterPairCreationHook {
+        /// the contract to unsubscribe
+        contract: String,
+    },
+}
+
 /// ## Description
 /// This structure describes the query messages of the contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -135,6 +757,12 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
     Config {},
+    /// CreationRateLimit returns the pair creation limits and the state of an address in a
+    /// [`CreationRateLimitResponse`] object
+    CreationRateLimit {
+        /// the address whose next allowed creation time is returned
+        address: Option<String>,
+    },
     /// Pair returns a pair according to the specified parameters in `asset_infos` variable.
     Pair {
         /// the type of asset infos available in [`AssetInfo`]
@@ -152,6 +780,187 @@ pub enum QueryMsg {
         ///s the type of pair available in [`PairType`]
         pair_type: PairType,
     },
+    /// PairCreationHooks returns the contracts notified about created pairs in a [`PairCreationHooksResponse`] object
+    PairCreationHooks {},
+    /// PairsSummary returns aggregated pair counters in a [`PairsSummaryResponse`] object
+    PairsSummary {},
+    /// ExportConfig returns the full factory settings in a [`ExportConfigResponse`] object
+    ExportConfig {},
+    /// PoolsDetailed returns the registered pairs together with their reserves in a
+    /// [`PoolsDetailedResponse`] object. Pairs whose pool query fails are returned without reserves
+    PoolsDetailed {
+        /// the item to start reading after
+        start_after: Option<[AssetInfo; 2]>,
+        /// the number of items to be read, at most [`MAX_POOLS_DETAILED_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// AssetsMetadata returns the metadata of up to [`MAX_ASSETS_METADATA`] assets in a
+    /// [`AssetsMetadataResponse`] object
+    AssetsMetadata {
+        /// the assets to describe
+        assets: Vec<AssetInfo>,
+    },
+    /// EffectiveFee returns the fees currently applied to a pair, resolved from its pair config
+    /// and fee override, in an [`EffectiveFeeResponse`] object
+    EffectiveFee {
+        /// the pair contract address
+        pair_contract: String,
+    },
+    /// InconsistentPairs returns the registered pairs whose registry entry diverged from their
+    /// pair info in an [`InconsistentPairsResponse`] object. Pairs whose query fails are returned too
+    InconsistentPairs {
+        /// the item to start reading after
+        start_after: Option<[AssetInfo; 2]>,
+        /// the number of registered pairs to check, at most [`MAX_INCONSISTENT_PAIRS_LIMIT`]
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// This enum describes where the effective fees of a pair come from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeSource {
+    /// the fees of the pair config of the pair type
+    PairConfig {},
+    /// the fee override of the pair, see [`ExecuteMsg::SetPairFeeOverride`]
+    Override {},
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the fees applied to a pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EffectiveFeeResponse {
+    /// the pair contract address
+    pub pair_contract: Addr,
+    /// the type of the pair
+    pub pair_type: PairType,
+    /// the fees applied to the pair
+    pub fees: PairFees,
+    /// where the fees come from
+    pub source: FeeSource,
+}
+
+impl EffectiveFeeResponse {
+    /// ## Description
+    /// Resolves the fees of a pair. The fee override of the pair takes precedence over the fees
+    /// of its pair config.
+    /// ## Params
+    /// * **pair_contract** is the pair contract address.
+    ///
+    /// * **config** is the [`PairConfig`] of the pair type.
+    ///
+    /// * **fee_override** is an [`Option`] field with the fee override of the pair.
+    pub fn resolve(
+        pair_contract: Addr,
+        config: &PairConfig,
+        fee_override: Option<PairFees>,
+    ) -> Self {
+        let (fees, source) = match fee_override {
+            Some(fees) => (fees, FeeSource::Override {}),
+            None => (config.fees(), FeeSource::PairConfig {}),
+        };
+        EffectiveFeeResponse {
+            pair_contract,
+            pair_type: config.pair_type.clone(),
+            fees,
+            source,
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the full settings of a factory which can be imported into another
+/// factory. The owner and the created pairs are not part of the export.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigExport {
+    /// the version of the export format, [`CONFIG_EXPORT_VERSION`]
+    pub version: u32,
+    /// Pair contract code IDs which are allowed to create pairs
+    pub pair_configs: Vec<PairConfig>,
+    /// CW20 token contract code identifier
+    pub token_code_id: u64,
+    /// cw1 whitelist contract code id used to store 3rd party rewards in pools
+    pub whitelist_code_id: u64,
+    /// Contract address to send fees to
+    pub fee_address: Option<Addr>,
+    /// Contract address that used for auto_stake from pools
+    pub generator_address: Option<Addr>,
+    /// the contract which is notified about every swap
+    pub volume_tracker: Option<Addr>,
+    /// the address which can pause operations of pairs besides the owner
+    pub guardian: Option<Addr>,
+    /// the contracts notified about created pairs
+    pub pair_creation_hooks: Vec<Addr>,
+    /// the oracle factory new pairs are registered in
+    pub oracle_factory: Option<Addr>,
+    /// the limits of permissionless pair creation, unlimited if not set
+    pub creation_rate_limit: Option<CreationRateLimit>,
+    /// the deployers exempted from the creation limits
+    pub deployers: Vec<Addr>,
+    /// the router whose fee discounts the pairs honor, see [`ExecuteMsg::SetFeeDiscountPolicy`]
+    pub fee_discount_router: Option<Addr>,
+    /// the maximum fee discount in bps
+    pub max_fee_discount_bps: u16,
+    /// the fee overrides of pairs migrated to the importing factory, see
+    /// [`ExecuteMsg::SetPairFeeOverride`]
+    pub pair_fee_overrides: Vec<(Addr, PairFees)>,
+    /// whether pair creation and config changes are blocked, see [`ExecuteMsg::Freeze`]
+    pub frozen: bool,
+}
+
+impl ConfigExport {
+    /// ## Description
+    /// Deserializes and validates an exported config. Returns an [`Err`] if the format version is
+    /// unsupported, a pair config is invalid or duplicated, or a fee override is invalid.
+    /// ## Params
+    /// * **blob** is the binary serialized [`ConfigExport`].
+    pub fn from_blob(blob: &Binary) -> StdResult<Self> {
+        let export: ConfigExport = from_binary(blob)?;
+        if export.version != CONFIG_EXPORT_VERSION {
+            return Err(StdError::generic_err(format!(
+                "Unsupported config export version {}",
+                export.version
+            )));
+        }
+        if export.pair_creation_hooks.len() > MAX_PAIR_CREATION_HOOKS {
+            return Err(StdError::generic_err(format!(
+                "At most {} pair creation hooks can be registered",
+                MAX_PAIR_CREATION_HOOKS
+            )));
+        }
+        for (i, pair_config) in export.pair_configs.iter().enumerate() {
+            if !pair_config.valid_fee_bps() || !pair_config.valid_max_allowed_spread() {
+                return Err(StdError::generic_err(format!(
+                    "Invalid pair config of pair type {}",
+                    pair_config.pair_type
+                )));
+            }
+            if export.pair_configs[..i]
+                .iter()
+                .any(|other| other.pair_type == pair_config.pair_type)
+            {
+                return Err(StdError::generic_err(format!(
+                    "Duplicated pair config of pair type {}",
+                    pair_config.pair_type
+                )));
+            }
+        }
+        for (_, fees) in &export.pair_fee_overrides {
+            fees.validate()?;
+        }
+        Ok(export)
+    }
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the full factory settings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExportConfigResponse {
+    /// the exported settings
+    pub config: ConfigExport,
+    /// the binary serialized settings to pass to [`ExecuteMsg::ImportConfig`]
+    pub blob: Binary,
 }
 
 /// ## Description
@@ -170,6 +979,96 @@ pub struct ConfigResponse {
     pub generator_address: Option<Addr>,
     /// cw1 whitelist contract code id used to store 3rd party rewards in pools
     pub whitelist_code_id: u64,
+    /// the contract which is notified about every swap
+    pub volume_tracker: Option<Addr>,
+    /// the address which can pause operations of pairs besides the owner
+    pub guardian: Option<Addr>,
+    /// the oracle factory new pairs are registered in
+    #[serde(default)]
+    pub oracle_factory: Option<Addr>,
+    /// whether pair creation and config changes are blocked, see [`ExecuteMsg::Freeze`]
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+/// ## Description
+/// This structure describes the response to the creation rate limit query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreationRateLimitResponse {
+    /// the limits of pair creation, [`None`] if creation is unlimited
+    pub limit: Option<CreationRateLimit>,
+    /// the deployers exempted from the limits
+    pub deployers: Vec<Addr>,
+    /// the number of pairs created by limited addresses in the current block
+    pub created_in_block: u32,
+    /// the time from which the queried address can create the next pair, [`None`] if it is not
+    /// limited
+    pub next_creation: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the sudo messages sent by the chain governance on chains where the
+/// contract admin is the governance module. Each message behaves like the owner-only execute
+/// message of the same name, see [`SudoMsg::into_execute_msg`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Updates relevant code IDs and addresses
+    UpdateConfig {
+        /// CW20 token contract code identifier
+        token_code_id: Option<u64>,
+        /// contract address to send fees to
+        fee_address: Option<String>,
+        /// contract address that used for auto_stake from pools
+        generator_address: Option<String>,
+        /// cw1 whitelist contract code id used to store 3rd party rewards in pools
+        whitelist_code_id: Option<u64>,
+        /// the contract which is notified about every swap
+        volume_tracker: Option<UpdateAddr>,
+        /// the address which can pause operations of pairs besides the owner
+        guardian: Option<UpdateAddr>,
+        /// the oracle factory new pairs are registered in
+        oracle_factory: Option<UpdateAddr>,
+    },
+    /// Updates the config of a pair type
+    UpdatePairConfig {
+        /// new [`PairConfig`] settings for pair
+        config: PairConfig,
+        /// whether the new fees are pushed to existing pairs of the type
+        #[serde(default)]
+        push_fees: bool,
+    },
+}
+
+impl SudoMsg {
+    /// ## Description
+    /// Returns the execute message which is processed with the owner's authority.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn into_execute_msg(self) -> ExecuteMsg {
+        match self {
+            SudoMsg::UpdateConfig {
+                token_code_id,
+                fee_address,
+                generator_address,
+                whitelist_code_id,
+                volume_tracker,
+                guardian,
+                oracle_factory,
+            } => ExecuteMsg::UpdateConfig {
+                token_code_id,
+                fee_address,
+                generator_address,
+                whitelist_code_id,
+                volume_tracker,
+                guardian,
+                oracle_factory,
+            },
+            SudoMsg::UpdatePairConfig { config, push_fees } => {
+                ExecuteMsg::UpdatePairConfig { config, push_fees }
+            }
+        }
+    }
 }
 
 /// ## Description
@@ -197,6 +1096,208 @@ pub struct FeeInfoResponse {
     pub total_fee_bps: u16,
     /// Pair fees bps
     pub maker_fee_bps: u16,
+    /// the maximum spread users of the pair can allow
+    pub max_allowed_spread: Option<Decimal>,
+    /// the contract the pair notifies about every swap
+    pub volume_tracker: Option<Addr>,
+    /// the asset in which the pair charges the commission
+    #[serde(default)]
+    pub commission_mode: CommissionMode,
+    /// the bounds of the swap fee of a stable pair with a dynamic fee curve
+    #[serde(default)]
+    pub dynamic_fee_bounds: Option<DynamicFeeBounds>,
+    /// the router whose fee discounts the pair honors, see
+    /// [`crate::pair::PairFees::discounted_commission_rate`]
+    #[serde(default)]
+    pub fee_discount_router: Option<Addr>,
+    /// the maximum fee discount in bps
+    #[serde(default)]
+    pub max_fee_discount_bps: u16,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the contracts notified about created pairs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairCreationHooksResponse {
+    pub hooks: Vec<Addr>,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns aggregated pair counters.
+/// The counters are maintained on pair creation and deregistration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairsSummaryResponse {
+    /// the total number of registered pairs
+    pub total: u64,
+    /// the number of registered pairs per pair type
+    pub pair_types: Vec<PairTypeCount>,
+    /// the number of pairs whose pair config is disabled
+    pub disabled: u64,
+    /// the number of pairs with at least one paused operation
+    pub paused: u64,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns pairs together with their pools.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolsDetailedResponse {
+    pub pools: Vec<PoolDetailed>,
+}
+
+/// ## Description
+/// This structure describes a registered pair and the state of its pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolDetailed {
+    /// the pair information
+    pub pair_info: PairInfo,
+    /// the pool reserves, [`None`] if the pool query failed
+    pub assets: Option<[Asset; 2]>,
+    /// the total supply of the LP token
+    pub total_share: Option<Uint128>,
+    /// whether any operation of the pair is paused, `false` if the pool query failed
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the diverged pairs of a page.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InconsistentPairsResponse {
+    /// the diverged pairs of the page
+    pub pairs: Vec<PairDivergence>,
+    /// the asset infos of the last checked pair, the `start_after` of the next page
+    pub last_checked: Option<[AssetInfo; 2]>,
+}
+
+/// ## Description
+/// This structure describes a registry entry which diverged from the pair info of its pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairDivergence {
+    /// the registry entry
+    pub registered: PairInfo,
+    /// the pair info returned by the pair, [`None`] if the query failed
+    pub actual: Option<PairInfo>,
+    /// the names of the diverged fields, see [`diverged_pair_fields`]
+    pub fields: Vec<String>,
+}
+
+/// ## Description
+/// Returns the names of the fields of a registry entry which differ from the pair info returned
+/// by the pair. The asset infos are compared with [`pair_key`], so their order doesn't matter.
+/// ## Params
+/// * **registered** is the registry entry.
+///
+/// * **actual** is the pair info returned by the pair.
+pub fn diverged_pair_fields(registered: &PairInfo, actual: &PairInfo) -> Vec<String> {
+    let mut fields = vec![];
+    if registered.contract_addr != actual.contract_addr {
+        fields.push("contract_addr".to_string());
+    }
+    if pair_key(&registered.asset_infos) != pair_key(&actual.asset_infos) {
+        fields.push("asset_infos".to_string());
+    }
+    if registered.liquidity_token != actual.liquidity_token {
+        fields.push("liquidity_token".to_string());
+    }
+    if registered.pair_type != actual.pair_type {
+        fields.push("pair_type".to_string());
+    }
+    fields
+}
+
+/// ## Description
+/// Returns the repaired registry entry, or [`None`] if it didn't diverge. The asset infos, the
+/// LP token, the pair type and the capabilities are taken from the pair, the metadata and the
+/// official mark stay with the registry. Returns an [`Err`] if the pair reports another contract
+/// address or the same asset twice. If the asset infos changed, the caller moves the entry to the
+/// new [`pair_key`].
+/// ## Params
+/// * **registered** is the registry entry.
+///
+/// * **actual** is the pair info returned by the pair.
+pub fn sync_pair_info(registered: &PairInfo, actual: PairInfo) -> StdResult<Option<PairInfo>> {
+    let fields = diverged_pair_fields(registered, &actual);
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    if fields.iter().any(|field| field == "contract_addr") {
+        return Err(StdError::generic_err(format!(
+            "The pair {} reported the address {}",
+            registered.contract_addr, actual.contract_addr
+        )));
+    }
+    assert_distinct_assets(&actual.asset_infos)?;
+    Ok(Some(PairInfo {
+        asset_infos: actual.asset_infos,
+        liquidity_token: actual.liquidity_token,
+        pair_type: actual.pair_type,
+        capabilities: actual.capabilities,
+        ..registered.clone()
+    }))
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the metadata of the requested assets.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetsMetadataResponse {
+    /// the metadata in the order of the requested assets
+    pub assets: Vec<AssetMetadata>,
+}
+
+/// ## Description
+/// This structure describes what the factory knows about an asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetMetadata {
+    /// the type of asset available in [`AssetInfo`]
+    pub info: AssetInfo,
+    /// the decimals of the asset, [`None`] if the token info query of a CW20 token failed
+    pub decimals: Option<u8>,
+    /// the pair whose liquidity token the asset is, [`None`] if it is not a registered LP token
+    pub lp_token_of: Option<Addr>,
+    /// the registered pairs which contain the asset
+    pub pairs: Vec<Addr>,
+}
+
+/// ## Description
+/// This structure describes the number of registered pairs of a pair type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairTypeCount {
+    /// the type of pair available in [`PairType`]
+    pub pair_type: PairType,
+    /// the number of registered pairs of this type
+    pub count: u64,
+}
+
+/// ## Description
+/// This structure describes the message sent by the factory to every registered pair creation hook.
+/// Subscribed contracts have to support it as a variant of their execute messages.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairCreationHookMsg {
+    /// Notifies about a newly created pair
+    OnPairCreated {
+        /// the information about the created pair
+        pair_info: PairInfo,
+    },
+}
+
+/// ## Description
+/// This structure describes the message sent by pairs to the volume tracker after every swap.
+/// Pairs send it as a submessage which ignores errors, so a failing tracker never blocks swaps.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeTrackerMsg {
+    /// Records a swap
+    TrackSwap {
+        /// the sender of the swap
+        trader: Addr,
+        /// the pair contract address
+        pair: Addr,
+        /// the swapped asset
+        offer_asset: Asset,
+        /// the asset received by the trader with the commission deducted
+        return_asset: Asset,
+    },
 }
 
 /// ## Description
@@ -209,3 +1310,20 @@ pub enum UpdateAddr {
     /// Removes contract address.
     Remove {},
 }
+
+impl UpdateAddr {
+    /// ## Description
+    /// Returns the validated new address, or [`None`] if the address is removed.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **api** is a object of type [`Api`].
+    ///
+    /// * **field** is the name of the updated field.
+    pub fn validate(&self, api: &dyn Api, field: &str) -> StdResult<Option<Addr>> {
+        match self {
+            UpdateAddr::Set(addr) => addr_field_validate(api, field, addr).map(Some),
+            UpdateAddr::Remove {} => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, StdError, StdResult, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The length of a single distribution epoch (one week) in seconds
+pub const EPOCH_LENGTH: u64 = 7 * 86400;
+/// The maximum number of epochs that can be claimed in a single transaction
+pub const MAX_CLAIM_EPOCHS: usize = 52;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the contract which provides checkpointed (xASTRO/vxASTRO) balances
+    pub voting_power_contract: String,
+    /// the assets distributed by the contract
+    pub reward_assets: Vec<AssetInfo>,
+    /// the timestamp at which the first epoch starts
+    pub start_time: u64,
+    /// the policy applied to rewards which were not claimed
+    pub unclaimed_policy: UnclaimedPolicy,
+}
+
+/// ## Description
+/// This enum describes what happens with rewards which were not claimed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnclaimedPolicy {
+    /// Unclaimed rewards stay claimable forever
+    Retain {},
+    /// Unclaimed rewards are moved into the current epoch once the given number of epochs has passed
+    Rollover { after_epochs: u64 },
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Buckets all reward assets received since the last checkpoint into the current epoch
+    Checkpoint {},
+    /// ## Description
+    /// Claims the rewards of all finished epochs which were not claimed yet (up to [`MAX_CLAIM_EPOCHS`])
+    Claim {
+        /// the recipient of the rewards
+        recipient: Option<String>,
+    },
+    /// ## Description
+    /// Claims the rewards of the specified finished epochs
+    ClaimMany {
+        /// the epochs to claim
+        epochs: Vec<u64>,
+        /// the recipient of the rewards
+        recipient: Option<String>,
+    },
+    /// ## Description
+    /// Moves the unclaimed rewards of expired epochs into the current epoch according to the [`UnclaimedPolicy`]
+    RolloverUnclaimed {
+        /// the expired epochs to roll over
+        epochs: Vec<u64>,
+    },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the contract which provides checkpointed balances
+        voting_power_contract: Option<String>,
+        /// new reward assets to distribute
+        add_reward_assets: Option<Vec<AssetInfo>>,
+        /// the policy applied to rewards which were not claimed
+        unclaimed_policy: Option<UnclaimedPolicy>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Adds the received tokens to the rewards of the current epoch
+    DepositRewards {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns the current epoch in a [`EpochInfoResponse`] object
+    CurrentEpoch {},
+    /// Returns information about the specified epoch in a [`EpochInfoResponse`] object
+    Epoch { epoch: u64 },
+    /// Returns a list of epochs in a [`EpochsResponse`] object
+    Epochs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the rewards of a user for the specified epoch in a [`UserRewardResponse`] object
+    UserReward { user: String, epoch: u64 },
+    /// Returns all rewards claimable by a user in a [`PendingRewardsResponse`] object
+    PendingRewards { user: String },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the contract which provides checkpointed balances
+    pub voting_power_contract: Addr,
+    /// the assets distributed by the contract
+    pub reward_assets: Vec<AssetInfo>,
+    /// the timestamp at which the first epoch starts
+    pub start_time: u64,
+    /// the policy applied to rewards which were not claimed
+    pub unclaimed_policy: UnclaimedPolicy,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochInfoResponse {
+    /// the epoch number
+    pub epoch: u64,
+    /// the start time of the epoch
+    pub start_time: u64,
+    /// the end time of the epoch
+    pub end_time: u64,
+    /// the rewards bucketed into the epoch
+    pub rewards: Vec<Asset>,
+    /// the rewards of the epoch which were already claimed
+    pub claimed: Vec<Asset>,
+    /// the total voting power checkpointed at the end of the epoch
+    pub total_voting_power: Option<Uint128>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochsResponse {
+    pub epochs: Vec<EpochInfoResponse>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserRewardResponse {
+    /// the epoch number
+    pub epoch: u64,
+    /// the rewards of the user for the epoch
+    pub rewards: Vec<Asset>,
+    /// whether the rewards were already claimed
+    pub claimed: bool,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    /// the total claimable rewards
+    pub rewards: Vec<Asset>,
+    /// the epochs which are claimable
+    pub epochs: Vec<u64>,
+}
+
+/// ## Description
+/// This structure describes the queries the contract sends to the voting power contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingPowerQueryMsg {
+    /// Returns the voting power of a user at the given time in a [`VotingPowerResponse`] object
+    UserVotingPowerAt { user: String, time: u64 },
+    /// Returns the total voting power at the given time in a [`VotingPowerResponse`] object
+    TotalVotingPowerAt { time: u64 },
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingPowerResponse {
+    pub voting_power: Uint128,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns the epoch which contains the specified timestamp.
+/// ## Params
+/// * **start_time** is the timestamp at which the first epoch starts.
+///
+/// * **time** is the timestamp for which the epoch is calculated.
+pub fn get_epoch(start_time: u64, time: u64) -> StdResult<u64> {
+    if time < start_time {
+        return Err(StdError::generic_err(
+            "Time is before the start of the first epoch",
+        ));
+    }
+
+    Ok((time - start_time) / EPOCH_LENGTH)
+}
+
+/// ## Description
+/// Returns the start timestamp of the specified epoch.
+/// ## Params
+/// * **start_time** is the timestamp at which the first epoch starts.
+///
+/// * **epoch** is the epoch number.
+pub fn get_epoch_start(start_time: u64, epoch: u64) -> u64 {
+    start_time + epoch * EPOCH_LENGTH
+}
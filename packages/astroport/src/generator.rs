@@ -1,7 +1,841 @@
-use cosmwasm_std::{Addr, Decimal, Uint128, Uint64};
-use cw20::Cw20ReceiveMsg;
+use crate::asset::PairInfo;
+use crate::factory::UpdateAddr;
+use crate::querier::query_pair_by_lp_token;
+use crate::DecimalCheckedOps;
+use cosmwasm_std::{
+    to_binary, Addr, Coin, CosmosMsg, Decimal, Decimal256, Fraction, QuerierWrapper, ReplyOn,
+    StdError, StdResult, SubMsg, Uint128, Uint256, Uint64, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+
+/// ## Description
+/// Returns the reward index whose numerator is the given amount, i.e. the amount scaled by the
+/// `1e18` precision of [`Decimal256`].
+/// ## Params
+/// * **atomics** is the scaled amount of rewards per share.
+fn reward_index_from_atomics(atomics: Uint256) -> Decimal256 {
+    Decimal256::from_ratio(atomics, Decimal256::one().numerator())
+}
+
+/// ## Description
+/// Returns the increase of a reward index for distributing the reward among the total share.
+/// Reward indexes are [`Decimal256`] amounts of rewards per share, so unlike [`Decimal`] they
+/// don't overflow for large rewards over a small LP supply. The part of a reward below the `1e18`
+/// precision is lost, see [`reward_index_increase_with_remainder`] to carry it over instead.
+/// ## Params
+/// * **reward** is the amount of distributed rewards.
+///
+/// * **total_share** is the total amount of LP tokens deposited in the pool.
+pub fn reward_index_increase(reward: Uint128, total_share: Uint128) -> StdResult<Decimal256> {
+    if total_share.is_zero() {
+        return Err(StdError::generic_err(
+            "Cannot distribute rewards to empty pool",
+        ));
+    }
+
+    Ok(Decimal256::from_ratio(reward, total_share))
+}
+
+/// ## Description
+/// Returns the proxy reward index increase and the new orphan proxy rewards of a pool after the
+/// proxy received new rewards. Rewards received while no LP tokens are staked can't be
+/// distributed, so they are added to the orphan proxy rewards which are recovered with
+/// [`ExecuteMsg::SendOrphanProxyReward`].
+/// ## Params
+/// * **received** is the amount of proxy rewards received since the last update.
+///
+/// * **total_share** is the total amount of LP tokens deposited in the pool.
+///
+/// * **orphan_proxy_rewards** are the current orphan proxy rewards of the pool.
+pub fn distribute_proxy_rewards(
+    received: Uint128,
+    total_share: Uint128,
+    orphan_proxy_rewards: Uint128,
+) -> StdResult<(Decimal256, Uint128)> {
+    if total_share.is_zero() {
+        return Ok((
+            Decimal256::zero(),
+            orphan_proxy_rewards.checked_add(received)?,
+        ));
+    }
+    Ok((
+        reward_index_increase(received, total_share)?,
+        orphan_proxy_rewards,
+    ))
+}
+
+/// ## Description
+/// Returns the amount of LP tokens multiplied by the increase of a reward index, scaled by the
+/// `1e18` precision of [`Decimal256`].
+/// ## Params
+/// * **amount** is the amount of LP tokens deposited by the user.
+///
+/// * **index** is the current reward index.
+///
+/// * **user_index** is the reward index at the last update of the user's rewards.
+fn scaled_rewards(
+    amount: Uint128,
+    index: Decimal256,
+    user_index: Decimal256,
+) -> StdResult<Uint256> {
+    let index_delta = index
+        .numerator()
+        .checked_sub(user_index.numerator())
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Uint256::from(amount)
+        .checked_mul(index_delta)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// ## Description
+/// Returns the rewards accrued by a deposit between two values of a reward index.
+/// ## Params
+/// * **amount** is the amount of LP tokens deposited by the user.
+///
+/// * **index** is the current reward index.
+///
+/// * **user_index** is the reward index at the last update of the user's rewards.
+pub fn accrued_rewards(
+    amount: Uint128,
+    index: Decimal256,
+    user_index: Decimal256,
+) -> StdResult<Uint128> {
+    (scaled_rewards(amount, index, user_index)? / Decimal256::one().numerator())
+        .try_into()
+        .map_err(|_| StdError::generic_err("Accrued rewards do not fit into Uint128"))
+}
+
+/// ## Description
+/// Converts a reward index stored as [`Decimal`] by previous versions of the generator to a
+/// [`Decimal256`]. Both have a precision of `1e18`, so the conversion is exact. Used by the state
+/// migration.
+/// ## Params
+/// * **index** is the object of type [`Decimal`].
+pub fn decimal_to_reward_index(index: Decimal) -> Decimal256 {
+    Decimal256::from_ratio(index.numerator(), index.denominator())
+}
+
+/// ## Description
+/// Returns the increase of a reward index like [`reward_index_increase`], carrying the part of
+/// the reward below the precision of the index over to the next distribution instead of leaving
+/// it as dust in the generator, so tiny emissions over a huge LP supply are not lost.
+/// Returns the index increase and the new pool remainder, scaled by `1e18`.
+/// ## Params
+/// * **reward** is the amount of distributed rewards.
+///
+/// * **total_share** is the total amount of LP tokens deposited in the pool.
+///
+/// * **remainder** is the scaled reward left by the previous distribution.
+pub fn reward_index_increase_with_remainder(
+    reward: Uint128,
+    total_share: Uint128,
+    remainder: Uint256,
+) -> StdResult<(Decimal256, Uint256)> {
+    if total_share.is_zero() {
+        return Err(StdError::generic_err(
+            "Cannot distribute rewards to empty pool",
+        ));
+    }
+
+    let scaled = Uint256::from(reward)
+        .checked_mul(Decimal256::one().numerator())
+        .and_then(|scaled| scaled.checked_add(remainder))
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let total_share = Uint256::from(total_share);
+    let increase = scaled / total_share;
+    Ok((
+        reward_index_from_atomics(increase),
+        scaled - increase * total_share,
+    ))
+}
+
+/// ## Description
+/// Returns the rewards accrued by a deposit like [`accrued_rewards`], carrying the fraction of a
+/// token which can't be paid out over to the next claim of the user.
+/// Returns the rewards and the new user remainder, scaled by `1e18`.
+/// ## Params
+/// * **amount** is the amount of LP tokens deposited by the user.
+///
+/// * **index** is the current reward index.
+///
+/// * **user_index** is the reward index at the last update of the user's rewards.
+///
+/// * **remainder** is the scaled reward left by the previous claim of the user.
+pub fn accrued_rewards_with_remainder(
+    amount: Uint128,
+    index: Decimal256,
+    user_index: Decimal256,
+    remainder: Uint256,
+) -> StdResult<(Uint128, Uint256)> {
+    let scaled = scaled_rewards(amount, index, user_index)?
+        .checked_add(remainder)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let precision = Decimal256::one().numerator();
+
+    let rewards = scaled / precision;
+    let remainder = scaled - rewards * precision;
+    Ok((
+        rewards
+            .try_into()
+            .map_err(|_| StdError::generic_err("Accrued rewards do not fit into Uint128"))?,
+        remainder,
+    ))
+}
+
+/// The maximum number of users in a single [`QueryMsg::PendingTokenMany`] query
+pub const MAX_PENDING_TOKEN_USERS: usize = 50;
+
+/// The minimum delay in seconds between scheduling allocation point changes and their effective time
+pub const MIN_SCHEDULE_DELAY: u64 = 86400;
+
+/// ## Description
+/// Returns an [`Err`] if scheduled allocation point changes take effect sooner than
+/// [`MIN_SCHEDULE_DELAY`], a pool is scheduled more than once or the total share after applying
+/// the changes exceeds one. Pools which are not listed keep their shares, so the projected total
+/// is the current total minus the current shares of the scheduled pools plus their new shares.
+/// ## Params
+/// * **pools** are the LP tokens and their new allocation points.
+///
+/// * **current_alloc_points** are the current allocation points of the scheduled pools in the same order, zero for new pools.
+///
+/// * **total_alloc_point** is the current total allocation share of all pools.
+///
+/// * **time** is the current timestamp.
+///
+/// * **effective_at** is the timestamp at which the changes take effect.
+pub fn validate_pools_schedule(
+    pools: &[(String, Decimal)],
+    current_alloc_points: &[Decimal],
+    total_alloc_point: Decimal,
+    time: u64,
+    effective_at: u64,
+) -> StdResult<()> {
+    if pools.is_empty() {
+        return Err(StdError::generic_err("No pools are scheduled"));
+    }
+    let earliest = time.saturating_add(MIN_SCHEDULE_DELAY);
+    if effective_at < earliest {
+        return Err(StdError::generic_err(format!(
+            "Allocation point changes can't take effect before {}",
+            earliest
+        )));
+    }
+    if current_alloc_points.len() != pools.len() {
+        return Err(StdError::generic_err(
+            "The current allocation points must match the scheduled pools",
+        ));
+    }
+    let mut total = total_alloc_point;
+    for (i, (lp_token, _)) in pools.iter().enumerate() {
+        if pools[..i].iter().any(|(other, _)| other == lp_token) {
+            return Err(StdError::generic_err(format!(
+                "Pool {} is scheduled more than once",
+                lp_token
+            )));
+        }
+        total = total.checked_sub(current_alloc_points[i])?;
+    }
+    for (_, alloc_point) in pools {
+        total = total.checked_add(*alloc_point)?;
+    }
+    if total > Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "The total allocation share {} after the scheduled changes can't exceed 1",
+            total
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the number of blocks for which a pool earns ASTRO since its last reward update. A pool
+/// with an activation block earns nothing before it.
+/// ## Params
+/// * **last_reward_block** is the block of the last reward update of the pool.
+///
+/// * **current_block** is the current block.
+///
+/// * **activation_block** is an [`Option`] field with the block at which the pool emissions start.
+pub fn get_rewarded_blocks(
+    last_reward_block: u64,
+    current_block: u64,
+    activation_block: Option<u64>,
+) -> u64 {
+    let start = std::cmp::max(last_reward_block, activation_block.unwrap_or_default());
+    current_block.saturating_sub(start)
+}
+
+/// ## Description
+/// Splits the blocks since the last reward update of a pool into the blocks for which the pool
+/// earns ASTRO and the blocks skipped while it is paused, see [`ExecuteMsg::PausePool`]. Returns
+/// the rewarded and the skipped blocks.
+/// ## Params
+/// * **last_reward_block** is the block of the last reward update of the pool.
+///
+/// * **current_block** is the current block.
+///
+/// * **activation_block** is an [`Option`] field with the block at which the pool emissions start.
+///
+/// * **paused_since** is an [`Option`] field with the block at which the pool was paused.
+pub fn get_paused_blocks(
+    last_reward_block: u64,
+    current_block: u64,
+    activation_block: Option<u64>,
+    paused_since: Option<u64>,
+) -> (u64, u64) {
+    let rewarded = get_rewarded_blocks(last_reward_block, current_block, activation_block);
+    match paused_since {
+        Some(paused_since) => {
+            let start = current_block - rewarded;
+            let rewarded_until = std::cmp::min(current_block, std::cmp::max(start, paused_since));
+            (rewarded_until - start, current_block - rewarded_until)
+        }
+        None => (rewarded, 0),
+    }
+}
+
+/// ## Description
+/// This enum describes what happens to the ASTRO a paused pool would have earned.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PausedEmissions {
+    /// The ASTRO is burned
+    Burn {},
+    /// The ASTRO is sent back to the vesting contract
+    ReturnToVesting {},
+}
+
+impl Default for PausedEmissions {
+    fn default() -> Self {
+        PausedEmissions::ReturnToVesting {}
+    }
+}
+
+impl PausedEmissions {
+    /// ## Description
+    /// Returns the message which burns or returns the ASTRO skipped by a paused pool, or
+    /// [`None`] if nothing was skipped.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **astro_token** is the ASTRO token contract.
+    ///
+    /// * **vesting_contract** is the vesting contract which funds the emissions.
+    ///
+    /// * **amount** is the skipped ASTRO.
+    pub fn msg(
+        &self,
+        astro_token: &Addr,
+        vesting_contract: &Addr,
+        amount: Uint128,
+    ) -> StdResult<Option<CosmosMsg>> {
+        if amount.is_zero() {
+            return Ok(None);
+        }
+        let msg = match self {
+            PausedEmissions::Burn {} => Cw20ExecuteMsg::Burn { amount },
+            PausedEmissions::ReturnToVesting {} => Cw20ExecuteMsg::Transfer {
+                recipient: vesting_contract.to_string(),
+                amount,
+            },
+        };
+        Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: astro_token.to_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        })))
+    }
+}
+
+/// The maximum number of points in the emission schedule
+pub const MAX_EMISSION_POINTS: usize = 20;
+
+/// ## Description
+/// This structure describes a change of the ASTRO emission rate at a given block.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionPoint {
+    /// the block from which the rate applies
+    pub start_block: u64,
+    /// the ASTRO emitted per block from the start block
+    pub tokens_per_block: Uint128,
+}
+
+/// ## Description
+/// Returns an [`Err`] if the emission schedule has more than [`MAX_EMISSION_POINTS`] points, its
+/// points are not ordered by strictly increasing start blocks or a point is not in the future.
+/// ## Params
+/// * **schedule** are the points of the emission schedule.
+///
+/// * **current_block** is the current block.
+pub fn validate_emission_schedule(schedule: &[EmissionPoint], current_block: u64) -> StdResult<()> {
+    if schedule.len() > MAX_EMISSION_POINTS {
+        return Err(StdError::generic_err(format!(
+            "The emission schedule can't have more than {} points",
+            MAX_EMISSION_POINTS
+        )));
+    }
+    let mut last_block = current_block;
+    for point in schedule {
+        if point.start_block <= last_block {
+            return Err(StdError::generic_err(format!(
+                "Emission point start block {} must be greater than {}",
+                point.start_block, last_block
+            )));
+        }
+        last_block = point.start_block;
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the ASTRO emitted per block at the given block.
+/// ## Params
+/// * **tokens_per_block** is the rate applied before the first point of the schedule.
+///
+/// * **schedule** are the points of the emission schedule ordered by start block.
+///
+/// * **block** is the block for which the rate is returned.
+pub fn get_tokens_per_block(
+    tokens_per_block: Uint128,
+    schedule: &[EmissionPoint],
+    block: u64,
+) -> Uint128 {
+    schedule
+        .iter()
+        .take_while(|point| point.start_block <= block)
+        .last()
+        .map(|point| point.tokens_per_block)
+        .unwrap_or(tokens_per_block)
+}
+
+/// ## Description
+/// Returns the total ASTRO emitted from `from_block` up to `to_block`, applying every rate of
+/// the schedule to the blocks it covers, so rewards are accrued correctly across schedule boundaries.
+/// ## Params
+/// * **tokens_per_block** is the rate applied before the first point of the schedule.
+///
+/// * **schedule** are the points of the emission schedule ordered by start block.
+///
+/// * **from_block** is the block of the last reward update.
+///
+/// * **to_block** is the current block.
+pub fn get_emissions(
+    tokens_per_block: Uint128,
+    schedule: &[EmissionPoint],
+    from_block: u64,
+    to_block: u64,
+) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    let mut block = from_block;
+    let mut rate = get_tokens_per_block(tokens_per_block, schedule, from_block);
+    for point in schedule
+        .iter()
+        .filter(|point| point.start_block > from_block && point.start_block < to_block)
+    {
+        total = total.checked_add(rate.checked_mul(Uint128::from(point.start_block - block))?)?;
+        block = point.start_block;
+        rate = point.tokens_per_block;
+    }
+    if to_block > block {
+        total = total.checked_add(rate.checked_mul(Uint128::from(to_block - block))?)?;
+    }
+    Ok(total)
+}
+
+/// The average block time in milliseconds used to convert seconds into blocks for projections
+pub const ESTIMATED_BLOCK_TIME_MS: u64 = crate::chain::BLOCK_TIME_MS;
+
+/// ## Description
+/// Returns the number of blocks expected in the specified number of seconds.
+/// ## Params
+/// * **seconds** is the projection period.
+pub fn estimate_blocks(seconds: u64) -> u64 {
+    seconds.saturating_mul(1000) / ESTIMATED_BLOCK_TIME_MS
+}
+
+/// ## Description
+/// Returns the ASTRO projected to be distributed to a pool and earned by a deposit, assuming the
+/// current allocation points and boosts don't change. The emission schedule is applied by the
+/// caller via [`get_emissions`].
+/// ## Params
+/// * **emissions** is the ASTRO emitted by the generator in the projected blocks.
+///
+/// * **alloc_point** is the allocation share of the pool.
+///
+/// * **total_alloc_point** is the total allocation share of all pools.
+///
+/// * **stake** is the effective stake of the user, see [`effective_stake`].
+pub fn project_rewards(
+    emissions: Uint128,
+    alloc_point: Decimal,
+    total_alloc_point: Decimal,
+    stake: &UserEffectiveStake,
+) -> (Uint128, Uint128) {
+    if total_alloc_point.is_zero() {
+        return (Uint128::zero(), Uint128::zero());
+    }
+    let pool_rewards =
+        emissions.multiply_ratio(alloc_point.numerator(), total_alloc_point.numerator());
+    (pool_rewards, pool_rewards * stake.emission_share)
+}
+
+/// ## Description
+/// Returns the fraction of the emissions distributed to a pool. Allocation shares are normalized
+/// by their total, so the emissions are fully distributed even if the shares sum to less than one.
+/// Returns zero if no pool has an allocation share.
+/// ## Params
+/// * **alloc_point** is the allocation share of the pool.
+///
+/// * **total_alloc_point** is the total allocation share of all pools.
+pub fn pool_emission_share(alloc_point: Decimal, total_alloc_point: Decimal) -> Decimal {
+    if total_alloc_point.is_zero() {
+        return Decimal::zero();
+    }
+    Decimal::from_ratio(alloc_point.numerator(), total_alloc_point.numerator())
+}
+
+/// ## Description
+/// Returns the new total allocation share after replacing the share of a pool, or an [`Err`] if
+/// the share or the total would exceed one. A share above one is usually a legacy integer
+/// allocation point, callers convert them with [`normalize_alloc_points`].
+/// ## Params
+/// * **total_alloc_point** is the current total allocation share of all pools.
+///
+/// * **old_alloc_point** is the current allocation share of the pool.
+///
+/// * **new_alloc_point** is the new allocation share of the pool.
+pub fn update_total_alloc_point(
+    total_alloc_point: Decimal,
+    old_alloc_point: Decimal,
+    new_alloc_point: Decimal,
+) -> StdResult<Decimal> {
+    if new_alloc_point > Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "The allocation share {} can't exceed 1, integer allocation points must be converted to shares",
+            new_alloc_point
+        )));
+    }
+    let total = total_alloc_point
+        .checked_sub(old_alloc_point)?
+        .checked_add(new_alloc_point)?;
+    if total > Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "The total allocation share {} can't exceed 1",
+            total
+        )));
+    }
+    Ok(total)
+}
+
+/// ## Description
+/// Returns an [`Err`] if the activation block of a new pool is not in the future.
+/// ## Params
+/// * **current_block** is the current block.
+///
+/// * **activation_block** is the block at which the pool emissions start.
+pub fn validate_activation_block(current_block: u64, activation_block: u64) -> StdResult<()> {
+    if activation_block <= current_block {
+        return Err(StdError::generic_err(format!(
+            "The activation block must be after the current block {}",
+            current_block
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns an [`Err`] if the LP token is neither whitelisted as an external token nor the
+/// liquidity token of a pair registered in the factory.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factory** is an [`Option`] field with the factory contract address.
+///
+/// * **lp_token** is the LP token contract address.
+///
+/// * **external_lp_tokens** are the whitelisted tokens which are not factory LP tokens.
+pub fn validate_lp_token(
+    querier: &QuerierWrapper,
+    factory: Option<&Addr>,
+    lp_token: &Addr,
+    external_lp_tokens: &[Addr],
+) -> StdResult<()> {
+    if external_lp_tokens.contains(lp_token) {
+        return Ok(());
+    }
+    let factory = factory.ok_or_else(|| {
+        StdError::generic_err(format!(
+            "{} is not whitelisted and the factory is not set",
+            lp_token
+        ))
+    })?;
+    match query_pair_by_lp_token(querier, factory.clone(), lp_token.clone())? {
+        Some(_) => Ok(()),
+        None => Err(StdError::generic_err(format!(
+            "{} is not the LP token of a registered pair",
+            lp_token
+        ))),
+    }
+}
+
+/// The maximum number of pools harvested by a single [`ExecuteMsg::HarvestProxies`]
+pub const MAX_HARVEST_PROXIES: usize = 10;
+
+/// ## Description
+/// Returns true if the reward proxy of a pool can be harvested. Without a harvest interval proxy
+/// rewards are claimed on user actions, so harvests are always allowed.
+/// ## Params
+/// * **last_harvest** is an [`Option`] field with the time of the last harvest of the pool.
+///
+/// * **interval** is an [`Option`] field with the minimum number of seconds between two harvests.
+///
+/// * **time** is the current timestamp.
+pub fn is_proxy_harvest_due(last_harvest: Option<u64>, interval: Option<u64>, time: u64) -> bool {
+    match (last_harvest, interval) {
+        (Some(last_harvest), Some(interval)) => time >= last_harvest.saturating_add(interval),
+        _ => true,
+    }
+}
+
+/// The maximum number of hooks of a pool
+pub const MAX_POOL_HOOKS: usize = 5;
+/// The gas limit of a single pool hook call, so hooks can't make user actions run out of gas
+pub const POOL_HOOK_GAS_LIMIT: u64 = 200_000;
+/// The reply identifier of pool hook calls, their failures are ignored
+pub const POOL_HOOK_REPLY_ID: u64 = 1;
+
+/// ## Description
+/// Returns an [`Err`] if there are more than [`MAX_POOL_HOOKS`] hooks or a hook is duplicated.
+/// ## Params
+/// * **hooks** are the hook contracts of the pool.
+pub fn validate_pool_hooks(hooks: &[Addr]) -> StdResult<()> {
+    if hooks.len() > MAX_POOL_HOOKS {
+        return Err(StdError::generic_err(format!(
+            "A pool can have at most {} hooks",
+            MAX_POOL_HOOKS
+        )));
+    }
+    for (i, hook) in hooks.iter().enumerate() {
+        if hooks[..i].contains(hook) {
+            return Err(StdError::generic_err(format!(
+                "Duplicated pool hook {}",
+                hook
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the sub-messages notifying the hooks of a pool. Each call has [`POOL_HOOK_GAS_LIMIT`]
+/// and replies with [`POOL_HOOK_REPLY_ID`] on error only, so a failing hook doesn't revert the
+/// user action.
+/// ## Params
+/// * **hooks** are the hook contracts of the pool.
+///
+/// * **msg** is the object of type [`PoolHookMsg`].
+pub fn pool_hook_msgs(hooks: &[Addr], msg: &PoolHookMsg) -> StdResult<Vec<SubMsg>> {
+    let msg = to_binary(msg)?;
+    Ok(hooks
+        .iter()
+        .map(|hook| SubMsg {
+            id: POOL_HOOK_REPLY_ID,
+            msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: hook.to_string(),
+                msg: msg.clone(),
+                funds: vec![],
+            }),
+            gas_limit: Some(POOL_HOOK_GAS_LIMIT),
+            reply_on: ReplyOn::Error,
+        })
+        .collect())
+}
+
+/// ## Description
+/// Returns an [`Err`] if the funds attached to [`ExecuteMsg::RegisterPool`] don't cover the
+/// registration deposit.
+/// ## Params
+/// * **deposit** is an [`Option`] field with the required registration deposit.
+///
+/// * **funds** are the coins attached to the message.
+pub fn assert_registration_deposit(deposit: Option<&Coin>, funds: &[Coin]) -> StdResult<()> {
+    if let Some(deposit) = deposit {
+        let sent = funds
+            .iter()
+            .find(|coin| coin.denom == deposit.denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+        if sent < deposit.amount {
+            return Err(StdError::generic_err(format!(
+                "A registration deposit of {} is required",
+                deposit
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// ## Description
+/// This structure describes the deposit caps of a pool during guarded launches.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositCaps {
+    /// the maximum amount of LP tokens deposited by a single user
+    pub per_user: Option<Uint128>,
+    /// the maximum amount of LP tokens deposited in the pool
+    pub per_pool: Option<Uint128>,
+}
+
+impl DepositCaps {
+    /// ## Description
+    /// Returns an [`Err`] if a deposit exceeds the cap per user or per pool.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **user_amount** is the amount deposited by the user including the new deposit.
+    ///
+    /// * **pool_amount** is the amount deposited in the pool including the new deposit.
+    pub fn assert_deposit_allowed(
+        &self,
+        user_amount: Uint128,
+        pool_amount: Uint128,
+    ) -> StdResult<()> {
+        if let Some(cap) = self.per_user {
+            if user_amount > cap {
+                return Err(StdError::generic_err(format!(
+                    "The deposit exceeds the cap of {} per user",
+                    cap
+                )));
+            }
+        }
+        if let Some(cap) = self.per_pool {
+            if pool_amount > cap {
+                return Err(StdError::generic_err(format!(
+                    "The deposit exceeds the pool cap of {}",
+                    cap
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The maximum reward weight multiplier of a boost NFT tier
+pub const MAX_BOOST_MULTIPLIER: &str = "2.5";
+
+/// ## Description
+/// Returns an [`Err`] if the multiplier of a boost tier is lower than one or higher than
+/// [`MAX_BOOST_MULTIPLIER`].
+/// ## Params
+/// * **multiplier** is the reward weight multiplier of the tier.
+pub fn validate_boost_multiplier(multiplier: Decimal) -> StdResult<()> {
+    let max_multiplier: Decimal = MAX_BOOST_MULTIPLIER.parse()?;
+    if multiplier < Decimal::one() || multiplier > max_multiplier {
+        return Err(StdError::generic_err(format!(
+            "Boost multiplier must be between 1 and {}",
+            max_multiplier
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the reward weight of a deposit boosted by the multiplier of its NFT tier.
+/// ## Params
+/// * **amount** is the amount of LP tokens deposited by the user.
+///
+/// * **multiplier** is the multiplier of the tier of the NFT attached to the deposit, if any.
+pub fn boosted_amount(amount: Uint128, multiplier: Option<Decimal>) -> Uint128 {
+    match multiplier {
+        Some(multiplier) => amount.multiply_ratio(multiplier.numerator(), multiplier.denominator()),
+        None => amount,
+    }
+}
+
+/// ## Description
+/// Returns the effective stake of a user in a pool.
+/// ## Params
+/// * **amount** is the amount of LP tokens deposited by the user.
+///
+/// * **multiplier** is the reward weight multiplier of the deposit.
+///
+/// * **total_effective** is the sum of the boosted reward weights of all deposits in the pool.
+pub fn effective_stake(
+    amount: Uint128,
+    multiplier: Decimal,
+    total_effective: Uint128,
+) -> StdResult<UserEffectiveStake> {
+    let effective = boosted_amount(amount, Some(multiplier));
+    let max_multiplier: Decimal = MAX_BOOST_MULTIPLIER.parse()?;
+    let emission_share = if total_effective.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(effective, total_effective)
+    };
+    Ok(UserEffectiveStake {
+        amount,
+        effective,
+        multiplier,
+        max_effective: boosted_amount(amount, Some(max_multiplier)),
+        emission_share,
+    })
+}
+
+/// ## Description
+/// Checks that activating a pool keeps the number of pools with a nonzero allocation point within
+/// the limit. Returns an [`Err`] if the limit is exceeded or the evicted pools are not valid.
+/// ## Params
+/// * **active_pools** are the LP tokens of the pools which have a nonzero allocation point.
+///
+/// * **limit** is the maximum number of simultaneously incentivized pools, zero means no limit.
+///
+/// * **lp_token** is the LP token of the pool which gets a nonzero allocation point.
+///
+/// * **evict** are the LP tokens of the pools whose allocation point is set to zero.
+pub fn assert_active_pools_limit(
+    active_pools: &[Addr],
+    limit: Option<u32>,
+    lp_token: &Addr,
+    evict: &[Addr],
+) -> StdResult<()> {
+    for (i, evicted) in evict.iter().enumerate() {
+        if evicted == lp_token {
+            return Err(StdError::generic_err(format!(
+                "Pool {} can't be activated and evicted at once",
+                evicted
+            )));
+        }
+        if !active_pools.contains(evicted) {
+            return Err(StdError::generic_err(format!(
+                "Pool {} is not active and can't be evicted",
+                evicted
+            )));
+        }
+        if evict[..i].contains(evicted) {
+            return Err(StdError::generic_err(format!(
+                "Pool {} is evicted more than once",
+                evicted
+            )));
+        }
+    }
+
+    if let Some(limit) = limit.filter(|limit| *limit > 0) {
+        let mut count = active_pools.len() - evict.len();
+        if !active_pools.contains(lp_token) {
+            count += 1;
+        }
+        if count > limit as usize {
+            return Err(StdError::generic_err(format!(
+                "The number of active pools {} exceeds the limit {}, specify the pools to evict",
+                count, limit
+            )));
+        }
+    }
+
+    Ok(())
+}
 
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
@@ -19,6 +853,39 @@ pub struct InstantiateMsg {
     pub allowed_reward_proxies: Vec<String>,
     /// a vesting contract
     pub vesting_contract: String,
+    /// the factory contract which notifies the generator about created pairs
+    pub factory: Option<String>,
+    /// the maximum number of pools with a nonzero allocation point
+    pub active_pools_limit: Option<u32>,
+    /// the maker contract which swaps proxy rewards to ASTRO for users who opted in
+    pub maker_contract: Option<String>,
+    /// the CW721 contract whose NFTs boost the reward weight of deposits
+    pub boost_nft: Option<String>,
+    /// the staking contract whose xASTRO locks boost the reward weight of deposits
+    pub staking_boost: Option<String>,
+    /// whether the chain may update the reward indexes via [`SudoMsg::UpdateEmissions`]
+    #[serde(default)]
+    pub sudo_updates_enabled: bool,
+    /// the changes of the emission rate after the start block, see [`validate_emission_schedule`]
+    #[serde(default)]
+    pub emission_schedule: Vec<EmissionPoint>,
+    /// the anti-spam deposit paid by [`ExecuteMsg::RegisterPool`], registration is free if not set
+    #[serde(default)]
+    pub registration_deposit: Option<Coin>,
+    /// the minimum number of seconds between two [`ExecuteMsg::HarvestProxies`] of a pool. Proxy
+    /// rewards are claimed on every user action if not set
+    #[serde(default)]
+    pub proxy_harvest_interval: Option<u64>,
+    /// the address which can zero the allocation point of a pool via [`ExecuteMsg::EmergencyZeroAlloc`]
+    #[serde(default)]
+    pub guardian: Option<String>,
+    /// what happens to the ASTRO paused pools would earn
+    #[serde(default)]
+    pub paused_emissions: PausedEmissions,
+    /// the previous generator whose deposits can be moved with [`ExecuteMsg::MigrateUserPositions`]
+    /// and [`ExecuteMsg::MigrateMyPosition`]
+    #[serde(default)]
+    pub legacy_generator: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -31,18 +898,74 @@ pub enum ExecuteMsg {
     UpdateConfig {
         /// the vesting contract
         vesting_contract: Option<String>,
+        /// the factory contract which notifies the generator about created pairs
+        factory: Option<String>,
+        /// the maximum number of pools with a nonzero allocation point, zero removes the limit
+        active_pools_limit: Option<u32>,
+        /// the maker contract which swaps proxy rewards to ASTRO
+        maker_contract: Option<String>,
+        /// the CW721 contract whose NFTs boost the reward weight of deposits
+        boost_nft: Option<String>,
+        /// the staking contract whose xASTRO locks boost the reward weight of deposits
+        staking_boost: Option<String>,
+        /// whether the chain may update the reward indexes via [`SudoMsg::UpdateEmissions`]
+        sudo_updates_enabled: Option<bool>,
+        /// the anti-spam deposit paid by [`ExecuteMsg::RegisterPool`]
+        #[serde(default)]
+        registration_deposit: Option<Coin>,
+        /// the minimum number of seconds between two proxy harvests of a pool, zero claims proxy
+        /// rewards on every user action again
+        #[serde(default)]
+        proxy_harvest_interval: Option<u64>,
+        /// the address which can zero the allocation point of a pool
+        #[serde(default)]
+        guardian: Option<UpdateAddr>,
+        /// what happens to the ASTRO paused pools would earn
+        #[serde(default)]
+        paused_emissions: Option<PausedEmissions>,
     },
     /// ## Description
-    /// Add a new liquidity pool token:
+    /// Add a new liquidity pool token. The LP token must pass [`validate_lp_token`]
     /// ## Executor
     /// Only owner can execute it
     Add {
         /// the LP token contract address
         lp_token: String,
-        /// the allocation point of liquidity pool
-        alloc_point: Uint64,
+        /// the allocation share of liquidity pool, the shares of all pools can't sum to more than
+        /// one, see [`update_total_alloc_point`]. This was an integer weight before, which is
+        /// rejected now unless converted with [`normalize_alloc_points`]
+        alloc_point: Decimal,
         /// the reward proxy contract
         reward_proxy: Option<String>,
+        /// the pools whose allocation point is set to zero to stay within the active pools limit
+        evict: Option<Vec<String>>,
+        /// the block at which the pool starts earning ASTRO, immediately if not set. LP tokens
+        /// can be deposited before it
+        #[serde(default)]
+        activation_block: Option<u64>,
+    },
+    /// ## Description
+    /// Creates a pool with zero allocation point for the LP token of a newly created pair.
+    /// Sent by the factory as a pair creation hook.
+    /// ## Executor
+    /// Only factory can execute it
+    OnPairCreated {
+        /// the information about the created pair
+        pair_info: PairInfo,
+    },
+    /// ## Description
+    /// Creates a pool with zero allocation point for the LP token of an existing factory pair, so
+    /// incentive campaigns via reward proxies can start before governance allocates ASTRO. The
+    /// registration deposit must be attached, see [`assert_registration_deposit`], and is sent to
+    /// the owner
+    /// ## Executor
+    /// Anyone can execute it
+    RegisterPool {
+        /// the LP token contract address, it must pass [`validate_lp_token`] without whitelisted
+        /// external tokens
+        lp_token: String,
+        /// the reward proxy contract, it must be allowed
+        reward_proxy: Option<String>,
     },
     /// ## Description
     /// Update the given pool's ASTRO allocation point
@@ -51,8 +974,143 @@ pub enum ExecuteMsg {
     Set {
         /// the LP token contract address
         lp_token: String,
-        /// the allocation point of liquidity pool
-        alloc_point: Uint64,
+        /// the allocation share of liquidity pool, the shares of all pools can't sum to more than
+        /// one, see [`update_total_alloc_point`]. This was an integer weight before, which is
+        /// rejected now unless converted with [`normalize_alloc_points`]
+        alloc_point: Decimal,
+        /// the pools whose allocation point is set to zero to stay within the active pools limit
+        evict: Option<Vec<String>>,
+    },
+    /// ## Description
+    /// Sets the allocation point of the given pool to zero immediately, e.g. when one of its
+    /// tokens is exploited. Rewards accrued so far stay claimable and deposits can still be
+    /// withdrawn. The scheduled allocation point change of the pool is dropped, so only the owner
+    /// can allocate ASTRO to it again
+    /// ## Executor
+    /// Only guardian can execute it
+    EmergencyZeroAlloc {
+        /// the LP token contract address
+        lp_token: String,
+    },
+    /// ## Description
+    /// Freezes the reward accrual of the given pool without changing its allocation point, e.g.
+    /// when the underlying of the LP token is halted. The ASTRO the pool would earn while paused
+    /// is handled according to the [`PausedEmissions`] of the config, see [`get_paused_blocks`]
+    /// ## Executor
+    /// Only owner or guardian can execute it
+    PausePool {
+        /// the LP token contract address
+        lp_token: String,
+    },
+    /// ## Description
+    /// Resumes the reward accrual of the given paused pool from the current block
+    /// ## Executor
+    /// Only owner or guardian can execute it
+    ResumePool {
+        /// the LP token contract address
+        lp_token: String,
+    },
+    /// ## Description
+    /// Schedules allocation point changes of the given pools which take effect at `effective_at`,
+    /// at least [`MIN_SCHEDULE_DELAY`] seconds later. Replaces the previously scheduled changes.
+    /// ## Executor
+    /// Only owner can execute it
+    ScheduleSetupPools {
+        /// the LP token contract addresses and their new allocation points
+        pools: Vec<(String, Decimal)>,
+        /// the timestamp at which the changes take effect
+        effective_at: u64,
+    },
+    /// ## Description
+    /// Cancels the scheduled allocation point changes
+    /// ## Executor
+    /// Only owner can execute it
+    CancelScheduledPools {},
+    /// ## Description
+    /// Applies the scheduled allocation point changes once their effective time has passed.
+    /// Can be executed by anyone
+    ApplyScheduledPools {},
+    /// ## Description
+    /// Allows or disallows the conversion of the pool's proxy rewards to ASTRO via the maker
+    /// ## Executor
+    /// Only owner can execute it
+    SetProxyRewardConversion {
+        /// the LP token contract address
+        lp_token: String,
+        /// whether users of the pool can convert their proxy rewards
+        enabled: bool,
+    },
+    /// ## Description
+    /// Sets the address which receives the rewards of the sender's deposit in the pool, so e.g.
+    /// a vault can custody the LP tokens while its users receive the emissions directly. Only the
+    /// owner of the deposit can withdraw it or change the beneficiary
+    SetRewardBeneficiary {
+        /// the LP token contract address
+        lp_token: String,
+        /// the recipient of the rewards, [`None`] sends them to the owner of the deposit again
+        beneficiary: Option<String>,
+    },
+    /// ## Description
+    /// Sets the preference of the sender to receive the proxy rewards of the pool in ASTRO. The
+    /// preference only applies to pools with the proxy reward conversion enabled.
+    SetRewardPreference {
+        /// the LP token contract address
+        lp_token: String,
+        /// whether the proxy rewards are converted to ASTRO
+        convert_proxy_rewards: bool,
+    },
+    /// ## Description
+    /// Sets the reward weight multipliers of boost tiers. Every multiplier must be between one
+    /// and [`MAX_BOOST_MULTIPLIER`] and replaces the previous multiplier of its tier
+    /// ## Executor
+    /// Only owner can execute it
+    SetBoostTiers {
+        /// the tiers and their multipliers
+        tiers: Vec<(u8, Decimal)>,
+    },
+    /// ## Description
+    /// Assigns boost tiers to NFTs of the boost NFT contract
+    /// ## Executor
+    /// Only owner can execute it
+    SetNftTiers {
+        /// the NFT token identifiers and their tiers
+        nfts: Vec<(String, u8)>,
+    },
+    /// ## Description
+    /// Attaches an NFT owned by the sender to the sender's deposit, the NFT stays in the sender's
+    /// wallet and is checked to be owned by the sender at every claim
+    AttachBoostNft {
+        /// the LP token contract address
+        lp_token: String,
+        /// the NFT token identifier
+        token_id: String,
+    },
+    /// ## Description
+    /// Detaches the NFT from the sender's deposit
+    DetachBoostNft {
+        /// the LP token contract address
+        lp_token: String,
+    },
+    /// ## Description
+    /// Claims the rewards of the reward proxies of the given pools into the proxy reward buffers
+    /// of the generator, so user claims only move buffered rewards. Pools harvested within the
+    /// proxy harvest interval are skipped, see [`is_proxy_harvest_due`]
+    /// ## Executor
+    /// Anyone can execute it, it is meant to be triggered by keepers
+    HarvestProxies {
+        /// the LP tokens of the pools, at most [`MAX_HARVEST_PROXIES`]
+        lp_tokens: Vec<String>,
+    },
+    /// ## Description
+    /// Replaces the hook contracts of the pool which are notified with a [`PoolHookMsg`] on every
+    /// deposit, withdrawal and claim, see [`pool_hook_msgs`]
+    /// ## Executor
+    /// Only owner can execute it
+    SetPoolHooks {
+        /// the LP token contract address
+        lp_token: String,
+        /// the hook contracts, at most [`MAX_POOL_HOOKS`]
+        hooks: Vec<String>,
     },
     /// ## Description
     /// Updates reward variables for all pools
@@ -72,6 +1130,42 @@ pub enum ExecuteMsg {
         amount: Uint128,
     },
     /// ## Description
+    /// Claims the pending ASTRO and proxy rewards of the sender's deposits in the given pools and
+    /// sends them to the recipient instead of the sender. The deposits stay in the generator.
+    ClaimRewardsTo {
+        /// the LP token contract addresses
+        lp_tokens: Vec<String>,
+        /// the recipient of the rewards
+        recipient: String,
+    },
+    /// ## Description
+    /// Moves the deposits of the users from the legacy generator into this one, paginated over
+    /// the pools of the legacy generator. See [`legacy_release_msg`]
+    /// ## Executor
+    /// Only owner can execute it
+    MigrateUserPositions {
+        /// the users whose deposits are moved
+        users: Vec<String>,
+        /// the LP token after which the pools are migrated
+        start_after: Option<String>,
+        /// the number of pools to migrate, at most [`MAX_MIGRATE_POOLS_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// ## Description
+    /// Moves the deposits of the sender from the legacy generator into this one
+    MigrateMyPosition {},
+    /// ## Description
+    /// Claims the pending rewards of the user to the user, then sends the user's deposits in the
+    /// pools to the successor generator with [`Cw20HookMsg::DepositFor`]
+    /// ## Executor
+    /// Only the successor generator set by the [`MigrateMsg`] can execute it
+    ReleaseToSuccessor {
+        /// the user whose deposits are moved
+        user: String,
+        /// the LP token contract addresses
+        lp_tokens: Vec<String>,
+    },
+    /// ## Description
     /// Withdraw LP tokens from Generator without caring about rewards.
     EmergencyWithdraw {
         /// the LP token contract address
@@ -84,7 +1178,43 @@ pub enum ExecuteMsg {
         proxies: Vec<String>,
     },
     /// ## Description
-    /// Sends the orphan proxy rewards which are left by emergency withdrawals
+    /// Whitelists LP tokens which are not liquidity tokens of factory pairs, so pools can be set
+    /// up for them. Pools of all other LP tokens are validated against the factory
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateExternalLpTokens {
+        /// the tokens to whitelist
+        add: Vec<String>,
+        /// the tokens to remove from the whitelist, existing pools are not affected
+        remove: Vec<String>,
+    },
+    /// ## Description
+    /// Sets or removes the deposit caps of a pool, enforced on every deposit with
+    /// [`DepositCaps::assert_deposit_allowed`]. Existing deposits above the caps are kept
+    /// ## Executor
+    /// Only owner can execute it
+    SetDepositCaps {
+        /// the LP token contract address
+        lp_token: String,
+        /// the new caps, [`None`] removes them
+        caps: Option<DepositCaps>,
+    },
+    /// ## Description
+    /// Attaches an allowed reward proxy to a pool which has none and moves the LP tokens staked
+    /// in the pool into the proxy, so the users start earning the proxy rewards next to ASTRO
+    /// ## Executor
+    /// Only owner can execute it
+    MoveToProxy {
+        /// the LP token contract address
+        lp_token: String,
+        /// the reward proxy contract, must be one of the allowed reward proxies
+        proxy: String,
+    },
+    /// ## Description
+    /// Sends the orphan proxy rewards which are left by emergency withdrawals or were received
+    /// by a proxy while no LP tokens were staked
+    /// ## Executor
+    /// Only owner can execute it
     SendOrphanProxyReward {
         /// the recipient of withdraw
         recipient: String,
@@ -95,6 +1225,39 @@ pub enum ExecuteMsg {
     /// Receives a message of type [`Cw20ReceiveMsg`]
     Receive(Cw20ReceiveMsg),
     /// ## Description
+    /// Handles a [`crate::token::TransferHookMsg`] of an LP token. Transfers from or to the
+    /// generator itself are not notified, as they are deposits and withdrawals
+    /// ## Executor
+    /// Only the LP token of a registered pool can execute it
+    TransferNotification {
+        /// the previous owner of the LP tokens
+        sender: String,
+        /// the new owner of the LP tokens
+        recipient: String,
+        /// the amount of moved LP tokens
+        amount: Uint128,
+    },
+    /// ## Description
+    /// Handles a [`crate::token::TransferHookMsg::MintNotification`] of an LP token
+    /// ## Executor
+    /// Only the LP token of a registered pool can execute it
+    MintNotification {
+        /// the owner of the minted LP tokens
+        recipient: String,
+        /// the amount of minted LP tokens
+        amount: Uint128,
+    },
+    /// ## Description
+    /// Handles a [`crate::token::TransferHookMsg::BurnNotification`] of an LP token
+    /// ## Executor
+    /// Only the LP token of a registered pool can execute it
+    BurnNotification {
+        /// the previous owner of the burned LP tokens
+        owner: String,
+        /// the amount of burned LP tokens
+        amount: Uint128,
+    },
+    /// ## Description
     /// a new count of tokens per block
     /// ## Executor
     /// Only owner can execute it
@@ -103,6 +1266,15 @@ pub enum ExecuteMsg {
         amount: Uint128,
     },
     /// ## Description
+    /// Replaces the emission schedule. The rates of past points are folded into the tokens per
+    /// block, so only future points can be set, see [`validate_emission_schedule`]
+    /// ## Executor
+    /// Only owner can execute it
+    SetEmissionSchedule {
+        /// the future points of the schedule ordered by start block
+        schedule: Vec<EmissionPoint>,
+    },
+    /// ## Description
     /// Creates a request to change ownership
     /// ## Executor
     /// Only owner can execute it
@@ -133,16 +1305,397 @@ pub enum QueryMsg {
     Deposit { lp_token: String, user: String },
     /// PendingToken
     PendingToken { lp_token: String, user: String },
+    /// PendingTokenMany returns the pending rewards of several users, at most
+    /// [`MAX_PENDING_TOKEN_USERS`], in a [`PendingTokenManyResponse`] object
+    PendingTokenMany {
+        lp_token: String,
+        users: Vec<String>,
+    },
     /// Config returns the base setting of the generator
     Config {},
     /// RewardInfo returns reward information for the specified token.
     RewardInfo { lp_token: String },
-    /// OrphanProxyRewards returns reward information for the specified token.
+    /// OrphanProxyRewards returns the amount of orphan proxy rewards for the specified LP token
+    /// in a [`OrphanProxyRewardsResponse`] object.
     OrphanProxyRewards { lp_token: String },
     /// PoolInfo returns currently stored information regarding that Pool alongwith the pending total ASTRO and proxy rewards claimable by the users
     PoolInfo { lp_token: String },
     /// SimulateFutureReward returns the amount of ASTRO distributed at the future block
     SimulateFutureReward { lp_token: String, future_block: u64 },
+    /// ActivePools returns the pools with a nonzero allocation point in a [`ActivePoolsResponse`] object
+    ActivePools {},
+    /// RewardPreference returns the reward preference of a user for the specified LP token in a
+    /// [`RewardPreferenceResponse`] object
+    RewardPreference { lp_token: String, user: String },
+    /// RewardBeneficiary returns the owner and the reward recipient of a user's deposit in a
+    /// [`RewardBeneficiaryResponse`] object
+    RewardBeneficiary { lp_token: String, user: String },
+    /// BoostTiers returns the boost tiers and their multipliers in a [`BoostTiersResponse`] object
+    BoostTiers {},
+    /// UserBoost returns the boost of a user's deposit in a [`UserBoostResponse`] object
+    UserBoost { lp_token: String, user: String },
+    /// ScheduledPools returns the scheduled allocation point changes in a [`ScheduledPoolsResponse`] object
+    ScheduledPools {},
+    /// PoolShares returns the exact fraction of the emissions of every pool with a nonzero
+    /// allocation share in a [`PoolSharesResponse`] object
+    PoolShares {},
+    /// ExternalLpTokens returns the whitelisted LP tokens which are not factory LP tokens in a
+    /// [`ExternalLpTokensResponse`] object
+    ExternalLpTokens {},
+    /// PendingActivationPools returns the pools whose activation block has not been reached yet
+    /// in a [`PendingActivationPoolsResponse`] object
+    PendingActivationPools {},
+    /// UserInfoList returns all deposits of a user together with the pending rewards in a
+    /// [`UserInfoListResponse`] object
+    UserInfoList {
+        /// the user address
+        user: String,
+        /// the LP token to start reading after
+        start_after: Option<String>,
+        /// the number of items to be read, at most [`MAX_USER_INFO_LIST_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// UserPortfolio returns the deposits of a user across all pools together with the pending
+    /// rewards and the boost in a [`UserPortfolioResponse`] object
+    UserPortfolio {
+        /// the user address
+        user: String,
+        /// the LP token to start reading after
+        start_after: Option<String>,
+        /// the number of deposits to be read, at most [`MAX_USER_INFO_LIST_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// EmissionSchedule returns the current and upcoming emission rates in an
+    /// [`EmissionScheduleResponse`] object
+    EmissionSchedule {},
+    /// EffectiveStake returns the raw and boosted stake of a pool, and of a user if specified,
+    /// in an [`EffectiveStakeResponse`] object
+    EffectiveStake {
+        /// the LP token contract address
+        lp_token: String,
+        /// the user address
+        user: Option<String>,
+    },
+    /// SimulateRewards returns the ASTRO a user is projected to earn in the given number of
+    /// seconds in a [`SimulateRewardsResponse`] object, see [`project_rewards`]
+    SimulateRewards {
+        /// the user address
+        user: String,
+        /// the LP token contract address
+        lp_token: String,
+        /// the projection period, converted into blocks with [`ESTIMATED_BLOCK_TIME_MS`]
+        future_seconds: u64,
+    },
+    /// DustBalances returns the undistributed remainders of the reward tokens in a
+    /// [`DustBalancesResponse`] object, see [`reward_index_increase_with_remainder`] and
+    /// [`accrued_rewards_with_remainder`]
+    DustBalances {},
+}
+
+/// ## Description
+/// This structure describes the undistributed remainder of a reward token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DustBalance {
+    /// the reward token
+    pub reward_token: Addr,
+    /// the scaled remainders carried over by the pools to their next distribution
+    pub pool_remainder: Uint256,
+    /// the scaled remainders carried over by the users to their next claim
+    pub user_remainder: Uint256,
+    /// the whole tokens held by the generator for the remainders
+    pub amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the response to the dust balances query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DustBalancesResponse {
+    pub balances: Vec<DustBalance>,
+}
+
+/// ## Description
+/// This structure describes the response to the reward simulation query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateRewardsResponse {
+    /// the number of blocks the projection covers
+    pub future_blocks: u64,
+    /// the ASTRO projected to be distributed to the pool
+    pub pool_rewards: Uint128,
+    /// the ASTRO projected to be earned by the user
+    pub user_rewards: Uint128,
+    /// the ASTRO the user can already claim
+    pub pending_rewards: Uint128,
+    /// the stake of the user the projection is based on
+    pub stake: UserEffectiveStake,
+}
+
+/// ## Description
+/// This structure describes the response to the effective stake query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EffectiveStakeResponse {
+    /// the LP token contract address
+    pub lp_token: Addr,
+    /// the amount of LP tokens deposited in the pool
+    pub total_amount: Uint128,
+    /// the sum of the boosted reward weights of all deposits in the pool
+    pub total_effective: Uint128,
+    /// the stake of the user, [`None`] if no user was specified
+    pub user: Option<UserEffectiveStake>,
+}
+
+/// ## Description
+/// This structure describes the stake of a user in a pool, see [`effective_stake`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserEffectiveStake {
+    /// the amount of LP tokens deposited by the user
+    pub amount: Uint128,
+    /// the boosted reward weight of the deposit
+    pub effective: Uint128,
+    /// the reward weight multiplier of the deposit
+    pub multiplier: Decimal,
+    /// the reward weight of the deposit at [`MAX_BOOST_MULTIPLIER`]
+    pub max_effective: Uint128,
+    /// the share of the pool emissions earned by the deposit
+    pub emission_share: Decimal,
+}
+
+/// ## Description
+/// This structure describes the response to the emission schedule query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionScheduleResponse {
+    /// the ASTRO emitted per block at the current block
+    pub current_tokens_per_block: Uint128,
+    /// the points of the schedule which were not reached yet
+    pub upcoming: Vec<EmissionPoint>,
+}
+
+/// The default number of deposits returned by [`QueryMsg::UserInfoList`]
+pub const DEFAULT_USER_INFO_LIST_LIMIT: u32 = 10;
+/// The maximum number of deposits returned by [`QueryMsg::UserInfoList`]
+pub const MAX_USER_INFO_LIST_LIMIT: u32 = 30;
+
+/// ## Description
+/// This structure describes the response to the user info list query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserInfoListResponse {
+    /// the deposits of the user ordered by the LP token address
+    pub deposits: Vec<UserDepositInfo>,
+}
+
+/// ## Description
+/// This structure describes a deposit of a user.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserDepositInfo {
+    /// the LP token contract address
+    pub lp_token: Addr,
+    /// the deposited amount of LP tokens
+    pub amount: Uint128,
+    /// the pending ASTRO rewards
+    pub pending: Uint128,
+    /// the pending proxy rewards
+    pub pending_on_proxy: Option<Uint128>,
+    /// the proxy reward token
+    pub proxy_reward_token: Option<Addr>,
+    /// the recipient of the rewards, [`None`] if they are sent to the owner of the deposit
+    #[serde(default)]
+    pub reward_beneficiary: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes a deposit of a user together with its boost.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PortfolioPosition {
+    /// the deposit and its pending rewards
+    pub deposit: UserDepositInfo,
+    /// the reward weight multiplier of the deposit
+    pub boost_multiplier: Decimal,
+    /// the boosted reward weight of the deposit, see [`boosted_amount`]
+    pub boosted_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the response to the user portfolio query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserPortfolioResponse {
+    /// the nonzero deposits of the user ordered by the LP token address
+    pub positions: Vec<PortfolioPosition>,
+    /// the pending ASTRO rewards of the returned positions
+    pub total_pending: Uint128,
+    /// the `start_after` of the next page, [`None`] if this is the last page
+    pub next_start_after: Option<Addr>,
+}
+
+impl UserPortfolioResponse {
+    /// ## Description
+    /// Returns a page of the portfolio. Empty deposits are omitted and the next page starts after
+    /// the last read deposit if the page is full.
+    /// ## Params
+    /// * **positions** are the read deposits ordered by the LP token address.
+    ///
+    /// * **limit** is the number of deposits read by the query.
+    pub fn new(positions: Vec<PortfolioPosition>, limit: usize) -> StdResult<Self> {
+        let next_start_after = if positions.len() == limit {
+            positions
+                .last()
+                .map(|position| position.deposit.lp_token.clone())
+        } else {
+            None
+        };
+        let positions: Vec<_> = positions
+            .into_iter()
+            .filter(|position| !position.deposit.amount.is_zero())
+            .collect();
+        let total_pending = positions
+            .iter()
+            .try_fold(Uint128::zero(), |acc, position| {
+                acc.checked_add(position.deposit.pending)
+            })?;
+        Ok(UserPortfolioResponse {
+            positions,
+            total_pending,
+            next_start_after,
+        })
+    }
+}
+
+/// ## Description
+/// This structure describes the response to the external LP tokens query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExternalLpTokensResponse {
+    /// the whitelisted LP tokens
+    pub lp_tokens: Vec<Addr>,
+}
+
+/// ## Description
+/// This structure describes the response to the pending activation pools query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingActivationPoolsResponse {
+    /// the pools ordered by their activation block
+    pub pools: Vec<PendingActivationPool>,
+}
+
+/// ## Description
+/// This structure describes a pool which does not earn ASTRO yet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingActivationPool {
+    /// the LP token contract address
+    pub lp_token: Addr,
+    /// the allocation point of the pool
+    pub alloc_point: Decimal,
+    /// the block at which the pool starts earning ASTRO
+    pub activation_block: u64,
+}
+
+/// ## Description
+/// This structure describes the response to the pool shares query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolSharesResponse {
+    /// the total allocation share of all pools
+    pub total_alloc_point: Decimal,
+    /// the pools with a nonzero allocation share
+    pub pools: Vec<PoolShare>,
+}
+
+/// ## Description
+/// This structure describes the emission share of a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolShare {
+    /// the LP token contract address
+    pub lp_token: Addr,
+    /// the allocation share of the pool
+    pub alloc_point: Decimal,
+    /// the fraction of the emissions distributed to the pool, see [`pool_emission_share`]
+    pub emission_share: Decimal,
+}
+
+/// ## Description
+/// This structure describes the response to the scheduled pools query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledPoolsResponse {
+    /// the LP tokens and their scheduled allocation points
+    pub pools: Vec<(Addr, Decimal)>,
+    /// the timestamp at which the changes take effect, [`None`] if nothing is scheduled
+    pub effective_at: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the response to the boost tiers query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostTiersResponse {
+    /// the tiers and their multipliers
+    pub tiers: Vec<(u8, Decimal)>,
+}
+
+/// ## Description
+/// This structure describes the response to the user boost query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserBoostResponse {
+    /// the attached NFT token identifier
+    pub token_id: Option<String>,
+    /// the tier of the attached NFT
+    pub tier: Option<u8>,
+    /// the reward weight multiplier, one if the NFT is missing or not owned by the user anymore
+    pub multiplier: Decimal,
+    /// the boosted reward weight of the deposit
+    pub boosted_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the response to the reward beneficiary query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardBeneficiaryResponse {
+    /// the owner of the deposit which can withdraw it
+    pub owner: Addr,
+    /// the recipient of the rewards of the deposit, the owner if no beneficiary is set
+    pub beneficiary: Addr,
+}
+
+impl RewardBeneficiaryResponse {
+    /// ## Description
+    /// Returns the owner and the reward recipient of a deposit.
+    /// ## Params
+    /// * **owner** is the owner of the deposit.
+    ///
+    /// * **beneficiary** is the beneficiary set with [`ExecuteMsg::SetRewardBeneficiary`].
+    pub fn new(owner: Addr, beneficiary: Option<Addr>) -> Self {
+        RewardBeneficiaryResponse {
+            beneficiary: beneficiary.unwrap_or_else(|| owner.clone()),
+            owner,
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the response to the reward preference query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardPreferenceResponse {
+    /// whether the pool allows the proxy reward conversion
+    pub conversion_enabled: bool,
+    /// whether the user chose to receive the proxy rewards in ASTRO
+    pub convert_proxy_rewards: bool,
+}
+
+/// ## Description
+/// This structure describes the response to the active pools query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActivePoolsResponse {
+    /// the maximum number of pools with a nonzero allocation point
+    pub limit: Option<u32>,
+    /// the number of pools with a nonzero allocation point
+    pub count: u32,
+    /// the active pools ordered by allocation point in descending order, the last ones are the
+    /// first candidates for eviction
+    pub pools: Vec<ActivePool>,
+}
+
+/// ## Description
+/// This structure describes a pool with a nonzero allocation point.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActivePool {
+    /// the LP token contract address
+    pub lp_token: Addr,
+    /// the allocation point of liquidity pool
+    pub alloc_point: Decimal,
 }
 
 /// ## Description
@@ -162,6 +1715,24 @@ pub struct PendingTokenResponse {
     pub pending_on_proxy: Option<Uint128>,
 }
 
+/// ## Description
+/// This structure describes the pending rewards of a single user.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserPendingToken {
+    /// the user address
+    pub user: Addr,
+    /// the pending rewards of the user
+    pub pending: PendingTokenResponse,
+}
+
+/// ## Description
+/// This structure describes the response to the pending tokens of several users.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingTokenManyResponse {
+    /// the pending rewards in the order of the requested users
+    pub users: Vec<UserPendingToken>,
+}
+
 /// ## Description
 /// This structure describes the response to the reward information.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -172,30 +1743,68 @@ pub struct RewardInfoResponse {
     pub proxy_reward_token: Option<Addr>,
 }
 
+/// ## Description
+/// This structure describes the response to the orphan proxy rewards query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrphanProxyRewardsResponse {
+    /// the proxy reward token
+    pub proxy_reward_token: Addr,
+    /// the orphan proxy rewards which are left by emergency withdrawals or were received while no
+    /// LP tokens were staked, see [`distribute_proxy_rewards`]
+    pub amount: Uint128,
+}
+
 /// ## Description
 /// This structure describes the response to the Pool information.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PoolInfoResponse {
-    /// Allocation point is used to control reward distribution among the pools
-    pub alloc_point: Uint64,
+    /// Allocation share is used to control reward distribution among the pools, see
+    /// [`pool_emission_share`]
+    pub alloc_point: Decimal,
     /// ASTRO tokens being distributed per block to this LP pool
     pub astro_tokens_per_block: Uint128,
     /// Accumulated amount of reward per share unit. Used for reward calculations
     pub last_reward_block: u64,
     /// Current block number. Useful for off-chain expected APR calculations
     pub current_block: u64,
-    pub accumulated_rewards_per_share: Decimal,
+    /// Accumulated amount of ASTRO rewards per share unit
+    pub accumulated_rewards_per_share: Decimal256,
     /// Pending amount of total ASTRO rewards which are claimable by the users
     pub pending_astro_rewards: Uint128,
     /// the reward proxy contract
     pub reward_proxy: Option<Addr>,
     /// Pending amount of total proxy rewards which are claimable by the users
     pub pending_proxy_rewards: Option<Uint128>,
-    pub accumulated_proxy_rewards_per_share: Decimal,
+    /// Accumulated amount of proxy rewards per share unit
+    pub accumulated_proxy_rewards_per_share: Decimal256,
     /// for calculation of new proxy rewards
     pub proxy_reward_balance_before_update: Uint128,
-    /// the orphan proxy rewards which are left by emergency withdrawals
+    /// the orphan proxy rewards which are left by emergency withdrawals or were received while no
+    /// LP tokens were staked
     pub orphan_proxy_rewards: Uint128,
+    /// whether users can convert the proxy rewards to ASTRO via the maker
+    pub proxy_reward_conversion: bool,
+    /// the block at which the pool starts earning ASTRO
+    #[serde(default)]
+    pub activation_block: Option<u64>,
+    /// the deposit caps of the pool
+    #[serde(default)]
+    pub deposit_caps: Option<DepositCaps>,
+    /// the harvested proxy rewards which were not claimed by the users yet
+    #[serde(default)]
+    pub proxy_reward_buffer: Uint128,
+    /// the time of the last [`ExecuteMsg::HarvestProxies`] of the pool
+    #[serde(default)]
+    pub last_proxy_harvest: Option<u64>,
+    /// the sender of [`ExecuteMsg::RegisterPool`], [`None`] if the pool was added by the owner or factory
+    #[serde(default)]
+    pub registered_by: Option<Addr>,
+    /// the hook contracts notified about deposits, withdrawals and claims
+    #[serde(default)]
+    pub hooks: Vec<Addr>,
+    /// the block at which the pool was paused, [`None`] if it is not paused
+    #[serde(default)]
+    pub paused_since: Option<u64>,
 }
 
 /// ## Description
@@ -208,20 +1817,171 @@ pub struct ConfigResponse {
     pub astro_token: Addr,
     /// tokens per block
     pub tokens_per_block: Uint128,
-    /// total allocation point
-    pub total_alloc_point: Uint64,
+    /// the sum of the allocation shares of all pools, at most one
+    pub total_alloc_point: Decimal,
     /// start block
     pub start_block: Uint64,
     /// allowed reward proxies
     pub allowed_reward_proxies: Vec<Addr>,
     /// a vesting contract
     pub vesting_contract: Addr,
+    /// the factory contract which notifies the generator about created pairs
+    pub factory: Option<Addr>,
+    /// the maximum number of pools with a nonzero allocation point
+    pub active_pools_limit: Option<u32>,
+    /// the maker contract which swaps proxy rewards to ASTRO
+    pub maker_contract: Option<Addr>,
+    /// the CW721 contract whose NFTs boost the reward weight of deposits
+    pub boost_nft: Option<Addr>,
+    /// whether the chain may update the reward indexes via [`SudoMsg::UpdateEmissions`]
+    pub sudo_updates_enabled: bool,
+    /// the staking contract whose xASTRO locks boost the reward weight of deposits. A deposit is
+    /// weighted by the higher of its NFT tier multiplier and the lock boost of the user
+    #[serde(default)]
+    pub staking_boost: Option<Addr>,
+    /// the anti-spam deposit paid by [`ExecuteMsg::RegisterPool`]
+    #[serde(default)]
+    pub registration_deposit: Option<Coin>,
+    /// the minimum number of seconds between two proxy harvests of a pool
+    #[serde(default)]
+    pub proxy_harvest_interval: Option<u64>,
+    /// the address which can zero the allocation point of a pool
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    /// what happens to the ASTRO paused pools would earn
+    #[serde(default)]
+    pub paused_emissions: PausedEmissions,
+    /// the previous generator whose deposits can be migrated into this one
+    #[serde(default)]
+    pub legacy_generator: Option<Addr>,
+    /// the next generator which can move deposits out with [`ExecuteMsg::ReleaseToSuccessor`]
+    #[serde(default)]
+    pub successor_generator: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes the sudo messages sent by chains with permissioned end-blocker hooks
+/// or by the chain governance when it is the contract admin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Updates the reward indexes of all pools with a nonzero allocation point like
+    /// [`ExecuteMsg::MassUpdatePools`]. Ignored unless `sudo_updates_enabled` is set in the config
+    UpdateEmissions {},
+    /// Sets the allocation points of the given pools immediately like an
+    /// [`ExecuteMsg::ScheduleSetupPools`] without the delay, so the chain governance can adjust
+    /// emissions without an owner account. Pools which are not listed keep their allocation point
+    SetupPools {
+        /// the LP token contract addresses and their new allocation points
+        pools: Vec<(String, Decimal)>,
+    },
 }
 
 /// ## Description
 /// This structure describes a migration message.
+/// The migration converts the reward indexes of all pools and users with [`decimal_to_reward_index`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    /// removes the pools with no deposits whose LP token fails [`validate_lp_token`]
+    #[serde(default)]
+    pub purge_invalid_pools: bool,
+    /// the next generator allowed to move deposits out with [`ExecuteMsg::ReleaseToSuccessor`]
+    #[serde(default)]
+    pub successor_generator: Option<String>,
+    /// converts the allocation points stored as integers before they became shares, see
+    /// [`normalize_alloc_points`]
+    #[serde(default)]
+    pub normalize_alloc_points: bool,
+}
+
+/// ## Description
+/// Returns the allocation shares of pools whose allocation points were stored as integers, e.g.
+/// `"100"`, which deserialize as the decimal 100. Each share is the allocation point of the pool
+/// divided by the sum of all allocation points, so the emission split doesn't change and the
+/// total share is at most one.
+/// ## Params
+/// * **alloc_points** are the stored allocation points of all pools.
+pub fn normalize_alloc_points(alloc_points: &[Decimal]) -> StdResult<Vec<Decimal>> {
+    let total = alloc_points
+        .iter()
+        .try_fold(Decimal::zero(), |acc, alloc_point| {
+            acc.checked_add(*alloc_point)
+        })?;
+    Ok(alloc_points
+        .iter()
+        .map(|alloc_point| pool_emission_share(*alloc_point, total))
+        .collect())
+}
+
+/// The maximum number of pools migrated by a single [`ExecuteMsg::MigrateUserPositions`]
+pub const MAX_MIGRATE_POOLS_LIMIT: u32 = 10;
+
+/// ## Description
+/// Returns the message which makes the legacy generator release the deposits of a user in the
+/// pools to this generator. Returns [`None`] if the user has no deposits to move.
+/// ## Params
+/// * **legacy_generator** is the legacy generator contract.
+///
+/// * **user** is the user whose deposits are moved.
+///
+/// * **positions** are the LP tokens and the amounts the user deposited in the legacy generator.
+pub fn legacy_release_msg(
+    legacy_generator: &Addr,
+    user: &Addr,
+    positions: &[(Addr, Uint128)],
+) -> StdResult<Option<CosmosMsg>> {
+    let lp_tokens: Vec<String> = positions
+        .iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(lp_token, _)| lp_token.to_string())
+        .collect();
+    if lp_tokens.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: legacy_generator.to_string(),
+        msg: to_binary(&ExecuteMsg::ReleaseToSuccessor {
+            user: user.to_string(),
+            lp_tokens,
+        })?,
+        funds: vec![],
+    })))
+}
+
+/// ## Description
+/// This structure describes the messages sent to the hooks of a pool.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+#[serde(rename_all = "snake_case")]
+pub enum PoolHookMsg {
+    /// LP tokens were deposited for the user
+    PoolDeposit {
+        /// the LP token contract address
+        lp_token: Addr,
+        /// the owner of the deposit
+        user: Addr,
+        /// the amount of deposited LP tokens
+        amount: Uint128,
+    },
+    /// LP tokens were withdrawn by the user, including emergency withdrawals
+    PoolWithdraw {
+        /// the LP token contract address
+        lp_token: Addr,
+        /// the owner of the deposit
+        user: Addr,
+        /// the amount of withdrawn LP tokens
+        amount: Uint128,
+    },
+    /// ASTRO rewards of the pool were claimed by the user
+    PoolClaim {
+        /// the LP token contract address
+        lp_token: Addr,
+        /// the owner of the deposit
+        user: Addr,
+        /// the amount of claimed ASTRO
+        amount: Uint128,
+    },
+}
 
 /// ## Description
 /// This structure describes the custom hooks for the CW20.
@@ -19,31 +19,38 @@ pub struct InstantiateMsg {
     pub reward_token_addr: String,
 }
 
+/// ## Description
+/// This structure describes a CW20 hook message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
+    /// Stakes the received LP tokens in the reward contract. Only LP tokens sent by the
+    /// generator are accepted
     Deposit {},
 }
 
 /// ## Description
-/// This structure describes the execute messages of the contract.
+/// This structure describes the execute messages of the contract. Every proxy wrapping a
+/// third-party staking contract implements these messages, so the generator can stake LP tokens
+/// through it and distribute the third-party rewards next to ASTRO. All messages except
+/// [`ExecuteMsg::Receive`] can only be executed by the generator.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Receives a message of type [`Cw20ReceiveMsg`]
     Receive(Cw20ReceiveMsg),
-    /// Withdrawal pending rewards
+    /// Claims the pending rewards from the reward contract to the proxy
     UpdateRewards {},
     /// Sends rewards to the recipient
     SendRewards { account: String, amount: Uint128 },
-    /// Withdrawal the rewards
+    /// Withdraws LP tokens from the reward contract and sends them to the account
     Withdraw {
         /// the recipient for withdrawal
         account: String,
         /// the amount of withdraw
         amount: Uint128,
     },
-    /// Withdrawal the rewards
+    /// Withdraws LP tokens from the reward contract without claiming the rewards
     EmergencyWithdraw {
         /// the recipient for withdrawal
         account: String,
@@ -72,15 +79,15 @@ pub enum CallbackMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    /// Returns the contract's configuration
+    /// Returns the contract's configuration in a [`ConfigResponse`] object
     Config {},
-    /// Returns the deposit amount
+    /// Returns the amount of LP tokens the generator deposited through the proxy as [`Uint128`]
     Deposit {},
-    /// Returns the balance of reward token
+    /// Returns the balance of reward token held by the proxy as [`Uint128`]
     Reward {},
-    /// Returns the pending rewards
+    /// Returns the rewards pending on the reward contract as [`Uint128`]
     PendingToken {},
-    /// Returns the reward token contract address
+    /// Returns the reward token contract address as [`Addr`]
     RewardInfo {},
 }
 
@@ -0,0 +1,149 @@
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Event, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The current version of the [`HookEnvelope`] of CW20 hook messages
+pub const HOOK_VERSION: u32 = 1;
+
+/// The event emitted when a CW20 hook message is rejected and the tokens are refunded
+pub const HOOK_REJECTED_EVENT: &str = "cw20_hook_rejected";
+
+/// ## Description
+/// This structure describes a versioned CW20 hook message. The `msg` holds the hook message of
+/// the receiving contract, e.g. [`crate::pair::Cw20HookMsg`] or [`crate::router::Cw20HookMsg`].
+/// Hook messages sent without an envelope are parsed as the current version.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HookEnvelope {
+    /// the version of the hook message format, see [`HOOK_VERSION`]
+    pub version: u32,
+    /// the hook message
+    pub msg: Binary,
+}
+
+/// ## Description
+/// This enum describes the errors of parsing a CW20 hook message.
+#[derive(Error, Debug, PartialEq)]
+pub enum HookError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Hook message version {version} sent by token {token} is not supported, expected {expected}")]
+    UnsupportedVersion {
+        token: Addr,
+        version: u32,
+        expected: u32,
+    },
+
+    #[error("Unknown hook message sent by token {token}: {reason}")]
+    UnknownMessage { token: Addr, reason: String },
+}
+
+impl From<HookError> for StdError {
+    fn from(err: HookError) -> Self {
+        match err {
+            HookError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+/// ## Description
+/// Parses a CW20 hook message which is either wrapped in a [`HookEnvelope`] or sent as is.
+/// Returns a [`HookError`] naming the sender token if the version is not supported or the
+/// message is not a known variant.
+/// ## Params
+/// * **token** is the CW20 token which sent the hook.
+///
+/// * **msg** is the binary hook message of the [`cw20::Cw20ReceiveMsg`].
+pub fn parse_hook_msg<T: DeserializeOwned>(token: &Addr, msg: &Binary) -> Result<T, HookError> {
+    let msg = match from_binary::<HookEnvelope>(msg) {
+        Ok(envelope) if envelope.version != HOOK_VERSION => {
+            return Err(HookError::UnsupportedVersion {
+                token: token.clone(),
+                version: envelope.version,
+                expected: HOOK_VERSION,
+            })
+        }
+        Ok(envelope) => envelope.msg,
+        Err(_) => msg.clone(),
+    };
+    from_binary(&msg).map_err(|err| HookError::UnknownMessage {
+        token: token.clone(),
+        reason: err.to_string(),
+    })
+}
+
+/// ## Description
+/// This structure describes a rejected CW20 hook message. Instead of reverting, a contract can
+/// refund the tokens with [`HookRejection::refund_msg`] and record the rejection, so operators
+/// see failing integrations in the [`RejectedHooksResponse`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HookRejection {
+    /// the CW20 token which sent the hook
+    pub token: Addr,
+    /// the sender of the tokens, who receives the refund
+    pub sender: Addr,
+    /// the refunded amount
+    pub amount: Uint128,
+    /// the reason of the rejection
+    pub reason: String,
+    /// the height of the rejection
+    pub height: u64,
+}
+
+impl HookRejection {
+    /// ## Description
+    /// Returns the message which sends the tokens back to the sender.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn refund_msg(&self) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: self.sender.to_string(),
+                amount: self.amount,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    /// ## Description
+    /// Returns the [`HOOK_REJECTED_EVENT`] of the rejection.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn to_event(&self) -> Event {
+        Event::new(HOOK_REJECTED_EVENT)
+            .add_attribute("token", self.token.to_string())
+            .add_attribute("sender", self.sender.to_string())
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("reason", self.reason.clone())
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct RejectedHooksResponse {
+    /// the number of rejected hook messages
+    pub count: u64,
+    /// the latest rejection
+    pub last_rejection: Option<HookRejection>,
+}
+
+impl RejectedHooksResponse {
+    /// ## Description
+    /// Counts a rejection and keeps it as the latest one.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **rejection** is the object of type [`HookRejection`].
+    pub fn record(&mut self, rejection: HookRejection) {
+        self.count += 1;
+        self.last_rejection = Some(rejection);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, StdError, StdResult, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The minimum number of seconds between proposing and executing a payout
+pub const MIN_PAYOUT_TIMELOCK: u64 = 3 * 86400;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the governance contract which can propose payouts
+    pub governance_contract: String,
+    /// the number of seconds between proposing and executing a payout, at least [`MIN_PAYOUT_TIMELOCK`]
+    pub payout_timelock: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Deposits the attached native tokens into the fund
+    Deposit {},
+    /// ## Description
+    /// Proposes a payout which can be executed once the payout timelock has passed
+    /// ## Executor
+    /// Only governance can execute it
+    ProposePayout {
+        /// the recipient of the payout
+        recipient: String,
+        /// the assets to pay out
+        assets: Vec<Asset>,
+        /// the reason of the payout
+        description: String,
+    },
+    /// ## Description
+    /// Cancels a proposed payout
+    /// ## Executor
+    /// Only governance or owner can execute it
+    CancelPayout {
+        /// the identifier of the payout
+        id: u64,
+    },
+    /// ## Description
+    /// Executes a proposed payout once the payout timelock has passed. Can be executed by anyone
+    ExecutePayout {
+        /// the identifier of the payout
+        id: u64,
+    },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the governance contract which can propose payouts
+        governance_contract: Option<String>,
+        /// the number of seconds between proposing and executing a payout
+        payout_timelock: Option<u64>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposits the received tokens into the fund
+    Deposit {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns the size of the fund per asset in a [`FundSizeResponse`] object
+    FundSize {},
+    /// Returns the deposit accounting of an asset in a [`FundAssetResponse`] object
+    FundAsset { asset_info: AssetInfo },
+    /// Returns a payout in a [`PayoutResponse`] object
+    Payout { id: u64 },
+    /// Returns a list of payouts in a [`PayoutsResponse`] object
+    Payouts {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the governance contract which can propose payouts
+    pub governance_contract: Addr,
+    /// the number of seconds between proposing and executing a payout
+    pub payout_timelock: u64,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundSizeResponse {
+    /// the current balances of the fund
+    pub assets: Vec<Asset>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundAssetResponse {
+    /// the asset
+    pub asset_info: AssetInfo,
+    /// the total amount deposited into the fund
+    pub total_deposited: Uint128,
+    /// the total amount paid out by the fund
+    pub total_paid_out: Uint128,
+    /// the amount reserved by proposed payouts which were not executed yet
+    pub reserved: Uint128,
+}
+
+/// ## Description
+/// This enum describes the status of a payout.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutStatus {
+    Pending {},
+    Executed {},
+    Cancelled {},
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutResponse {
+    /// the identifier of the payout
+    pub id: u64,
+    /// the recipient of the payout
+    pub recipient: Addr,
+    /// the assets to pay out
+    pub assets: Vec<Asset>,
+    /// the reason of the payout
+    pub description: String,
+    /// the timestamp from which the payout can be executed
+    pub executable_at: u64,
+    /// the status of the payout
+    pub status: PayoutStatus,
+}
+
+impl PayoutResponse {
+    /// ## Description
+    /// Returns true if the payout is pending and its timelock has passed.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn is_executable(&self, time: u64) -> bool {
+        self.status == PayoutStatus::Pending {} && time >= self.executable_at
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutsResponse {
+    pub payouts: Vec<PayoutResponse>,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns an [`Err`] if the available balances of the fund don't cover the payout.
+/// ## Params
+/// * **available** are the balances of the fund minus the amounts reserved by other payouts.
+///
+/// * **payout** are the assets to pay out.
+pub fn assert_payout_covered(available: &[Asset], payout: &[Asset]) -> StdResult<()> {
+    if payout.is_empty() || payout.iter().any(|asset| asset.amount.is_zero()) {
+        return Err(StdError::generic_err("Payout amounts must be positive"));
+    }
+    for (i, asset) in payout.iter().enumerate() {
+        if payout[..i].iter().any(|a| a.info == asset.info) {
+            return Err(StdError::generic_err(format!(
+                "Duplicated payout asset {}",
+                asset.info
+            )));
+        }
+        let balance = available
+            .iter()
+            .find(|a| a.info == asset.info)
+            .map(|a| a.amount)
+            .unwrap_or_default();
+        if balance < asset.amount {
+            return Err(StdError::generic_err(format!(
+                "Insufficient fund balance of {}: {} < {}",
+                asset.info, balance, asset.amount
+            )));
+        }
+    }
+    Ok(())
+}
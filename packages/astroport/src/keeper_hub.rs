@@ -0,0 +1,192 @@
+use crate::asset::Asset;
+use cosmwasm_std::{Addr, Binary};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the maximum number of jobs, unlimited if not set
+    pub max_jobs: Option<u32>,
+}
+
+/// ## Description
+/// This structure describes the settings of a maintenance job.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobParams {
+    /// the human readable name of the job, e.g. "maker collect"
+    pub name: String,
+    /// the contract executed by the job
+    pub contract: String,
+    /// the execute message sent to the contract
+    pub msg: Binary,
+    /// the reward paid to the keeper for each execution
+    pub reward: Asset,
+    /// the minimum number of seconds between two executions
+    pub interval: u64,
+    /// the query which must return `{"executable": true}` for the job to be performed
+    pub condition: Option<JobCondition>,
+}
+
+/// ## Description
+/// This structure describes the smart query checked before a job is performed.
+/// The queried contract must respond with a [`JobConditionResponse`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobCondition {
+    /// the queried contract
+    pub contract: String,
+    /// the query message
+    pub msg: Binary,
+}
+
+/// ## Description
+/// This structure describes the response expected from a [`JobCondition`] query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobConditionResponse {
+    pub executable: bool,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
+    /// template.
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Registers a new job
+    /// ## Executor
+    /// Only owner can execute it
+    RegisterJob { params: JobParams },
+    /// ## Description
+    /// Updates the settings of a job
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateJob { id: u64, params: JobParams },
+    /// ## Description
+    /// Removes a job and refunds its remaining funding to the owner
+    /// ## Executor
+    /// Only owner can execute it
+    RemoveJob { id: u64 },
+    /// ## Description
+    /// Adds the attached native tokens to the funding of a job
+    FundJob { id: u64 },
+    /// ## Description
+    /// Performs a job if its interval has passed and its condition holds, and pays the reward to
+    /// the sender
+    PerformJob { id: u64 },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Adds the received tokens to the funding of a job
+    FundJob { id: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns information about a job in a [`JobResponse`] object
+    Job { id: u64 },
+    /// Returns a list of jobs in a [`JobsResponse`] object
+    Jobs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the jobs which can be performed now in a [`JobsResponse`] object
+    ExecutableJobs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the maximum number of jobs
+    pub max_jobs: Option<u32>,
+    /// the number of registered jobs
+    pub jobs_count: u32,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobResponse {
+    /// the job identifier
+    pub id: u64,
+    /// the settings of the job
+    pub params: JobParams,
+    /// the remaining funding of the job
+    pub funding: Asset,
+    /// the time of the last execution
+    pub last_performed: Option<u64>,
+    /// the number of executions
+    pub executions: u64,
+}
+
+impl JobResponse {
+    /// ## Description
+    /// Returns true if the interval of the job has passed and the funding covers the reward.
+    /// The [`JobCondition`] has to be checked separately.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn is_due(&self, time: u64) -> bool {
+        let interval_passed = self
+            .last_performed
+            .map(|last_performed| time >= last_performed.saturating_add(self.params.interval))
+            .unwrap_or(true);
+        interval_passed && self.funding.amount >= self.params.reward.amount
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobsResponse {
+    pub jobs: Vec<JobResponse>,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
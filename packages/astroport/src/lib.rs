@@ -1,16 +1,28 @@
+pub mod airdrop;
 pub mod asset;
+pub mod chain;
 pub mod common;
+pub mod compat_router;
 pub mod factory;
+pub mod fee_distributor;
 pub mod generator;
 pub mod generator_proxy;
+pub mod hook;
+pub mod insurance_fund;
+pub mod keeper_hub;
 pub mod maker;
+pub mod math;
 pub mod oracle;
 pub mod pair;
 pub mod pair_stable_bluna;
 pub mod querier;
 pub mod router;
 pub mod staking;
+pub mod stats;
 pub mod token;
+pub mod token_converter;
+pub mod twap_executor;
+pub mod vault_manager;
 pub mod vesting;
 pub mod whitelist;
 
@@ -33,6 +45,7 @@ mod decimal_checked_ops {
     use std::convert::TryInto;
     pub trait DecimalCheckedOps {
         fn checked_add(self, other: Decimal) -> Result<Decimal, OverflowError>;
+        fn checked_sub(self, other: Decimal) -> Result<Decimal, OverflowError>;
         fn checked_mul(self, other: Uint128) -> Result<Uint128, OverflowError>;
     }
 
@@ -42,6 +55,16 @@ mod decimal_checked_ops {
                 .checked_add(other.numerator().into())
                 .map(|_| self + other)
         }
+        fn checked_sub(self, other: Decimal) -> Result<Decimal, OverflowError> {
+            if other > self {
+                return Err(OverflowError::new(
+                    cosmwasm_std::OverflowOperation::Sub,
+                    self,
+                    other,
+                ));
+            }
+            Ok(self - other)
+        }
         fn checked_mul(self, other: Uint128) -> Result<Uint128, OverflowError> {
             if self.is_zero() || other.is_zero() {
                 return Ok(Uint128::zero());
@@ -1,6 +1,12 @@
-use crate::asset::{Asset, AssetInfo};
+use crate::asset::{Asset, AssetInfo, PairInfo};
 use crate::factory::UpdateAddr;
-use cosmwasm_std::{Addr, Decimal, Uint128, Uint64};
+use crate::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, FillMode};
+use crate::querier::query_pair_info;
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, Fraction, QuerierWrapper, ReplyOn, StdError, StdResult,
+    SubMsg, Uint128, Uint64, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -18,44 +24,303 @@ pub struct InstantiateMsg {
     pub staking_contract: String,
     /// the governance contract address
     pub governance_contract: Option<String>,
-    /// the governance percent
+    /// the governance percent, it must be zero if no governance is set, see [`validate_governance`]
     pub governance_percent: Option<Uint64>,
+    /// the percent of collected ASTRO burned on each distribution, see [`validate_burn_percent`]
+    #[serde(default)]
+    pub burn_percent: Option<Uint64>,
     /// the maximum spread
     pub max_spread: Option<Decimal>,
+    /// the remote governance which receives the governance percent over IBC instead of `governance_contract`
+    pub remote_governance: Option<RemoteGovernance>,
+    /// the minimum number of seconds between two distributions
+    pub min_distribution_interval: Option<u64>,
+    /// the minimum amount of ASTRO distributed at once
+    pub min_distribution_amount: Option<Uint128>,
+    /// the insurance fund contract address
+    pub insurance_fund: Option<String>,
+    /// the percent of distributed ASTRO sent to the insurance fund, see
+    /// [`validate_insurance_percent`]
+    pub insurance_percent: Option<Uint64>,
+    /// the maximum amount of ASTRO distributed per second, everything is distributed at once if not set
+    pub distribution_rate: Option<Uint128>,
+    /// the settings of the tranche-wise conversion into ASTRO, assets are swapped at once if not set
+    pub buyback: Option<BuybackParams>,
+    /// the factories whose pairs are used for bridge swaps when the main factory has none, in
+    /// priority order, at most [`MAX_ADDITIONAL_FACTORIES`]
+    #[serde(default)]
+    pub additional_factories: Vec<String>,
+    /// the minimum number of seconds between two collects, collects are not limited if not set
+    pub collect_cooldown: Option<u64>,
+    /// the share of the ASTRO received by a collect paid to its caller in bps, at most
+    /// [`MAX_CALLER_REWARD_BPS`]
+    pub caller_reward_bps: Option<u16>,
+    /// the address which can maintain the bridges and the collect throttling besides the owner,
+    /// see [`ExecuteMsg::assert_owner_or_operator`]
+    #[serde(default)]
+    pub operator: Option<String>,
+}
+
+/// The maximum share of the collected ASTRO paid to the caller of a collect in bps
+pub const MAX_CALLER_REWARD_BPS: u16 = 100;
+
+/// ## Description
+/// Returns an [`Err`] if the caller reward exceeds [`MAX_CALLER_REWARD_BPS`].
+/// ## Params
+/// * **caller_reward_bps** is the share of the collected ASTRO paid to the caller in bps.
+pub fn validate_caller_reward_bps(caller_reward_bps: u16) -> StdResult<()> {
+    if caller_reward_bps > MAX_CALLER_REWARD_BPS {
+        return Err(StdError::generic_err(format!(
+            "Caller reward can't exceed {} bps",
+            MAX_CALLER_REWARD_BPS
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns an [`Err`] if the last collect happened less than `collect_cooldown` seconds ago.
+/// ## Params
+/// * **last_collect_time** is the time of the last collect.
+///
+/// * **collect_cooldown** is the minimum number of seconds between two collects.
+///
+/// * **time** is the current timestamp.
+pub fn assert_collect_cooldown(
+    last_collect_time: u64,
+    collect_cooldown: u64,
+    time: u64,
+) -> StdResult<()> {
+    let next_collect_time = last_collect_time.saturating_add(collect_cooldown);
+    if time < next_collect_time {
+        return Err(StdError::generic_err(format!(
+            "The next collect is allowed at {}",
+            next_collect_time
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the ASTRO paid to the caller of a collect.
+/// ## Params
+/// * **astro_received** is the amount of ASTRO received by the swaps of the collect.
+///
+/// * **caller_reward_bps** is the share of the collected ASTRO paid to the caller in bps.
+pub fn compute_caller_reward(astro_received: Uint128, caller_reward_bps: u16) -> Uint128 {
+    astro_received.multiply_ratio(caller_reward_bps, 10_000u16)
+}
+
+/// The maximum price impact of a single buyback tranche
+pub const MAX_TRANCHE_PRICE_IMPACT: &str = "0.1";
+
+/// ## Description
+/// This structure describes the settings of the tranche-wise conversion into ASTRO. When set,
+/// [`ExecuteMsg::Collect`] only swaps the first tranche of an asset into ASTRO and keeps the rest
+/// pending until [`ExecuteMsg::ContinueCollect`] swaps the next tranches in later blocks.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BuybackParams {
+    /// the maximum price impact of a single tranche on the ASTRO pair of the asset
+    pub max_price_impact: Decimal,
+    /// the minimum number of blocks between two tranches of the same asset
+    pub min_blocks_between_tranches: u64,
+}
+
+impl BuybackParams {
+    /// ## Description
+    /// Returns an [`Err`] if the price impact is zero or higher than [`MAX_TRANCHE_PRICE_IMPACT`]
+    /// or if tranches could be swapped in the same block.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        let max_price_impact: Decimal = MAX_TRANCHE_PRICE_IMPACT.parse()?;
+        if self.max_price_impact.is_zero() || self.max_price_impact > max_price_impact {
+            return Err(StdError::generic_err(format!(
+                "The tranche price impact must be positive and at most {}",
+                max_price_impact
+            )));
+        }
+        if self.min_blocks_between_tranches == 0 {
+            return Err(StdError::generic_err(
+                "Tranches must be at least one block apart",
+            ));
+        }
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the amount of the next tranche swapped into an xyk pool, which is the pending
+    /// amount capped by the offer amount moving the price by at most `max_price_impact`.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **pending** is the amount of the asset which is not swapped yet.
+    ///
+    /// * **offer_pool** is the pool of the offered asset in its ASTRO pair.
+    pub fn next_tranche_amount(&self, pending: Uint128, offer_pool: Uint128) -> Uint128 {
+        // the marginal price moves by offer / (offer_pool + offer), which is at most the price
+        // impact for offer <= offer_pool * impact / (1 - impact)
+        let max_amount = offer_pool.multiply_ratio(
+            self.max_price_impact.numerator(),
+            (Decimal::one() - self.max_price_impact).numerator(),
+        );
+        std::cmp::min(pending, max_amount)
+    }
 }
 
 /// ## Description
 /// This structure describes the execute messages of the contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
-    /// Collects astro tokens from the given assets
+    /// Collects astro tokens from the given assets. Can be called by anyone once per collect
+    /// cooldown, see [`assert_collect_cooldown`]. The caller receives [`compute_caller_reward`] of
+    /// the ASTRO received by the swaps, the rest is kept in the maker until it is distributed by
+    /// [`ExecuteMsg::DistributeAstro`].
+    /// LP tokens of pairs registered in the factory are withdrawn from their pairs first and
+    /// the underlying assets are swapped via the bridges.
+    /// The response data contains a [`CollectResponse`] object.
     Collect {
         /// the assets to collect
         assets: Vec<AssetWithLimit>,
+        /// the minimum total ASTRO received by all swaps of the collect including the bridge
+        /// hops, otherwise the whole collect reverts, see [`assert_min_astro_received`]
+        #[serde(default)]
+        min_astro_received: Option<Uint128>,
+        /// whether the fees of the registered fee sources are withdrawn first, see
+        /// [`fee_source_withdraw_msgs`]
+        #[serde(default)]
+        pull_sources: bool,
+    },
+    /// ## Description
+    /// Registers an external contract which accrues fees for the maker, e.g. a lending market or
+    /// a launchpad. The contract has to support [`FeeSourceExecuteMsg::WithdrawFees`]
+    /// ## Executor
+    /// Only owner can execute it
+    RegisterFeeSource {
+        /// the fee source contract
+        contract: String,
+    },
+    /// ## Description
+    /// Removes a registered fee source
+    /// ## Executor
+    /// Only owner can execute it
+    DeregisterFeeSource {
+        /// the fee source contract
+        contract: String,
     },
     /// Updates general settings that contains in the  [`Config`]
     UpdateConfig {
         /// the factory contract address
         factory_contract: Option<String>,
+        /// the factories whose pairs are used when the main factory has none, in priority order
+        additional_factories: Option<Vec<String>>,
         /// the staking contract address
         staking_contract: Option<String>,
         /// the governance contract address
         governance_contract: Option<UpdateAddr>,
-        /// the governance percent
+        /// the governance percent, the settings after the update must pass [`validate_governance`]
         governance_percent: Option<Uint64>,
+        /// the percent of collected ASTRO burned, see [`validate_burn_percent`]
+        #[serde(default)]
+        burn_percent: Option<Uint64>,
         /// the maximum spread
         max_spread: Option<Decimal>,
+        /// the remote governance which receives the governance percent over IBC
+        remote_governance: Option<UpdateRemoteGovernance>,
+        /// the minimum number of seconds between two distributions
+        min_distribution_interval: Option<u64>,
+        /// the minimum amount of ASTRO distributed at once
+        min_distribution_amount: Option<Uint128>,
+        /// the insurance fund contract address
+        insurance_fund: Option<UpdateAddr>,
+        /// the percent of distributed ASTRO sent to the insurance fund, see
+        /// [`validate_insurance_percent`]
+        insurance_percent: Option<Uint64>,
+        /// the maximum amount of ASTRO distributed per second, zero removes the limit
+        distribution_rate: Option<Uint128>,
+        /// the minimum number of seconds between two collects, zero removes the limit
+        collect_cooldown: Option<u64>,
+        /// the share of the ASTRO received by a collect paid to its caller in bps
+        caller_reward_bps: Option<u16>,
+        /// the address which can maintain the bridges and the collect throttling
+        #[serde(default)]
+        operator: Option<UpdateAddr>,
+    },
+    /// ## Description
+    /// Updates the collect throttling settings
+    /// ## Executor
+    /// Only owner or operator can execute it
+    UpdateCollectSettings {
+        /// the minimum number of seconds between two collects, zero removes the limit
+        collect_cooldown: Option<u64>,
+        /// the share of the ASTRO received by a collect paid to its caller in bps
+        caller_reward_bps: Option<u16>,
     },
-    /// Add bridges
+    /// ## Description
+    /// Resends the governance percent of an IBC transfer which timed out and was refunded to the maker
+    /// ## Executor
+    /// Only owner can execute it
+    RetryIbcTransfer {
+        /// the identifier of the pending transfer
+        id: u64,
+    },
+    /// Add bridges. All added assets are validated with [`AssetInfo::check`].
+    /// Only owner or operator can execute it
     UpdateBridges {
         add: Option<Vec<(AssetInfo, AssetInfo)>>,
         remove: Option<Vec<AssetInfo>>,
+        /// full bridge routes from an asset to ASTRO, validated with [`validate_bridge_route`].
+        /// A route is swapped hop by hop within a single collect and replaces the bridge of the asset
+        #[serde(default)]
+        add_routes: Option<Vec<(AssetInfo, Vec<AssetInfo>)>>,
     },
     /// Swap rewards via bridge assets
     SwapBridgeAssets { assets: Vec<AssetInfo>, depth: u64 },
-    /// Distribute rewards in ASTRO tokens
+    /// Internal use
+    /// Checks the ASTRO received by a collect after all of its swaps were executed
+    AssertMinAstroReceived {
+        /// the ASTRO balance of the maker before the collect
+        prev_balance: Uint128,
+        /// the minimum total ASTRO received by the collect
+        min_astro_received: Uint128,
+    },
+    /// Swaps the next tranche of every asset with a pending buyback into ASTRO according to the
+    /// [`BuybackParams`]. Can be called by anyone, e.g. a keeper bot, once per tranche interval
+    ContinueCollect {},
+    /// ## Description
+    /// Sets or removes the settings of the tranche-wise conversion into ASTRO. Removing them
+    /// swaps the pending amounts at once on the next collect
+    /// ## Executor
+    /// Only owner can execute it
+    SetBuyback {
+        /// the tranche settings, [`None`] disables the tranche-wise conversion
+        params: Option<BuybackParams>,
+    },
+    /// Distribute rewards in ASTRO tokens. Fails if the minimum distribution interval has not
+    /// passed or the ASTRO balance is below the minimum distribution amount.
+    /// At most the distribution rate per second since the last distribution is distributed, the
+    /// remainder stays in the maker as undistributed ASTRO.
+    /// If the staking contract rejects the transfer, the
+    /// staking share is kept in the maker as held ASTRO instead of failing the distribution
     DistributeAstro {},
+    /// Sends the held ASTRO which the staking contract rejected before to the staking contract
+    RetryDistribute {},
+    /// ## Description
+    /// Sets the destinations of distributed ASTRO and their weights, replacing the staking and
+    /// governance split. An empty list restores the split by `governance_percent`.
+    /// ## Executor
+    /// Only owner can execute it
+    SetDistributionWeights {
+        /// the destination addresses and their weights which sum to one, at most
+        /// [`MAX_DISTRIBUTION_DESTINATIONS`]
+        weights: Vec<(String, Decimal)>,
+        /// the destinations which receive their share in another asset instead of ASTRO. The
+        /// share is swapped through the ASTRO pair of the asset registered in the factory
+        #[serde(default)]
+        target_assets: Vec<(String, AssetInfo)>,
+    },
     /// Creates a request to change ownership.
     ProposeNewOwner {
         /// a new owner
@@ -69,6 +334,127 @@ pub enum ExecuteMsg {
     ClaimOwnership {},
     /// Enables rewards collecting
     EnableRewards { blocks: u64 },
+    /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
+    /// template.
+    Receive(Cw20ReceiveMsg),
+}
+
+impl ExecuteMsg {
+    /// ## Description
+    /// Returns an [`Err`] if the sender can't execute the message. The owner can execute every
+    /// message, the operator only [`ExecuteMsg::UpdateBridges`] and
+    /// [`ExecuteMsg::UpdateCollectSettings`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **sender** is the sender of the message.
+    ///
+    /// * **owner** is the owner of the maker.
+    ///
+    /// * **operator** is the operator of the maker.
+    pub fn assert_owner_or_operator(
+        &self,
+        sender: &Addr,
+        owner: &Addr,
+        operator: Option<&Addr>,
+    ) -> StdResult<()> {
+        let operator_allowed = matches!(
+            self,
+            ExecuteMsg::UpdateBridges { .. } | ExecuteMsg::UpdateCollectSettings { .. }
+        );
+        if sender == owner || (operator_allowed && operator == Some(sender)) {
+            Ok(())
+        } else {
+            Err(StdError::generic_err("Unauthorized"))
+        }
+    }
+}
+
+/// The maximum number of registered fee sources
+pub const MAX_FEE_SOURCES: usize = 20;
+/// The gas limit of the withdrawal of a single fee source
+pub const FEE_SOURCE_GAS_LIMIT: u64 = 300_000;
+/// The reply ID of the withdrawals of the fee sources
+pub const FEE_SOURCE_REPLY_ID: u64 = 1;
+
+/// ## Description
+/// This structure describes the execute messages a fee source has to support.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeSourceExecuteMsg {
+    /// Sends all fees accrued for the maker to the recipient
+    WithdrawFees { to: String },
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeSourcesResponse {
+    pub sources: Vec<Addr>,
+}
+
+/// ## Description
+/// Returns an [`Err`] if the fee source is already registered or the registry is full.
+/// ## Params
+/// * **sources** are the registered fee sources.
+///
+/// * **source** is the fee source to register.
+pub fn assert_fee_source_registrable(sources: &[Addr], source: &Addr) -> StdResult<()> {
+    if sources.contains(source) {
+        return Err(StdError::generic_err(format!(
+            "Fee source {} is already registered",
+            source
+        )));
+    }
+    if sources.len() >= MAX_FEE_SOURCES {
+        return Err(StdError::generic_err(format!(
+            "The maker can't have more than {} fee sources",
+            MAX_FEE_SOURCES
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the messages which withdraw the fees of the registered sources to the maker. Every
+/// withdrawal is gas limited and its failure is handled in the reply with
+/// [`FEE_SOURCE_REPLY_ID`], so a broken fee source doesn't block the collect. The collect itself
+/// is dispatched after the withdrawals so that the withdrawn fees are included.
+/// ## Params
+/// * **sources** are the registered fee sources.
+///
+/// * **maker** is the maker contract.
+pub fn fee_source_withdraw_msgs(sources: &[Addr], maker: &Addr) -> StdResult<Vec<SubMsg>> {
+    sources
+        .iter()
+        .map(|source| {
+            Ok(SubMsg {
+                id: FEE_SOURCE_REPLY_ID,
+                msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: source.to_string(),
+                    msg: to_binary(&FeeSourceExecuteMsg::WithdrawFees {
+                        to: maker.to_string(),
+                    })?,
+                    funds: vec![],
+                }),
+                gas_limit: Some(FEE_SOURCE_GAS_LIMIT),
+                reply_on: ReplyOn::Error,
+            })
+        })
+        .collect()
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Swaps the received tokens to ASTRO via the bridges and sends the ASTRO to the receiver
+    /// instead of distributing it. Used by the generator to consolidate proxy rewards.
+    SwapToAstro {
+        /// the recipient of the ASTRO
+        receiver: String,
+    },
 }
 
 /// ## Description
@@ -83,6 +469,322 @@ pub enum QueryMsg {
         assets: Vec<AssetInfo>,
     },
     Bridges {},
+    /// Returns the full bridge routes in a [`BridgeRoutesResponse`] object
+    BridgeRoutes {},
+    /// Returns the registered fee sources in a [`FeeSourcesResponse`] object
+    FeeSources {},
+    /// Returns the governance percent transfers sent over IBC which are not confirmed yet in a
+    /// [`PendingIbcTransfersResponse`] object
+    PendingIbcTransfers {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the tranche settings and the pending buybacks in a [`BuybackResponse`] object
+    Buyback {},
+    /// Simulates a collect of the assets against the current pool states without executing it
+    /// and returns the expected ASTRO and the assets which would be skipped in a
+    /// [`SimulateCollectResponse`] object
+    SimulateCollect {
+        assets: Vec<AssetWithLimit>,
+    },
+    /// Returns everything a keeper needs to decide whether a collect or a distribution is worth
+    /// executing in a [`StatusResponse`] object, see [`maker_status`]
+    Status {
+        /// the assets whose balances are reported, the bridge assets if empty
+        #[serde(default)]
+        assets: Vec<AssetInfo>,
+        /// the balances at or below this amount are omitted
+        #[serde(default)]
+        min_amount: Uint128,
+    },
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatusResponse {
+    /// the balances of the maker above the requested minimum amount
+    pub balances: Vec<Asset>,
+    /// the assets of `balances` which have a route to ASTRO
+    pub routable_assets: Vec<AssetInfo>,
+    /// the time of the last collect
+    pub last_collect_time: u64,
+    /// the earliest time of the next collect, see [`assert_collect_cooldown`]
+    pub next_collect_time: u64,
+    /// the time of the last distribution
+    pub last_distribution_time: u64,
+    /// the earliest time of the next distribution, see [`assert_distribution_allowed`]
+    pub next_distribution_time: u64,
+    /// the ASTRO which can be distributed now, see [`distributable_amount`]
+    pub distributable_astro: Uint128,
+    /// whether rewards collecting was enabled with [`ExecuteMsg::EnableRewards`]
+    pub rewards_enabled: bool,
+    /// whether a distribution can be executed now
+    pub distribution_allowed: bool,
+}
+
+/// ## Description
+/// Returns the status of the maker for a [`QueryMsg::Status`] query.
+/// ## Params
+/// * **config** is the object of type [`ConfigResponse`].
+///
+/// * **time** is the current timestamp.
+///
+/// * **balances** are the balances of the requested assets.
+///
+/// * **min_amount** is the amount at or below which balances are omitted.
+///
+/// * **has_route** returns whether an asset has a route to ASTRO.
+///
+/// * **rewards_enabled** is whether rewards collecting is enabled.
+pub fn maker_status(
+    config: &ConfigResponse,
+    time: u64,
+    balances: Vec<Asset>,
+    min_amount: Uint128,
+    has_route: impl Fn(&AssetInfo) -> bool,
+    rewards_enabled: bool,
+) -> StatusResponse {
+    let balances: Vec<Asset> = balances
+        .into_iter()
+        .filter(|asset| asset.amount > min_amount)
+        .collect();
+    let routable_assets = balances
+        .iter()
+        .map(|asset| asset.info.clone())
+        .filter(|info| has_route(info))
+        .collect();
+    let distributable_astro = distributable_amount(config, time);
+
+    StatusResponse {
+        balances,
+        routable_assets,
+        last_collect_time: config.last_collect_time,
+        next_collect_time: config
+            .last_collect_time
+            .saturating_add(config.collect_cooldown),
+        last_distribution_time: config.last_distribution_time,
+        next_distribution_time: config
+            .last_distribution_time
+            .saturating_add(config.min_distribution_interval),
+        distributable_astro,
+        rewards_enabled,
+        distribution_allowed: rewards_enabled
+            && assert_distribution_allowed(config, time, distributable_astro).is_ok(),
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateCollectResponse {
+    /// the total amount of ASTRO expected from the collect
+    pub astro_amount: Uint128,
+    /// the simulated swaps of the collected assets
+    pub swaps: Vec<SimulatedCollectSwap>,
+    /// the assets which would be skipped by the collect
+    pub skipped: Vec<SkippedAsset>,
+}
+
+/// ## Description
+/// This structure describes the simulated conversion of a collected asset into ASTRO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatedCollectSwap {
+    /// the offered asset
+    pub offer: Asset,
+    /// the assets traversed by the bridges, ending with ASTRO
+    pub route: Vec<AssetInfo>,
+    /// the expected amount of ASTRO
+    pub astro_amount: Uint128,
+    /// the highest spread of the swaps on the route
+    pub max_spread: Decimal,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BuybackResponse {
+    /// the settings of the tranche-wise conversion, [`None`] if disabled
+    pub params: Option<BuybackParams>,
+    /// the amounts of the assets which are not swapped into ASTRO yet
+    pub pending: Vec<Asset>,
+    /// the block of the last swapped tranche
+    pub last_tranche_block: u64,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BridgeRoutesResponse {
+    /// the assets and their routes to ASTRO, the route ends with ASTRO
+    pub routes: Vec<(AssetInfo, Vec<AssetInfo>)>,
+}
+
+/// The maximum number of hops of a bridge route
+pub const MAX_BRIDGE_ROUTE_HOPS: usize = 3;
+
+/// ## Description
+/// Returns the message swapping a collected asset in a pair. Native assets, including the gas
+/// token, are swapped directly with the coins attached to the message net of the tax, so native
+/// fee denoms don't need to be wrapped into a CW20 token. Tokens are sent to the pair with a
+/// [`PairCw20HookMsg::Swap`] hook.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **pair** is the pair contract address.
+///
+/// * **offer** is the collected asset to swap.
+///
+/// * **max_spread** is the maximum spread of the swap.
+///
+/// * **to** is an [`Option`] field with the recipient of the swap, the maker if not set.
+pub fn build_swap_msg(
+    querier: &QuerierWrapper,
+    pair: &Addr,
+    offer: Asset,
+    max_spread: Decimal,
+    to: Option<String>,
+) -> StdResult<CosmosMsg> {
+    match &offer.info {
+        AssetInfo::NativeToken { .. } => {
+            let coin = offer.deduct_tax(querier)?;
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: pair.to_string(),
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: offer.info.clone(),
+                        amount: coin.amount,
+                    },
+                    belief_price: None,
+                    max_spread: Some(max_spread),
+                    to,
+                    deadline: None,
+                    fill_mode: FillMode::FillOrKill {},
+                    fee_discount_bps: 0,
+                })?,
+                funds: vec![coin],
+            }))
+        }
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount: offer.amount,
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: Some(max_spread),
+                    to,
+                    deadline: None,
+                    fill_mode: FillMode::FillOrKill {},
+                    fee_discount_bps: 0,
+                })?,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+/// ## Description
+/// Returns the ASTRO received by a collect. Returns an [`Err`] if it is less than the minimum,
+/// which reverts the whole collect.
+/// ## Params
+/// * **prev_balance** is the ASTRO balance of the maker before the collect.
+///
+/// * **balance** is the ASTRO balance of the maker after all swaps of the collect.
+///
+/// * **min_astro_received** is the minimum total ASTRO received by the collect.
+pub fn assert_min_astro_received(
+    prev_balance: Uint128,
+    balance: Uint128,
+    min_astro_received: Uint128,
+) -> StdResult<Uint128> {
+    let received = balance.saturating_sub(prev_balance);
+    if received < min_astro_received {
+        return Err(StdError::generic_err(format!(
+            "Collect received {} ASTRO, less than the minimum of {}",
+            received, min_astro_received
+        )));
+    }
+    Ok(received)
+}
+
+/// The maximum number of factories the maker resolves pairs in besides the main factory
+pub const MAX_ADDITIONAL_FACTORIES: usize = 5;
+
+/// ## Description
+/// Returns the pair of the assets from the first factory which has one, so pairs of the main
+/// factory take priority over pairs of legacy factories.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factories** are the factory contract addresses in priority order.
+///
+/// * **asset_infos** are the assets of the pair.
+pub fn query_pair_in_factories(
+    querier: &QuerierWrapper,
+    factories: &[Addr],
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    factories
+        .iter()
+        .find_map(|factory| query_pair_info(querier, factory.clone(), asset_infos).ok())
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "No pair found for the bridge {} -> {}",
+                asset_infos[0], asset_infos[1]
+            ))
+        })
+}
+
+/// ## Description
+/// Returns an [`Err`] if the bridge route of the asset doesn't terminate in ASTRO, is longer than
+/// [`MAX_BRIDGE_ROUTE_HOPS`], visits an asset twice or a hop has no pair in any of the factories.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factories** are the factory contract addresses in priority order.
+///
+/// * **asset** is the asset which is swapped via the route.
+///
+/// * **route** are the assets the asset is swapped to in order, the last one has to be ASTRO.
+///
+/// * **astro** is the ASTRO token.
+pub fn validate_bridge_route(
+    querier: &QuerierWrapper,
+    factories: &[Addr],
+    asset: &AssetInfo,
+    route: &[AssetInfo],
+    astro: &AssetInfo,
+) -> StdResult<()> {
+    if route.len() > MAX_BRIDGE_ROUTE_HOPS {
+        return Err(StdError::generic_err(format!(
+            "Bridge route of {} exceeds {} hops",
+            asset, MAX_BRIDGE_ROUTE_HOPS
+        )));
+    }
+    match route.last() {
+        Some(last) if last.equal(astro) => {}
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "Bridge route of {} must terminate in ASTRO",
+                asset
+            )))
+        }
+    }
+
+    let mut from = asset;
+    for (i, to) in route.iter().enumerate() {
+        if to.equal(asset) || route[..i].iter().any(|visited| visited.equal(to)) {
+            return Err(StdError::generic_err(format!(
+                "Bridge route of {} visits {} twice",
+                asset, to
+            )));
+        }
+        query_pair_in_factories(querier, factories, &[from.clone(), to.clone()])?;
+        from = to;
+    }
+
+    Ok(())
 }
 
 /// ## Description
@@ -95,18 +797,450 @@ pub struct ConfigResponse {
     pub astro_token_contract: Addr,
     /// the factory contract address
     pub factory_contract: Addr,
+    /// the factories whose pairs are used when the main factory has none, in priority order
+    #[serde(default)]
+    pub additional_factories: Vec<Addr>,
     /// the staking contract address
     pub staking_contract: Addr,
     /// the governance contract address
     pub governance_contract: Option<Addr>,
-    /// the governance percent
+    /// the governance percent, see [`ConfigResponse::effective_governance_percent`]
     pub governance_percent: Uint64,
+    /// the percent of collected ASTRO burned on each distribution
+    #[serde(default)]
+    pub burn_percent: Uint64,
+    /// the cumulative amount of burned ASTRO
+    #[serde(default)]
+    pub burned_astro: Uint128,
     /// the maximum spread
     pub max_spread: Decimal,
     /// the remainder of pre-upgrade ASTRO fee
     pub remainder_reward: Uint128,
     /// the amount of collected ASTRO fee before enabling rewards distribution
     pub pre_upgrade_astro_amount: Uint128,
+    /// the remote governance which receives the governance percent over IBC
+    pub remote_governance: Option<RemoteGovernance>,
+    /// the staking share of ASTRO held by the maker because the staking contract rejected it
+    pub held_astro: Uint128,
+    /// the minimum number of seconds between two distributions
+    pub min_distribution_interval: u64,
+    /// the minimum amount of ASTRO distributed at once
+    pub min_distribution_amount: Uint128,
+    /// the time of the last distribution
+    pub last_distribution_time: u64,
+    /// the insurance fund contract address
+    pub insurance_fund: Option<Addr>,
+    /// the percent of distributed ASTRO sent to the insurance fund
+    pub insurance_percent: Uint64,
+    /// the destinations of distributed ASTRO and their weights, the split by
+    /// `governance_percent` is used if empty
+    pub distribution_weights: Vec<(Addr, Decimal)>,
+    /// the destinations which receive their share in another asset instead of ASTRO
+    #[serde(default)]
+    pub target_assets: Vec<(Addr, AssetInfo)>,
+    /// the maximum amount of ASTRO distributed per second
+    pub distribution_rate: Option<Uint128>,
+    /// the collected ASTRO which is not distributed yet
+    pub undistributed_astro: Uint128,
+    /// the minimum number of seconds between two collects
+    #[serde(default)]
+    pub collect_cooldown: u64,
+    /// the share of the ASTRO received by a collect paid to its caller in bps
+    #[serde(default)]
+    pub caller_reward_bps: u16,
+    /// the time of the last collect
+    #[serde(default)]
+    pub last_collect_time: u64,
+    /// the address which can maintain the bridges and the collect throttling
+    #[serde(default)]
+    pub operator: Option<Addr>,
+}
+
+/// The maximum governance percent
+pub const MAX_GOVERNANCE_PERCENT: u64 = 100;
+
+impl ConfigResponse {
+    /// ## Description
+    /// Returns the governance percent applied to distributions. It is zero while neither the
+    /// governance contract nor the remote governance is set, so all ASTRO goes to the staking.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn effective_governance_percent(&self) -> Uint64 {
+        if self.governance_contract.is_none() && self.remote_governance.is_none() {
+            return Uint64::zero();
+        }
+        self.governance_percent
+    }
+}
+
+/// ## Description
+/// Returns an [`Err`] if the governance settings are inconsistent: the percent exceeds
+/// [`MAX_GOVERNANCE_PERCENT`] or is nonzero without a governance to receive it. Enabling the
+/// governance while ASTRO is undistributed is rejected too, as that ASTRO was collected for the
+/// staking only and must be distributed before the governance receives a share.
+/// ## Params
+/// * **config** is the current object of type [`ConfigResponse`], [`None`] on instantiation.
+///
+/// * **has_governance** is whether the governance contract or the remote governance is set after the update.
+///
+/// * **governance_percent** is the governance percent after the update.
+pub fn validate_governance(
+    config: Option<&ConfigResponse>,
+    has_governance: bool,
+    governance_percent: Uint64,
+) -> StdResult<()> {
+    if governance_percent.u64() > MAX_GOVERNANCE_PERCENT {
+        return Err(StdError::generic_err(format!(
+            "The governance percent can't exceed {}",
+            MAX_GOVERNANCE_PERCENT
+        )));
+    }
+    if !has_governance && !governance_percent.is_zero() {
+        return Err(StdError::generic_err(
+            "The governance percent must be zero while the governance is not set",
+        ));
+    }
+    if let Some(config) = config {
+        let had_governance =
+            config.governance_contract.is_some() || config.remote_governance.is_some();
+        if !had_governance && has_governance && !config.undistributed_astro.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "Distribute the {} undistributed ASTRO before setting the governance",
+                config.undistributed_astro
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns an [`Err`] if the insurance percent exceeds [`MAX_GOVERNANCE_PERCENT`].
+/// ## Params
+/// * **insurance_percent** is the insurance percent after the update.
+pub fn validate_insurance_percent(insurance_percent: Uint64) -> StdResult<()> {
+    if insurance_percent.u64() > MAX_GOVERNANCE_PERCENT {
+        return Err(StdError::generic_err(format!(
+            "The insurance percent can't exceed {}",
+            MAX_GOVERNANCE_PERCENT
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns an [`Err`] if the burn percent together with the governance and the insurance
+/// percents exceeds [`MAX_GOVERNANCE_PERCENT`], so the staking share can't become negative.
+/// ## Params
+/// * **governance_percent** is the governance percent after the update.
+///
+/// * **burn_percent** is the burn percent after the update.
+///
+/// * **insurance_percent** is the insurance percent after the update.
+pub fn validate_burn_percent(
+    governance_percent: Uint64,
+    burn_percent: Uint64,
+    insurance_percent: Uint64,
+) -> StdResult<()> {
+    let total = governance_percent
+        .checked_add(burn_percent)
+        .and_then(|total| total.checked_add(insurance_percent));
+    if !matches!(total, Ok(total) if total.u64() <= MAX_GOVERNANCE_PERCENT) {
+        return Err(StdError::generic_err(format!(
+            "The burn percent together with the governance and the insurance percents can't exceed {}",
+            MAX_GOVERNANCE_PERCENT
+        )));
+    }
+    Ok(())
+}
+
+impl ConfigResponse {
+    /// ## Description
+    /// Returns the message burning the `burn_percent` share of the distributed ASTRO and adds the
+    /// burned amount to `burned_astro`. Returns [`None`] if nothing is burned. The governance
+    /// share is still computed from the whole distributed amount and the staking receives the rest.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **amount** is the amount of ASTRO distributed.
+    pub fn burn_msg(&mut self, amount: Uint128) -> StdResult<Option<(Uint128, CosmosMsg)>> {
+        let burn_amount = amount.multiply_ratio(self.burn_percent.u64(), MAX_GOVERNANCE_PERCENT);
+        if burn_amount.is_zero() {
+            return Ok(None);
+        }
+        self.burned_astro = self.burned_astro.checked_add(burn_amount)?;
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.astro_token_contract.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: burn_amount,
+            })?,
+            funds: vec![],
+        });
+        Ok(Some((burn_amount, msg)))
+    }
+}
+
+/// ## Description
+/// Returns the amount of ASTRO which can be distributed according to the distribution rate.
+/// ## Params
+/// * **config** is the object of type [`ConfigResponse`].
+///
+/// * **time** is the current timestamp.
+pub fn distributable_amount(config: &ConfigResponse, time: u64) -> Uint128 {
+    match config.distribution_rate {
+        Some(rate) => {
+            let elapsed = time.saturating_sub(config.last_distribution_time);
+            std::cmp::min(
+                config.undistributed_astro,
+                rate.saturating_mul(Uint128::from(elapsed)),
+            )
+        }
+        None => config.undistributed_astro,
+    }
+}
+
+/// The maximum number of destinations in [`ExecuteMsg::SetDistributionWeights`]
+pub const MAX_DISTRIBUTION_DESTINATIONS: usize = 10;
+
+/// ## Description
+/// Returns an [`Err`] if the distribution weights are not positive, contain a destination more
+/// than once or don't sum to one.
+/// ## Params
+/// * **weights** are the destination addresses and their weights.
+pub fn validate_distribution_weights(weights: &[(String, Decimal)]) -> StdResult<()> {
+    if weights.len() > MAX_DISTRIBUTION_DESTINATIONS {
+        return Err(StdError::generic_err(format!(
+            "At most {} distribution destinations are allowed",
+            MAX_DISTRIBUTION_DESTINATIONS
+        )));
+    }
+    let mut total = Decimal::zero();
+    for (i, (destination, weight)) in weights.iter().enumerate() {
+        if weight.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "The weight of {} must be positive",
+                destination
+            )));
+        }
+        if weights[..i].iter().any(|(other, _)| other == destination) {
+            return Err(StdError::generic_err(format!(
+                "Duplicated distribution destination {}",
+                destination
+            )));
+        }
+        total = total + *weight;
+    }
+    if !weights.is_empty() && total != Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "Distribution weights must sum to 1, got {}",
+            total
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns an [`Err`] if a target asset is set for an address which is not a distribution
+/// destination, is set more than once for a destination or is ASTRO itself.
+/// ## Params
+/// * **weights** are the destination addresses and their weights.
+///
+/// * **target_assets** are the destination addresses and the assets they receive.
+///
+/// * **astro_token** is the ASTRO token contract address.
+pub fn validate_target_assets(
+    weights: &[(String, Decimal)],
+    target_assets: &[(String, AssetInfo)],
+    astro_token: &Addr,
+) -> StdResult<()> {
+    let astro = AssetInfo::Token {
+        contract_addr: astro_token.clone(),
+    };
+    for (i, (destination, asset)) in target_assets.iter().enumerate() {
+        if !weights.iter().any(|(other, _)| other == destination) {
+            return Err(StdError::generic_err(format!(
+                "{} is not a distribution destination",
+                destination
+            )));
+        }
+        if target_assets[..i]
+            .iter()
+            .any(|(other, _)| other == destination)
+        {
+            return Err(StdError::generic_err(format!(
+                "Duplicated target asset of {}",
+                destination
+            )));
+        }
+        if asset.equal(&astro) {
+            return Err(StdError::generic_err(format!(
+                "The target asset of {} can't be ASTRO",
+                destination
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Splits the distributed amount among the destinations by their weights. The rounding remainder
+/// goes to the last destination, so the parts always sum to the amount.
+/// ## Params
+/// * **amount** is the amount of ASTRO to distribute.
+///
+/// * **weights** are the validated destination addresses and their weights.
+pub fn split_by_weights(amount: Uint128, weights: &[(Addr, Decimal)]) -> Vec<(Addr, Uint128)> {
+    let mut remaining = amount;
+    weights
+        .iter()
+        .enumerate()
+        .map(|(i, (destination, weight))| {
+            let part = if i + 1 == weights.len() {
+                remaining
+            } else {
+                std::cmp::min(amount * *weight, remaining)
+            };
+            remaining -= part;
+            (destination.clone(), part)
+        })
+        .collect()
+}
+
+/// ## Description
+/// Returns an [`Err`] if ASTRO can't be distributed yet.
+/// ## Params
+/// * **config** is the object of type [`ConfigResponse`].
+///
+/// * **time** is the current timestamp.
+///
+/// * **amount** is the amount of ASTRO to distribute.
+pub fn assert_distribution_allowed(
+    config: &ConfigResponse,
+    time: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    let next_distribution_time = config
+        .last_distribution_time
+        .saturating_add(config.min_distribution_interval);
+    if time < next_distribution_time {
+        return Err(StdError::generic_err(format!(
+            "ASTRO can't be distributed before {}",
+            next_distribution_time
+        )));
+    }
+    if amount.is_zero() || amount < config.min_distribution_amount {
+        return Err(StdError::generic_err(format!(
+            "The amount to distribute {} is below the minimum {}",
+            amount, config.min_distribution_amount
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// This structure describes the data set in the response of the collect operation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectResponse {
+    /// the collected assets, LP tokens are replaced by their underlying assets
+    pub collected: Vec<Asset>,
+    /// the assets swapped to ASTRO or to a bridge asset
+    pub swapped: Vec<Asset>,
+    /// the amount of ASTRO received by the swaps
+    pub astro_received: Uint128,
+    /// the assets which were not collected
+    pub skipped: Vec<SkippedAsset>,
+    /// the ASTRO paid to the caller out of the received amount
+    #[serde(default)]
+    pub caller_reward: Uint128,
+}
+
+/// ## Description
+/// This structure describes an asset skipped by the collect operation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SkippedAsset {
+    /// the skipped asset
+    pub info: AssetInfo,
+    /// the reason the asset was skipped
+    pub reason: SkipReason,
+}
+
+/// ## Description
+/// This enum describes the reasons an asset is skipped by the collect operation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The maker has no balance of the asset
+    ZeroBalance {},
+    /// There is neither a pair with ASTRO nor a bridge for the asset
+    NoRoute {},
+    /// The swap of the asset failed
+    SwapFailed { error: String },
+    /// The spread of a swap on the route of the asset is above the maximum spread
+    SpreadTooHigh { spread: Decimal },
+}
+
+/// ## Description
+/// This structure describes the governance living on a remote chain which receives the governance
+/// percent via a CW20-ICS20 contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RemoteGovernance {
+    /// the CW20-ICS20 contract used to transfer ASTRO over IBC
+    pub ics20_contract: String,
+    /// the IBC channel to the remote chain
+    pub channel: String,
+    /// the governance address on the remote chain
+    pub address: String,
+    /// the timeout of a transfer in seconds
+    pub timeout: u64,
+}
+
+/// ## Description
+/// This is an enumeration for setting and unsetting the remote governance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateRemoteGovernance {
+    /// Sets the remote governance
+    Set(RemoteGovernance),
+    /// Removes the remote governance
+    Remove {},
+}
+
+/// ## Description
+/// This structure describes the hook message sent to a CW20-ICS20 contract to transfer tokens over IBC.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ics20TransferMsg {
+    /// the local channel to send the packets on
+    pub channel: String,
+    /// the remote address to send to
+    pub remote_address: String,
+    /// how long the packet lives in seconds
+    pub timeout: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes a governance percent transfer sent over IBC.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingIbcTransfer {
+    /// the identifier of the transfer
+    pub id: u64,
+    /// the amount of ASTRO sent
+    pub amount: Uint128,
+    /// the IBC channel used
+    pub channel: String,
+    /// the receiver on the remote chain
+    pub receiver: String,
+    /// the time at which the transfer was sent
+    pub sent_at: u64,
+    /// the time after which the transfer is considered timed out and can be retried
+    pub timeout_at: u64,
+    /// the number of retries of the transfer
+    pub retries: u32,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the pending IBC transfers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingIbcTransfersResponse {
+    pub transfers: Vec<PendingIbcTransfer>,
 }
 
 /// ## Description
@@ -116,6 +1250,98 @@ pub struct BalancesResponse {
     pub balances: Vec<Asset>,
 }
 
+/// ## Description
+/// This structure describes the sudo messages sent by the chain governance on chains where the
+/// contract admin is the governance module, see [`SudoMsg::into_execute_msg`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Updates general settings like [`ExecuteMsg::UpdateConfig`]
+    UpdateConfig {
+        /// the factory contract address
+        factory_contract: Option<String>,
+        /// the factories whose pairs are used when the main factory has none, in priority order
+        additional_factories: Option<Vec<String>>,
+        /// the staking contract address
+        staking_contract: Option<String>,
+        /// the governance contract address
+        governance_contract: Option<UpdateAddr>,
+        /// the governance percent, the settings after the update must pass [`validate_governance`]
+        governance_percent: Option<Uint64>,
+        /// the percent of collected ASTRO burned, see [`validate_burn_percent`]
+        #[serde(default)]
+        burn_percent: Option<Uint64>,
+        /// the maximum spread
+        max_spread: Option<Decimal>,
+        /// the remote governance which receives the governance percent over IBC
+        remote_governance: Option<UpdateRemoteGovernance>,
+        /// the minimum number of seconds between two distributions
+        min_distribution_interval: Option<u64>,
+        /// the minimum amount of ASTRO distributed at once
+        min_distribution_amount: Option<Uint128>,
+        /// the insurance fund contract address
+        insurance_fund: Option<UpdateAddr>,
+        /// the percent of distributed ASTRO sent to the insurance fund, see
+        /// [`validate_insurance_percent`]
+        insurance_percent: Option<Uint64>,
+        /// the maximum amount of ASTRO distributed per second, zero removes the limit
+        distribution_rate: Option<Uint128>,
+        /// the minimum number of seconds between two collects, zero removes the limit
+        collect_cooldown: Option<u64>,
+        /// the share of the ASTRO received by a collect paid to its caller in bps
+        caller_reward_bps: Option<u16>,
+        /// the address which can maintain the bridges and the collect throttling
+        #[serde(default)]
+        operator: Option<UpdateAddr>,
+    },
+}
+
+impl SudoMsg {
+    /// ## Description
+    /// Returns the execute message which is processed with the owner's authority.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn into_execute_msg(self) -> ExecuteMsg {
+        match self {
+            SudoMsg::UpdateConfig {
+                factory_contract,
+                additional_factories,
+                staking_contract,
+                governance_contract,
+                governance_percent,
+                burn_percent,
+                max_spread,
+                remote_governance,
+                min_distribution_interval,
+                min_distribution_amount,
+                insurance_fund,
+                insurance_percent,
+                distribution_rate,
+                collect_cooldown,
+                caller_reward_bps,
+                operator,
+            } => ExecuteMsg::UpdateConfig {
+                factory_contract,
+                additional_factories,
+                staking_contract,
+                governance_contract,
+                governance_percent,
+                burn_percent,
+                max_spread,
+                remote_governance,
+                min_distribution_interval,
+                min_distribution_amount,
+                insurance_fund,
+                insurance_percent,
+                distribution_rate,
+                collect_cooldown,
+                caller_reward_bps,
+                operator,
+            },
+        }
+    }
+}
+
 /// ## Description
 /// This structure describes a migration message.
 /// We currently take no arguments for migrations.
@@ -0,0 +1,1176 @@
+use cosmwasm_std::{
+    Decimal, Decimal256, Fraction, OverflowError, StdError, StdResult, Uint128, Uint256,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use thiserror::Error;
+
+use crate::factory::DynamicFeeBounds;
+use crate::pair::{
+    DynamicFeeParams, ImbalanceFee, ImbalanceFeeParams, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE,
+};
+use crate::DecimalCheckedOps;
+
+/// The number of assets in a pool
+pub const N_COINS: u8 = 2;
+/// The maximum number of iterations of Newton's method used by the stableswap math
+const ITERATIONS: u8 = 32;
+
+/// ## Description
+/// This enum describes the errors of the swap math. Routers can match on the typed variants to
+/// skip a pool instead of treating the failure as a hard error.
+#[derive(Error, Debug, PartialEq)]
+pub enum SwapError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Insufficient liquidity: requested {requested}, available {available}")]
+    InsufficientLiquidity {
+        requested: Uint128,
+        available: Uint128,
+    },
+
+    #[error("Operation exceeds max spread limit: spread {spread}, max spread {max_spread}")]
+    MaxSpreadAssertion {
+        spread: Decimal,
+        max_spread: Decimal,
+    },
+
+    #[error("The swap amount is zero")]
+    ZeroAmount {},
+
+    #[error("The pool has no liquidity")]
+    PoolEmpty {},
+}
+
+impl From<SwapError> for StdError {
+    fn from(err: SwapError) -> Self {
+        match err {
+            SwapError::Std(err) => err,
+            SwapError::Overflow(err) => err.into(),
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+/// ## Description
+/// Computes the result of an xyk swap. Returns the return amount, the spread amount and the
+/// commission amount. The return amount has the commission already deducted.
+/// ## Params
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **offer_amount** is the amount being swapped.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn compute_swap(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), SwapError> {
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+    if offer_amount.is_zero() {
+        return Err(SwapError::ZeroAmount {});
+    }
+
+    let offer_pool: Uint256 = offer_pool.into();
+    let ask_pool: Uint256 = ask_pool.into();
+    let offer_amount: Uint256 = offer_amount.into();
+
+    // the new ask pool is rounded up so that the invariant never decreases
+    let cp = offer_pool * ask_pool;
+    let return_amount = ask_pool - ceil_div(cp, offer_pool + offer_amount)?;
+    let spread_amount = (offer_amount * ask_pool / offer_pool).saturating_sub(return_amount);
+
+    let return_amount = to_uint128(return_amount)?;
+    let commission_amount = return_amount * commission_rate;
+
+    Ok((
+        return_amount - commission_amount,
+        to_uint128(spread_amount)?,
+        commission_amount,
+    ))
+}
+
+/// ## Description
+/// Computes the result of an xyk swap which charges the commission in the offer asset. Returns
+/// the return amount, the spread amount and the commission amount. The commission amount is
+/// denominated in the offer asset and is deducted from the offer amount before the swap.
+/// ## Params
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **offer_amount** is the amount being swapped, including the commission.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn compute_swap_offer_commission(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), SwapError> {
+    let commission_amount = offer_amount * commission_rate;
+    let (return_amount, spread_amount, _) = compute_swap(
+        offer_pool,
+        ask_pool,
+        offer_amount - commission_amount,
+        Decimal::zero(),
+    )?;
+
+    Ok((return_amount, spread_amount, commission_amount))
+}
+
+/// ## Description
+/// Clears a batch of swaps in an xyk pool. The offers of both directions are netted against each
+/// other at the pool price before the batch and only the net imbalance is swapped against the
+/// pool, so every order of a direction receives the same price. Returns the return amounts and
+/// the commission amounts per direction, the item `i` belongs to the orders offering the asset `i`
+/// and is denominated in the other asset.
+/// ## Params
+/// * **pools** are the pool balances before the batch.
+///
+/// * **offers** are the total offered amounts per asset.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn compute_batch_clearing(
+    pools: [Uint128; 2],
+    offers: [Uint128; 2],
+    commission_rate: Decimal,
+) -> Result<([Uint128; 2], [Uint128; 2]), SwapError> {
+    assert_pools_not_empty(pools[0], pools[1])?;
+
+    // the offer of the second direction valued in the first asset at the pool price
+    let matched_first = offers[1].multiply_ratio(pools[0], pools[1]);
+    let gross = if offers[0] >= matched_first {
+        let residual = offers[0] - matched_first;
+        let swapped = if residual.is_zero() {
+            Uint128::zero()
+        } else {
+            compute_swap(pools[0], pools[1], residual, Decimal::zero())?.0
+        };
+        [offers[1].checked_add(swapped)?, matched_first]
+    } else {
+        let matched_second = offers[0].multiply_ratio(pools[1], pools[0]);
+        let residual = offers[1] - matched_second;
+        let swapped = compute_swap(pools[1], pools[0], residual, Decimal::zero())?.0;
+        [matched_second, offers[0].checked_add(swapped)?]
+    };
+
+    let commissions = [gross[0] * commission_rate, gross[1] * commission_rate];
+    Ok((
+        [gross[0] - commissions[0], gross[1] - commissions[1]],
+        commissions,
+    ))
+}
+
+/// ## Description
+/// Returns an [`Err`] if a flash swap did not restore the xyk invariant. The amounts sent back to
+/// the pool are charged the commission, so the borrower has to return the borrowed value plus the
+/// swap fee, in any combination of the pool assets.
+/// ## Params
+/// * **pools_before** are the pool balances before the borrowed amounts were sent out.
+///
+/// * **pools_after** are the pool balances after the callback of the borrower.
+///
+/// * **borrowed** are the borrowed amounts of both assets.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn assert_flash_swap_repaid(
+    pools_before: [Uint128; 2],
+    pools_after: [Uint128; 2],
+    borrowed: [Uint128; 2],
+    commission_rate: Decimal,
+) -> StdResult<()> {
+    let mut adjusted = [Uint128::zero(); 2];
+    for i in 0..2 {
+        let lent_pool = pools_before[i].checked_sub(borrowed[i])?;
+        let repaid = pools_after[i].saturating_sub(lent_pool);
+        // the fee is rounded up in favor of the pool
+        let fee: Uint128 = ((repaid.full_mul(commission_rate.numerator())
+            + Uint256::from(commission_rate.denominator() - 1))
+            / Uint256::from(commission_rate.denominator()))
+        .try_into()?;
+        adjusted[i] = pools_after[i].checked_sub(fee)?;
+    }
+    if adjusted[0].full_mul(adjusted[1]) < pools_before[0].full_mul(pools_before[1]) {
+        return Err(StdError::generic_err(
+            "The flash swap was not repaid with the fee",
+        ));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Computes the offer amount needed to receive the specified ask amount in an xyk pool.
+/// Returns the offer amount, the spread amount and the commission amount.
+/// ## Params
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **ask_amount** is the amount to receive after the commission is deducted.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn compute_offer_amount(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    ask_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), SwapError> {
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+    if ask_amount.is_zero() {
+        return Err(SwapError::ZeroAmount {});
+    }
+    if commission_rate >= Decimal::one() {
+        return Err(StdError::generic_err("Commission rate must be less than one").into());
+    }
+
+    // the amount to take from the pool before the commission is deducted
+    let before_commission = ceil_div(
+        Uint256::from(ask_amount) * Uint256::from(Decimal::one().numerator()),
+        Uint256::from((Decimal::one() - commission_rate).numerator()),
+    )?;
+    let before_commission = to_uint128(before_commission)?;
+    if before_commission >= ask_pool {
+        return Err(SwapError::InsufficientLiquidity {
+            requested: before_commission,
+            available: ask_pool,
+        });
+    }
+
+    let cp = Uint256::from(offer_pool) * Uint256::from(ask_pool);
+    let offer_amount =
+        ceil_div(cp, (ask_pool - before_commission).into())? - Uint256::from(offer_pool);
+    let offer_amount = to_uint128(offer_amount)?;
+
+    let spread_amount = offer_amount
+        .multiply_ratio(ask_pool, offer_pool)
+        .saturating_sub(before_commission);
+
+    Ok((offer_amount, spread_amount, before_commission - ask_amount))
+}
+
+/// ## Description
+/// Computes the amount of LP shares minted for the specified deposits.
+/// The first deposit mints the geometric mean of the deposits.
+/// ## Params
+/// * **pools** are the pool balances before the deposit.
+///
+/// * **deposits** are the deposited amounts.
+///
+/// * **total_share** is the LP token supply before the deposit.
+pub fn compute_share(
+    pools: [Uint128; 2],
+    deposits: [Uint128; 2],
+    total_share: Uint128,
+) -> StdResult<Uint128> {
+    if total_share.is_zero() {
+        return to_uint128(sqrt(
+            Uint256::from(deposits[0]) * Uint256::from(deposits[1]),
+        ));
+    }
+
+    assert_pools_not_empty(pools[0], pools[1])?;
+    Ok(std::cmp::min(
+        deposits[0].multiply_ratio(total_share, pools[0]),
+        deposits[1].multiply_ratio(total_share, pools[1]),
+    ))
+}
+
+/// ## Description
+/// Splits the first deposit of an empty xyk pool into the amounts which seed the pool at the
+/// requested initial price and the excess refunded to the depositor, so the deposited amounts
+/// don't have to match the price. The assets are in pool order, i.e. the order of the asset
+/// infos of the pair, and the price is the amount of the second asset per unit of the first
+/// asset. Returns an [`Err`] if the price is zero or the seeded amounts would be empty.
+/// ## Params
+/// * **deposits** are the deposited amounts in pool order.
+///
+/// * **initial_price** is the price of the first pool asset in the second pool asset.
+pub fn compute_initial_deposit(
+    deposits: [Uint128; 2],
+    initial_price: Decimal,
+) -> StdResult<([Uint128; 2], [Uint128; 2])> {
+    if initial_price.is_zero() {
+        return Err(StdError::generic_err("The initial price must be positive"));
+    }
+
+    // a value of the first deposit which overflows is more than the whole second deposit
+    let seeded = match initial_price.checked_mul(deposits[0]) {
+        Ok(second_at_price) if second_at_price <= deposits[1] => [deposits[0], second_at_price],
+        _ => [
+            deposits[1].multiply_ratio(initial_price.denominator(), initial_price.numerator()),
+            deposits[1],
+        ],
+    };
+    if seeded[0].is_zero() || seeded[1].is_zero() {
+        return Err(StdError::generic_err(
+            "The first deposit is too small to set the initial price",
+        ));
+    }
+    Ok((seeded, [deposits[0] - seeded[0], deposits[1] - seeded[1]]))
+}
+
+/// ## Description
+/// Returns an [`Err`] if the first deposit of a pool doesn't set the price requested by the
+/// depositor. The price is the amount of the second asset per unit of the first asset in pool
+/// order, so a deposit whose ratio was skewed by a donation before it was executed is rejected.
+/// Pairs which refund the excess seed the pool with [`compute_initial_deposit`] instead.
+/// ## Params
+/// * **deposits** are the deposited amounts.
+///
+/// * **initial_price** is the requested price of the first asset in the second asset.
+///
+/// * **slippage_tolerance** is the maximum deviation from the price, [`DEFAULT_SLIPPAGE`] by default.
+pub fn assert_initial_price(
+    deposits: [Uint128; 2],
+    initial_price: Decimal,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<()> {
+    if deposits[0].is_zero() || deposits[1].is_zero() {
+        return Err(StdError::generic_err(
+            "The first deposit must contain both assets",
+        ));
+    }
+    if initial_price.is_zero() {
+        return Err(StdError::generic_err("The initial price must be positive"));
+    }
+
+    let tolerance = resolve_max_spread(slippage_tolerance, None)?;
+    // the price of any deposit fits into a Decimal256, unlike into a Decimal
+    let price = Decimal256::from_ratio(deposits[1], deposits[0]);
+    let expected = Decimal256::from_ratio(initial_price.numerator(), initial_price.denominator());
+    let deviation = if price > expected {
+        price - expected
+    } else {
+        expected - price
+    };
+    let max_deviation = Decimal256::from_ratio(
+        Uint256::from(initial_price.numerator()) * Uint256::from(tolerance.numerator()),
+        Uint256::from(tolerance.denominator()) * Uint256::from(initial_price.denominator()),
+    );
+    if deviation > max_deviation {
+        return Err(StdError::generic_err(format!(
+            "The deposit sets the price {}, expected {}",
+            price, initial_price
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Computes the assets returned for burning the specified amount of LP shares.
+/// ## Params
+/// * **pools** are the pool balances before the withdrawal.
+///
+/// * **share** is the amount of LP shares to burn.
+///
+/// * **total_share** is the LP token supply before the withdrawal.
+pub fn compute_withdraw(
+    pools: [Uint128; 2],
+    share: Uint128,
+    total_share: Uint128,
+) -> StdResult<[Uint128; 2]> {
+    if share > total_share || total_share.is_zero() {
+        return Err(StdError::generic_err(
+            "The share exceeds the total share of the pool",
+        ));
+    }
+
+    Ok([
+        pools[0].multiply_ratio(share, total_share),
+        pools[1].multiply_ratio(share, total_share),
+    ])
+}
+
+/// ## Description
+/// Computes the stableswap invariant D for the specified pool balances. Returns an [`Err`] if the
+/// amplification coefficient is zero.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **pools** are the pool balances.
+pub fn compute_d(amp: u64, pools: [Uint128; 2]) -> StdResult<Uint128> {
+    assert_amp_not_zero(amp)?;
+    let sum_x = Uint256::from(pools[0]) + Uint256::from(pools[1]);
+    if sum_x.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    assert_pools_not_empty(pools[0], pools[1])?;
+
+    let n_coins = Uint256::from(N_COINS);
+    let leverage = Uint256::from(amp) * n_coins;
+    let mut d = sum_x;
+    for _ in 0..ITERATIONS {
+        let mut d_p = d;
+        for pool in pools.iter() {
+            d_p = d_p * d / (Uint256::from(*pool) * n_coins);
+        }
+        let d_prev = d;
+        d = (leverage * sum_x + d_p * n_coins) * d
+            / ((leverage - Uint256::from(1u8)) * d + (n_coins + Uint256::from(1u8)) * d_p);
+        if abs_diff(d, d_prev) <= Uint256::from(1u8) {
+            return to_uint128(d);
+        }
+    }
+
+    Err(StdError::generic_err(
+        "Newton method for D failed to converge",
+    ))
+}
+
+/// ## Description
+/// This structure describes the stableswap invariant of a pool state. Stable pairs store it and
+/// simulations reuse it while the amplification coefficient and the reserves are unchanged, so
+/// D is recomputed lazily only after the reserves change.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InvariantCache {
+    /// the amplification coefficient the invariant was computed with
+    pub amp: u64,
+    /// the pool balances the invariant was computed for
+    pub pools: [Uint128; 2],
+    /// the invariant D
+    pub d: Uint128,
+}
+
+impl InvariantCache {
+    /// ## Description
+    /// Returns the cached invariant if it was computed for the same amplification coefficient
+    /// and balances in any order, otherwise computes it with [`compute_d`].
+    /// ## Params
+    /// * **cache** is the stored object of type [`InvariantCache`], if any.
+    ///
+    /// * **amp** is the current amplification coefficient.
+    ///
+    /// * **pools** are the current pool balances.
+    pub fn load_or_compute(
+        cache: Option<&InvariantCache>,
+        amp: u64,
+        pools: [Uint128; 2],
+    ) -> StdResult<InvariantCache> {
+        match cache {
+            Some(cache)
+                if cache.amp == amp
+                    && (cache.pools == pools || cache.pools == [pools[1], pools[0]]) =>
+            {
+                Ok(cache.clone())
+            }
+            _ => Ok(InvariantCache {
+                amp,
+                pools,
+                d: compute_d(amp, pools)?,
+            }),
+        }
+    }
+}
+
+/// ## Description
+/// Computes the imbalance of a stable pool measured against the invariant, i.e. the distance of
+/// the pool balances from the balanced state where each balance equals `D / 2`, relative to D.
+/// Returns zero for a balanced or empty pool.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **pools** are the pool balances.
+pub fn compute_imbalance(amp: u64, pools: [Uint128; 2]) -> StdResult<Decimal> {
+    let d = compute_d(amp, pools)?;
+    if d.is_zero() {
+        return Ok(Decimal::zero());
+    }
+
+    let ideal = Uint256::from(d) / Uint256::from(N_COINS);
+    let distance = pools.iter().fold(Uint256::zero(), |acc, pool| {
+        acc + abs_diff(Uint256::from(*pool), ideal)
+    });
+
+    Ok(Decimal::from_ratio(to_uint128(distance)?, d))
+}
+
+/// ## Description
+/// Returns the fee applied to a stable pool provide or withdrawal which changes the pool balances.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **params** is the object of type [`ImbalanceFeeParams`].
+///
+/// * **commission_rate** is the total fee rate of the pair.
+///
+/// * **pools_before** are the pool balances before the operation.
+///
+/// * **pools_after** are the pool balances after the operation.
+pub fn compute_imbalance_fee(
+    amp: u64,
+    params: &ImbalanceFeeParams,
+    commission_rate: Decimal,
+    pools_before: [Uint128; 2],
+    pools_after: [Uint128; 2],
+) -> StdResult<ImbalanceFee> {
+    let before = compute_imbalance(amp, pools_before)?;
+    let after = compute_imbalance(amp, pools_after)?;
+    let scale = |multiplier: Decimal| {
+        Decimal::from_ratio(
+            Uint128::from(commission_rate.numerator()) * multiplier,
+            commission_rate.denominator(),
+        )
+    };
+
+    Ok(if after > before {
+        ImbalanceFee::Charge(scale(params.fee_multiplier))
+    } else if after < before {
+        ImbalanceFee::Rebate(scale(params.rebate_multiplier))
+    } else {
+        ImbalanceFee::None
+    })
+}
+
+/// ## Description
+/// Computes the relative deviation of the balance ratio of a pool from the target ratio, i.e.
+/// `|pools[0] / pools[1] - target_ratio| / target_ratio`. Returns zero for an empty pool.
+/// ## Params
+/// * **pools** are the pool balances.
+///
+/// * **target_ratio** is the ratio of the first pool balance to the second one the pool is pegged to.
+pub fn compute_peg_deviation(pools: [Uint128; 2], target_ratio: Decimal) -> StdResult<Decimal> {
+    if pools[1].is_zero() || target_ratio.is_zero() {
+        return Ok(Decimal::zero());
+    }
+
+    let ratio = Uint256::from(pools[0]) * Uint256::from(target_ratio.denominator())
+        / Uint256::from(pools[1]);
+    let target = Uint256::from(target_ratio.numerator());
+    Ok(Decimal::from_ratio(
+        to_uint128(abs_diff(ratio, target))?,
+        target_ratio.numerator(),
+    ))
+}
+
+/// ## Description
+/// Computes the swap fee rate of a stable pool with a dynamic fee curve. The base fee is scaled
+/// by `1 + sensitivity * (deviation_after - deviation_before)` and clamped to the bounds.
+/// ## Params
+/// * **base_fee** is the total fee rate of the pair.
+///
+/// * **params** is the object of type [`DynamicFeeParams`].
+///
+/// * **bounds** is the object of type [`DynamicFeeBounds`].
+///
+/// * **pools_before** are the pool balances before the swap.
+///
+/// * **pools_after** are the pool balances after the swap.
+pub fn compute_dynamic_fee(
+    base_fee: Decimal,
+    params: &DynamicFeeParams,
+    bounds: &DynamicFeeBounds,
+    pools_before: [Uint128; 2],
+    pools_after: [Uint128; 2],
+) -> StdResult<Decimal> {
+    let before = compute_peg_deviation(pools_before, params.target_ratio)?;
+    let after = compute_peg_deviation(pools_after, params.target_ratio)?;
+    let one = Uint256::from(Decimal::one().numerator());
+    let base = Uint256::from(base_fee.numerator());
+    let change = |delta: Decimal| {
+        base * Uint256::from(params.sensitivity.numerator()) * Uint256::from(delta.numerator())
+            / one
+            / one
+    };
+
+    let fee = if after > before {
+        base + change(after - before)
+    } else {
+        base.saturating_sub(change(before - after))
+    };
+    let min_fee = Uint256::from(bounds.min_fee_bps as u128) * one / Uint256::from(10_000u128);
+    let max_fee = Uint256::from(bounds.max_fee_bps as u128) * one / Uint256::from(10_000u128);
+    let fee = std::cmp::min(std::cmp::max(fee, min_fee), max_fee);
+
+    Ok(Decimal::from_ratio(
+        to_uint128(fee)?,
+        Decimal::one().numerator(),
+    ))
+}
+
+/// ## Description
+/// Computes the fair value of xyk LP tokens in the second asset as `2 * sqrt(x * y * price)`
+/// per total share, which can't be manipulated by moving the reserves along the curve. Returns an
+/// [`Err`] instead of overflowing.
+/// ## Params
+/// * **pools** are the pool balances.
+///
+/// * **price** is the average price of the first asset in the second asset.
+///
+/// * **share** is the amount of LP tokens to value.
+///
+/// * **total_share** is the LP token supply.
+pub fn fair_xyk_lp_value(
+    pools: [Uint128; 2],
+    price: Decimal,
+    share: Uint128,
+    total_share: Uint128,
+) -> StdResult<Uint128> {
+    if total_share.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    // the product of two Uint128 reserves always fits, the price is applied after the root
+    let sqrt_k = sqrt(Uint256::from(pools[0]) * Uint256::from(pools[1]));
+    // the root of the price scaled by its denominator, which keeps its fractional digits
+    let sqrt_price =
+        sqrt(Uint256::from(price.numerator()).checked_mul(Uint256::from(price.denominator()))?);
+    let value = sqrt_k
+        .checked_mul(Uint256::from(2u8))?
+        .checked_mul(sqrt_price)?
+        / Uint256::from(price.denominator());
+
+    to_uint128(value.multiply_ratio(share, total_share))
+}
+
+/// ## Description
+/// Computes the fair value of stable LP tokens in the second asset as the invariant D valued at
+/// the cheaper of the two assets per total share.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **pools** are the pool balances.
+///
+/// * **price** is the average price of the first asset in the second asset.
+///
+/// * **share** is the amount of LP tokens to value.
+///
+/// * **total_share** is the LP token supply.
+pub fn fair_stable_lp_value(
+    amp: u64,
+    pools: [Uint128; 2],
+    price: Decimal,
+    share: Uint128,
+    total_share: Uint128,
+) -> StdResult<Uint128> {
+    if total_share.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let d = compute_d(amp, pools)?;
+    let unit_price = std::cmp::min(price, Decimal::one());
+    Ok((d * unit_price).multiply_ratio(share, total_share))
+}
+
+/// ## Description
+/// Computes the result of a stableswap swap. Returns the return amount, the spread amount and
+/// the commission amount. The return amount has the commission already deducted.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **offer_amount** is the amount being swapped.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn compute_stable_swap(
+    amp: u64,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), SwapError> {
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+    if offer_amount.is_zero() {
+        return Err(SwapError::ZeroAmount {});
+    }
+
+    let d = compute_d(amp, [offer_pool, ask_pool])?;
+    compute_stable_swap_with_d(amp, offer_pool, ask_pool, offer_amount, commission_rate, d)
+}
+
+/// ## Description
+/// Computes a stable pool swap like [`compute_stable_swap`] with a precomputed invariant, e.g.
+/// from an [`InvariantCache`], so several offers against the same pool state compute D once.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **offer_amount** is the amount being swapped.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+///
+/// * **d** is the invariant of the pool balances.
+pub fn compute_stable_swap_with_d(
+    amp: u64,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+    d: Uint128,
+) -> Result<(Uint128, Uint128, Uint128), SwapError> {
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+    if offer_amount.is_zero() {
+        return Err(SwapError::ZeroAmount {});
+    }
+
+    let new_ask_pool = compute_new_balance(amp, offer_pool.checked_add(offer_amount)?, d)?;
+    // one unit is kept in the pool to protect the invariant from rounding
+    let return_amount = ask_pool.saturating_sub(new_ask_pool + Uint128::from(1u8));
+    let spread_amount = offer_amount.saturating_sub(return_amount);
+    let commission_amount = return_amount * commission_rate;
+
+    Ok((
+        return_amount - commission_amount,
+        spread_amount,
+        commission_amount,
+    ))
+}
+
+/// ## Description
+/// Computes the offer amount needed to receive the specified ask amount in a stable pool.
+/// Returns the offer amount, the spread amount and the commission amount.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **ask_amount** is the amount to receive after the commission is deducted.
+///
+/// * **commission_rate** is the total fee rate of the pair.
+pub fn compute_stable_offer_amount(
+    amp: u64,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    ask_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), SwapError> {
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+    if ask_amount.is_zero() {
+        return Err(SwapError::ZeroAmount {});
+    }
+    if commission_rate >= Decimal::one() {
+        return Err(StdError::generic_err("Commission rate must be less than one").into());
+    }
+
+    // the amount to take from the pool before the commission is deducted
+    let before_commission = to_uint128(ceil_div(
+        Uint256::from(ask_amount) * Uint256::from(Decimal::one().numerator()),
+        Uint256::from((Decimal::one() - commission_rate).numerator()),
+    )?)?;
+    // one unit is kept in the pool like in [`compute_stable_swap`]
+    if before_commission + Uint128::from(1u8) >= ask_pool {
+        return Err(SwapError::InsufficientLiquidity {
+            requested: before_commission,
+            available: ask_pool,
+        });
+    }
+
+    let d = compute_d(amp, [offer_pool, ask_pool])?;
+    let new_offer_pool =
+        compute_new_balance(amp, ask_pool - before_commission - Uint128::from(1u8), d)?;
+    // the extra unit covers the rounding of the Newton method
+    let offer_amount = new_offer_pool.saturating_sub(offer_pool) + Uint128::from(1u8);
+    let spread_amount = offer_amount.saturating_sub(before_commission);
+
+    Ok((offer_amount, spread_amount, before_commission - ask_amount))
+}
+
+/// ## Description
+/// Returns the maximum spread applied to a swap. Returns an [`Err`] if the requested spread exceeds
+/// the ceiling.
+/// ## Params
+/// * **max_spread** is the maximum spread requested by the user, [`DEFAULT_SLIPPAGE`] by default.
+///
+/// * **ceiling** is the maximum spread allowed by the factory, [`MAX_ALLOWED_SLIPPAGE`] by default.
+pub fn resolve_max_spread(
+    max_spread: Option<Decimal>,
+    ceiling: Option<Decimal>,
+) -> Result<Decimal, SwapError> {
+    let default_spread: Decimal = DEFAULT_SLIPPAGE.parse()?;
+    let ceiling = match ceiling {
+        Some(ceiling) => ceiling,
+        None => MAX_ALLOWED_SLIPPAGE.parse()?,
+    };
+
+    let max_spread = max_spread.unwrap_or(default_spread);
+    if max_spread > ceiling {
+        return Err(StdError::generic_err(format!(
+            "Max spread {} exceeds the allowed maximum {}",
+            max_spread, ceiling
+        ))
+        .into());
+    }
+    Ok(max_spread)
+}
+
+/// ## Description
+/// Returns a [`SwapError::MaxSpreadAssertion`] if the spread of a swap exceeds the maximum spread.
+///
+/// The spread is the relative difference between the expected and the actual return, it is the
+/// same for all pair types. With a belief price the expected return is `offer_amount / belief_price`,
+/// otherwise it is the return at the spot price, i.e. `return_amount + spread_amount`. The
+/// commission is not a part of the spread.
+/// ## Params
+/// * **belief_price** is the price the user expects.
+///
+/// * **max_spread** is the maximum allowed spread returned by [`resolve_max_spread`].
+///
+/// * **offer_amount** is the amount being swapped.
+///
+/// * **return_amount** is the amount returned by the swap before the commission is deducted.
+///
+/// * **spread_amount** is the spread amount computed by the swap math.
+pub fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Decimal,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> Result<(), SwapError> {
+    let (spread_amount, total_amount) = match belief_price {
+        Some(belief_price) => {
+            if belief_price.is_zero() {
+                return Err(StdError::generic_err("Belief price must not be zero").into());
+            }
+            let expected_return =
+                offer_amount.multiply_ratio(belief_price.denominator(), belief_price.numerator());
+            (
+                expected_return.saturating_sub(return_amount),
+                expected_return,
+            )
+        }
+        None => (spread_amount, return_amount.checked_add(spread_amount)?),
+    };
+
+    if total_amount.is_zero() {
+        return Ok(());
+    }
+    let spread = Decimal::from_ratio(spread_amount, total_amount);
+    if spread > max_spread {
+        return Err(SwapError::MaxSpreadAssertion { spread, max_spread });
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the largest part of the offer an xyk pair can fill within the maximum spread, see
+/// [`crate::pair::FillMode::PartialFill`]. Without a belief price the spread of an offer `o`
+/// is about `o / (offer_pool + o)`, with a belief price `b` it is about
+/// `1 - ask_pool * b / (offer_pool + o)`. The closed form bounds the fill, which is then narrowed
+/// with [`compute_swap`] and [`assert_max_spread`] so rounding can't push it over the max spread.
+/// The rest of the offer is refunded.
+/// ## Params
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **offer_amount** is the amount offered by the user.
+///
+/// * **belief_price** is the price the user expects.
+///
+/// * **max_spread** is the maximum allowed spread returned by [`resolve_max_spread`].
+pub fn compute_partial_fill_offer(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    belief_price: Option<Decimal>,
+    max_spread: Decimal,
+) -> Result<Uint128, SwapError> {
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+    let denominator = Uint256::from(Decimal::one().numerator());
+    let spread = Uint256::from(max_spread.numerator());
+    if spread >= denominator {
+        return Ok(offer_amount);
+    }
+
+    let max_offer = match belief_price {
+        None => Uint256::from(offer_pool) * spread / (denominator - spread),
+        Some(belief_price) => {
+            if belief_price.is_zero() {
+                return Err(StdError::generic_err("Belief price must not be zero").into());
+            }
+            let offer_pool: Uint256 = offer_pool.into();
+            let reachable = Uint256::from(ask_pool) * Uint256::from(belief_price.numerator())
+                / (denominator - spread);
+            if reachable > offer_pool {
+                reachable - offer_pool
+            } else {
+                Uint256::zero()
+            }
+        }
+    };
+    let max_offer = if max_offer >= Uint256::from(offer_amount) {
+        offer_amount
+    } else {
+        to_uint128(max_offer)?
+    };
+
+    let fills = |offer: Uint128| -> Result<bool, SwapError> {
+        if offer.is_zero() {
+            return Ok(true);
+        }
+        let (return_amount, spread_amount, _) =
+            compute_swap(offer_pool, ask_pool, offer, Decimal::zero())?;
+        match assert_max_spread(
+            belief_price,
+            max_spread,
+            offer,
+            return_amount,
+            spread_amount,
+        ) {
+            Ok(()) => Ok(true),
+            Err(SwapError::MaxSpreadAssertion { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    };
+    if fills(max_offer)? {
+        return Ok(max_offer);
+    }
+
+    let (mut low, mut high) = (Uint128::zero(), max_offer);
+    while high - low > Uint128::new(1) {
+        let middle = low + (high - low) / Uint128::new(2);
+        if fills(middle)? {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(low)
+}
+
+/// ## Description
+/// Returns the largest offer amount which keeps the spread of an xyk swap within the maximum
+/// spread for the current reserves, see [`compute_partial_fill_offer`]. Returns an [`Err`] if the
+/// maximum spread is not below one, as any offer would be within it.
+/// ## Params
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **max_spread** is the maximum allowed spread.
+pub fn compute_max_offer_within_spread(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    max_spread: Decimal,
+) -> Result<Uint128, SwapError> {
+    assert_max_spread_below_one(max_spread)?;
+    compute_partial_fill_offer(offer_pool, ask_pool, Uint128::MAX, None, max_spread)
+}
+
+/// ## Description
+/// Returns the largest offer amount which keeps the spread of a stableswap swap within the
+/// maximum spread for the current reserves. The spread grows with the offer, so the amount is
+/// found with a binary search over [`compute_stable_swap`]. An offer of `ask_pool / (1 - max_spread)`
+/// can't return more than the whole ask pool, so it bounds the search.
+/// ## Params
+/// * **amp** is the amplification coefficient.
+///
+/// * **offer_pool** is the pool balance of the offer asset.
+///
+/// * **ask_pool** is the pool balance of the ask asset.
+///
+/// * **max_spread** is the maximum allowed spread.
+pub fn compute_stable_max_offer_within_spread(
+    amp: u64,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    max_spread: Decimal,
+) -> Result<Uint128, SwapError> {
+    assert_max_spread_below_one(max_spread)?;
+    assert_pools_not_empty(offer_pool, ask_pool)?;
+
+    let fills = |offer: Uint128| -> Result<bool, SwapError> {
+        if offer.is_zero() {
+            return Ok(true);
+        }
+        let (return_amount, spread_amount, commission_amount) =
+            compute_stable_swap(amp, offer_pool, ask_pool, offer, Decimal::zero())?;
+        match assert_max_spread(
+            None,
+            max_spread,
+            offer,
+            return_amount + commission_amount,
+            spread_amount,
+        ) {
+            Ok(()) => Ok(true),
+            Err(SwapError::MaxSpreadAssertion { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    };
+
+    let (mut low, mut high) = (
+        Uint128::zero(),
+        ask_pool.multiply_ratio(
+            Decimal::one().numerator(),
+            (Decimal::one() - max_spread).numerator(),
+        ) + Uint128::new(1),
+    );
+    while high - low > Uint128::new(1) {
+        let middle = low + (high - low) / Uint128::new(2);
+        if fills(middle)? {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(low)
+}
+
+/// ## Description
+/// Returns an [`Err`] if the maximum spread is not below one.
+fn assert_max_spread_below_one(max_spread: Decimal) -> StdResult<()> {
+    if max_spread >= Decimal::one() {
+        return Err(StdError::generic_err("The max spread must be below 1"));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Computes the balance of the other asset which keeps the invariant D for the new balance.
+fn compute_new_balance(amp: u64, new_pool: Uint128, d: Uint128) -> StdResult<Uint128> {
+    assert_amp_not_zero(amp)?;
+    let n_coins = Uint256::from(N_COINS);
+    let leverage = Uint256::from(amp) * n_coins;
+    let new_pool = Uint256::from(new_pool);
+    let d = Uint256::from(d);
+
+    let c = d * d / (new_pool * n_coins) * d / (leverage * n_coins);
+    let b = new_pool + d / leverage;
+    let mut y = d;
+    for _ in 0..ITERATIONS {
+        let y_prev = y;
+        y = (y * y + c) / (y + y + b - d);
+        if abs_diff(y, y_prev) <= Uint256::from(1u8) {
+            return to_uint128(y);
+        }
+    }
+
+    Err(StdError::generic_err(
+        "Newton method for the new balance failed to converge",
+    ))
+}
+
+/// ## Description
+/// Returns an [`Err`] if the amplification coefficient is zero, the stableswap math divides by it.
+fn assert_amp_not_zero(amp: u64) -> StdResult<()> {
+    if amp == 0 {
+        return Err(StdError::generic_err(
+            "The amplification coefficient must be greater than zero",
+        ));
+    }
+    Ok(())
+}
+
+/// The absolute rounding error of the invariant tolerated by [`assert_invariant_per_share`]
+pub const INVARIANT_TOLERANCE: u128 = 2;
+
+/// ## Description
+/// Computes the xyk invariant as the geometric mean `sqrt(x * y)` of the pool balances, so that
+/// it scales linearly with the LP token supply like the stableswap invariant D.
+/// ## Params
+/// * **pools** are the pool balances.
+pub fn compute_xyk_invariant(pools: [Uint128; 2]) -> StdResult<Uint128> {
+    to_uint128(sqrt(pools[0].full_mul(pools[1])))
+}
+
+/// ## Description
+/// Returns an [`Err`] if the invariant per LP token decreased by more than
+/// [`INVARIANT_TOLERANCE`] during an operation or the LP token supply is inconsistent with the
+/// invariant. Swaps keep the supply and must not decrease the invariant, provides and withdrawals
+/// must not decrease the invariant backing a single LP token.
+/// ## Params
+/// * **invariant_before** is the invariant (D or [`compute_xyk_invariant`]) before the operation.
+///
+/// * **share_before** is the LP token supply before the operation.
+///
+/// * **invariant_after** is the invariant after the operation.
+///
+/// * **share_after** is the LP token supply after the operation.
+pub fn assert_invariant_per_share(
+    invariant_before: Uint128,
+    share_before: Uint128,
+    invariant_after: Uint128,
+    share_after: Uint128,
+) -> StdResult<()> {
+    if share_after.is_zero() != invariant_after.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "Invariant violation: LP supply {} is inconsistent with the reserves",
+            share_after
+        )));
+    }
+    if share_before.is_zero() || share_after.is_zero() {
+        return Ok(());
+    }
+    let before = invariant_before.full_mul(share_after);
+    let after = (Uint256::from(invariant_after) + Uint256::from(INVARIANT_TOLERANCE))
+        * Uint256::from(share_before);
+    if after < before {
+        return Err(StdError::generic_err(
+            "Invariant violation: the invariant per LP token decreased",
+        ));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns a [`SwapError::PoolEmpty`] if any of the pools is empty, so simulations and swaps
+/// against a pool without liquidity fail with a typed error instead of a division error.
+/// ## Params
+/// * **first** is the pool balance of the first asset.
+///
+/// * **second** is the pool balance of the second asset.
+pub fn assert_pools_not_empty(first: Uint128, second: Uint128) -> Result<(), SwapError> {
+    if first.is_zero() || second.is_zero() {
+        return Err(SwapError::PoolEmpty {});
+    }
+    Ok(())
+}
+
+fn ceil_div(numerator: Uint256, denominator: Uint256) -> StdResult<Uint256> {
+    let quotient = numerator
+        .checked_div(denominator)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    if quotient * denominator == numerator {
+        Ok(quotient)
+    } else {
+        Ok(quotient + Uint256::from(1u8))
+    }
+}
+
+fn abs_diff(a: Uint256, b: Uint256) -> Uint256 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// ## Description
+/// Returns the integer square root of the value using Newton's method.
+fn sqrt(value: Uint256) -> Uint256 {
+    if value.is_zero() {
+        return value;
+    }
+
+    let two = Uint256::from(2u8);
+    let mut x = value;
+    let mut y = (x + Uint256::from(1u8)) / two;
+    while y < x {
+        x = y;
+        y = (x + value / x) / two;
+    }
+    x
+}
+
+fn to_uint128(value: Uint256) -> StdResult<Uint128> {
+    value
+        .try_into()
+        .map_err(|_| StdError::generic_err("The amount does not fit into Uint128"))
+}
@@ -60,10 +60,7 @@ pub(crate) fn balances_to_map(
             contract_balances_map.insert(addr.to_string(), **balance);
         }
 
-        balances_map.insert(
-            String::from(contract_addr.to_string()),
-            contract_balances_map,
-        );
+        balances_map.insert(contract_addr.to_string(), contract_balances_map);
     }
     balances_map
 }
@@ -146,7 +143,7 @@ impl CW20QueryHandler {
     pub fn execute(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
         match &request {
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
-                match from_binary(&msg).unwrap() {
+                match from_binary(msg).unwrap() {
                     Cw20QueryMsg::TokenInfo {} => {
                         let balances: &HashMap<String, Uint128> =
                             match self.token_querier.balances.get(contract_addr) {
@@ -167,7 +164,7 @@ impl CW20QueryHandler {
                                 name: "mAPPL".to_string(),
                                 symbol: "mAPPL".to_string(),
                                 decimals: 6,
-                                total_supply: total_supply,
+                                total_supply,
                             })
                             .into(),
                         )
@@ -235,7 +232,7 @@ impl DefaultQueryHandler {
             QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: _,
                 msg,
-            }) => match from_binary(&msg).unwrap() {
+            }) => match from_binary(msg).unwrap() {
                 FactoryQueryMsg::Pair { asset_infos } => {
                     let key = asset_infos[0].to_string() + asset_infos[1].to_string().as_str();
                     match self.astroport_factory_querier.pairs.get(&key) {
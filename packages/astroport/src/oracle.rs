@@ -1,7 +1,50 @@
 use crate::asset::AssetInfo;
-use cosmwasm_std::Uint128;
+use crate::pair::TWAP_PRECISION;
+use cosmwasm_std::{
+    Addr, Binary, Decimal, Decimal256, Event, Fraction, StdError, StdResult, Uint128, Uint256,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// The maximum number of oracles in a quote route
+pub const MAX_QUOTE_ROUTE_LENGTH: usize = 4;
+/// The default TWAP window in seconds
+pub const DEFAULT_PERIOD: u64 = 86400;
+/// The minimum TWAP window in seconds
+pub const MIN_PERIOD: u64 = 60;
+/// The maximum number of stored price attestations
+pub const MAX_ATTESTATION_HISTORY: u32 = 100;
+/// The type of the event emitted when the reserves of the pair fall below the minimum liquidity
+pub const INVALIDATION_EVENT: &str = "oracle_invalidated";
+
+/// ## Description
+/// This enum describes the errors of the price consultation. Lending integrations can match on
+/// [`OracleError::PriceUnreliable`] to pause borrowing against the asset.
+#[derive(Error, Debug, PartialEq)]
+pub enum OracleError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error(
+        "Price is unreliable: the reserves {}, {} are below the minimum liquidity {}, {}",
+        .reserves[0], .reserves[1], .min_liquidity[0], .min_liquidity[1]
+    )]
+    PriceUnreliable {
+        reserves: [Uint128; 2],
+        min_liquidity: [Uint128; 2],
+    },
+}
+
+impl From<OracleError> for StdError {
+    fn from(err: OracleError) -> Self {
+        match err {
+            OracleError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
 
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
@@ -11,6 +54,19 @@ pub struct InstantiateMsg {
     pub factory_contract: String,
     /// the type of asset infos available in [`AssetInfo`]
     pub asset_infos: [AssetInfo; 2],
+    /// contract address that used for controls settings, quote routes can't be registered if not set
+    pub owner: Option<String>,
+    /// the TWAP window in seconds, [`DEFAULT_PERIOD`] by default
+    #[serde(default)]
+    pub period: Option<u64>,
+    /// the number of price attestations stored on every update, at most [`MAX_ATTESTATION_HISTORY`].
+    /// Attestations are not stored if not set
+    #[serde(default)]
+    pub attestation_history: Option<u32>,
+    /// the minimum reserves of the pair assets below which prices are unreliable, see
+    /// [`assert_price_reliable`]. Not checked if not set
+    #[serde(default)]
+    pub min_liquidity: Option<[Uint128; 2]>,
 }
 
 /// ## Description
@@ -20,6 +76,42 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     /// Update prices
     Update {},
+    /// ## Description
+    /// Registers or replaces the route used to quote prices in the specified asset. Each oracle of
+    /// the route converts the ask asset of the previous leg, starting with the counter asset of
+    /// this oracle's pair, and the last one returns amounts in `quote_in`
+    /// ## Executor
+    /// Only owner can execute it
+    SetQuoteRoute {
+        /// the asset prices are quoted in
+        quote_in: AssetInfo,
+        /// the oracle contracts of the route
+        oracles: Vec<String>,
+    },
+    /// ## Description
+    /// Updates the TWAP window, at least [`MIN_PERIOD`] seconds
+    /// ## Executor
+    /// Only owner can execute it
+    UpdatePeriod { period: u64 },
+    /// ## Description
+    /// Removes the quote route of the specified asset
+    /// ## Executor
+    /// Only owner can execute it
+    RemoveQuoteRoute {
+        /// the asset prices are quoted in
+        quote_in: AssetInfo,
+    },
+    /// ## Description
+    /// Updates the number of stored price attestations, at most [`MAX_ATTESTATION_HISTORY`].
+    /// Zero disables storing and removes the stored attestations
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateAttestationHistory { size: u32 },
+    /// ## Description
+    /// Updates the minimum reserves of the pair assets, [`None`] disables the check
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateMinLiquidity { min_liquidity: Option<[Uint128; 2]> },
 }
 
 /// ## Description
@@ -27,17 +119,329 @@ pub enum ExecuteMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    /// Validates assets and calculates a new average amount with updated precision
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Validates assets and calculates a new average amount with updated precision. With
+    /// `price_only` the amount is ignored and the average price of the token is returned in a
+    /// [`ConsultPriceResponse`] object instead, so amounts exceeding the pool depth can be priced.
+    /// Fails with [`OracleError::PriceUnreliable`] while the oracle is invalidated
     Consult {
         /// the assets to validate
         token: AssetInfo,
         /// the amount
         amount: Uint128,
+        /// whether to return the average price instead of the amount
+        #[serde(default)]
+        price_only: bool,
     },
+    /// Calculates the average amount of the token in terms of `quote_in` by following the
+    /// registered quote route, and returns it in a [`ConsultInResponse`] object.
+    /// [`QueryMsg::Consult`] keeps returning a bare amount for compatibility
+    ConsultIn {
+        /// the assets to validate
+        token: AssetInfo,
+        /// the amount
+        amount: Uint128,
+        /// the asset the amount is quoted in
+        quote_in: AssetInfo,
+    },
+    /// Returns the registered quote route of the specified asset in a [`QuoteRouteResponse`] object
+    QuoteRoute { quote_in: AssetInfo },
+    /// Calculates the fair value of the LP token of the pair from the average price and the pool
+    /// invariant instead of the spot reserves, and returns it in a [`ConsultLpResponse`] object
+    ConsultLp {
+        /// the LP token of the pair of the oracle
+        lp_token: String,
+        /// the amount of LP tokens
+        amount: Uint128,
+    },
+    /// Returns the latest average price with its commitment in an [`AttestationResponse`] object
+    Attestation {},
+    /// Returns the stored attestations ordered by height in an [`AttestationsResponse`] object
+    Attestations {
+        /// the height after which attestations are returned
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Option<Addr>,
+    /// the factory contract address
+    pub factory: Addr,
+    /// the assets of the pair
+    pub asset_infos: [AssetInfo; 2],
+    /// the pair contract whose cumulative prices are snapshotted
+    pub pair: Addr,
+    /// the TWAP window in seconds
+    pub period: u64,
+    /// the timestamp of the last snapshot
+    pub block_timestamp_last: u64,
+    /// the number of stored price attestations
+    #[serde(default)]
+    pub attestation_history: u32,
+    /// the minimum reserves of the pair assets below which prices are unreliable
+    #[serde(default)]
+    pub min_liquidity: Option<[Uint128; 2]>,
+    /// whether the reserves were below the minimum liquidity at the last update, in which case
+    /// consultations fail with [`OracleError::PriceUnreliable`] until the next update
+    #[serde(default)]
+    pub invalidated: bool,
+}
+
+/// ## Description
+/// Returns an [`OracleError::PriceUnreliable`] if any of the reserves is below the minimum
+/// liquidity, so a price which is cheap to manipulate is not reported.
+/// ## Params
+/// * **reserves** are the reserves of the pair assets.
+///
+/// * **min_liquidity** are the minimum reserves, the check is skipped if not set.
+pub fn assert_price_reliable(
+    reserves: [Uint128; 2],
+    min_liquidity: Option<[Uint128; 2]>,
+) -> Result<(), OracleError> {
+    match min_liquidity {
+        Some(min_liquidity) if reserves[0] < min_liquidity[0] || reserves[1] < min_liquidity[1] => {
+            Err(OracleError::PriceUnreliable {
+                reserves,
+                min_liquidity,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// Returns the event emitted by an update which invalidates the oracle.
+/// ## Params
+/// * **pair** is the pair contract of the oracle.
+///
+/// * **reserves** are the reserves of the pair assets at the update.
+pub fn invalidation_event(pair: &Addr, reserves: [Uint128; 2]) -> Event {
+    Event::new(INVALIDATION_EVENT)
+        .add_attribute("pair", pair.as_str())
+        .add_attribute("reserve0", reserves[0].to_string())
+        .add_attribute("reserve1", reserves[1].to_string())
+}
+
+/// ## Description
+/// Returns an [`Err`] if the TWAP window has not passed since the last snapshot.
+/// ## Params
+/// * **block_timestamp_last** is the timestamp of the last snapshot.
+///
+/// * **time** is the current timestamp.
+///
+/// * **period** is the TWAP window in seconds.
+pub fn assert_update_allowed(block_timestamp_last: u64, time: u64, period: u64) -> StdResult<u64> {
+    let elapsed = time.saturating_sub(block_timestamp_last);
+    if elapsed < period {
+        return Err(StdError::generic_err(format!(
+            "Period not elapsed: {} of {} seconds",
+            elapsed, period
+        )));
+    }
+    Ok(elapsed)
+}
+
+/// ## Description
+/// Returns the average price between two snapshots of a pair cumulative price. The cumulative
+/// prices of pairs are scaled by [`TWAP_PRECISION`] decimals and are allowed to overflow.
+/// ## Params
+/// * **cumulative_now** is the current cumulative price.
+///
+/// * **cumulative_last** is the cumulative price of the last snapshot.
+///
+/// * **elapsed** is the number of seconds between the snapshots.
+pub fn compute_average_price(
+    cumulative_now: Uint128,
+    cumulative_last: Uint128,
+    elapsed: u64,
+) -> StdResult<Decimal> {
+    if elapsed == 0 {
+        return Err(StdError::generic_err(
+            "No time elapsed since the last snapshot",
+        ));
+    }
+
+    let scale = 10u128.pow(TWAP_PRECISION as u32);
+    Ok(Decimal::from_ratio(
+        cumulative_now.wrapping_sub(cumulative_last),
+        Uint128::from(elapsed).checked_mul(Uint128::new(scale))?,
+    ))
+}
+
+/// ## Description
+/// Returns the average price between two snapshots of a pair cumulative price like
+/// [`compute_average_price`], as a [`Decimal256`] which doesn't overflow for large prices.
+/// The cumulative prices carry [`TWAP_PRECISION`] decimals, so the price is exact up to
+/// `10^-TWAP_PRECISION / elapsed` and the remaining decimals are rounded down.
+/// ## Params
+/// * **cumulative_now** is the current cumulative price.
+///
+/// * **cumulative_last** is the cumulative price of the last snapshot.
+///
+/// * **elapsed** is the number of seconds between the snapshots.
+pub fn compute_average_price_256(
+    cumulative_now: Uint128,
+    cumulative_last: Uint128,
+    elapsed: u64,
+) -> StdResult<Decimal256> {
+    if elapsed == 0 {
+        return Err(StdError::generic_err(
+            "No time elapsed since the last snapshot",
+        ));
+    }
+
+    let scale = Uint256::from(10u128.pow(TWAP_PRECISION as u32));
+    Ok(Decimal256::from_ratio(
+        cumulative_now.wrapping_sub(cumulative_last),
+        Uint256::from(elapsed) * scale,
+    ))
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConsultPriceResponse {
+    /// the average price of the consulted token in the other asset of the pair, independent of
+    /// the amount, see [`compute_average_price_256`] for its precision
+    pub price: Decimal256,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConsultLpResponse {
+    /// the fair value of the LP tokens
+    pub value: Uint128,
+    /// the asset the value is denominated in, the second asset of the pair
+    pub quote_asset_info: AssetInfo,
+    /// the average price of the first asset of the pair used for the valuation
+    pub price: Decimal,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConsultInResponse {
+    /// the amount quoted in the requested asset
+    pub amount: Uint128,
+    /// the legs of the quote
+    pub legs: Vec<QuoteLeg>,
+}
+
+/// ## Description
+/// This structure describes a single conversion of the quote.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuoteLeg {
+    /// the oracle used by the leg
+    pub oracle: Addr,
+    /// the converted asset
+    pub offer_asset_info: AssetInfo,
+    /// the asset received by the leg
+    pub ask_asset_info: AssetInfo,
+    /// the amount received by the leg
+    pub amount: Uint128,
+    /// the timestamp of the last price update of the oracle
+    pub last_updated: u64,
+    /// whether the price of the oracle was not updated within its update period
+    pub is_stale: bool,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuoteRouteResponse {
+    /// the asset prices are quoted in
+    pub quote_in: AssetInfo,
+    /// the oracle contracts of the route
+    pub oracles: Vec<Addr>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttestationResponse {
+    /// the pair contract whose cumulative prices are snapshotted
+    pub pair: Addr,
+    /// the average price of the first asset of the pair in the second one
+    pub price: Decimal,
+    /// the block height of the update which computed the price
+    pub height: u64,
+    /// the TWAP window in seconds
+    pub window: u64,
+    /// the commitment to the other fields, see [`attestation_commitment`]
+    pub commitment: Binary,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttestationsResponse {
+    pub attestations: Vec<AttestationResponse>,
+}
+
+/// ## Description
+/// Returns the SHA-256 hash committing to a price attestation. The preimage is the length of the
+/// pair address as a big-endian u32 followed by its bytes, the numerator of the price as a
+/// big-endian u128 (18 decimals), the height and the window as big-endian u64s.
+/// ## Params
+/// * **pair** is the pair contract whose price is attested.
+///
+/// * **price** is the average price.
+///
+/// * **height** is the block height of the update which computed the price.
+///
+/// * **window** is the TWAP window in seconds.
+pub fn attestation_commitment(pair: &Addr, price: Decimal, height: u64, window: u64) -> Binary {
+    let mut hasher = Sha256::new();
+    hasher.update((pair.as_str().len() as u32).to_be_bytes());
+    hasher.update(pair.as_bytes());
+    hasher.update(price.numerator().to_be_bytes());
+    hasher.update(height.to_be_bytes());
+    hasher.update(window.to_be_bytes());
+    Binary::from(hasher.finalize().as_slice())
+}
+
+impl AttestationResponse {
+    /// ## Description
+    /// Creates an attestation and computes its commitment.
+    /// ## Params
+    /// * **pair** is the pair contract whose price is attested.
+    ///
+    /// * **price** is the average price.
+    ///
+    /// * **height** is the block height of the update which computed the price.
+    ///
+    /// * **window** is the TWAP window in seconds.
+    pub fn new(pair: Addr, price: Decimal, height: u64, window: u64) -> Self {
+        let commitment = attestation_commitment(&pair, price, height, window);
+        AttestationResponse {
+            pair,
+            price,
+            height,
+            window,
+            commitment,
+        }
+    }
+
+    /// ## Description
+    /// Returns true if the commitment matches the other fields of the attestation.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn verify(&self) -> bool {
+        self.commitment == attestation_commitment(&self.pair, self.price, self.height, self.window)
+    }
 }
 
 /// ## Description
 /// This structure describes a migration message.
-/// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// contract address that used for controls settings
+    pub owner: Option<String>,
+}
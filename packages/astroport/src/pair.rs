@@ -1,10 +1,14 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::asset::{Asset, AssetInfo};
+use crate::asset::{Asset, AssetInfo, PairMetadata};
 
-use cosmwasm_std::{Binary, Decimal, Uint128};
-use cw20::Cw20ReceiveMsg;
+use cosmwasm_std::{
+    attr, to_binary, Addr, Attribute, Binary, CosmosMsg, Decimal, Event, Fraction, QuerierWrapper,
+    StdError, StdResult, Uint128, Uint256, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use std::fmt;
 
 /// the default slippage
 pub const DEFAULT_SLIPPAGE: &str = "0.005";
@@ -13,6 +17,36 @@ pub const MAX_ALLOWED_SLIPPAGE: &str = "0.5";
 
 pub const TWAP_PRECISION: u8 = 6;
 
+/// the minimum weight of an asset in a weighted xyk pool
+pub const MIN_XYK_WEIGHT: &str = "0.02";
+/// the minimum time period over which xyk pool weights can be changed
+pub const MIN_WEIGHTS_CHANGING_TIME: u64 = 86400;
+
+/// the maximum number of offers that can be simulated in a single [`QueryMsg::SimulateMany`] query
+pub const MAX_SIMULATE_MANY_OFFERS: usize = 30;
+
+/// the maximum amplification coefficient of a stable pool
+pub const MAX_AMP: u64 = 1_000_000;
+/// the maximum factor by which the amplification coefficient can be changed at once
+pub const MAX_AMP_CHANGE: u64 = 10;
+/// the minimum time period over which the amplification coefficient can be changed
+pub const MIN_AMP_CHANGING_TIME: u64 = 86400;
+
+/// the maximum multiplier of the fee charged on stable pool operations which worsen the imbalance
+pub const MAX_IMBALANCE_FEE_MULTIPLIER: u64 = 10;
+/// the maximum relative swap fee change per unit of peg deviation change of a stable pool
+pub const MAX_DYNAMIC_FEE_SENSITIVITY: u64 = 1000;
+
+/// the number of seconds in a year used to annualize the APR
+pub const SECONDS_PER_YEAR: u64 = 365 * 86400;
+/// the default trailing window of the [`QueryMsg::Apr`] query
+pub const DEFAULT_APR_WINDOW: u64 = 7 * 86400;
+
+/// the maximum surcharge on swaps of a retired pair in bps
+pub const MAX_RETIREMENT_SURCHARGE_BPS: u16 = 500;
+/// the default number of seconds after which an incomplete escrowed provide can be refunded
+pub const DEFAULT_DEPOSIT_TIMEOUT: u64 = 3600;
+
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -25,6 +59,17 @@ pub struct InstantiateMsg {
     pub factory_addr: String,
     /// the optional binary serialised parameters for custom pool types
     pub init_params: Option<Binary>,
+    /// the number of blocks between reserve checkpoints, checkpoints are disabled if not set
+    pub reserves_checkpoint_interval: Option<u64>,
+    /// the account which created the pair through the factory, it can update the pair metadata
+    #[serde(default)]
+    pub creator: Option<String>,
+    /// the optional labels of the pair
+    #[serde(default)]
+    pub metadata: Option<PairMetadata>,
+    /// the contract notified with a [`crate::token::TransferHookMsg`] when LP tokens change owner
+    #[serde(default)]
+    pub lp_transfer_hook: Option<String>,
 }
 
 /// ## Description
@@ -45,6 +90,15 @@ pub enum ExecuteMsg {
         auto_stake: Option<bool>,
         /// the receiver of provide liquidity
         receiver: Option<String>,
+        /// the block time after which the execution is rejected, see [`assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+        /// the price of the first pool asset in the second pool asset, in the order of the asset
+        /// infos of the pair, that the first deposit of an empty pool sets. Xyk pairs seed the
+        /// pool at this price with [`crate::math::compute_initial_deposit`] and refund the excess,
+        /// so the first deposit can be asymmetric. Rejected once the pool has liquidity
+        #[serde(default)]
+        initial_price: Option<Decimal>,
     },
     /// Swap an offer asset to the other
     Swap {
@@ -52,13 +106,706 @@ pub enum ExecuteMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// the block time after which the execution is rejected, see [`assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+        /// whether the swap is rejected or partially filled if it exceeds the max spread
+        #[serde(default)]
+        fill_mode: FillMode,
+        /// the fee discount of the trader in bps, see [`PairFees::discounted_commission_rate`].
+        /// Ignored unless the sender is the fee discount router of the factory
+        #[serde(default)]
+        fee_discount_bps: u16,
     },
     /// Update pair config if required
     UpdateConfig { params: Binary },
+    /// Pauses or resumes swaps, provides and withdrawals of the pair. The pair keeps recording
+    /// price observations while swaps are paused, see [`accumulate_prices`]. The settings must
+    /// pass [`PausedOperations::validate`]
+    /// ## Executor
+    /// Only the factory owner or guardian can execute it
+    SetPausedOperations { paused: PausedOperations },
+    /// Refunds the escrowed deposit of the sender created by [`Cw20HookMsg::DepositSide`] once
+    /// it has expired
+    RefundDeposit {},
+    /// Enables or disables the batch mode. In the batch mode swaps are queued and all swaps of a
+    /// block are cleared at once by [`ExecuteMsg::SettleBatch`]
+    /// ## Executor
+    /// Only the factory owner can execute it
+    SetBatchMode { enabled: bool },
+    /// Settles the queued swaps of the previous blocks. Orders of the same direction receive the
+    /// same price regardless of their order in the block, orders violating their max spread are
+    /// refunded. Can be executed by anyone
+    SettleBatch {},
+    /// Replaces the metadata of the pair, see [`PairMetadata`]
+    /// ## Executor
+    /// Only the pair creator or the factory owner can execute it
+    UpdateMetadata { metadata: PairMetadata },
+    /// Sends the amount of the asset to the sender and calls it with
+    /// [`FlashSwapReceiverMsg::FlashSwapCallback`]. The sender has to send back the borrowed
+    /// value plus the swap fee in any of the pool assets within the callback, which is verified
+    /// with [`crate::math::assert_flash_swap_repaid`], otherwise the whole transaction reverts.
+    /// Swaps, provides and withdrawals of the pair are rejected until the flash swap is repaid
+    FlashSwap {
+        /// the borrowed asset
+        asset: AssetInfo,
+        /// the borrowed amount
+        amount: Uint128,
+        /// the binary message passed to the callback of the borrower
+        msg: Binary,
+    },
+    /// Internal use
+    /// Verifies that the flash swap was repaid and releases the pair for other operations
+    AssertFlashSwapRepaid {
+        /// the pool balances before the borrowed amount was sent out
+        pools_before: [Asset; 2],
+        /// the borrowed asset
+        borrowed: Asset,
+    },
+    /// Marks the pair as official or removes the mark
+    /// ## Executor
+    /// Only the factory owner can execute it
+    SetOfficial { official: bool },
+    /// ## Description
+    /// Replaces the fees of the pair, so that existing pairs adopt new protocol fees without
+    /// a migration. Changes are recorded with [`record_fee_change`] and emit a [`FEE_CHANGE_EVENT`]
+    /// ## Executor
+    /// Only the factory can execute it
+    UpdateFees { fees: PairFees },
+    /// ## Description
+    /// Enables or disables the invariant check executed at the end of every swap, provide and
+    /// withdrawal, see [`crate::math::assert_invariant_per_share`]. Checks are enabled by default
+    /// ## Executor
+    /// Only the factory owner can execute it
+    SetInvariantChecks { enabled: bool },
+    /// ## Description
+    /// Sends the protocol fees accrued by the pair to the fee address of the factory. The maker
+    /// share of swap fees is accrued in the pair instead of being sent on every swap, so it can
+    /// be withdrawn once it is worth the gas, see [`QueryMsg::ProtocolFees`]
+    /// ## Executor
+    /// Anyone can execute it
+    WithdrawProtocolFees {},
+    /// ## Description
+    /// Retires the pair in favour of a successor pool. Swaps of a retired pair pay a surcharge
+    /// which stays in the pool for the remaining LPs and new liquidity can't be provided, so LPs
+    /// are incentivized to migrate with [`Cw20HookMsg::MigrateLiquidity`]
+    /// ## Executor
+    /// Only the factory owner can execute it
+    Retire {
+        /// the pair which replaces the retired pair, it must have the same assets
+        successor_pair: String,
+        /// the surcharge on swaps in bps, at most [`MAX_RETIREMENT_SURCHARGE_BPS`]
+        surcharge_bps: u16,
+    },
+    /// ## Description
+    /// Experimental. Enables the capture of same-block round-trip profits, see
+    /// [`MevCaptureParams`], or disables it if `params` is not set
+    /// ## Executor
+    /// Only the factory owner can execute it
+    SetMevCapture { params: Option<MevCaptureParams> },
+}
+
+/// ## Description
+/// This structure describes the capture of round-trip profits of a pair. When a trader sells back
+/// within the same block an asset bought earlier in that block, as the closing leg of a sandwich
+/// does, a part of the profit of the matched amount is charged as an extra fee which stays in the
+/// pool for the LPs, see [`TraderFlow::round_trip_fee`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MevCaptureParams {
+    /// the part of the round-trip profit charged as a fee
+    pub capture_ratio: Decimal,
+    /// the profit in the ask asset below which no fee is charged
+    pub min_profit: Uint128,
+}
+
+impl MevCaptureParams {
+    /// ## Description
+    /// Returns an [`Err`] if the capture ratio is zero or exceeds one.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if self.capture_ratio.is_zero() || self.capture_ratio > Decimal::one() {
+            return Err(StdError::generic_err(
+                "The MEV capture ratio must be within (0, 1]",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This structure describes the swaps of a trader in a block, indexed by the pool asset.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct TraderFlow {
+    /// the height of the block of the swaps
+    pub height: u64,
+    /// the amounts offered by the trader per pool asset
+    pub offered: [Uint128; 2],
+    /// the amounts returned to the trader per pool asset, after all fees
+    pub returned: [Uint128; 2],
+}
+
+impl TraderFlow {
+    /// ## Description
+    /// Returns the round-trip fee of a swap. The offered amount is matched against the net amount
+    /// of the offer asset bought by the trader earlier in the block, and the fee is the capture
+    /// ratio of the return of the matched amount over what the trader paid for it. Returns zero
+    /// if the flow is from an earlier block or the profit is below the minimum profit.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **params** is the object of type [`MevCaptureParams`].
+    ///
+    /// * **height** is the current block height.
+    ///
+    /// * **offer_index** is the index of the offer asset in the pool.
+    ///
+    /// * **offer_amount** is the offered amount of the swap.
+    ///
+    /// * **return_amount** is the return amount of the swap after the commission.
+    pub fn round_trip_fee(
+        &self,
+        params: &MevCaptureParams,
+        height: u64,
+        offer_index: usize,
+        offer_amount: Uint128,
+        return_amount: Uint128,
+    ) -> StdResult<Uint128> {
+        let ask_index = 1 - offer_index;
+        if self.height != height || offer_amount.is_zero() {
+            return Ok(Uint128::zero());
+        }
+        let bought = self.returned[offer_index].saturating_sub(self.offered[offer_index]);
+        let matched = offer_amount.min(bought);
+        if matched.is_zero() {
+            return Ok(Uint128::zero());
+        }
+
+        let spent = self.offered[ask_index].saturating_sub(self.returned[ask_index]);
+        let cost = spent.multiply_ratio(matched, bought);
+        let matched_return = return_amount.multiply_ratio(matched, offer_amount);
+        let profit = matched_return.saturating_sub(cost);
+        if profit.is_zero() || profit < params.min_profit {
+            return Ok(Uint128::zero());
+        }
+        Ok(profit * params.capture_ratio)
+    }
+
+    /// ## Description
+    /// Adds a swap to the flow, resetting it first if it is from an earlier block.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **height** is the current block height.
+    ///
+    /// * **offer_index** is the index of the offer asset in the pool.
+    ///
+    /// * **offer_amount** is the offered amount of the swap.
+    ///
+    /// * **return_amount** is the return amount of the swap after all fees.
+    pub fn record_swap(
+        &mut self,
+        height: u64,
+        offer_index: usize,
+        offer_amount: Uint128,
+        return_amount: Uint128,
+    ) -> StdResult<()> {
+        if self.height != height {
+            *self = TraderFlow {
+                height,
+                ..TraderFlow::default()
+            };
+        }
+        self.offered[offer_index] = self.offered[offer_index].checked_add(offer_amount)?;
+        self.returned[1 - offer_index] =
+            self.returned[1 - offer_index].checked_add(return_amount)?;
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This structure describes the fees of a pair pushed by the factory.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub struct PairFees {
+    /// the total fee charged on swaps in bps
+    pub total_fee_bps: u16,
+    /// the share of the total fee sent to the maker in bps
+    pub maker_fee_bps: u16,
+}
+
+impl PairFees {
+    /// ## Description
+    /// Returns an [`Err`] if any of the fees exceeds 10000 bps.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if self.total_fee_bps > 10_000 || self.maker_fee_bps > 10_000 {
+            return Err(StdError::generic_err("Fee bps can't exceed 10000"));
+        }
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the total fee as a rate.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn commission_rate(&self) -> Decimal {
+        Decimal::from_ratio(self.total_fee_bps, 10_000u16)
+    }
+
+    /// ## Description
+    /// Returns the total fee as a rate after the fee discount of the trader. The discount is
+    /// capped by the maximum discount of the factory fee info.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **discount_bps** is the discount requested by the router in bps of the total fee.
+    ///
+    /// * **max_discount_bps** is the maximum discount allowed by the factory in bps.
+    pub fn discounted_commission_rate(&self, discount_bps: u16, max_discount_bps: u16) -> Decimal {
+        let discount_bps = discount_bps.min(max_discount_bps).min(10_000);
+        Decimal::from_ratio(
+            u128::from(self.total_fee_bps) * u128::from(10_000 - discount_bps),
+            100_000_000u128,
+        )
+    }
+
+    /// ## Description
+    /// Returns the share of the total fee sent to the maker as a rate.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn maker_fee_rate(&self) -> Decimal {
+        Decimal::from_ratio(self.maker_fee_bps, 10_000u16)
+    }
+}
+
+/// The maximum number of fee changes kept by a pair, older changes are dropped
+pub const MAX_FEE_HISTORY: usize = 20;
+/// The type of the event emitted when the fees of a pair change
+pub const FEE_CHANGE_EVENT: &str = "pair_fee_change";
+
+/// ## Description
+/// This structure describes a change of the fees of a pair by [`ExecuteMsg::UpdateFees`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub struct FeeChange {
+    /// the height of the block in which the fees changed
+    pub height: u64,
+    /// the fees before the change
+    pub old: PairFees,
+    /// the fees after the change
+    pub new: PairFees,
+}
+
+impl FeeChange {
+    /// ## Description
+    /// Returns the [`FEE_CHANGE_EVENT`] describing the change.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn to_event(&self) -> Event {
+        Event::new(FEE_CHANGE_EVENT)
+            .add_attribute("height", self.height.to_string())
+            .add_attribute("old_total_fee_bps", self.old.total_fee_bps.to_string())
+            .add_attribute("old_maker_fee_bps", self.old.maker_fee_bps.to_string())
+            .add_attribute("new_total_fee_bps", self.new.total_fee_bps.to_string())
+            .add_attribute("new_maker_fee_bps", self.new.maker_fee_bps.to_string())
+    }
+}
+
+/// ## Description
+/// Appends a fee change to the history of a pair and drops the oldest changes beyond
+/// [`MAX_FEE_HISTORY`]. Returns false without recording anything if the fees didn't change.
+/// ## Params
+/// * **history** is the fee history of the pair ordered by height.
+///
+/// * **change** is the object of type [`FeeChange`].
+pub fn record_fee_change(history: &mut Vec<FeeChange>, change: FeeChange) -> bool {
+    if change.old == change.new {
+        return false;
+    }
+    history.push(change);
+    if history.len() > MAX_FEE_HISTORY {
+        history.drain(..history.len() - MAX_FEE_HISTORY);
+    }
+    true
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeHistoryResponse {
+    /// the latest fee changes ordered by height, at most [`MAX_FEE_HISTORY`]
+    pub changes: Vec<FeeChange>,
+}
+
+/// ## Description
+/// This enum describes how a pair executes a swap which exceeds the max spread.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FillMode {
+    /// The swap is rejected
+    FillOrKill {},
+    /// Only the part of the offer which stays within the max spread is swapped and the rest is
+    /// refunded to the sender, see [`crate::math::compute_partial_fill_offer`]
+    PartialFill {},
+}
+
+impl Default for FillMode {
+    fn default() -> Self {
+        FillMode::FillOrKill {}
+    }
 }
 
 /// ## Description
-/// This structure describes a CW20 hook message.
+/// This enum describes why a pair refunds assets.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundReason {
+    /// The part of a provide which exceeds the pool ratio
+    ExcessProvide {},
+    /// The part of an offer which was not swapped by a [`FillMode::PartialFill`] swap
+    PartialFill {},
+    /// A batch order which violated its max spread at settlement
+    BatchSpreadViolation {},
+    /// An escrowed deposit which expired, see [`ExecuteMsg::RefundDeposit`]
+    ExpiredDeposit {},
+    /// The assets sent back because a hook of the operation failed
+    FailedHook {},
+}
+
+impl fmt::Display for RefundReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RefundReason::ExcessProvide {} => write!(f, "excess_provide"),
+            RefundReason::PartialFill {} => write!(f, "partial_fill"),
+            RefundReason::BatchSpreadViolation {} => write!(f, "batch_spread_violation"),
+            RefundReason::ExpiredDeposit {} => write!(f, "expired_deposit"),
+            RefundReason::FailedHook {} => write!(f, "failed_hook"),
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the assets refunded by a pair operation. Pairs add its
+/// [`RefundReport::attributes`] to the response and set it as the response data, so integrators
+/// don't have to diff balances to discover refunds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RefundReport {
+    /// the refunded assets
+    pub refund_assets: Vec<Asset>,
+    /// the recipient of the refund
+    pub refund_receiver: Addr,
+    /// why the assets were refunded
+    pub reason: RefundReason,
+}
+
+impl RefundReport {
+    /// ## Description
+    /// Returns the report of a refund, or [`None`] if all refunded amounts are zero.
+    /// ## Params
+    /// * **refund_assets** are the refunded assets, zero amounts are dropped.
+    ///
+    /// * **refund_receiver** is the recipient of the refund.
+    ///
+    /// * **reason** is the object of type [`RefundReason`].
+    pub fn new(
+        refund_assets: Vec<Asset>,
+        refund_receiver: Addr,
+        reason: RefundReason,
+    ) -> Option<Self> {
+        let refund_assets: Vec<Asset> = refund_assets
+            .into_iter()
+            .filter(|asset| !asset.amount.is_zero())
+            .collect();
+        if refund_assets.is_empty() {
+            return None;
+        }
+        Some(RefundReport {
+            refund_assets,
+            refund_receiver,
+            reason,
+        })
+    }
+
+    /// ## Description
+    /// Returns the `refund_assets`, `refund_receiver` and `refund_reason` attributes of the refund.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn attributes(&self) -> Vec<Attribute> {
+        let refund_assets: Vec<String> = self
+            .refund_assets
+            .iter()
+            .map(|asset| asset.to_string())
+            .collect();
+        vec![
+            attr("refund_assets", refund_assets.join(",")),
+            attr("refund_receiver", self.refund_receiver.as_str()),
+            attr("refund_reason", self.reason.to_string()),
+        ]
+    }
+}
+
+/// ## Description
+/// Returns an [`Err`] if the deadline of a swap or liquidity operation has passed.
+/// ## Params
+/// * **deadline** is an [`Option`] field with the block time after which the execution is rejected.
+///
+/// * **time** is the current block time.
+pub fn assert_deadline(deadline: Option<u64>, time: u64) -> StdResult<()> {
+    match deadline {
+        Some(deadline) if time > deadline => Err(StdError::generic_err(format!(
+            "Transaction deadline {} has passed",
+            deadline
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// This enum describes the operations of a pair which can be paused.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairOperation {
+    Swap,
+    Provide,
+    Withdraw,
+}
+
+/// ## Description
+/// This structure describes which operations of a pair are paused.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PausedOperations {
+    /// whether swaps are paused
+    pub swap: bool,
+    /// whether providing liquidity is paused
+    pub provide: bool,
+    /// whether withdrawing liquidity is paused
+    pub withdraw: bool,
+    /// the price of the first pool asset in the second one recorded by the TWAP while swaps are
+    /// paused, the cumulative prices are frozen if not set
+    #[serde(default)]
+    pub reference_price: Option<Decimal>,
+}
+
+impl PausedOperations {
+    /// ## Description
+    /// Returns an [`Err`] if the reference price is zero or set while swaps are not paused.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        match self.reference_price {
+            Some(_) if !self.swap => Err(StdError::generic_err(
+                "The reference price can only be set while swaps are paused",
+            )),
+            Some(price) if price.is_zero() => Err(StdError::generic_err(
+                "The reference price must be greater than zero",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// ## Description
+    /// Returns whether any operation of the pair is paused. Query responses carry it as the
+    /// `paused` flag.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn is_paused(&self) -> bool {
+        self.swap || self.provide || self.withdraw
+    }
+
+    /// ## Description
+    /// Returns an [`Err`] if the specified operation is paused.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **operation** is the object of type [`PairOperation`].
+    pub fn assert_allowed(&self, operation: PairOperation) -> StdResult<()> {
+        let paused = match operation {
+            PairOperation::Swap => self.swap,
+            PairOperation::Provide => self.provide,
+            PairOperation::Withdraw => self.withdraw,
+        };
+        if paused {
+            return Err(StdError::generic_err(format!(
+                "Operation {:?} is paused",
+                operation
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// Returns the cumulative prices of the pair after the elapsed time. The prices carry
+/// [`TWAP_PRECISION`] decimals and wrap around on overflow, as only their differences are used.
+/// While swaps are paused the
+/// reference price is recorded instead of the pool price, or the cumulative prices stay frozen
+/// if no reference price is set.
+/// ## Params
+/// * **paused** is the object of type [`PausedOperations`].
+///
+/// * **pools** are the reserves of the pool assets.
+///
+/// * **cumulative_last** are the cumulative prices of the first and the second asset.
+///
+/// * **time_elapsed** is the number of seconds since the last accumulation.
+pub fn accumulate_prices(
+    paused: &PausedOperations,
+    pools: [Uint128; 2],
+    cumulative_last: [Uint128; 2],
+    time_elapsed: u64,
+) -> StdResult<[Uint128; 2]> {
+    let (numerator, denominator) = match paused.reference_price {
+        Some(price) if paused.swap => (price.numerator(), price.denominator()),
+        _ if paused.swap || pools[0].is_zero() || pools[1].is_zero() => return Ok(cumulative_last),
+        _ => (pools[1].u128(), pools[0].u128()),
+    };
+    let scaled_time =
+        Uint128::from(time_elapsed) * Uint128::from(10u128.pow(TWAP_PRECISION as u32));
+    Ok([
+        cumulative_last[0].wrapping_add(wrapping_ratio(scaled_time, numerator, denominator)?),
+        cumulative_last[1].wrapping_add(wrapping_ratio(scaled_time, denominator, numerator)?),
+    ])
+}
+
+/// ## Description
+/// Returns `value * numerator / denominator` truncated to its lower 128 bits, so a price
+/// increase which doesn't fit into [`Uint128`] wraps around like the cumulative prices.
+fn wrapping_ratio(value: Uint128, numerator: u128, denominator: u128) -> StdResult<Uint128> {
+    let ratio = value
+        .full_mul(numerator)
+        .checked_div(Uint256::from(denominator))
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let mut low_bytes = [0u8; 16];
+    low_bytes.copy_from_slice(&ratio.to_be_bytes()[16..]);
+    Ok(Uint128::new(u128::from_be_bytes(low_bytes)))
+}
+
+/// ## Description
+/// This structure describes the retirement of a pair, see [`ExecuteMsg::Retire`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RetirementInfo {
+    /// the pair which replaces the retired pair
+    pub successor_pair: Addr,
+    /// the surcharge on swaps in bps
+    pub surcharge_bps: u16,
+}
+
+impl RetirementInfo {
+    /// ## Description
+    /// Returns an [`Err`] if the surcharge exceeds [`MAX_RETIREMENT_SURCHARGE_BPS`] or the pair
+    /// would be its own successor.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **pair** is the address of the retired pair.
+    pub fn validate(&self, pair: &Addr) -> StdResult<()> {
+        if self.surcharge_bps > MAX_RETIREMENT_SURCHARGE_BPS {
+            return Err(StdError::generic_err(format!(
+                "The retirement surcharge can't exceed {} bps",
+                MAX_RETIREMENT_SURCHARGE_BPS
+            )));
+        }
+        if &self.successor_pair == pair {
+            return Err(StdError::generic_err("A pair can't be its own successor"));
+        }
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the surcharge withheld from the return amount of a swap.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **return_amount** is the return amount of the swap after the commission.
+    pub fn compute_surcharge(&self, return_amount: Uint128) -> Uint128 {
+        return_amount.multiply_ratio(self.surcharge_bps, 10_000u16)
+    }
+
+    /// ## Description
+    /// Returns an [`Err`] naming the successor pair, as liquidity can't be provided to a retired pair.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn assert_provide_allowed(&self) -> StdResult<()> {
+        Err(StdError::generic_err(format!(
+            "The pair is retired, provide liquidity to {} instead",
+            self.successor_pair
+        )))
+    }
+}
+
+/// ## Description
+/// Returns the messages which provide the assets withdrawn from a retired pair to its successor.
+/// Tokens are approved to the successor first, native coins are sent with the provide after the tax.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **successor_pair** is the pair which replaces the retired pair.
+///
+/// * **assets** are the assets withdrawn from the retired pair.
+///
+/// * **receiver** is the owner of the migrated liquidity.
+///
+/// * **slippage_tolerance** is an [`Option`] field with the slippage tolerance of the provide.
+///
+/// * **auto_stake** is an [`Option`] field which determines whether an autostake will be performed.
+pub fn migrate_liquidity_msgs(
+    querier: &QuerierWrapper,
+    successor_pair: &Addr,
+    assets: [Asset; 2],
+    receiver: &Addr,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: Option<bool>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages = vec![];
+    let mut funds = vec![];
+    let mut provided = vec![];
+    for asset in assets {
+        match &asset.info {
+            AssetInfo::NativeToken { .. } => {
+                let coin = asset.deduct_tax(querier)?;
+                provided.push(Asset {
+                    info: asset.info.clone(),
+                    amount: coin.amount,
+                });
+                if !coin.amount.is_zero() {
+                    funds.push(coin);
+                }
+            }
+            AssetInfo::Token { contract_addr } => {
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: successor_pair.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }));
+                provided.push(asset);
+            }
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: successor_pair.to_string(),
+        msg: to_binary(&ExecuteMsg::ProvideLiquidity {
+            assets: [provided[0].clone(), provided[1].clone()],
+            slippage_tolerance,
+            auto_stake,
+            receiver: Some(receiver.to_string()),
+            deadline: None,
+            initial_price: None,
+        })?,
+        funds,
+    }));
+    Ok(messages)
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message. It can be wrapped in a versioned
+/// [`crate::hook::HookEnvelope`] and is parsed with [`crate::hook::parse_hook_msg`]. Unknown
+/// messages are refunded to the sender and counted in [`QueryMsg::RejectedHooks`]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
@@ -67,9 +814,58 @@ pub enum Cw20HookMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// the block time after which the execution is rejected, see [`assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+        /// whether the swap is rejected or partially filled if it exceeds the max spread
+        #[serde(default)]
+        fill_mode: FillMode,
+        /// the fee discount of the trader in bps, see [`PairFees::discounted_commission_rate`].
+        /// Ignored unless the sender is the fee discount router of the factory
+        #[serde(default)]
+        fee_discount_bps: u16,
     },
     /// Withdrawing liquidity from the pool
-    WithdrawLiquidity {},
+    WithdrawLiquidity {
+        /// the block time after which the execution is rejected, see [`assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+    },
+    /// Withdraws the liquidity of a retired pair and provides it to the successor pair in the
+    /// same transaction, see [`migrate_liquidity_msgs`]. The sender receives the new LP tokens
+    MigrateLiquidity {
+        /// the slippage tolerance of the provide to the successor pair
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether an autostake will be performed on the generator
+        auto_stake: Option<bool>,
+    },
+    /// Escrows one side of a provide without an allowance. The liquidity is provided as soon as
+    /// the sender has deposited both assets, the parameters of the second deposit are used
+    DepositSide {
+        /// the slippage tolerance for sets the maximum percent of price movement
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether an autostake will be performed on the generator
+        auto_stake: Option<bool>,
+        /// the receiver of provide liquidity
+        receiver: Option<String>,
+        /// the number of seconds after which the deposit can be refunded,
+        /// [`DEFAULT_DEPOSIT_TIMEOUT`] by default
+        timeout: Option<u64>,
+    },
+}
+
+/// ## Description
+/// This structure describes the execute message a flash swap borrower has to support.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlashSwapReceiverMsg {
+    /// Called by the pair after the borrowed amount was sent to the borrower
+    FlashSwapCallback {
+        /// the borrowed asset
+        borrowed: Asset,
+        /// the binary message of [`ExecuteMsg::FlashSwap`]
+        msg: Binary,
+    },
 }
 
 /// ## Description
@@ -87,10 +883,128 @@ pub enum QueryMsg {
     Share { amount: Uint128 },
     /// Returns information about the simulation of the swap in a [`SimulationResponse`] object.
     Simulation { offer_asset: Asset },
+    /// Returns information about the simulations of several swaps against the current pool state
+    /// in a [`SimulateManyResponse`] object. Stable pairs compute the invariant once for all
+    /// offers, reusing the stored [`crate::math::InvariantCache`] while the reserves are unchanged.
+    SimulateMany { offers: Vec<Asset> },
     /// Returns information about the reverse simulation in a [`ReverseSimulationResponse`] object.
     ReverseSimulation { ask_asset: Asset },
     /// Returns information about the cumulative prices in a [`CumulativePricesResponse`] object
     CumulativePrices {},
+    /// Returns the latest reserve checkpoint at or before the specified height in a [`ReservesAtResponse`] object
+    ReservesAt { height: u64 },
+    /// Returns the escrowed deposit of a user in a [`PendingDepositResponse`] object
+    PendingDeposit { user: String },
+    /// Returns the metadata of the LP token in a [`LpTokenInfoResponse`] object
+    LpTokenInfo {},
+    /// Returns the queued swaps which are not settled yet in a [`PendingBatchResponse`] object
+    PendingBatch {},
+    /// Returns the APR of the pool over a trailing window in an [`AprResponse`] object. The fee
+    /// APR is derived from the LP fees accrued during the window, the ASTRO and external APRs
+    /// from the current generator emissions and proxy rewards of the LP token
+    Apr {
+        /// the trailing window in seconds, [`DEFAULT_APR_WINDOW`] if not set
+        window: Option<u64>,
+    },
+    /// Returns the accrued protocol fees which were not withdrawn yet in a
+    /// [`ProtocolFeesResponse`] object
+    ProtocolFees {},
+    /// Returns the latest fee changes pushed by the factory in a [`FeeHistoryResponse`] object
+    FeeHistory {},
+    /// Returns the largest offer of the asset which keeps the spread within the bound for the
+    /// current reserves in a [`MaxSwapWithinSpreadResponse`] object, see
+    /// [`crate::math::compute_max_offer_within_spread`]
+    MaxSwapWithinSpread {
+        offer_asset_info: AssetInfo,
+        /// the maximum spread, it must be below one
+        max_spread: Decimal,
+    },
+    /// Returns the number of rejected CW20 hook messages and the latest rejection in a
+    /// [`crate::hook::RejectedHooksResponse`] object
+    RejectedHooks {},
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProtocolFeesResponse {
+    /// the accrued protocol fees per pool asset, excluded from the pool reserves
+    pub fees: Vec<Asset>,
+    /// the height of the last block in which fees were accrued
+    pub last_accrual_height: Option<u64>,
+    /// the height of the last withdrawal by [`ExecuteMsg::WithdrawProtocolFees`]
+    pub last_withdrawal_height: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response. All values are
+/// denominated in the quote asset, which is the first asset of the pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AprResponse {
+    /// the trailing window in seconds
+    pub window: u64,
+    /// the asset in which the TVL and the rewards are valued
+    pub quote_asset_info: AssetInfo,
+    /// the value of the pool liquidity
+    pub tvl: Uint128,
+    /// the APR from the LP fees
+    pub fee_apr: Decimal,
+    /// the APR from the generator ASTRO emissions
+    pub astro_apr: Decimal,
+    /// the APR from the external rewards distributed by the generator proxy
+    pub external_apr: Decimal,
+    /// the sum of all APRs
+    pub total_apr: Decimal,
+}
+
+/// ## Description
+/// Returns the annualized rate of rewards earned by the liquidity over a window. Returns zero
+/// if the pool has no liquidity.
+/// ## Params
+/// * **reward_value** is the value of the rewards earned during the window.
+///
+/// * **tvl** is the value of the pool liquidity.
+///
+/// * **window** is the length of the window in seconds.
+pub fn compute_apr(reward_value: Uint128, tvl: Uint128, window: u64) -> StdResult<Decimal> {
+    if window == 0 {
+        return Err(StdError::generic_err("The APR window must be positive"));
+    }
+    if tvl.is_zero() {
+        return Ok(Decimal::zero());
+    }
+    Ok(Decimal::from_ratio(
+        reward_value.checked_mul(Uint128::from(SECONDS_PER_YEAR))?,
+        tvl.checked_mul(Uint128::from(window))?,
+    ))
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MaxSwapWithinSpreadResponse {
+    /// the largest offer amount within the maximum spread
+    pub offer_amount: Uint128,
+    /// the return amount of the offer after the commission
+    pub return_amount: Uint128,
+    /// the spread amount of the offer
+    pub spread_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LpTokenInfoResponse {
+    /// the LP token contract address
+    pub contract_addr: Addr,
+    /// the LP token name
+    pub name: String,
+    /// the LP token symbol
+    pub symbol: String,
+    /// the LP token decimals
+    pub decimals: u8,
+    /// the LP token total supply
+    pub total_supply: Uint128,
 }
 
 /// ## Description
@@ -99,6 +1013,9 @@ pub enum QueryMsg {
 pub struct PoolResponse {
     pub assets: [Asset; 2],
     pub total_share: Uint128,
+    /// whether any operation of the pair is paused, see [`PausedOperations::is_paused`]
+    #[serde(default)]
+    pub paused: bool,
 }
 
 /// ## Description
@@ -108,6 +1025,27 @@ pub struct ConfigResponse {
     /// the last time block
     pub block_time_last: u64,
     pub params: Option<Binary>,
+    /// the paused operations of the pair
+    #[serde(default)]
+    pub paused_operations: PausedOperations,
+    /// whether swaps are queued and settled in batches
+    #[serde(default)]
+    pub batch_mode: bool,
+    /// the fees pushed by the factory, [`None`] if the pair was not updated since instantiation
+    #[serde(default)]
+    pub fees: Option<PairFees>,
+    /// whether the invariant check after state-mutating operations is disabled
+    #[serde(default)]
+    pub invariant_checks_disabled: bool,
+    /// the retirement of the pair, [`None`] if the pair is active
+    #[serde(default)]
+    pub retirement: Option<RetirementInfo>,
+    /// the contract notified when LP tokens change owner, [`None`] if transfers are not hooked
+    #[serde(default)]
+    pub lp_transfer_hook: Option<Addr>,
+    /// the round-trip profit capture of the pair, [`None`] if it is disabled
+    #[serde(default)]
+    pub mev_capture: Option<MevCaptureParams>,
 }
 
 /// ## Description
@@ -117,6 +1055,16 @@ pub struct SimulationResponse {
     pub return_amount: Uint128,
     pub spread_amount: Uint128,
     pub commission_amount: Uint128,
+    /// whether any operation of the pair is paused, see [`PausedOperations::is_paused`]
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// ## Description
+/// SimulateManyResponse returns swap simulation responses in the same order as the offers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateManyResponse {
+    pub simulations: Vec<SimulationResponse>,
 }
 
 /// ## Description
@@ -126,6 +1074,9 @@ pub struct ReverseSimulationResponse {
     pub offer_amount: Uint128,
     pub spread_amount: Uint128,
     pub commission_amount: Uint128,
+    /// whether any operation of the pair is paused, see [`PausedOperations::is_paused`]
+    #[serde(default)]
+    pub paused: bool,
 }
 
 /// ## Description
@@ -136,6 +1087,62 @@ pub struct CumulativePricesResponse {
     pub total_share: Uint128,
     pub price0_cumulative_last: Uint128,
     pub price1_cumulative_last: Uint128,
+    /// whether any operation of the pair is paused, see [`accumulate_prices`] for the prices
+    /// recorded meanwhile
+    #[serde(default)]
+    pub paused: bool,
+    /// the reference price recorded while swaps are paused, [`None`] if the pool price is
+    /// recorded or the prices are frozen
+    #[serde(default)]
+    pub reference_price: Option<Decimal>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReservesAtResponse {
+    /// the height at which the checkpoint was stored
+    pub height: u64,
+    /// the time at which the checkpoint was stored
+    pub time: u64,
+    /// the pool reserves at the checkpoint
+    pub assets: [Asset; 2],
+    /// the total share of the pool at the checkpoint
+    pub total_share: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDepositResponse {
+    /// the escrowed assets
+    pub assets: Vec<Asset>,
+    /// the time after which the deposit can be refunded
+    pub expires_at: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBatchResponse {
+    /// the height of the first block of the queued swaps
+    pub height: Option<u64>,
+    /// the queued swaps
+    pub orders: Vec<BatchOrder>,
+}
+
+/// ## Description
+/// This structure describes a queued swap of the batch mode.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchOrder {
+    /// the sender of the swap
+    pub sender: Addr,
+    /// the offered asset
+    pub offer_asset: Asset,
+    pub belief_price: Option<Decimal>,
+    pub max_spread: Option<Decimal>,
+    /// the recipient of the returned asset
+    pub to: Addr,
 }
 
 /// ## Description
@@ -148,17 +1155,276 @@ pub struct MigrateMsg {}
 #[serde(rename_all = "snake_case")]
 pub struct StablePoolParams {
     pub amp: u64,
+    #[serde(default)]
+    pub imbalance_fee: Option<ImbalanceFeeParams>,
+    #[serde(default)]
+    pub dynamic_fee: Option<DynamicFeeParams>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct StablePoolConfig {
     pub amp: Decimal,
+    #[serde(default)]
+    pub imbalance_fee: Option<ImbalanceFeeParams>,
+    #[serde(default)]
+    pub dynamic_fee: Option<DynamicFeeParams>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum StablePoolUpdateParams {
-    StartChangingAmp { next_amp: u64, next_amp_time: u64 },
+    StartChangingAmp {
+        next_amp: u64,
+        next_amp_time: u64,
+    },
     StopChangingAmp {},
+    /// Sets or removes the fee charged on provides and withdrawals depending on the pool imbalance
+    SetImbalanceFee {
+        params: Option<ImbalanceFeeParams>,
+    },
+    /// Sets or removes the swap fee curve depending on the deviation from the peg ratio
+    SetDynamicFee {
+        params: Option<DynamicFeeParams>,
+    },
+}
+
+/// ## Description
+/// Returns an [`Err`] if the amplification coefficient can't be ramped from `current_amp` to
+/// `next_amp` until `next_amp_time`.
+/// ## Params
+/// * **current_amp** is the current amplification coefficient.
+///
+/// * **next_amp** is the target amplification coefficient.
+///
+/// * **time** is the current timestamp.
+///
+/// * **next_amp_time** is the timestamp at which the target is reached.
+pub fn validate_amp_change(
+    current_amp: u64,
+    next_amp: u64,
+    time: u64,
+    next_amp_time: u64,
+) -> StdResult<()> {
+    if next_amp == 0 || next_amp > MAX_AMP {
+        return Err(StdError::generic_err(format!(
+            "Amp must be between 1 and {}",
+            MAX_AMP
+        )));
+    }
+    if next_amp > current_amp.saturating_mul(MAX_AMP_CHANGE)
+        || next_amp.saturating_mul(MAX_AMP_CHANGE) < current_amp
+    {
+        return Err(StdError::generic_err(format!(
+            "Amp can't be changed by more than {} times at once",
+            MAX_AMP_CHANGE
+        )));
+    }
+    if next_amp_time < time.saturating_add(MIN_AMP_CHANGING_TIME) {
+        return Err(StdError::generic_err(format!(
+            "Amp can't be changed faster than over {} seconds",
+            MIN_AMP_CHANGING_TIME
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the amplification coefficient at the specified time while it is linearly ramped from
+/// `init_amp` at `init_amp_time` to `next_amp` at `next_amp_time`.
+/// ## Params
+/// * **init_amp** is the amplification coefficient at the start of the change.
+///
+/// * **init_amp_time** is the time when the change started.
+///
+/// * **next_amp** is the amplification coefficient at the end of the change.
+///
+/// * **next_amp_time** is the time when the change ends.
+///
+/// * **time** is the current timestamp.
+pub fn compute_current_amp(
+    init_amp: u64,
+    init_amp_time: u64,
+    next_amp: u64,
+    next_amp_time: u64,
+    time: u64,
+) -> u64 {
+    if time >= next_amp_time || next_amp_time <= init_amp_time {
+        return next_amp;
+    }
+    if time <= init_amp_time {
+        return init_amp;
+    }
+
+    let elapsed = (time - init_amp_time) as u128;
+    let duration = (next_amp_time - init_amp_time) as u128;
+    if next_amp > init_amp {
+        init_amp + ((next_amp - init_amp) as u128 * elapsed / duration) as u64
+    } else {
+        init_amp - ((init_amp - next_amp) as u128 * elapsed / duration) as u64
+    }
+}
+
+/// ## Description
+/// This structure describes the fee charged on stable pool provides and withdrawals depending on
+/// whether they worsen or improve the pool imbalance. Both multipliers are applied to the total
+/// fee rate of the pair.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ImbalanceFeeParams {
+    /// the multiplier of the fee charged on operations which worsen the imbalance
+    pub fee_multiplier: Decimal,
+    /// the multiplier of the rebate paid on operations which improve the imbalance
+    pub rebate_multiplier: Decimal,
+}
+
+impl ImbalanceFeeParams {
+    /// ## Description
+    /// Returns an [`Err`] if the fee multiplier exceeds [`MAX_IMBALANCE_FEE_MULTIPLIER`] or the
+    /// rebate multiplier exceeds one, so that rebates never exceed the fees of swaps.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if self.fee_multiplier > Decimal::from_ratio(MAX_IMBALANCE_FEE_MULTIPLIER, 1u8) {
+            return Err(StdError::generic_err(format!(
+                "Imbalance fee multiplier can't exceed {}",
+                MAX_IMBALANCE_FEE_MULTIPLIER
+            )));
+        }
+        if self.rebate_multiplier > Decimal::one() {
+            return Err(StdError::generic_err(
+                "Imbalance rebate multiplier can't exceed 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This structure describes the swap fee curve of a stable pool. The swap fee is scaled by how
+/// far a swap moves the balance ratio of the pool from the target peg ratio, so swaps restoring
+/// the peg pay less and swaps worsening it pay more, see [`crate::math::compute_dynamic_fee`].
+/// The fee is kept within the [`crate::factory::DynamicFeeBounds`] set by the factory.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DynamicFeeParams {
+    /// the ratio of the first pool balance to the second one the pool is pegged to
+    pub target_ratio: Decimal,
+    /// the relative fee change per unit of peg deviation change
+    pub sensitivity: Decimal,
+}
+
+impl DynamicFeeParams {
+    /// ## Description
+    /// Returns an [`Err`] if the target ratio is zero or the sensitivity exceeds
+    /// [`MAX_DYNAMIC_FEE_SENSITIVITY`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if self.target_ratio.is_zero() {
+            return Err(StdError::generic_err("Target ratio must be positive"));
+        }
+        if self.sensitivity > Decimal::from_ratio(MAX_DYNAMIC_FEE_SENSITIVITY, 1u8) {
+            return Err(StdError::generic_err(format!(
+                "Dynamic fee sensitivity can't exceed {}",
+                MAX_DYNAMIC_FEE_SENSITIVITY
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This enum describes the fee applied to a stable pool provide or withdrawal.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImbalanceFee {
+    /// The operation worsens the imbalance, the rate is charged on the operation amounts
+    Charge(Decimal),
+    /// The operation improves the imbalance, the rate is rebated on the operation amounts
+    Rebate(Decimal),
+    /// The operation doesn't change the imbalance
+    None,
+}
+
+/// ## Description
+/// This structure describes the parameters used to gradually shift the asset weights of an xyk pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum XykPoolUpdateParams {
+    /// Starts changing the weights from the current ones to `next_weights` until `next_weights_time`
+    StartChangingWeights {
+        next_weights: [Decimal; 2],
+        next_weights_time: u64,
+    },
+    /// Stops changing the weights, keeping the current ones
+    StopChangingWeights {},
+}
+
+/// ## Description
+/// This structure describes the asset weights of an xyk pool returned in [`ConfigResponse`] params.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct XykPoolConfig {
+    /// the weights at the start of the change
+    pub init_weights: [Decimal; 2],
+    /// the time when the change started
+    pub init_weights_time: u64,
+    /// the weights at the end of the change
+    pub next_weights: [Decimal; 2],
+    /// the time when the change ends
+    pub next_weights_time: u64,
+}
+
+impl XykPoolConfig {
+    /// ## Description
+    /// Returns the weights at the specified time, linearly interpolated between the initial and the next weights.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the block time in seconds.
+    pub fn current_weights(&self, time: u64) -> [Decimal; 2] {
+        if time >= self.next_weights_time || self.next_weights_time <= self.init_weights_time {
+            return self.next_weights;
+        }
+        if time <= self.init_weights_time {
+            return self.init_weights;
+        }
+
+        let elapsed = time - self.init_weights_time;
+        let total = self.next_weights_time - self.init_weights_time;
+        // the part of the weight delta which has elapsed so far
+        let progress = |delta: Decimal| {
+            Decimal::from_ratio(
+                delta.numerator() * elapsed as u128,
+                delta.denominator() * total as u128,
+            )
+        };
+        let first = if self.next_weights[0] >= self.init_weights[0] {
+            self.init_weights[0] + progress(self.next_weights[0] - self.init_weights[0])
+        } else {
+            self.init_weights[0] - progress(self.init_weights[0] - self.next_weights[0])
+        };
+
+        [first, Decimal::one() - first]
+    }
+}
+
+/// ## Description
+/// Returns [`Ok`] if the weights sum to one and each weight is at least [`MIN_XYK_WEIGHT`].
+/// ## Params
+/// * **weights** is an array with two items of type [`Decimal`].
+pub fn validate_weights(weights: &[Decimal; 2]) -> StdResult<()> {
+    let min_weight: Decimal = MIN_XYK_WEIGHT.parse()?;
+    if weights[0] + weights[1] != Decimal::one() {
+        return Err(StdError::generic_err("Weights must sum to one"));
+    }
+    if weights.iter().any(|weight| *weight < min_weight) {
+        return Err(StdError::generic_err(format!(
+            "Each weight must be at least {}",
+            MIN_XYK_WEIGHT
+        )));
+    }
+
+    Ok(())
 }
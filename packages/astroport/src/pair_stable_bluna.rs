@@ -2,6 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::asset::Asset;
+use crate::pair::PausedOperations;
 
 use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
@@ -24,6 +25,9 @@ pub enum ExecuteMsg {
         auto_stake: Option<bool>,
         /// the receiver of provide liquidity
         receiver: Option<String>,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
     },
     /// Swap an offer asset to the other
     Swap {
@@ -31,9 +35,16 @@ pub enum ExecuteMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
     },
     /// Update pair config if required
     UpdateConfig { params: Binary },
+    /// Pauses or resumes swaps, provides and withdrawals of the pair
+    /// ## Executor
+    /// Only the factory owner or guardian can execute it
+    SetPausedOperations { paused: PausedOperations },
     /// Claims the Bluna reward and sends it to the receiver
     ClaimReward { receiver: Option<String> },
     /// Callback for distributing Bluna reward
@@ -61,12 +72,19 @@ pub enum QueryMsg {
     Share { amount: Uint128 },
     /// Returns information about the simulation of the swap in a [`SimulationResponse`] object.
     Simulation { offer_asset: Asset },
+    /// Returns information about the simulations of several swaps against the current pool state
+    /// in a [`SimulateManyResponse`] object.
+    SimulateMany { offers: Vec<Asset> },
     /// Returns information about the reverse simulation in a [`ReverseSimulationResponse`] object.
     ReverseSimulation { ask_asset: Asset },
     /// Returns information about the cumulative prices in a [`CumulativePricesResponse`] object
     CumulativePrices {},
+    /// Returns the latest reserve checkpoint at or before the specified height in a [`ReservesAtResponse`] object
+    ReservesAt { height: u64 },
     /// Returns pending reward for a user in a [`PendingRewardResponse`] object
     PendingReward { user: String },
+    /// Returns the metadata of the LP token in a [`crate::pair::LpTokenInfoResponse`] object
+    LpTokenInfo {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -1,16 +1,22 @@
-use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::asset::{
+    Asset, AssetInfo, PairInfo, TransferAllowedResponse, TransferRestrictionQueryMsg,
+};
 use crate::factory::{
-    ConfigResponse as FactoryConfigResponse, FeeInfoResponse, PairType, PairsResponse,
-    QueryMsg as FactoryQueryMsg,
+    AssetsMetadataResponse, CommissionMode, ConfigResponse as FactoryConfigResponse,
+    FeeInfoResponse, PairType, PairsResponse, QueryMsg as FactoryQueryMsg,
+};
+use crate::pair::{
+    QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulateManyResponse, SimulationResponse,
 };
-use crate::pair::{QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulationResponse};
 
 use cosmwasm_std::{
     to_binary, Addr, AllBalanceResponse, BalanceResponse, BankQuery, Coin, Decimal, QuerierWrapper,
     QueryRequest, StdResult, Uint128, WasmQuery,
 };
 
-use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use cw20::{
+    BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, MinterResponse, TokenInfoResponse,
+};
 
 // It's defined at https://github.com/terra-money/core/blob/d8e277626e74f9d6417dcd598574686882f0274c/types/assets/assets.go#L15
 const NATIVE_TOKEN_PRECISION: u8 = 6;
@@ -55,7 +61,7 @@ pub fn query_all_balances(querier: &QuerierWrapper, account_addr: Addr) -> StdRe
 /// * **querier** is the object of type [`QuerierWrapper`].
 ///
 /// * **contract_addr** is the object of type [`Addr`]. Sets the address of the contract for which
-/// the balance will be requested
+///   the balance will be requested
 ///
 /// * **account_addr** is the object of type [`Addr`].
 pub fn query_token_balance(
@@ -93,6 +99,39 @@ pub fn query_token_symbol(querier: &QuerierWrapper, contract_addr: Addr) -> StdR
     Ok(res.symbol)
 }
 
+/// ## Description
+/// Returns whether a transfer of a restricted native denom is allowed by the registry.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **registry** is the transfer restriction registry contract.
+///
+/// * **denom** is the native denom of the transfer.
+///
+/// * **sender** is the address the funds are sent from.
+///
+/// * **recipient** is the address where the funds will be sent.
+///
+/// * **amount** is the amount of the transfer.
+pub fn query_transfer_allowed(
+    querier: &QuerierWrapper,
+    registry: Addr,
+    denom: String,
+    sender: Addr,
+    recipient: Addr,
+    amount: Uint128,
+) -> StdResult<TransferAllowedResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: registry.to_string(),
+        msg: to_binary(&TransferRestrictionQueryMsg::TransferAllowed {
+            denom,
+            sender: sender.to_string(),
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+    }))
+}
+
 /// ## Description
 /// Returns the total supply at the specified contract address.
 /// ## Params
@@ -151,6 +190,8 @@ pub struct FeeInfo {
     pub total_fee_rate: Decimal,
     /// the maker fee rate
     pub maker_fee_rate: Decimal,
+    /// the asset in which the commission is charged
+    pub commission_mode: CommissionMode,
 }
 
 /// ## Description
@@ -175,6 +216,7 @@ pub fn query_fee_info(
         fee_address: res.fee_address,
         total_fee_rate: Decimal::from_ratio(Uint128::from(res.total_fee_bps), Uint128::new(10000)),
         maker_fee_rate: Decimal::from_ratio(Uint128::from(res.maker_fee_bps), Uint128::new(10000)),
+        commission_mode: res.commission_mode,
     })
 }
 
@@ -199,6 +241,45 @@ pub fn query_pair_info(
     }))
 }
 
+/// ## Description
+/// Returns the pair information for the specified LP token if the token is the liquidity token
+/// of a pair registered in the factory. Otherwise returns [`None`].
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is the object of type [`Addr`].
+///
+/// * **lp_token** is the object of type [`Addr`].
+pub fn query_pair_by_lp_token(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+    lp_token: Addr,
+) -> StdResult<Option<PairInfo>> {
+    // the minter of a liquidity token is the pair contract
+    let minter: Option<MinterResponse> = querier
+        .query_wasm_smart(lp_token.clone(), &Cw20QueryMsg::Minter {})
+        .unwrap_or(None);
+    let minter = match minter {
+        Some(minter) => minter.minter,
+        None => return Ok(None),
+    };
+
+    let pair_info: PairInfo = match querier.query_wasm_smart(minter, &PairQueryMsg::Pair {}) {
+        Ok(pair_info) => pair_info,
+        Err(_) => return Ok(None),
+    };
+    if pair_info.liquidity_token != lp_token {
+        return Ok(None);
+    }
+
+    match query_pair_info(querier, factory_contract, &pair_info.asset_infos) {
+        Ok(registered) if registered.contract_addr == pair_info.contract_addr => {
+            Ok(Some(pair_info))
+        }
+        _ => Ok(None),
+    }
+}
+
 /// ## Description
 /// Returns the vector that contains items of type [`PairInfo`]
 /// ## Params
@@ -221,6 +302,25 @@ pub fn query_pairs_info(
     }))
 }
 
+/// ## Description
+/// Returns the metadata of the specified assets in a [`AssetsMetadataResponse`] object.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is the object of type [`Addr`].
+///
+/// * **assets** is the list of assets of type [`AssetInfo`].
+pub fn query_assets_metadata(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+    assets: Vec<AssetInfo>,
+) -> StdResult<AssetsMetadataResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: factory_contract.to_string(),
+        msg: to_binary(&FactoryQueryMsg::AssetsMetadata { assets })?,
+    }))
+}
+
 /// ## Description
 /// Returns information about the simulation of the swap in a [`SimulationResponse`] object.
 /// ## Params
@@ -242,6 +342,27 @@ pub fn simulate(
     }))
 }
 
+/// ## Description
+/// Returns information about the simulations of several swaps in a [`SimulateManyResponse`] object.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **pair_contract** is the object of type [`Addr`].
+///
+/// * **offers** is the list of offer assets of type [`Asset`].
+pub fn simulate_many(
+    querier: &QuerierWrapper,
+    pair_contract: &Addr,
+    offers: &[Asset],
+) -> StdResult<SimulateManyResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: pair_contract.to_string(),
+        msg: to_binary(&PairQueryMsg::SimulateMany {
+            offers: offers.to_vec(),
+        })?,
+    }))
+}
+
 /// ## Description
 /// Returns information about the reverse simulation in a [`ReverseSimulationResponse`] object.
 /// ## Params
@@ -262,3 +383,33 @@ pub fn reverse_simulate(
         })?,
     }))
 }
+
+/// ## Description
+/// Returns the parameters of the tokenfactory module of the chain with a Stargate query.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+#[cfg(feature = "stargate")]
+pub fn query_tokenfactory_params(
+    querier: &QuerierWrapper,
+) -> StdResult<crate::factory::TokenFactoryParams> {
+    use cosmwasm_std::{to_vec, ContractResult, Empty, StdError, SystemResult};
+
+    // the response is protobuf encoded, so it is decoded manually instead of with `query`
+    let request: QueryRequest<Empty> = QueryRequest::Stargate {
+        path: crate::chain::TOKENFACTORY_PARAMS_PATH.to_string(),
+        data: Default::default(),
+    };
+    match querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Ok(ContractResult::Ok(data)) => {
+            crate::factory::TokenFactoryParams::decode(data.as_slice())
+        }
+        SystemResult::Ok(ContractResult::Err(err)) => Err(StdError::generic_err(format!(
+            "Tokenfactory params query failed: {}",
+            err
+        ))),
+        SystemResult::Err(err) => Err(StdError::generic_err(format!(
+            "Tokenfactory params query failed: {}",
+            err
+        ))),
+    }
+}
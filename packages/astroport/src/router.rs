@@ -1,19 +1,127 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{to_vec, Addr, Binary, Decimal, StdError, StdResult, Storage, Uint128};
 use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Map;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
 
-use crate::asset::AssetInfo;
+use crate::asset::{Asset, AssetInfo};
+use crate::factory::PairType;
 
+/// the default maximum number of swap operations in a route
 pub const MAX_SWAP_OPERATIONS: usize = 50;
 
+/// the maximum number of parallel routes in a split swap
+pub const MAX_SPLIT_ROUTES: usize = 5;
+
+/// the maximum number of input assets in a basket swap
+pub const MAX_BASKET_INPUTS: usize = 10;
+
+/// the maximum number of recipients of the output of a swap
+pub const MAX_OUTPUT_RECIPIENTS: usize = 10;
+/// The number of latest route executions kept by the router, older records are overwritten
+pub const MAX_RECENT_EXECUTIONS: u64 = 100;
+/// The maximum number of execution records returned by a single query
+pub const MAX_RECENT_EXECUTIONS_LIMIT: u32 = 30;
+/// The number of blocks during which a client id can't be reused by the same caller
+pub const CLIENT_ID_WINDOW_BLOCKS: u64 = 100;
+/// The maximum length of a client id
+pub const MAX_CLIENT_ID_LENGTH: usize = 64;
+
+/// the complexity of a native swap performed by the Terra market module
+pub const NATIVE_SWAP_COMPLEXITY: u64 = 1;
+/// the complexity of a swap performed by an Astroport pair
+pub const ASTRO_SWAP_COMPLEXITY: u64 = 2;
+/// the complexity of wrapping or unwrapping a native token via a registered wrapper contract
+pub const WRAP_COMPLEXITY: u64 = 1;
+
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     /// the astroport factory contract address
     pub astroport_factory: String,
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the maximum number of swap operations in a route, [`MAX_SWAP_OPERATIONS`] by default
+    pub max_swap_operations: Option<u64>,
+    /// the maximum total complexity of a route, unlimited by default
+    pub max_complexity: Option<u64>,
+    /// the pre-trade hook queried with [`TradeHookQueryMsg::CheckTrade`] before every swap,
+    /// disabled if not set
+    #[serde(default)]
+    pub trade_hook: Option<String>,
+    /// the fee discounts of the holders of the staking token, disabled if not set
+    #[serde(default)]
+    pub fee_discount: Option<FeeDiscountConfig>,
+}
+
+/// The maximum number of tiers of a [`FeeDiscountConfig`]
+pub const MAX_FEE_DISCOUNT_TIERS: usize = 5;
+
+/// ## Description
+/// This structure describes a tier of the fee discounts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscountTier {
+    /// the minimum balance of the staking token of the trader
+    pub min_balance: Uint128,
+    /// the discount of the total fee in bps
+    pub discount_bps: u16,
+}
+
+/// ## Description
+/// This structure describes the fee discounts the router passes to pairs for holders of the
+/// staking token, e.g. xASTRO or vxASTRO.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDiscountConfig {
+    /// the CW20 token whose balance of the trader is queried
+    pub token: String,
+    /// the tiers in ascending order of the minimum balance
+    pub tiers: Vec<FeeDiscountTier>,
+}
+
+impl FeeDiscountConfig {
+    /// ## Description
+    /// Returns an [`Err`] if there are more than [`MAX_FEE_DISCOUNT_TIERS`] tiers, the tiers are
+    /// not strictly ascending in both the balance and the discount, or a discount exceeds 10000 bps.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if self.tiers.is_empty() || self.tiers.len() > MAX_FEE_DISCOUNT_TIERS {
+            return Err(StdError::generic_err(format!(
+                "The number of fee discount tiers must be between 1 and {}",
+                MAX_FEE_DISCOUNT_TIERS
+            )));
+        }
+        if self.tiers.iter().any(|tier| tier.discount_bps > 10_000) {
+            return Err(StdError::generic_err("Fee discount can't exceed 10000 bps"));
+        }
+        if self.tiers.windows(2).any(|pair| {
+            pair[0].min_balance >= pair[1].min_balance
+                || pair[0].discount_bps >= pair[1].discount_bps
+        }) {
+            return Err(StdError::generic_err(
+                "Fee discount tiers must be in ascending order",
+            ));
+        }
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the fee discount of a trader in bps, zero if the balance is below all tiers.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **balance** is the balance of the staking token of the trader.
+    pub fn discount_bps(&self, balance: Uint128) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| balance >= tier.min_balance)
+            .map_or(0, |tier| tier.discount_bps)
+    }
 }
 
 /// ## Description
@@ -35,6 +143,22 @@ pub enum SwapOperation {
         /// the asks asset info
         ask_asset_info: AssetInfo,
     },
+    /// Wraps a native token into its CW20 representation. The pair of the denom and the CW20
+    /// token must be registered in the router wrappers.
+    Wrap {
+        /// the native denom to wrap
+        denom: String,
+        /// the CW20 wrapper token
+        cw20: Addr,
+    },
+    /// Unwraps a CW20 representation into its native token. The pair of the denom and the CW20
+    /// token must be registered in the router wrappers.
+    Unwrap {
+        /// the CW20 wrapper token
+        cw20: Addr,
+        /// the native denom to receive
+        denom: String,
+    },
 }
 
 impl SwapOperation {
@@ -44,10 +168,380 @@ impl SwapOperation {
                 denom: ask_denom.clone(),
             },
             SwapOperation::AstroSwap { ask_asset_info, .. } => ask_asset_info.clone(),
+            SwapOperation::Wrap { cw20, .. } => AssetInfo::Token {
+                contract_addr: cw20.clone(),
+            },
+            SwapOperation::Unwrap { denom, .. } => AssetInfo::NativeToken {
+                denom: denom.clone(),
+            },
+        }
+    }
+
+    pub fn get_offer_asset_info(&self) -> AssetInfo {
+        match self {
+            SwapOperation::NativeSwap { offer_denom, .. } => AssetInfo::NativeToken {
+                denom: offer_denom.clone(),
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info, ..
+            } => offer_asset_info.clone(),
+            SwapOperation::Wrap { denom, .. } => AssetInfo::NativeToken {
+                denom: denom.clone(),
+            },
+            SwapOperation::Unwrap { cw20, .. } => AssetInfo::Token {
+                contract_addr: cw20.clone(),
+            },
+        }
+    }
+
+    /// ## Description
+    /// Returns the deterministic complexity of the operation which is used to bound the gas of a route.
+    pub fn complexity(&self) -> u64 {
+        match self {
+            SwapOperation::NativeSwap { .. } => NATIVE_SWAP_COMPLEXITY,
+            SwapOperation::AstroSwap { .. } => ASTRO_SWAP_COMPLEXITY,
+            SwapOperation::Wrap { .. } | SwapOperation::Unwrap { .. } => WRAP_COMPLEXITY,
         }
     }
 }
 
+/// ## Description
+/// Validates a route of swap operations. Returns an [`Err`] naming the offending hop index if the
+/// route is empty, too long, not continuous or exceeds the complexity budget.
+/// ## Params
+/// * **operations** is the list of swap operations of type [`SwapOperation`].
+///
+/// * **max_swap_operations** is the maximum number of operations in the route.
+///
+/// * **max_complexity** is an [`Option`] field with the maximum total complexity of the route.
+pub fn validate_swap_operations(
+    operations: &[SwapOperation],
+    max_swap_operations: u64,
+    max_complexity: Option<u64>,
+) -> StdResult<()> {
+    if operations.is_empty() {
+        return Err(StdError::generic_err("Must provide swap operations"));
+    }
+
+    if operations.len() as u64 > max_swap_operations {
+        return Err(StdError::generic_err(format!(
+            "Hop {}: the route exceeds the maximum of {} swap operations",
+            max_swap_operations, max_swap_operations
+        )));
+    }
+
+    let mut complexity = 0u64;
+    for (index, operation) in operations.iter().enumerate() {
+        if index > 0
+            && !operations[index - 1]
+                .get_target_asset_info()
+                .equal(&operation.get_offer_asset_info())
+        {
+            return Err(StdError::generic_err(format!(
+                "Hop {}: the offer asset does not match the ask asset of the previous operation",
+                index
+            )));
+        }
+
+        complexity += operation.complexity();
+        if let Some(max_complexity) = max_complexity {
+            if complexity > max_complexity {
+                return Err(StdError::generic_err(format!(
+                    "Hop {}: the route exceeds the complexity budget of {}",
+                    index, max_complexity
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Validates a canonical route. Returns an [`Err`] if the route does not start with the offer asset
+/// and end with the ask asset, or if it is invalid according to [`validate_swap_operations`].
+/// ## Params
+/// * **offer_asset_info** is the object of type [`AssetInfo`] the route starts with.
+///
+/// * **ask_asset_info** is the object of type [`AssetInfo`] the route ends with.
+///
+/// * **operations** is the list of swap operations of type [`SwapOperation`].
+///
+/// * **max_swap_operations** is the maximum number of operations in the route.
+///
+/// * **max_complexity** is an [`Option`] field with the maximum total complexity of the route.
+pub fn validate_route(
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: &AssetInfo,
+    operations: &[SwapOperation],
+    max_swap_operations: u64,
+    max_complexity: Option<u64>,
+) -> StdResult<()> {
+    validate_swap_operations(operations, max_swap_operations, max_complexity)?;
+
+    if !operations[0].get_offer_asset_info().equal(offer_asset_info) {
+        return Err(StdError::generic_err(
+            "Hop 0: the route must start with the offer asset",
+        ));
+    }
+    if !operations[operations.len() - 1]
+        .get_target_asset_info()
+        .equal(ask_asset_info)
+    {
+        return Err(StdError::generic_err(format!(
+            "Hop {}: the route must end with the ask asset",
+            operations.len() - 1
+        )));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// This structure describes one of the parallel routes of a split swap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SplitRoute {
+    /// the share of the offer amount swapped through the route
+    pub weight: Decimal,
+    /// the operations of the route
+    pub operations: Vec<SwapOperation>,
+}
+
+/// ## Description
+/// Validates the parallel routes of a split swap. Returns an [`Err`] naming the offending route
+/// index if the weights are not positive or don't sum to 1, if a route is invalid according to
+/// [`validate_swap_operations`] or if the routes don't share the same offer and ask assets.
+/// The complexity budget applies to all routes together.
+/// ## Params
+/// * **routes** is the list of parallel routes of type [`SplitRoute`].
+///
+/// * **max_swap_operations** is the maximum number of operations in a single route.
+///
+/// * **max_complexity** is an [`Option`] field with the maximum total complexity of the routes.
+pub fn validate_split_routes(
+    routes: &[SplitRoute],
+    max_swap_operations: u64,
+    max_complexity: Option<u64>,
+) -> StdResult<()> {
+    if routes.is_empty() {
+        return Err(StdError::generic_err("Must provide split routes"));
+    }
+    if routes.len() > MAX_SPLIT_ROUTES {
+        return Err(StdError::generic_err(format!(
+            "At most {} split routes are allowed",
+            MAX_SPLIT_ROUTES
+        )));
+    }
+
+    let mut total_weight = Decimal::zero();
+    let mut complexity = 0u64;
+    for (index, route) in routes.iter().enumerate() {
+        if route.weight.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "Route {}: the weight must be positive",
+                index
+            )));
+        }
+        total_weight = total_weight + route.weight;
+
+        validate_swap_operations(&route.operations, max_swap_operations, None)
+            .map_err(|e| prefix_route_error(index, e))?;
+        if index > 0 {
+            validate_route(
+                &routes[0].operations[0].get_offer_asset_info(),
+                &routes[0].operations[routes[0].operations.len() - 1].get_target_asset_info(),
+                &route.operations,
+                max_swap_operations,
+                None,
+            )
+            .map_err(|e| prefix_route_error(index, e))?;
+        }
+
+        complexity += route
+            .operations
+            .iter()
+            .map(|op| op.complexity())
+            .sum::<u64>();
+        if let Some(max_complexity) = max_complexity {
+            if complexity > max_complexity {
+                return Err(StdError::generic_err(format!(
+                    "Route {}: the routes exceed the complexity budget of {}",
+                    index, max_complexity
+                )));
+            }
+        }
+    }
+
+    if total_weight != Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "Split route weights must sum to 1, got {}",
+            total_weight
+        )));
+    }
+
+    Ok(())
+}
+
+fn prefix_route_error(index: usize, err: StdError) -> StdError {
+    match err {
+        StdError::GenericErr { msg, .. } => {
+            StdError::generic_err(format!("Route {}: {}", index, msg))
+        }
+        err => err,
+    }
+}
+
+/// ## Description
+/// Splits the offer amount among the routes by their weights. The rounding remainder goes to the
+/// last route, so the parts always sum to the offer amount.
+/// ## Params
+/// * **offer_amount** is the total offer amount of the swap.
+///
+/// * **routes** is the list of validated parallel routes of type [`SplitRoute`].
+pub fn split_offer_amount(offer_amount: Uint128, routes: &[SplitRoute]) -> Vec<Uint128> {
+    let mut remaining = offer_amount;
+    routes
+        .iter()
+        .enumerate()
+        .map(|(i, route)| {
+            let part = if i + 1 == routes.len() {
+                remaining
+            } else {
+                std::cmp::min(offer_amount * route.weight, remaining)
+            };
+            remaining -= part;
+            part
+        })
+        .collect()
+}
+
+/// ## Description
+/// Returns an [`Err`] if the output recipients are set together with `to`, there are more than
+/// [`MAX_OUTPUT_RECIPIENTS`] of them, a recipient is duplicated or has a zero weight, or the
+/// weights don't sum to 1. No recipients means the output goes to `to` or the sender.
+/// ## Params
+/// * **to** is an [`Option`] field with the single recipient of the output.
+///
+/// * **recipients** are the recipients of the output and their weights.
+pub fn validate_output_recipients(
+    to: Option<&str>,
+    recipients: &[(String, Decimal)],
+) -> StdResult<()> {
+    if recipients.is_empty() {
+        return Ok(());
+    }
+    if to.is_some() {
+        return Err(StdError::generic_err(
+            "Either to or recipients can be set, but not both",
+        ));
+    }
+    if recipients.len() > MAX_OUTPUT_RECIPIENTS {
+        return Err(StdError::generic_err(format!(
+            "At most {} output recipients are allowed",
+            MAX_OUTPUT_RECIPIENTS
+        )));
+    }
+
+    let mut total_weight = Decimal::zero();
+    for (i, (recipient, weight)) in recipients.iter().enumerate() {
+        if weight.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "The weight of {} must be positive",
+                recipient
+            )));
+        }
+        if recipients[..i].iter().any(|(other, _)| other == recipient) {
+            return Err(StdError::generic_err(format!(
+                "Duplicated output recipient {}",
+                recipient
+            )));
+        }
+        total_weight = total_weight + *weight;
+    }
+
+    if total_weight != Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "Output recipient weights must sum to 1, got {}",
+            total_weight
+        )));
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Splits the output of a swap among the recipients by their weights. The rounding remainder goes
+/// to the last recipient, so the parts always sum to the output.
+/// ## Params
+/// * **output** is the asset returned by the last swap operation.
+///
+/// * **recipients** are the validated recipients of the output and their weights.
+pub fn split_output(output: &Asset, recipients: &[(Addr, Decimal)]) -> Vec<(Addr, Asset)> {
+    let mut remaining = output.amount;
+    recipients
+        .iter()
+        .enumerate()
+        .map(|(i, (recipient, weight))| {
+            let amount = if i + 1 == recipients.len() {
+                remaining
+            } else {
+                std::cmp::min(output.amount * *weight, remaining)
+            };
+            remaining -= amount;
+            (
+                recipient.clone(),
+                Asset {
+                    info: output.info.clone(),
+                    amount,
+                },
+            )
+        })
+        .collect()
+}
+
+/// ## Description
+/// Returns an [`Err`] if a basket swap has no inputs or more than [`MAX_BASKET_INPUTS`], an input
+/// is duplicated, has a zero amount or is the ask asset itself.
+/// ## Params
+/// * **inputs** are the input assets of the basket swap.
+///
+/// * **ask_asset_info** is the asset all inputs are swapped to.
+pub fn validate_basket(inputs: &[Asset], ask_asset_info: &AssetInfo) -> StdResult<()> {
+    if inputs.is_empty() {
+        return Err(StdError::generic_err("The basket has no inputs"));
+    }
+    if inputs.len() > MAX_BASKET_INPUTS {
+        return Err(StdError::generic_err(format!(
+            "The basket can't have more than {} inputs",
+            MAX_BASKET_INPUTS
+        )));
+    }
+    for (i, input) in inputs.iter().enumerate() {
+        if input.amount.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "Basket input {} has a zero amount",
+                input.info
+            )));
+        }
+        if input.info.equal(ask_asset_info) {
+            return Err(StdError::generic_err(format!(
+                "Basket input {} is the ask asset",
+                input.info
+            )));
+        }
+        if inputs[..i]
+            .iter()
+            .any(|other| other.info.equal(&input.info))
+        {
+            return Err(StdError::generic_err(format!(
+                "Basket input {} is duplicated",
+                input.info
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// ## Description
 /// This structure describes the execute messages of the contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -61,7 +555,139 @@ pub enum ExecuteMsg {
         operations: Vec<SwapOperation>,
         minimum_receive: Option<Uint128>,
         to: Option<Addr>,
+        /// splits the final output among the recipients by their weights instead of sending it
+        /// to `to`, see [`validate_output_recipients`]
+        #[serde(default)]
+        recipients: Vec<(String, Decimal)>,
+        /// if a hop fails, deliver the output of the previous hops to the recipient instead of
+        /// reverting. The [`SwapResponseData`] contains a [`SwapReceipt`] describing where the
+        /// route stopped and the minimum receive is not checked
+        #[serde(default)]
+        allow_partial: bool,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+        /// an idempotency key of the submission. A retry with the same key by the same caller
+        /// within [`CLIENT_ID_WINDOW_BLOCKS`] is rejected, see [`register_client_id`]
+        #[serde(default)]
+        client_id: Option<String>,
+    },
+    /// Splits the offer asset among parallel routes by their weights and executes all of them
+    /// atomically. The minimum receive applies to the total amount returned by the routes
+    SwapOperationsSplit {
+        routes: Vec<SplitRoute>,
+        minimum_receive: Option<Uint128>,
+        to: Option<Addr>,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+    },
+
+    /// Swaps several input assets to a single ask asset in one transaction, each input via its
+    /// canonical route. Native inputs are attached as funds, CW20 inputs are transferred from the
+    /// sender using its allowances. The minimum receive applies to the consolidated output
+    ExecuteSwapBasket {
+        /// the input assets, validated with [`validate_basket`]
+        inputs: Vec<Asset>,
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+        /// the minimum total receive for swap
+        minimum_receive: Option<Uint128>,
+        /// the recipient
+        to: Option<String>,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
     },
+    /// Swaps the offer asset via the canonical route registered for the pair of assets
+    SwapCanonical {
+        /// the offer asset
+        offer_asset: Asset,
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+        /// the minimum receive for swap
+        minimum_receive: Option<Uint128>,
+        /// the recipient
+        to: Option<String>,
+    },
+    /// ## Description
+    /// Registers or replaces the canonical route between two assets
+    /// ## Executor
+    /// Only owner can execute it
+    SetRoute {
+        /// the offer asset info
+        offer_asset_info: AssetInfo,
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+        /// the operations of the route
+        operations: Vec<SwapOperation>,
+    },
+    /// ## Description
+    /// Removes the canonical route between two assets
+    /// ## Executor
+    /// Only owner can execute it
+    RemoveRoute {
+        /// the offer asset info
+        offer_asset_info: AssetInfo,
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+    },
+    /// ## Description
+    /// Registers or replaces the CW20 wrapper of a native denom used by the `Wrap` and `Unwrap`
+    /// operations
+    /// ## Executor
+    /// Only owner can execute it
+    SetWrapper {
+        /// the native denom
+        denom: String,
+        /// the CW20 wrapper token
+        cw20: String,
+    },
+    /// ## Description
+    /// Removes the CW20 wrapper of a native denom
+    /// ## Executor
+    /// Only owner can execute it
+    RemoveWrapper {
+        /// the native denom
+        denom: String,
+    },
+    /// ## Description
+    /// Sets or removes the pre-trade hook
+    /// ## Executor
+    /// Only owner can execute it
+    SetTradeHook {
+        /// the hook contract, [`None`] disables the hook
+        hook: Option<String>,
+    },
+    /// ## Description
+    /// Sets or removes the fee discounts of the holders of the staking token. The discount of the
+    /// trader is passed to the pairs, which cap it by the maximum discount of the factory
+    /// ## Executor
+    /// Only owner can execute it
+    SetFeeDiscount {
+        /// the discount tiers, [`None`] disables the discounts
+        fee_discount: Option<FeeDiscountConfig>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
 
     /// Internal use
     /// Swap all offer tokens to ask token
@@ -70,15 +696,26 @@ pub enum ExecuteMsg {
         to: Option<String>,
     },
     /// Internal use
-    /// Check the swap amount is exceed minimum_receive
+    /// Check the swap amount is exceed minimum_receive and set the [`SwapResponseData`] of the
+    /// execution
     AssertMinimumReceive {
         asset_info: AssetInfo,
         prev_balance: Uint128,
         minimum_receive: Uint128,
         receiver: String,
+        /// the executed routes reported in the response data
+        #[serde(default)]
+        routes: Vec<Vec<SwapOperation>>,
+        /// the assets returned to the sender reported in the response data
+        #[serde(default)]
+        refunds: Vec<Asset>,
     },
 }
 
+/// ## Description
+/// This structure describes a CW20 hook message. It can be wrapped in a versioned
+/// [`crate::hook::HookEnvelope`] and is parsed with [`crate::hook::parse_hook_msg`]. Unknown
+/// messages are refunded to the sender and counted in [`QueryMsg::RejectedHooks`]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
@@ -89,6 +726,38 @@ pub enum Cw20HookMsg {
         minimum_receive: Option<Uint128>,
         /// the recipient
         to: Option<String>,
+        /// the recipients of the final output and their weights, an alternative to `to`
+        #[serde(default)]
+        recipients: Vec<(String, Decimal)>,
+        /// whether the output of the previous hops is delivered if a hop fails
+        #[serde(default)]
+        allow_partial: bool,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+
+        /// the idempotency key of the submission
+        #[serde(default)]
+        client_id: Option<String>,
+    },
+    SwapOperationsSplit {
+        /// the parallel routes of the swap
+        routes: Vec<SplitRoute>,
+        /// the minimum total receive for swap
+        minimum_receive: Option<Uint128>,
+        /// the recipient
+        to: Option<String>,
+        /// the block time after which the execution is rejected, see [`crate::pair::assert_deadline`]
+        #[serde(default)]
+        deadline: Option<u64>,
+    },
+    SwapCanonical {
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+        /// the minimum receive for swap
+        minimum_receive: Option<Uint128>,
+        /// the recipient
+        to: Option<String>,
     },
 }
 
@@ -105,7 +774,311 @@ pub enum QueryMsg {
         offer_amount: Uint128,
         /// operations for swap
         operations: Vec<SwapOperation>,
+        /// whether the messages emitted by the route are estimated, see [`RouteMessages`]
+        #[serde(default)]
+        include_messages: bool,
+    },
+    /// Simulates multi-hop swap operations and returns the result of every hop together with
+    /// the reserves of the traversed pools after the swap in a
+    /// [`SimulateSwapOperationsDetailedResponse`] object
+    SimulateSwapOperationsDetailed {
+        /// the offer amount
+        offer_amount: Uint128,
+        /// operations for swap
+        operations: Vec<SwapOperation>,
+        /// whether the messages emitted by the route are estimated, see [`RouteMessages`]
+        #[serde(default)]
+        include_messages: bool,
+    },
+    /// Simulates a split swap in a [`SimulateSwapOperationsSplitResponse`] object. The routes
+    /// are simulated in order, so a pool traversed by several routes is priced with the reserves
+    /// left by the earlier routes, as the swap is executed
+    SimulateSwapOperationsSplit {
+        /// the total offer amount
+        offer_amount: Uint128,
+        /// the parallel routes of the swap
+        routes: Vec<SplitRoute>,
+    },
+    /// Simulates a basket swap via the canonical routes in a [`SimulateSwapBasketResponse`] object
+    SimulateSwapBasket {
+        /// the input assets
+        inputs: Vec<Asset>,
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+    },
+    /// Returns the canonical route between two assets in a [`RouteResponse`] object
+    Route {
+        /// the offer asset info
+        offer_asset_info: AssetInfo,
+        /// the ask asset info
+        ask_asset_info: AssetInfo,
+    },
+    /// Returns a list of canonical routes in a [`RoutesResponse`] object
+    Routes {
+        /// the offer and ask asset infos of the route to start reading after
+        start_after: Option<[AssetInfo; 2]>,
+        /// the number of items to be read
+        limit: Option<u32>,
     },
+    /// Returns the registered wrappers in a [`WrappersResponse`] object
+    Wrappers {},
+    /// Returns the latest route executions in ascending order in a [`RecentExecutionsResponse`]
+    /// object, at most [`MAX_RECENT_EXECUTIONS`] are kept
+    RecentExecutions {
+        /// the identifier of the execution to start reading after
+        start_after: Option<u64>,
+        /// the number of items to be read, at most [`MAX_RECENT_EXECUTIONS_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// Returns the number of rejected CW20 hook messages and the latest rejection in a
+    /// [`crate::hook::RejectedHooksResponse`] object
+    RejectedHooks {},
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateSwapBasketResponse {
+    /// the total amount of the ask asset
+    pub amount: Uint128,
+    /// the simulation of every input in the order of the inputs
+    pub inputs: Vec<SimulatedBasketInput>,
+}
+
+/// ## Description
+/// This structure describes the simulation of a single input of a basket swap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatedBasketInput {
+    /// the input asset
+    pub offer: Asset,
+    /// the canonical route of the input
+    pub operations: Vec<SwapOperation>,
+    /// the amount of the ask asset returned for the input
+    pub return_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the response data of every swap execution of the router, so calling
+/// contracts can parse the result in a reply handler instead of scanning attributes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapResponseData {
+    /// the asset received by the recipient
+    pub return_asset: Asset,
+    /// the executed routes, one per parallel route of a split swap or input of a basket swap
+    pub routes: Vec<Vec<SwapOperation>>,
+    /// the assets returned to the sender, e.g. the unused part of an offer
+    pub refunds: Vec<Asset>,
+    /// where the route stopped if it was executed with `allow_partial`
+    #[serde(default)]
+    pub receipt: Option<SwapReceipt>,
+}
+
+impl SwapResponseData {
+    /// ## Description
+    /// Returns the response data of a swap from the balances of the recipient before and after
+    /// the execution.
+    /// ## Params
+    /// * **asset_info** is the ask asset of the swap.
+    ///
+    /// * **prev_balance** is the balance of the recipient before the execution.
+    ///
+    /// * **balance** is the balance of the recipient after the execution.
+    ///
+    /// * **routes** are the executed routes.
+    ///
+    /// * **refunds** are the assets returned to the sender.
+    pub fn from_balances(
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        balance: Uint128,
+        routes: Vec<Vec<SwapOperation>>,
+        refunds: Vec<Asset>,
+    ) -> StdResult<Self> {
+        Ok(SwapResponseData {
+            return_asset: Asset {
+                info: asset_info,
+                amount: balance.checked_sub(prev_balance)?,
+            },
+            routes,
+            refunds,
+            receipt: None,
+        })
+    }
+}
+
+/// ## Description
+/// This structure describes where a route executed with `allow_partial` stopped, see
+/// [`ExecuteMsg::ExecuteSwapOperations`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapReceipt {
+    /// the number of hops which were executed
+    pub completed_hops: u64,
+    /// the index of the hop which failed, [`None`] if the whole route was executed
+    pub failed_hop: Option<u64>,
+    /// the error of the failed hop
+    pub error: Option<String>,
+    /// the asset delivered to the recipient
+    pub delivered: Asset,
+}
+
+impl SwapReceipt {
+    /// ## Description
+    /// Returns the receipt of a route which stopped at the failed hop. The recipient receives the
+    /// output of the hop before it, or the offer asset if the first hop failed.
+    /// ## Params
+    /// * **operations** is the list of swap operations of type [`SwapOperation`].
+    ///
+    /// * **failed_hop** is the index of the failed operation.
+    ///
+    /// * **amount** is the amount delivered to the recipient.
+    ///
+    /// * **error** is the error of the failed operation.
+    pub fn partial(
+        operations: &[SwapOperation],
+        failed_hop: usize,
+        amount: Uint128,
+        error: String,
+    ) -> StdResult<Self> {
+        let operation = operations.get(failed_hop).ok_or_else(|| {
+            StdError::generic_err(format!("Hop {}: the route has no such hop", failed_hop))
+        })?;
+        Ok(SwapReceipt {
+            completed_hops: failed_hop as u64,
+            failed_hop: Some(failed_hop as u64),
+            error: Some(error),
+            delivered: Asset {
+                info: operation.get_offer_asset_info(),
+                amount,
+            },
+        })
+    }
+}
+
+/// ## Description
+/// This structure describes the record of a route execution.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionRecord {
+    /// the sequential identifier of the execution
+    pub id: u64,
+    /// the sender of the swap
+    pub caller: Addr,
+    /// the hash of the executed routes, see [`route_hash`]
+    pub route_hash: Binary,
+    /// the asset offered by the caller
+    pub offer_asset: Asset,
+    /// the asset received by the recipient
+    pub return_asset: Asset,
+    /// the timestamp of the execution
+    pub timestamp: u64,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecentExecutionsResponse {
+    pub executions: Vec<ExecutionRecord>,
+}
+
+/// ## Description
+/// Returns the SHA-256 hash of the JSON serialized routes, so an execution record can be matched
+/// against the routes an integrator submitted.
+/// ## Params
+/// * **routes** are the executed routes, one per parallel route of a split swap.
+pub fn route_hash(routes: &[Vec<SwapOperation>]) -> StdResult<Binary> {
+    Ok(Binary::from(Sha256::digest(&to_vec(routes)?).as_slice()))
+}
+
+/// ## Description
+/// Returns the slot of the ring buffer in which the execution is stored.
+/// ## Params
+/// * **id** is the identifier of the execution.
+pub fn execution_slot(id: u64) -> u64 {
+    id % MAX_RECENT_EXECUTIONS
+}
+
+/// ## Description
+/// Returns the identifiers of the executions to read for a [`QueryMsg::RecentExecutions`] query,
+/// skipping the ones which were already overwritten.
+/// ## Params
+/// * **next_id** is the identifier of the next execution.
+///
+/// * **start_after** is the identifier of the execution to start reading after.
+///
+/// * **limit** is the number of items to be read.
+pub fn recent_execution_ids(
+    next_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> std::ops::Range<u64> {
+    let limit = limit
+        .unwrap_or(MAX_RECENT_EXECUTIONS_LIMIT)
+        .min(MAX_RECENT_EXECUTIONS_LIMIT) as u64;
+    let oldest = next_id.saturating_sub(MAX_RECENT_EXECUTIONS);
+    let start = start_after.map_or(oldest, |id| id.saturating_add(1).max(oldest));
+    start.min(next_id)..start.saturating_add(limit).min(next_id)
+}
+
+/// ## Description
+/// This enum describes the errors of the router. Wallets and bots can match on
+/// [`RouterError::DuplicateClientId`] to treat a retried submission as already executed.
+#[derive(Error, Debug, PartialEq)]
+pub enum RouterError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Swap with client id {client_id} was already submitted at height {height}")]
+    DuplicateClientId { client_id: String, height: u64 },
+}
+
+impl From<RouterError> for StdError {
+    fn from(err: RouterError) -> Self {
+        match err {
+            RouterError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+/// ## Description
+/// Registers the client id of a swap submission. Returns a [`RouterError::DuplicateClientId`] if
+/// the same caller used the id within [`CLIENT_ID_WINDOW_BLOCKS`], so a retried submission is not
+/// executed twice. The ids are keyed by caller, so a caller can't evict the ids of others, and an
+/// expired id is overwritten when it is used again.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **client_ids** is the map of the block heights the ids were used at by caller and id.
+///
+/// * **caller** is the sender of the swap.
+///
+/// * **client_id** is the idempotency key of the submission.
+///
+/// * **height** is the current block height.
+pub fn register_client_id<'a>(
+    storage: &mut dyn Storage,
+    client_ids: Map<'a, (&'a Addr, &'a str), u64>,
+    caller: &'a Addr,
+    client_id: &'a str,
+    height: u64,
+) -> Result<(), RouterError> {
+    if client_id.is_empty() || client_id.len() > MAX_CLIENT_ID_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "Client id must be between 1 and {} characters",
+            MAX_CLIENT_ID_LENGTH
+        ))
+        .into());
+    }
+    let key = (caller, client_id);
+    if let Some(used_at) = client_ids.may_load(storage, key)? {
+        if used_at.saturating_add(CLIENT_ID_WINDOW_BLOCKS) > height {
+            return Err(RouterError::DuplicateClientId {
+                client_id: client_id.to_string(),
+                height: used_at,
+            });
+        }
+    }
+    client_ids.save(storage, key, &height)?;
+    Ok(())
 }
 
 /// ## Description
@@ -114,6 +1087,60 @@ pub enum QueryMsg {
 pub struct ConfigResponse {
     /// the astroport factory contract address
     pub astroport_factory: String,
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the maximum number of swap operations in a route
+    pub max_swap_operations: u64,
+    /// the maximum total complexity of a route
+    pub max_complexity: Option<u64>,
+    /// the pre-trade hook queried before every swap
+    #[serde(default)]
+    pub trade_hook: Option<Addr>,
+    /// the fee discounts of the holders of the staking token
+    #[serde(default)]
+    pub fee_discount: Option<FeeDiscountConfig>,
+}
+
+/// ## Description
+/// This structure describes the query messages a pre-trade hook has to support.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeHookQueryMsg {
+    /// Returns whether the trade is allowed in a [`CheckTradeResponse`] object
+    CheckTrade {
+        /// the sender of the swap
+        sender: String,
+        /// the operations of the swap
+        operations: Vec<SwapOperation>,
+        /// the offered amount
+        amount: Uint128,
+    },
+}
+
+/// ## Description
+/// This structure describes the response to the check trade query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CheckTradeResponse {
+    /// whether the trade is allowed
+    pub allowed: bool,
+    /// the reason of the rejection
+    pub reason: Option<String>,
+}
+
+impl CheckTradeResponse {
+    /// ## Description
+    /// Returns an [`Err`] with the reason if the trade is not allowed.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn assert_allowed(&self) -> StdResult<()> {
+        if self.allowed {
+            return Ok(());
+        }
+        Err(StdError::generic_err(format!(
+            "Trade rejected by the pre-trade hook: {}",
+            self.reason.as_deref().unwrap_or("no reason given")
+        )))
+    }
 }
 
 /// ## Description
@@ -122,10 +1149,244 @@ pub struct ConfigResponse {
 pub struct SimulateSwapOperationsResponse {
     /// the amount of swap
     pub amount: Uint128,
+    /// the complexity of the simulated route
+    #[serde(default)]
+    pub complexity: RouteComplexity,
+    /// the messages emitted by the route, only set if requested
+    #[serde(default)]
+    pub messages: Option<RouteMessages>,
+}
+
+/// ## Description
+/// This structure describes the deterministic complexity of a route which clients can use to
+/// estimate the gas limit of the swap.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct RouteComplexity {
+    /// the number of operations in the route
+    pub hops: u64,
+    /// the types of the Astroport pairs traversed by the route in the order of the operations
+    pub pair_types: Vec<PairType>,
+    /// the number of submessages executed by the swap: a callback and the operation itself
+    /// per hop plus the final minimum receive assertion
+    pub submessages: u64,
+    /// the total complexity of the operations as in [`validate_swap_operations`]
+    pub complexity: u64,
+}
+
+impl RouteComplexity {
+    /// ## Description
+    /// Returns the complexity of a route.
+    /// ## Params
+    /// * **operations** is the list of swap operations of type [`SwapOperation`].
+    ///
+    /// * **pair_types** are the types of the pairs of the [`SwapOperation::AstroSwap`] operations.
+    pub fn new(operations: &[SwapOperation], pair_types: Vec<PairType>) -> Self {
+        let hops = operations.len() as u64;
+        RouteComplexity {
+            hops,
+            pair_types,
+            submessages: hops * 2 + 1,
+            complexity: operations.iter().map(|op| op.complexity()).sum(),
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the messages emitted by the execution of a route by type, so wallets
+/// can estimate the gas of a swap. Messages emitted by the pairs and wrappers are included.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct RouteMessages {
+    /// the wasm executes with native funds or without funds, including the router callbacks
+    pub wasm_executes: u64,
+    /// the CW20 transfers, sends and mints
+    pub cw20_transfers: u64,
+    /// the bank sends of native coins
+    pub bank_sends: u64,
+    /// the native chain swaps of [`SwapOperation::NativeSwap`]
+    pub native_swaps: u64,
+}
+
+impl RouteMessages {
+    /// ## Description
+    /// Returns the messages emitted by a route: a router callback per hop and the final minimum
+    /// receive assertion, the messages of the operations and the transfers of their outputs.
+    /// ## Params
+    /// * **operations** is the list of swap operations of type [`SwapOperation`].
+    pub fn new(operations: &[SwapOperation]) -> Self {
+        let mut messages = RouteMessages {
+            wasm_executes: operations.len() as u64 + 1,
+            ..RouteMessages::default()
+        };
+        for operation in operations {
+            match operation {
+                SwapOperation::NativeSwap { .. } => messages.native_swaps += 1,
+                SwapOperation::AstroSwap {
+                    offer_asset_info,
+                    ask_asset_info,
+                } => {
+                    messages.add_transfer_in(offer_asset_info);
+                    messages.add_transfer_out(ask_asset_info);
+                }
+                SwapOperation::Wrap { .. } => {
+                    messages.wasm_executes += 1;
+                    messages.cw20_transfers += 1;
+                }
+                SwapOperation::Unwrap { .. } => {
+                    messages.cw20_transfers += 1;
+                    messages.bank_sends += 1;
+                }
+            }
+        }
+        messages
+    }
+
+    /// ## Description
+    /// Returns the total number of messages.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn total(&self) -> u64 {
+        self.wasm_executes + self.cw20_transfers + self.bank_sends + self.native_swaps
+    }
+
+    /// ## Description
+    /// Adds the message that sends the offer asset to a pair. Native coins are attached to the
+    /// swap, tokens are sent with the swap hook.
+    fn add_transfer_in(&mut self, asset_info: &AssetInfo) {
+        if asset_info.is_native_token() {
+            self.wasm_executes += 1;
+        } else {
+            self.cw20_transfers += 1;
+        }
+    }
+
+    /// ## Description
+    /// Adds the transfer of the ask asset from a pair.
+    fn add_transfer_out(&mut self, asset_info: &AssetInfo) {
+        if asset_info.is_native_token() {
+            self.bank_sends += 1;
+        } else {
+            self.cw20_transfers += 1;
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateSwapOperationsDetailedResponse {
+    /// the amount of swap
+    pub amount: Uint128,
+    /// the results of the hops in the order of the operations
+    pub hops: Vec<SimulatedHop>,
+    /// the complexity of the simulated route
+    #[serde(default)]
+    pub complexity: RouteComplexity,
+    /// the messages emitted by the route, only set if requested
+    #[serde(default)]
+    pub messages: Option<RouteMessages>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateSwapOperationsSplitResponse {
+    /// the total amount returned by the routes
+    pub amount: Uint128,
+    /// the results of the routes in the order of the request
+    pub routes: Vec<SimulatedSplitRoute>,
+    /// the complexity of all routes together
+    pub complexity: RouteComplexity,
+}
+
+/// ## Description
+/// This structure describes the simulated result of a single route of a split swap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatedSplitRoute {
+    /// the amount offered to the route
+    pub offer_amount: Uint128,
+    /// the amount returned by the route
+    pub return_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the simulated result of a single swap operation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatedHop {
+    /// the simulated operation
+    pub operation: SwapOperation,
+    /// the amount offered to the operation
+    pub offer_amount: Uint128,
+    /// the amount returned by the operation
+    pub return_amount: Uint128,
+    /// the spread amount of the operation
+    pub spread_amount: Uint128,
+    /// the commission amount of the operation
+    pub commission_amount: Uint128,
+    /// the pair contract of the operation, not set for native swaps and wrapping
+    pub pair: Option<Addr>,
+    /// the reserves of the pair after the operation. If a route traverses the same pair twice,
+    /// the later hop is still simulated against the reserves left by the earlier one
+    pub reserves_after: Option<[Asset; 2]>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouteResponse {
+    /// the offer asset info
+    pub offer_asset_info: AssetInfo,
+    /// the ask asset info
+    pub ask_asset_info: AssetInfo,
+    /// the operations of the route
+    pub operations: Vec<SwapOperation>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoutesResponse {
+    pub routes: Vec<RouteResponse>,
+}
+
+/// ## Description
+/// This structure describes a native denom and its CW20 wrapper token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WrapperInfo {
+    /// the native denom
+    pub denom: String,
+    /// the CW20 wrapper token
+    pub cw20: Addr,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WrappersResponse {
+    pub wrappers: Vec<WrapperInfo>,
+}
+
+/// ## Description
+/// This structure describes the execute messages the router sends to a CW20 wrapper token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WrapperExecuteMsg {
+    /// Mints the wrapper tokens for the attached native tokens
+    Wrap { recipient: Option<String> },
+    /// Burns the wrapper tokens of the sender and releases the native tokens
+    Unwrap {
+        amount: Uint128,
+        recipient: Option<String>,
+    },
 }
 
 /// ## Description
 /// This structure describes a migration message.
-/// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// contract address that used for controls settings, required when migrating from a router without an owner
+    pub owner: Option<String>,
+    /// the maximum number of swap operations in a route, [`MAX_SWAP_OPERATIONS`] by default
+    pub max_swap_operations: Option<u64>,
+    /// the maximum total complexity of a route, unlimited by default
+    pub max_complexity: Option<u64>,
+}
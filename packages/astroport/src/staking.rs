@@ -1,8 +1,276 @@
-use cosmwasm_std::Addr;
+use crate::asset::Asset;
+use crate::fee_distributor::get_epoch;
+use cosmwasm_std::{Addr, Decimal, Fraction, StdError, StdResult, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// The minimum amount of ASTRO of the first stake
+pub const MINIMUM_STAKE_AMOUNT: Uint128 = Uint128::new(1_000_000);
+/// The amount of xASTRO minted to the staking contract itself on the first stake. These shares can
+/// never be burned, so the exchange rate can't be manipulated by donating ASTRO to an empty pool.
+/// Pools which already had stakers when the contract was migrated hold no dead shares, so none of
+/// their xASTRO is locked, see [`StakingSnapshot::dead_shares`].
+pub const DEAD_SHARES: Uint128 = Uint128::new(1_000);
+/// The minimum lock duration of [`Cw20HookMsg::Enter`] in seconds
+pub const MIN_LOCK_DURATION: u64 = 7 * 86400;
+/// The maximum lock duration of [`Cw20HookMsg::Enter`] in seconds
+pub const MAX_LOCK_DURATION: u64 = 2 * 365 * 86400;
+/// The boost multiplier of xASTRO locked for [`MAX_LOCK_DURATION`]
+pub const MAX_LOCK_BOOST: &str = "2.5";
+/// The maximum share of compounded rewards paid to the caller of [`ExecuteMsg::Compound`]
+pub const MAX_COMPOUND_INCENTIVE: &str = "0.01";
+/// The type of the event emitted when the exchange rate moved more than the allowed bound
+pub const RATE_ALARM_EVENT: &str = "xastro_rate_alarm";
+
+/// ## Description
+/// Returns the amount of xASTRO minted to the staker and the amount of dead xASTRO minted to the
+/// contract for the staked ASTRO amount.
+/// ## Params
+/// * **amount** is the amount of ASTRO staked.
+///
+/// * **total_deposit** is the ASTRO balance of the contract before the stake, donations included.
+///
+/// * **total_shares** is the total supply of xASTRO before the stake.
+pub fn compute_enter_shares(
+    amount: Uint128,
+    total_deposit: Uint128,
+    total_shares: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    if total_shares.is_zero() {
+        if amount < MINIMUM_STAKE_AMOUNT {
+            return Err(StdError::generic_err(format!(
+                "The first stake must be at least {}",
+                MINIMUM_STAKE_AMOUNT
+            )));
+        }
+        return Ok((amount - DEAD_SHARES, DEAD_SHARES));
+    }
+
+    if total_deposit.is_zero() {
+        return Err(StdError::generic_err("The staking pool has no ASTRO"));
+    }
+    let shares = amount.multiply_ratio(total_shares, total_deposit);
+    if shares.is_zero() {
+        return Err(StdError::generic_err(
+            "The stake is too small to mint any xASTRO",
+        ));
+    }
+    Ok((shares, Uint128::zero()))
+}
+
+/// ## Description
+/// Returns the amount of ASTRO returned for burning the specified amount of xASTRO.
+/// ## Params
+/// * **share** is the amount of xASTRO burned.
+///
+/// * **total_deposit** is the ASTRO balance of the contract.
+///
+/// * **total_shares** is the total supply of xASTRO.
+///
+/// * **dead_shares** is the amount of dead xASTRO held by the contract.
+pub fn compute_leave_amount(
+    share: Uint128,
+    total_deposit: Uint128,
+    total_shares: Uint128,
+    dead_shares: Uint128,
+) -> StdResult<Uint128> {
+    if share > total_shares.saturating_sub(dead_shares) {
+        return Err(StdError::generic_err(
+            "The share exceeds the total share of the pool",
+        ));
+    }
+    Ok(share.multiply_ratio(total_deposit, total_shares))
+}
+
+/// The reply identifier of the messages dispatched by `Enter`, whose reply asserts the
+/// [`StakingSnapshot`] post-conditions
+pub const ENTER_REPLY_ID: u64 = 1;
+/// The reply identifier of the messages dispatched by `Leave`, whose reply asserts the
+/// [`StakingSnapshot`] post-conditions
+pub const LEAVE_REPLY_ID: u64 = 2;
+
+/// ## Description
+/// This structure describes the pool balances of the staking contract. `Enter` and `Leave` compute
+/// their amounts against a snapshot taken before any message is dispatched, store the expected
+/// balances and check them with [`StakingSnapshot::assert_post_conditions`] in a reply once all
+/// messages were executed, so reentrant calls and donations during the dispatch can't skew the
+/// share price.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub struct StakingSnapshot {
+    /// the ASTRO balance of the contract
+    pub total_deposit: Uint128,
+    /// the total supply of xASTRO
+    pub total_shares: Uint128,
+    /// the dead xASTRO minted to the contract by the first stake, zero for pools which already had
+    /// stakers when the contract was migrated
+    #[serde(default)]
+    pub dead_shares: Uint128,
+}
+
+impl StakingSnapshot {
+    /// ## Description
+    /// Returns the snapshot before an `Enter`. The received ASTRO is already part of the balance
+    /// when the CW20 hook is executed, so it is deducted.
+    /// ## Params
+    /// * **balance** is the ASTRO balance of the contract including the received amount.
+    ///
+    /// * **amount** is the amount of ASTRO staked.
+    ///
+    /// * **total_shares** is the total supply of xASTRO.
+    ///
+    /// * **dead_shares** is the amount of dead xASTRO held by the contract.
+    pub fn before_enter(
+        balance: Uint128,
+        amount: Uint128,
+        total_shares: Uint128,
+        dead_shares: Uint128,
+    ) -> StdResult<Self> {
+        Ok(StakingSnapshot {
+            total_deposit: balance.checked_sub(amount)?,
+            total_shares,
+            dead_shares,
+        })
+    }
+
+    /// ## Description
+    /// Returns the xASTRO minted to the staker, the dead xASTRO minted to the contract and the
+    /// expected balances after the `Enter`, see [`compute_enter_shares`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **amount** is the amount of ASTRO staked.
+    pub fn enter(&self, amount: Uint128) -> StdResult<(Uint128, Uint128, StakingSnapshot)> {
+        let (shares, dead_shares) =
+            compute_enter_shares(amount, self.total_deposit, self.total_shares)?;
+        let expected = StakingSnapshot {
+            total_deposit: self.total_deposit.checked_add(amount)?,
+            total_shares: self
+                .total_shares
+                .checked_add(shares)?
+                .checked_add(dead_shares)?,
+            dead_shares: self.dead_shares.checked_add(dead_shares)?,
+        };
+        Ok((shares, dead_shares, expected))
+    }
+
+    /// ## Description
+    /// Returns the ASTRO returned to the staker and the expected balances after the `Leave`, see
+    /// [`compute_leave_amount`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **share** is the amount of xASTRO burned.
+    pub fn leave(&self, share: Uint128) -> StdResult<(Uint128, StakingSnapshot)> {
+        let amount = compute_leave_amount(
+            share,
+            self.total_deposit,
+            self.total_shares,
+            self.dead_shares,
+        )?;
+        let expected = StakingSnapshot {
+            total_deposit: self.total_deposit.checked_sub(amount)?,
+            total_shares: self.total_shares.checked_sub(share)?,
+            dead_shares: self.dead_shares,
+        };
+        Ok((amount, expected))
+    }
+
+    /// ## Description
+    /// Returns an [`Err`] if the xASTRO supply differs from the expected one or the ASTRO balance
+    /// is lower than expected after the dispatched messages were executed. A higher balance is
+    /// allowed, since donations only increase the exchange rate.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **total_deposit** is the ASTRO balance of the contract after the operation.
+    ///
+    /// * **total_shares** is the total supply of xASTRO after the operation.
+    pub fn assert_post_conditions(
+        &self,
+        total_deposit: Uint128,
+        total_shares: Uint128,
+    ) -> StdResult<()> {
+        if total_shares != self.total_shares {
+            return Err(StdError::generic_err(format!(
+                "The xASTRO supply is {}, expected {}",
+                total_shares, self.total_shares
+            )));
+        }
+        if total_deposit < self.total_deposit {
+            return Err(StdError::generic_err(format!(
+                "The ASTRO balance is {}, expected at least {}",
+                total_deposit, self.total_deposit
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// Returns the amount of ASTRO backing one xASTRO, one for an empty pool.
+/// ## Params
+/// * **total_deposit** is the ASTRO balance of the contract.
+///
+/// * **total_shares** is the total supply of xASTRO.
+pub fn compute_exchange_rate(total_deposit: Uint128, total_shares: Uint128) -> Decimal {
+    if total_shares.is_zero() {
+        return Decimal::one();
+    }
+    Decimal::from_ratio(total_deposit, total_shares)
+}
+
+/// ## Description
+/// Returns the relative change of the exchange rate if it exceeds the maximum change allowed
+/// within a single block, in which case `Enter` and `Leave` are paused until the guardian
+/// unpauses them.
+/// ## Params
+/// * **previous_rate** is the exchange rate at the end of the previous block.
+///
+/// * **rate** is the exchange rate after the operation.
+///
+/// * **max_rate_change** is the maximum relative change of the exchange rate per block.
+pub fn check_rate_change(
+    previous_rate: Decimal,
+    rate: Decimal,
+    max_rate_change: Decimal,
+) -> Option<Decimal> {
+    if previous_rate.is_zero() {
+        return None;
+    }
+    let delta = if rate > previous_rate {
+        rate - previous_rate
+    } else {
+        previous_rate - rate
+    };
+    let change = Decimal::from_ratio(delta.numerator(), previous_rate.numerator());
+    if change > max_rate_change {
+        Some(change)
+    } else {
+        None
+    }
+}
+
+/// ## Description
+/// Returns the boost multiplier of xASTRO locked for the specified duration. The multiplier grows
+/// linearly from one to [`MAX_LOCK_BOOST`] at [`MAX_LOCK_DURATION`].
+/// ## Params
+/// * **lock_duration** is the lock duration in seconds.
+pub fn compute_lock_boost(lock_duration: u64) -> StdResult<Decimal> {
+    if !(MIN_LOCK_DURATION..=MAX_LOCK_DURATION).contains(&lock_duration) {
+        return Err(StdError::generic_err(format!(
+            "The lock duration must be between {} and {} seconds",
+            MIN_LOCK_DURATION, MAX_LOCK_DURATION
+        )));
+    }
+    let max_extra = MAX_LOCK_BOOST.parse::<Decimal>()? - Decimal::one();
+    let extra = Decimal::from_ratio(
+        Uint128::from(max_extra.numerator()) * Uint128::from(lock_duration),
+        Uint128::from(max_extra.denominator()) * Uint128::from(MAX_LOCK_DURATION),
+    );
+    Ok(Decimal::one() + extra)
+}
+
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -11,6 +279,20 @@ pub struct InstantiateMsg {
     pub token_code_id: u64,
     /// the deposit token address
     pub deposit_token_addr: String,
+    /// the fee distributor contract which distributes fees to the staking pool
+    pub fee_distributor: Option<String>,
+    /// the share of compounded rewards paid to the caller of [`ExecuteMsg::Compound`],
+    /// at most [`MAX_COMPOUND_INCENTIVE`]
+    pub compound_incentive: Option<Decimal>,
+    /// the governance contract which sets the exchange rate bound
+    pub governance: Option<String>,
+    /// the address which can unpause the contract after a rate alarm
+    pub guardian: Option<String>,
+    /// the maximum relative change of the exchange rate per block, see [`check_rate_change`]
+    pub max_rate_change: Option<Decimal>,
+    /// the timestamp at which the first reward epoch starts, the `start_time` of the fee
+    /// distributor, see [`reward_epoch`]
+    pub reward_epochs_start: u64,
 }
 
 /// ## Description
@@ -21,6 +303,114 @@ pub enum ExecuteMsg {
     /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
     /// template.
     Receive(Cw20ReceiveMsg),
+    /// Claims the matured fee distributor epochs owed to the staking pool, which increases the
+    /// xASTRO exchange rate, and pays the compound incentive to the sender. Can be called by anyone
+    Compound {},
+    /// Returns the locked xASTRO of the sender once the lock has expired
+    Unlock {},
+    /// Delegates the voting power of the sender's xASTRO to another address. The xASTRO stays
+    /// with the sender, delegating again replaces the previous delegatee
+    Delegate {
+        /// the delegatee
+        to: String,
+    },
+    /// Removes the delegation of the sender
+    Undelegate {},
+    /// ## Description
+    /// Sets or removes the maximum relative change of the exchange rate per block
+    /// ## Executor
+    /// Only governance can execute it
+    SetMaxRateChange { max_rate_change: Option<Decimal> },
+    /// ## Description
+    /// Resumes `Enter` and `Leave` after they were paused by a rate alarm
+    /// ## Executor
+    /// Only the guardian or governance can execute it
+    Unpause {},
+    /// ## Description
+    /// Attaches the sent native coins to the reward epoch of the current time, see
+    /// [`RewardEpochResponse`]. ASTRO itself is compounded into the exchange rate instead
+    /// ## Executor
+    /// Only the fee distributor or governance can execute it
+    AttachRewards {},
+    /// ## Description
+    /// Claims the sender's share of the additional rewards of the finished epochs which were not
+    /// claimed yet, see [`claimable_epochs`]
+    ClaimRewards {
+        /// the number of epochs to claim, at most [`MAX_CLAIM_REWARD_EPOCHS`]
+        limit: Option<u32>,
+    },
+}
+
+/// The maximum number of reward assets attached to a single epoch
+pub const MAX_REWARD_ASSETS: usize = 10;
+/// The maximum number of reward epochs claimed or queried at once
+pub const MAX_CLAIM_REWARD_EPOCHS: u32 = 30;
+
+/// ## Description
+/// Returns the reward epoch which contains the timestamp. Reward epochs are counted from the
+/// `start_time` of the fee distributor like [`get_epoch`], so rewards attached by the fee
+/// distributor land in the reward epoch with the number of its epoch. Rewards attached during an
+/// epoch are shared among the xASTRO holders at its start, so staking right before an attachment
+/// doesn't earn any of it. Returns an [`Err`] if the timestamp is before the first epoch.
+/// ## Params
+/// * **start_time** is the timestamp at which the first epoch starts.
+///
+/// * **time** is the timestamp.
+pub fn reward_epoch(start_time: u64, time: u64) -> StdResult<u64> {
+    get_epoch(start_time, time)
+}
+
+/// ## Description
+/// Returns the epochs a user can claim. Only finished epochs can be claimed since rewards can
+/// still be attached to the current one.
+/// ## Params
+/// * **last_claimed** is the last epoch claimed by the user.
+///
+/// * **first_epoch** is the first epoch with attached rewards.
+///
+/// * **start_time** is the timestamp at which the first epoch starts.
+///
+/// * **time** is the current timestamp.
+///
+/// * **limit** is the number of epochs to claim.
+pub fn claimable_epochs(
+    last_claimed: Option<u64>,
+    first_epoch: u64,
+    start_time: u64,
+    time: u64,
+    limit: Option<u32>,
+) -> std::ops::Range<u64> {
+    let limit = limit
+        .unwrap_or(MAX_CLAIM_REWARD_EPOCHS)
+        .min(MAX_CLAIM_REWARD_EPOCHS) as u64;
+    // nothing is finished before the first epoch starts
+    let current = reward_epoch(start_time, time).unwrap_or_default();
+    let start = last_claimed.map_or(first_epoch, |epoch| (epoch + 1).max(first_epoch));
+    start.min(current)..start.saturating_add(limit).min(current)
+}
+
+/// ## Description
+/// Adds a reward asset to the assets of an epoch. Returns an [`Err`] if the epoch would hold more
+/// than [`MAX_REWARD_ASSETS`] assets.
+/// ## Params
+/// * **assets** are the reward assets of the epoch.
+///
+/// * **reward** is the attached reward.
+pub fn add_reward_asset(assets: &mut Vec<Asset>, reward: Asset) -> StdResult<()> {
+    if reward.amount.is_zero() {
+        return Err(StdError::generic_err("Reward amount must be positive"));
+    }
+    if let Some(asset) = assets.iter_mut().find(|asset| asset.info == reward.info) {
+        asset.amount = asset.amount.checked_add(reward.amount)?;
+    } else if assets.len() >= MAX_REWARD_ASSETS {
+        return Err(StdError::generic_err(format!(
+            "An epoch can't hold more than {} reward assets",
+            MAX_REWARD_ASSETS
+        )));
+    } else {
+        assets.push(reward);
+    }
+    Ok(())
 }
 
 /// ## Description
@@ -30,6 +420,226 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
     Config {},
+    /// LockInfo returns the locked xASTRO of a user in a [`LockInfoResponse`] object. The generator
+    /// reads the boost from it to weight the user's ASTRO emissions
+    LockInfo { user: String },
+    /// Delegation returns the current delegatee of a user in a [`DelegationResponse`] object
+    Delegation { user: String },
+    /// VotingPowerAt returns the checkpointed voting power of a user at the given time, including
+    /// the delegated power, in a [`VotingPowerAtResponse`] object
+    VotingPowerAt { user: String, time: u64 },
+    /// TotalVotingPowerAt returns the checkpointed total xASTRO supply at the given time
+    /// as [`Uint128`]
+    TotalVotingPowerAt { time: u64 },
+    /// RateGuard returns the state of the exchange rate alarm in a [`RateGuardResponse`] object
+    RateGuard {},
+    /// RewardEpochs returns the epochs with attached rewards in a [`RewardEpochsResponse`] object
+    RewardEpochs {
+        start_after: Option<u64>,
+        /// the number of items to be read, at most [`MAX_CLAIM_REWARD_EPOCHS`]
+        limit: Option<u32>,
+    },
+    /// PendingRewards returns the claimable additional rewards of a user in a
+    /// [`PendingRewardsResponse`] object
+    PendingRewards { user: String },
+    /// StakersSnapshot returns the xASTRO holders and their checkpointed balances at the current
+    /// block in ascending address order in a [`StakersSnapshotResponse`] object
+    StakersSnapshot {
+        start_after: Option<String>,
+        /// the number of items to be read, at most [`MAX_STAKERS_SNAPSHOT_LIMIT`]
+        limit: Option<u32>,
+    },
+}
+
+/// The maximum number of stakers returned by a single [`QueryMsg::StakersSnapshot`] query
+pub const MAX_STAKERS_SNAPSHOT_LIMIT: u32 = 100;
+/// The default number of stakers returned by a single [`QueryMsg::StakersSnapshot`] query
+pub const DEFAULT_STAKERS_SNAPSHOT_LIMIT: u32 = 30;
+
+/// ## Description
+/// This structure describes the xASTRO balance of a holder.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerBalance {
+    /// the holder
+    pub address: Addr,
+    /// the xASTRO balance
+    pub balance: Uint128,
+}
+
+/// ## Description
+/// This structure describes a page of the staker snapshot. All pages read at the same block
+/// together form the snapshot, the block is returned so pages of different blocks can be told
+/// apart.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakersSnapshotResponse {
+    /// the height of the snapshot
+    pub height: u64,
+    /// the time of the snapshot
+    pub time: u64,
+    /// the holders with a nonzero balance in ascending address order
+    pub stakers: Vec<StakerBalance>,
+    /// the `start_after` of the next page, [`None`] if this is the last page
+    pub next_start_after: Option<Addr>,
+}
+
+impl StakersSnapshotResponse {
+    /// ## Description
+    /// Returns a page of the snapshot. Zero balances are omitted and the next page starts after the
+    /// last read holder if the page is full.
+    /// ## Params
+    /// * **height** is the current block height.
+    ///
+    /// * **time** is the current block time.
+    ///
+    /// * **balances** are the read holders and their balances in ascending address order.
+    ///
+    /// * **limit** is the limit of the query, see [`stakers_snapshot_limit`].
+    pub fn new(height: u64, time: u64, balances: Vec<(Addr, Uint128)>, limit: usize) -> Self {
+        let next_start_after = if balances.len() == limit {
+            balances.last().map(|(address, _)| address.clone())
+        } else {
+            None
+        };
+        StakersSnapshotResponse {
+            height,
+            time,
+            stakers: balances
+                .into_iter()
+                .filter(|(_, balance)| !balance.is_zero())
+                .map(|(address, balance)| StakerBalance { address, balance })
+                .collect(),
+            next_start_after,
+        }
+    }
+}
+
+/// ## Description
+/// Returns the number of holders read by a [`QueryMsg::StakersSnapshot`] query.
+/// ## Params
+/// * **limit** is an [`Option`] field with the requested limit.
+pub fn stakers_snapshot_limit(limit: Option<u32>) -> usize {
+    limit
+        .unwrap_or(DEFAULT_STAKERS_SNAPSHOT_LIMIT)
+        .min(MAX_STAKERS_SNAPSHOT_LIMIT) as usize
+}
+
+/// ## Description
+/// This structure describes the additional rewards attached to an epoch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardEpochResponse {
+    /// the epoch, see [`reward_epoch`]
+    pub epoch: u64,
+    /// the xASTRO supply at the start of the epoch
+    pub total_shares: Uint128,
+    /// the attached reward assets
+    pub assets: Vec<Asset>,
+}
+
+impl RewardEpochResponse {
+    /// ## Description
+    /// Returns the rewards of a holder of the epoch, pro-rata to the xASTRO balance at the start
+    /// of the epoch and rounded down. Assets with a zero share are omitted.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **balance** is the xASTRO balance of the holder at the start of the epoch.
+    pub fn user_rewards(&self, balance: Uint128) -> Vec<Asset> {
+        if self.total_shares.is_zero() {
+            return vec![];
+        }
+        self.assets
+            .iter()
+            .map(|asset| Asset {
+                info: asset.info.clone(),
+                amount: asset.amount.multiply_ratio(balance, self.total_shares),
+            })
+            .filter(|asset| !asset.amount.is_zero())
+            .collect()
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardEpochsResponse {
+    pub epochs: Vec<RewardEpochResponse>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    /// the claimable rewards of the user over the next [`MAX_CLAIM_REWARD_EPOCHS`] epochs
+    pub rewards: Vec<Asset>,
+    /// the last epoch claimed by the user
+    pub last_claimed_epoch: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateGuardResponse {
+    /// the maximum relative change of the exchange rate per block, the alarm is off if not set
+    pub max_rate_change: Option<Decimal>,
+    /// the exchange rate at the end of the last block with an `Enter` or `Leave`
+    pub last_rate: Decimal,
+    /// the block of the last rate checkpoint
+    pub last_rate_block: u64,
+    /// the block at which `Enter` and `Leave` were paused by an alarm, [`None`] if not paused
+    pub paused_at: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegationResponse {
+    /// the delegatee, [`None`] if the user does not delegate
+    pub delegatee: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingPowerAtResponse {
+    /// the xASTRO balance of the user
+    pub balance: Uint128,
+    /// the voting power delegated to the user by others
+    pub delegated_in: Uint128,
+    /// whether the user delegated its own voting power to someone else
+    pub delegating: bool,
+    /// the resulting voting power, see [`compute_voting_power`]
+    pub voting_power: Uint128,
+}
+
+/// ## Description
+/// Returns the voting power of a user. The own balance counts only if it is not delegated.
+/// ## Params
+/// * **balance** is the xASTRO balance of the user.
+///
+/// * **delegated_in** is the voting power delegated to the user.
+///
+/// * **delegating** is whether the user delegated the own balance.
+pub fn compute_voting_power(
+    balance: Uint128,
+    delegated_in: Uint128,
+    delegating: bool,
+) -> StdResult<Uint128> {
+    let own = if delegating { Uint128::zero() } else { balance };
+    Ok(own.checked_add(delegated_in)?)
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockInfoResponse {
+    /// the amount of locked xASTRO
+    pub amount: Uint128,
+    /// the time at which the lock expires, [`None`] if nothing is locked
+    pub end: Option<u64>,
+    /// the boost multiplier of the lock, one if nothing is locked or the lock has expired
+    pub boost: Decimal,
+    /// the non-transferable boosted balance, the locked amount multiplied by the boost
+    pub boosted_balance: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -38,21 +648,60 @@ pub struct ConfigResponse {
     pub deposit_token_addr: Addr,
     /// the share token address
     pub share_token_addr: Addr,
+    /// the fee distributor contract which distributes fees to the staking pool
+    pub fee_distributor: Option<Addr>,
+    /// the share of compounded rewards paid to the caller of [`ExecuteMsg::Compound`]
+    pub compound_incentive: Decimal,
+    /// the governance contract which sets the exchange rate bound
+    #[serde(default)]
+    pub governance: Option<Addr>,
+    /// the address which can unpause the contract after a rate alarm
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    /// the timestamp at which the first reward epoch starts, see [`reward_epoch`]
+    #[serde(default)]
+    pub reward_epochs_start: u64,
 }
 
 /// ## Description
-/// This structure describes a migration message.
-/// We currently take no arguments for migrations.
+/// This structure describes a migration message. The migration stores no dead shares for a pool
+/// which already has stakers, so all of its xASTRO stays withdrawable. [`DEAD_SHARES`] are only
+/// minted by the first stake into an empty pool.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// the fee distributor contract which distributes fees to the staking pool
+    pub fee_distributor: Option<String>,
+    /// the share of compounded rewards paid to the caller of [`ExecuteMsg::Compound`]
+    pub compound_incentive: Option<Decimal>,
+    /// the timestamp at which the first reward epoch starts, the `start_time` of the fee
+    /// distributor, see [`reward_epoch`]
+    #[serde(default)]
+    pub reward_epochs_start: Option<u64>,
+}
 
 /// ## Description
 /// This structure describes a CW20 hook message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    /// Deposits token to get share token amount
-    Enter {},
+    /// Deposits token to get share token amount. The first stake must be at least
+    /// [`MINIMUM_STAKE_AMOUNT`] and mints [`DEAD_SHARES`] to the contract.
+    /// With a lock duration the minted xASTRO is kept locked in the contract and the sender gets
+    /// a boosted balance according to [`compute_lock_boost`]. Locking again extends the existing
+    /// lock, which can't be shortened.
+    /// The shares are computed against a [`StakingSnapshot`] taken before the xASTRO is minted.
+    Enter {
+        /// the lock duration in seconds between [`MIN_LOCK_DURATION`] and [`MAX_LOCK_DURATION`]
+        #[serde(default)]
+        lock_duration: Option<u64>,
+        /// the recipient of the xASTRO, the sender if not set
+        #[serde(default)]
+        receiver: Option<String>,
+    },
     /// Unstakes share token to move back deposit token amount. Burns share.
+    /// The amount is computed against a [`StakingSnapshot`] taken before the ASTRO is sent.
     Leave {},
+    /// Attaches the received CW20 tokens to the reward epoch of the current time like
+    /// [`ExecuteMsg::AttachRewards`]. Only the fee distributor or governance can send it
+    AttachRewards {},
 }
@@ -0,0 +1,208 @@
+use crate::asset::Asset;
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The default number of seconds between two snapshots (one day)
+pub const DEFAULT_SNAPSHOT_INTERVAL: u64 = 86400;
+/// The maximum number of pools tracked by the contract
+pub const MAX_TRACKED_POOLS: usize = 50;
+/// The maximum number of snapshots returned by a single history query
+pub const MAX_SNAPSHOTS_LIMIT: u32 = 30;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the factory contract which provides the pools
+    pub factory: String,
+    /// the staking contract which provides the xASTRO exchange rate
+    pub staking: String,
+    /// the generator contract which provides the emissions
+    pub generator: String,
+    /// the number of seconds between two snapshots, [`DEFAULT_SNAPSHOT_INTERVAL`] if not set
+    pub snapshot_interval: Option<u64>,
+    /// the pair contracts whose state is snapshotted, at most [`MAX_TRACKED_POOLS`]
+    pub pools: Vec<String>,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Takes a snapshot of the tracked pools, the staking exchange rate and the emissions.
+    /// Can be executed by anyone once per snapshot interval
+    TakeSnapshot {},
+    /// ## Description
+    /// Adds or removes tracked pools
+    /// ## Executor
+    /// Only owner can execute it
+    UpdatePools {
+        /// the pair contracts to start tracking
+        add: Vec<String>,
+        /// the pair contracts to stop tracking
+        remove: Vec<String>,
+    },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the staking contract which provides the xASTRO exchange rate
+        staking: Option<String>,
+        /// the generator contract which provides the emissions
+        generator: Option<String>,
+        /// the number of seconds between two snapshots
+        snapshot_interval: Option<u64>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns the latest snapshot in a [`SnapshotResponse`] object
+    LatestSnapshot {},
+    /// Returns the snapshot taken at the given timestamp in a [`SnapshotResponse`] object
+    Snapshot { timestamp: u64 },
+    /// Returns a list of snapshots in a [`SnapshotsResponse`] object
+    Snapshots {
+        start_after: Option<u64>,
+        /// the number of items to be read, at most [`MAX_SNAPSHOTS_LIMIT`]
+        limit: Option<u32>,
+    },
+    /// Returns the history of a single pool in a [`PoolHistoryResponse`] object
+    PoolHistory {
+        pair: String,
+        start_after: Option<u64>,
+        /// the number of items to be read, at most [`MAX_SNAPSHOTS_LIMIT`]
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the factory contract which provides the pools
+    pub factory: Addr,
+    /// the staking contract which provides the xASTRO exchange rate
+    pub staking: Addr,
+    /// the generator contract which provides the emissions
+    pub generator: Addr,
+    /// the number of seconds between two snapshots
+    pub snapshot_interval: u64,
+    /// the tracked pair contracts
+    pub pools: Vec<Addr>,
+}
+
+/// ## Description
+/// This structure describes the state of a pool at the time of a snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolSnapshot {
+    /// the pair contract
+    pub pair: Addr,
+    /// the reserves of the pool
+    pub reserves: Vec<Asset>,
+    /// the total supply of the LP token
+    pub total_share: Uint128,
+    /// the cumulative swap volume per pool asset
+    pub volumes: Vec<Asset>,
+    /// the cumulative commission per pool asset
+    pub fees: Vec<Asset>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SnapshotResponse {
+    /// the timestamp of the snapshot, aligned to the snapshot interval
+    pub timestamp: u64,
+    /// the block height at which the snapshot was taken
+    pub height: u64,
+    /// the state of the tracked pools
+    pub pools: Vec<PoolSnapshot>,
+    /// the amount of ASTRO redeemable for one xASTRO
+    pub staking_exchange_rate: Decimal,
+    /// the ASTRO emitted by the generator per block
+    pub tokens_per_block: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SnapshotsResponse {
+    pub snapshots: Vec<SnapshotResponse>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolHistoryResponse {
+    /// the pair contract
+    pub pair: Addr,
+    /// the snapshots of the pool as (timestamp, snapshot) pairs
+    pub history: Vec<(u64, PoolSnapshot)>,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns the timestamp of the snapshot which can be taken at `time`, aligned down to the
+/// snapshot interval. Returns an [`Err`] if the snapshot for the current interval was already taken.
+/// ## Params
+/// * **last_snapshot** is the timestamp of the latest snapshot.
+///
+/// * **interval** is the number of seconds between two snapshots.
+///
+/// * **time** is the current timestamp.
+pub fn get_snapshot_timestamp(
+    last_snapshot: Option<u64>,
+    interval: u64,
+    time: u64,
+) -> StdResult<u64> {
+    if interval == 0 {
+        return Err(StdError::generic_err("Snapshot interval must be positive"));
+    }
+    let timestamp = time - time % interval;
+    match last_snapshot {
+        Some(last) if last >= timestamp => Err(StdError::generic_err(format!(
+            "The next snapshot can be taken at {}",
+            last + interval
+        ))),
+        _ => Ok(timestamp),
+    }
+}
@@ -1,14 +1,124 @@
-use crate::asset::{format_lp_token_name, Asset, AssetInfo, PairInfo};
+use crate::airdrop::{claim_and_stake_msg, merkle_leaf, verify_merkle_proof, CampaignResponse};
+use crate::asset::{
+    addr_field_validate, addr_opt_validate, format_lp_token_name, format_lp_token_symbol,
+    is_restricted_denom, native_asset, native_asset_info, normalize_native_denom, token_asset,
+    token_asset_info, validate_addresses, validate_native_denom, Asset, AssetInfo, PairInfo,
+    PairMetadata, TransferAllowedResponse, CAPABILITY_FLASH_LOAN, CAPABILITY_SINGLE_SIDED,
+    CAPABILITY_SWAP_EXACT_OUT, PAIR_DESCRIPTION_MAX_LENGTH,
+};
 use crate::mock_querier::mock_dependencies;
 use crate::querier::{
     query_all_balances, query_balance, query_pair_info, query_supply, query_token_balance,
 };
 
-use crate::factory::PairType;
+use crate::chain::{BLOCK_TIME_MS, NATIVE_TAX_ENABLED};
+use crate::compat_router::{path_to_operations, router_swap_msg};
+use crate::factory::{
+    assert_distinct_assets, diverged_pair_fields, pair_key, sync_pair_info,
+    tokenfactory_creation_funds, CommissionMode, ConfigExport, CreationRateLimit, DynamicFeeBounds,
+    EffectiveFeeResponse, ExecuteMsg as FactoryExecuteMsg, FactoryOp, FeeSource, PairConfig,
+    PairCreationHookMsg, PairType, SudoMsg as FactorySudoMsg, TokenFactoryParams, UpdateAddr,
+    CONFIG_EXPORT_VERSION,
+};
+use crate::fee_distributor::{get_epoch, get_epoch_start, EPOCH_LENGTH};
+use crate::generator::{
+    accrued_rewards, accrued_rewards_with_remainder, assert_active_pools_limit,
+    assert_registration_deposit, boosted_amount, decimal_to_reward_index, distribute_proxy_rewards,
+    effective_stake, estimate_blocks, get_emissions, get_paused_blocks, get_rewarded_blocks,
+    get_tokens_per_block, is_proxy_harvest_due, legacy_release_msg, normalize_alloc_points,
+    pool_emission_share, pool_hook_msgs, project_rewards, reward_index_increase,
+    reward_index_increase_with_remainder, update_total_alloc_point, validate_activation_block,
+    validate_boost_multiplier, validate_emission_schedule, validate_lp_token, validate_pool_hooks,
+    validate_pools_schedule, DepositCaps, EmissionPoint, ExecuteMsg as GeneratorExecuteMsg,
+    PausedEmissions, PoolHookMsg, PortfolioPosition, RewardBeneficiaryResponse, UserDepositInfo,
+    UserPortfolioResponse, MAX_POOL_HOOKS, MIN_SCHEDULE_DELAY, POOL_HOOK_GAS_LIMIT,
+    POOL_HOOK_REPLY_ID,
+};
+use crate::hook::{
+    parse_hook_msg, HookEnvelope, HookError, HookRejection, RejectedHooksResponse, HOOK_VERSION,
+};
+use crate::insurance_fund::{assert_payout_covered, PayoutResponse, PayoutStatus};
+use crate::keeper_hub::{JobParams, JobResponse};
+use crate::maker::{
+    assert_collect_cooldown, assert_distribution_allowed, assert_fee_source_registrable,
+    assert_min_astro_received, build_swap_msg, compute_caller_reward, distributable_amount,
+    fee_source_withdraw_msgs, maker_status, query_pair_in_factories, split_by_weights,
+    validate_bridge_route, validate_burn_percent, validate_caller_reward_bps,
+    validate_distribution_weights, validate_governance, validate_insurance_percent,
+    validate_target_assets, BuybackParams, ConfigResponse as MakerConfigResponse,
+    ExecuteMsg as MakerExecuteMsg, FeeSourceExecuteMsg, SudoMsg as MakerSudoMsg,
+    FEE_SOURCE_GAS_LIMIT, FEE_SOURCE_REPLY_ID, MAX_FEE_SOURCES,
+};
+use crate::math::{
+    assert_flash_swap_repaid, assert_initial_price, assert_invariant_per_share, assert_max_spread,
+    compute_batch_clearing, compute_d, compute_dynamic_fee, compute_imbalance,
+    compute_imbalance_fee, compute_initial_deposit, compute_max_offer_within_spread,
+    compute_offer_amount, compute_partial_fill_offer, compute_peg_deviation, compute_share,
+    compute_stable_max_offer_within_spread, compute_stable_offer_amount, compute_stable_swap,
+    compute_stable_swap_with_d, compute_swap, compute_swap_offer_commission, compute_withdraw,
+    compute_xyk_invariant, fair_stable_lp_value, fair_xyk_lp_value, resolve_max_spread,
+    InvariantCache, SwapError,
+};
+use crate::oracle::{
+    assert_price_reliable, assert_update_allowed, attestation_commitment, compute_average_price,
+    compute_average_price_256, invalidation_event, AttestationResponse, OracleError,
+    INVALIDATION_EVENT,
+};
+use crate::pair::{
+    accumulate_prices, assert_deadline, compute_apr, compute_current_amp, migrate_liquidity_msgs,
+    record_fee_change, validate_amp_change, validate_weights, Cw20HookMsg as PairCw20HookMsg,
+    DynamicFeeParams, ExecuteMsg as PairExecuteMsg, FeeChange, FillMode, ImbalanceFee,
+    ImbalanceFeeParams, MevCaptureParams, PairFees, PairOperation, PausedOperations, RefundReason,
+    RefundReport, RetirementInfo, TraderFlow, XykPoolConfig, DEFAULT_APR_WINDOW, FEE_CHANGE_EVENT,
+    MAX_FEE_HISTORY, MIN_AMP_CHANGING_TIME, SECONDS_PER_YEAR,
+};
+use crate::router::{
+    execution_slot, recent_execution_ids, register_client_id, route_hash, split_offer_amount,
+    split_output, validate_basket, validate_output_recipients, validate_route,
+    validate_split_routes, validate_swap_operations, CheckTradeResponse,
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, FeeDiscountConfig,
+    FeeDiscountTier, RouteComplexity, RouteMessages, RouterError, SplitRoute, SwapOperation,
+    SwapReceipt, SwapResponseData, CLIENT_ID_WINDOW_BLOCKS, MAX_BASKET_INPUTS,
+    MAX_CLIENT_ID_LENGTH, MAX_RECENT_EXECUTIONS, MAX_SWAP_OPERATIONS,
+};
+use crate::staking::{
+    add_reward_asset, check_rate_change, claimable_epochs, compute_enter_shares,
+    compute_exchange_rate, compute_leave_amount, compute_lock_boost, compute_voting_power,
+    reward_epoch, stakers_snapshot_limit, Cw20HookMsg as StakingCw20HookMsg, RewardEpochResponse,
+    StakerBalance, StakersSnapshotResponse, StakingSnapshot, DEAD_SHARES, MAX_LOCK_BOOST,
+    MAX_LOCK_DURATION, MAX_REWARD_ASSETS, MAX_STAKERS_SNAPSHOT_LIMIT, MIN_LOCK_DURATION,
+};
+use crate::stats::{get_snapshot_timestamp, DEFAULT_SNAPSHOT_INTERVAL};
+use crate::token::TransferHookMsg;
+use crate::twap_executor::{
+    slice_minimum_receive, validate_order_params, OrderParams, OrderResponse,
+};
+use crate::vault_manager::{
+    compute_mint_shares, compute_performance_fee, compute_value, compute_withdrawn_assets,
+    validate_performance_fee, Strategy,
+};
+use crate::vesting::{
+    assert_solvency, parse_receipt_token_id, receipt_token_id, Revocation, SchedulePause,
+    SolvencyResponse, UnlockBucket, VestingInfo, VestingSchedule, VestingSchedulePoint,
+    MAX_UNLOCK_BUCKETS, MAX_VESTING_SCHEDULES,
+};
 use crate::DecimalCheckedOps;
-use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
-use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
-use cw20::Cw20ExecuteMsg;
+use cosmwasm_std::testing::{MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Decimal256,
+    ReplyOn, StdError, SubMsg, Uint128, Uint256, Uint64, WasmMsg,
+};
+use cw20::{Cw20CoinVerified, Cw20ExecuteMsg};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+
+/// The tax of the mocked 1% uusd tax with a cap of 1000000 on 123123 uusd, zero on chains without
+/// a native tax
+const UUSD_TAX: u128 = if NATIVE_TAX_ENABLED { 1220 } else { 0 };
+/// The 123123 uusd left after the tax is deducted
+const UUSD_AFTER_TAX: u128 = 123123 - UUSD_TAX;
 
 #[test]
 fn token_balance_querier() {
@@ -109,24 +219,18 @@ fn test_asset_info() {
         denom: "uusd".to_string(),
     };
 
-    assert_eq!(false, token_info.equal(&native_token_info));
+    assert!(!token_info.equal(&native_token_info));
 
-    assert_eq!(
-        false,
-        token_info.equal(&AssetInfo::Token {
-            contract_addr: Addr::unchecked("asset0001"),
-        })
-    );
+    assert!(!token_info.equal(&AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0001"),
+    }));
 
-    assert_eq!(
-        true,
-        token_info.equal(&AssetInfo::Token {
-            contract_addr: Addr::unchecked("asset0000"),
-        })
-    );
+    assert!(token_info.equal(&AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0000"),
+    }));
 
-    assert_eq!(true, native_token_info.is_native_token());
-    assert_eq!(false, token_info.is_native_token());
+    assert!(native_token_info.is_native_token());
+    assert!(!token_info.is_native_token());
 
     let mut deps = mock_dependencies(&[Coin {
         denom: "uusd".to_string(),
@@ -201,7 +305,7 @@ fn test_asset() {
         native_token_asset
             .compute_tax(&deps.as_ref().querier)
             .unwrap(),
-        Uint128::new(1220u128)
+        Uint128::new(UUSD_TAX)
     );
 
     assert_eq!(
@@ -210,7 +314,7 @@ fn test_asset() {
             .unwrap(),
         Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::new(121903u128),
+            amount: Uint128::new(UUSD_AFTER_TAX),
         }
     );
 
@@ -237,7 +341,7 @@ fn test_asset() {
             to_address: String::from("addr0000"),
             amount: vec![Coin {
                 denom: "uusd".to_string(),
-                amount: Uint128::new(121903u128),
+                amount: Uint128::new(UUSD_AFTER_TAX),
             }]
         })
     );
@@ -261,6 +365,9 @@ fn query_astroport_pair_contract() {
             contract_addr: Addr::unchecked("pair0000"),
             liquidity_token: Addr::unchecked("liquidity0000"),
             pair_type: PairType::Xyk {},
+            capabilities: vec![],
+            metadata: None,
+            official: false,
         },
     )]);
 
@@ -299,6 +406,9 @@ fn test_format_lp_token_name() {
             contract_addr: Addr::unchecked("pair0000"),
             liquidity_token: Addr::unchecked("liquidity0000"),
             pair_type: PairType::Xyk {},
+            capabilities: vec![],
+            metadata: None,
+            official: false,
         },
     )]);
 
@@ -323,8 +433,42 @@ fn test_format_lp_token_name() {
 
     deps.querier.with_cw20_query_handler();
 
-    let lp_name = format_lp_token_name(pair_info.asset_infos, &deps.as_ref().querier).unwrap();
-    assert_eq!(lp_name, "MAPP-UUSD-LP")
+    let lp_name =
+        format_lp_token_name(pair_info.asset_infos.clone(), &deps.as_ref().querier).unwrap();
+    assert_eq!(lp_name, "MAPP-UUSD-LP");
+
+    let lp_symbol = format_lp_token_symbol(
+        pair_info.asset_infos.clone(),
+        &PairType::Xyk {},
+        &deps.as_ref().querier,
+    )
+    .unwrap();
+    assert_eq!(lp_symbol, "MAPPUUSDLP");
+    let lp_symbol = format_lp_token_symbol(
+        pair_info.asset_infos,
+        &PairType::Stable {},
+        &deps.as_ref().querier,
+    )
+    .unwrap();
+    assert_eq!(lp_symbol, "MAPPUUSDSLP");
+
+    // only letters are kept and the symbol never exceeds the CW20 limit
+    let ibc_symbol = format_lp_token_symbol(
+        [
+            AssetInfo::NativeToken {
+                denom: "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+                    .to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        ],
+        &PairType::Custom("concentrated".to_string()),
+        &deps.as_ref().querier,
+    )
+    .unwrap();
+    assert_eq!(ibc_symbol, "IBCFULUNCLP");
+    assert!(ibc_symbol.len() <= 12);
 }
 
 #[test]
@@ -352,3 +496,5680 @@ fn test_decimal_checked_ops() {
             .is_err()
     );
 }
+
+#[test]
+fn test_fee_distributor_epochs() {
+    let start_time = 1_000_000u64;
+
+    assert!(get_epoch(start_time, start_time - 1).is_err());
+    assert_eq!(get_epoch(start_time, start_time).unwrap(), 0);
+    assert_eq!(
+        get_epoch(start_time, start_time + EPOCH_LENGTH - 1).unwrap(),
+        0
+    );
+    assert_eq!(get_epoch(start_time, start_time + EPOCH_LENGTH).unwrap(), 1);
+    assert_eq!(
+        get_epoch_start(start_time, 3),
+        start_time + 3 * EPOCH_LENGTH
+    );
+}
+
+#[test]
+fn test_validate_swap_operations() {
+    let astro_swap = |offer: &str, ask: &str| SwapOperation::AstroSwap {
+        offer_asset_info: AssetInfo::NativeToken {
+            denom: offer.to_string(),
+        },
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: ask.to_string(),
+        },
+    };
+
+    let operations = vec![
+        SwapOperation::NativeSwap {
+            offer_denom: "ukrw".to_string(),
+            ask_denom: "uusd".to_string(),
+        },
+        astro_swap("uusd", "uluna"),
+        astro_swap("uluna", "uasset"),
+    ];
+
+    validate_swap_operations(&operations, 3, Some(5)).unwrap();
+    assert_eq!(
+        RouteComplexity::new(&operations, vec![PairType::Xyk {}, PairType::Stable {}]),
+        RouteComplexity {
+            hops: 3,
+            pair_types: vec![PairType::Xyk {}, PairType::Stable {}],
+            submessages: 7,
+            complexity: 5,
+        }
+    );
+
+    assert_eq!(
+        validate_swap_operations(&[], 3, None).unwrap_err(),
+        StdError::generic_err("Must provide swap operations")
+    );
+    assert_eq!(
+        validate_swap_operations(&operations, 2, None).unwrap_err(),
+        StdError::generic_err("Hop 2: the route exceeds the maximum of 2 swap operations")
+    );
+    assert_eq!(
+        validate_swap_operations(&operations, 3, Some(4)).unwrap_err(),
+        StdError::generic_err("Hop 2: the route exceeds the complexity budget of 4")
+    );
+    assert_eq!(
+        validate_swap_operations(
+            &[astro_swap("uusd", "uluna"), astro_swap("uusd", "uasset")],
+            3,
+            None
+        )
+        .unwrap_err(),
+        StdError::generic_err(
+            "Hop 1: the offer asset does not match the ask asset of the previous operation"
+        )
+    );
+
+    let native = |denom: &str| AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    };
+    validate_route(&native("ukrw"), &native("uasset"), &operations, 3, None).unwrap();
+    assert_eq!(
+        validate_route(&native("uusd"), &native("uasset"), &operations, 3, None).unwrap_err(),
+        StdError::generic_err("Hop 0: the route must start with the offer asset")
+    );
+    assert_eq!(
+        validate_route(&native("ukrw"), &native("uluna"), &operations, 3, None).unwrap_err(),
+        StdError::generic_err("Hop 2: the route must end with the ask asset")
+    );
+
+    let wrapped = AssetInfo::Token {
+        contract_addr: Addr::unchecked("wrapped0000"),
+    };
+    let operations = vec![
+        SwapOperation::Wrap {
+            denom: "ibc/atom".to_string(),
+            cw20: Addr::unchecked("wrapped0000"),
+        },
+        SwapOperation::AstroSwap {
+            offer_asset_info: wrapped.clone(),
+            ask_asset_info: native("uusd"),
+        },
+    ];
+    validate_route(
+        &native("ibc/atom"),
+        &native("uusd"),
+        &operations,
+        3,
+        Some(3),
+    )
+    .unwrap();
+    assert_eq!(
+        validate_swap_operations(
+            &[
+                SwapOperation::Unwrap {
+                    cw20: Addr::unchecked("wrapped0000"),
+                    denom: "ibc/atom".to_string(),
+                },
+                operations[1].clone(),
+            ],
+            3,
+            None
+        )
+        .unwrap_err(),
+        StdError::generic_err(
+            "Hop 1: the offer asset does not match the ask asset of the previous operation"
+        )
+    );
+    assert_eq!(operations[0].get_target_asset_info(), wrapped);
+}
+
+#[test]
+fn test_asset_string_form() {
+    let native_token_info = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let token_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0000"),
+    };
+
+    assert_eq!(native_token_info.to_canonical_string(), "native:uusd");
+    assert_eq!(token_info.to_canonical_string(), "cw20:asset0000");
+    assert_eq!(
+        "native:uusd".parse::<AssetInfo>().unwrap(),
+        native_token_info
+    );
+    assert_eq!("cw20:asset0000".parse::<AssetInfo>().unwrap(), token_info);
+    assert!("uusd".parse::<AssetInfo>().is_err());
+    assert!("native:".parse::<AssetInfo>().is_err());
+
+    let asset = Asset {
+        info: native_token_info,
+        amount: Uint128::new(1000),
+    };
+    assert_eq!(asset.to_canonical_string(), "1000native:uusd");
+    assert_eq!("1000native:uusd".parse::<Asset>().unwrap(), asset);
+    assert!("native:uusd".parse::<Asset>().is_err());
+    assert!("1000".parse::<Asset>().is_err());
+
+    // the canonical form round trips, the bare display form doesn't
+    let infos = [
+        asset.info.clone(),
+        token_info,
+        AssetInfo::NativeToken {
+            denom: "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+                .to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "factory/terra1creator/7token".to_string(),
+        },
+    ];
+    for info in infos.iter() {
+        assert_eq!(
+            info.to_canonical_string().parse::<AssetInfo>().unwrap(),
+            *info
+        );
+        assert!(info.to_string().parse::<AssetInfo>().is_err());
+        for amount in [0u128, 1, u128::MAX].iter() {
+            let asset = Asset {
+                info: info.clone(),
+                amount: Uint128::new(*amount),
+            };
+            assert_eq!(asset.to_canonical_string().parse::<Asset>().unwrap(), asset);
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Msg {
+        #[serde(with = "crate::asset::string_form")]
+        asset: Asset,
+    }
+
+    let msg = Msg { asset };
+    let json = to_binary(&msg).unwrap();
+    assert_eq!(json.as_slice(), br#"{"asset":"1000native:uusd"}"#);
+    assert_eq!(from_binary::<Msg>(&json).unwrap(), msg);
+}
+
+#[test]
+fn test_asset_conversions() {
+    let coin = Coin::new(1000, "uusd");
+    let asset = Asset::from(&coin);
+    assert_eq!(asset, native_asset("uusd".to_string(), Uint128::new(1000)));
+    assert_eq!(Coin::try_from(&asset).unwrap(), coin);
+    assert!(Cw20CoinVerified::try_from(asset).is_err());
+
+    let cw20_coin = Cw20CoinVerified {
+        address: Addr::unchecked("asset0000"),
+        amount: Uint128::new(1000),
+    };
+    let asset = Asset::from(cw20_coin.clone());
+    assert_eq!(
+        asset.info,
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        }
+    );
+    assert_eq!(
+        Coin::try_from(asset.clone()).unwrap_err(),
+        StdError::generic_err("Asset asset0000 is not a native coin")
+    );
+    assert_eq!(Cw20CoinVerified::try_from(asset).unwrap(), cw20_coin);
+}
+
+#[cfg(feature = "cw-asset")]
+#[test]
+fn test_asset_cw_asset_conversions() {
+    let assets = [
+        native_asset("uusd".to_string(), Uint128::new(1000)),
+        token_asset(Addr::unchecked("asset0000"), Uint128::new(1000)),
+    ];
+    let cw_assets = [
+        cw_asset::Asset::native("uusd", 1000u128),
+        cw_asset::Asset::cw20(Addr::unchecked("asset0000"), 1000u128),
+    ];
+    for (asset, cw_asset) in assets.iter().zip(cw_assets.iter()) {
+        assert_eq!(cw_asset::Asset::from(asset.clone()), *cw_asset);
+        assert_eq!(Asset::from(cw_asset.clone()), *asset);
+    }
+}
+
+#[cfg(feature = "proto")]
+#[test]
+fn test_asset_proto_conversions() {
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+
+    let coin = ProtoCoin {
+        denom: "uusd".to_string(),
+        amount: "1000".to_string(),
+    };
+    let asset = Asset::try_from(coin.clone()).unwrap();
+    assert_eq!(asset, native_asset("uusd".to_string(), Uint128::new(1000)));
+    assert_eq!(ProtoCoin::try_from(asset).unwrap(), coin);
+
+    assert!(Asset::try_from(ProtoCoin {
+        amount: "-1".to_string(),
+        ..coin
+    })
+    .is_err());
+    assert_eq!(
+        ProtoCoin::try_from(token_asset(Addr::unchecked("asset0000"), Uint128::new(1)))
+            .unwrap_err(),
+        StdError::generic_err("Asset asset0000 is not a native coin")
+    );
+}
+
+#[test]
+fn test_pair_creation_hook_msg_compatibility() {
+    let pair_info = PairInfo {
+        asset_infos: [
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+        ],
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+        capabilities: vec![CAPABILITY_FLASH_LOAN.to_string()],
+        metadata: None,
+        official: false,
+    };
+
+    let hook_msg = to_binary(&PairCreationHookMsg::OnPairCreated {
+        pair_info: pair_info.clone(),
+    })
+    .unwrap();
+
+    assert_eq!(
+        from_binary::<GeneratorExecuteMsg>(&hook_msg).unwrap(),
+        GeneratorExecuteMsg::OnPairCreated { pair_info }
+    );
+}
+
+#[test]
+fn test_pair_info_capabilities() {
+    // pair infos stored before capabilities were introduced have none
+    let legacy = r#"{
+        "asset_infos": [{"token": {"contract_addr": "asset0000"}}, {"native_token": {"denom": "uusd"}}],
+        "contract_addr": "pair0000",
+        "liquidity_token": "liquidity0000",
+        "pair_type": {"xyk": {}}
+    }"#;
+    let pair_info: PairInfo = cosmwasm_std::from_slice(legacy.as_bytes()).unwrap();
+    assert!(pair_info.capabilities.is_empty());
+    assert!(!pair_info.has_capability(CAPABILITY_SWAP_EXACT_OUT));
+
+    let pair_info = PairInfo {
+        capabilities: vec![
+            CAPABILITY_SWAP_EXACT_OUT.to_string(),
+            CAPABILITY_SINGLE_SIDED.to_string(),
+        ],
+        ..pair_info
+    };
+    assert!(pair_info.has_capability(CAPABILITY_SWAP_EXACT_OUT));
+    assert!(pair_info.has_capability(CAPABILITY_SINGLE_SIDED));
+    assert!(!pair_info.has_capability(CAPABILITY_FLASH_LOAN));
+}
+
+#[test]
+fn test_pair_metadata() {
+    // pair infos stored before metadata was introduced are unlabeled and not official
+    let legacy = r#"{
+        "asset_infos": [{"token": {"contract_addr": "asset0000"}}, {"native_token": {"denom": "uusd"}}],
+        "contract_addr": "pair0000",
+        "liquidity_token": "liquidity0000",
+        "pair_type": {"xyk": {}}
+    }"#;
+    let pair_info: PairInfo = cosmwasm_std::from_slice(legacy.as_bytes()).unwrap();
+    assert_eq!(pair_info.metadata, None);
+    assert!(!pair_info.official);
+
+    let metadata = PairMetadata {
+        name: Some("ASTRO-UST".to_string()),
+        description: None,
+        url: Some("https://astroport.fi".to_string()),
+    };
+    metadata.validate().unwrap();
+    PairMetadata::default().validate().unwrap();
+
+    let err = PairMetadata {
+        name: Some(" ".to_string()),
+        ..metadata.clone()
+    }
+    .validate()
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Pair metadata name must be between 1 and 64 characters")
+    );
+
+    let err = PairMetadata {
+        description: Some("a".repeat(PAIR_DESCRIPTION_MAX_LENGTH + 1)),
+        ..metadata.clone()
+    }
+    .validate()
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Pair metadata description must be between 1 and 512 characters")
+    );
+
+    for url in [
+        "http://astroport.fi",
+        "javascript:alert(1)",
+        "https://astro port.fi",
+    ] {
+        let err = PairMetadata {
+            url: Some(url.to_string()),
+            ..metadata.clone()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Pair metadata url must be a https url")
+        );
+    }
+}
+
+#[test]
+fn test_xyk_pool_weights() {
+    let config = XykPoolConfig {
+        init_weights: [Decimal::percent(50), Decimal::percent(50)],
+        init_weights_time: 100,
+        next_weights: [Decimal::percent(80), Decimal::percent(20)],
+        next_weights_time: 200,
+    };
+
+    assert_eq!(config.current_weights(50), config.init_weights);
+    assert_eq!(
+        config.current_weights(150),
+        [Decimal::percent(65), Decimal::percent(35)]
+    );
+    assert_eq!(config.current_weights(250), config.next_weights);
+
+    let reversed = XykPoolConfig {
+        init_weights: config.next_weights,
+        next_weights: config.init_weights,
+        ..config
+    };
+    assert_eq!(
+        reversed.current_weights(150),
+        [Decimal::percent(65), Decimal::percent(35)]
+    );
+
+    validate_weights(&[Decimal::percent(80), Decimal::percent(20)]).unwrap();
+    assert!(validate_weights(&[Decimal::percent(80), Decimal::percent(30)]).is_err());
+    assert!(validate_weights(&[Decimal::percent(99), Decimal::percent(1)]).is_err());
+}
+
+#[test]
+fn test_scaled_reward_index() {
+    // the decimal index can't represent more than ~3.4e20 rewards per share
+    let reward = Uint128::new(1_000_000_000_000_000_000_000u128);
+    let index = reward_index_increase(reward, Uint128::new(1u128)).unwrap();
+    assert_eq!(
+        accrued_rewards(Uint128::new(1u128), index, Decimal256::zero()).unwrap(),
+        reward
+    );
+
+    // tiny emissions over a huge LP supply
+    let total_share = Uint128::new(1_000_000_000_000_000_000_000_000u128);
+    let rewards = [Uint128::new(1_000u128), Uint128::new(999_000u128)];
+    let user_amount = Uint128::new(1_000_000_000_000_000_000_000u128);
+
+    // the old decimal index rounds every distribution down to zero
+    let old_index = rewards.iter().fold(Decimal::zero(), |index, reward| {
+        index + Decimal::from_ratio(*reward, total_share)
+    });
+    assert_eq!(user_amount * old_index, Uint128::zero());
+
+    // the pool remainder carries them over until the index can represent them
+    let mut index = Decimal256::zero();
+    let mut remainder = Uint256::zero();
+    for reward in rewards {
+        let (increase, pool_remainder) =
+            reward_index_increase_with_remainder(reward, total_share, remainder).unwrap();
+        index = index + increase;
+        remainder = pool_remainder;
+    }
+    assert_eq!(remainder, Uint256::zero());
+    assert_eq!(
+        accrued_rewards(user_amount, index, Decimal256::zero()).unwrap(),
+        Uint128::new(1_000u128)
+    );
+
+    // indexes large enough for the decimal representation accrue the same amounts
+    let total_share = Uint128::new(1_000_000u128);
+    let reward = Uint128::new(333_333u128);
+    let user_amount = Uint128::new(250_000u128);
+    let old_index = Decimal::from_ratio(reward, total_share);
+    let index = reward_index_increase(reward, total_share).unwrap();
+    assert_eq!(
+        accrued_rewards(user_amount, index, Decimal256::zero()).unwrap(),
+        user_amount * old_index
+    );
+    // and the migration converts the stored indexes exactly
+    assert_eq!(decimal_to_reward_index(old_index), index);
+    assert_eq!(
+        decimal_to_reward_index(old_index).to_string(),
+        old_index.to_string()
+    );
+
+    assert!(reward_index_increase(reward, Uint128::zero()).is_err());
+    assert!(accrued_rewards(user_amount, Decimal256::zero(), index).is_err());
+}
+
+#[test]
+fn test_reward_remainders() {
+    // one token per distribution among three shares
+    let total_share = Uint128::new(3u128);
+    let reward = Uint128::new(1u128);
+    let user_amount = Uint128::new(1u128);
+
+    let mut index = Decimal256::zero();
+    let mut pool_remainder = Uint256::zero();
+    let mut user_index = Decimal256::zero();
+    let mut user_remainder = Uint256::zero();
+    let mut claimed = Uint128::zero();
+    for _ in 0..3 {
+        let (increase, remainder) =
+            reward_index_increase_with_remainder(reward, total_share, pool_remainder).unwrap();
+        index = index + increase;
+        pool_remainder = remainder;
+
+        // the user claims after every distribution
+        let (rewards, remainder) =
+            accrued_rewards_with_remainder(user_amount, index, user_index, user_remainder).unwrap();
+        claimed += rewards;
+        user_index = index;
+        user_remainder = remainder;
+    }
+    // without remainders every claim is rounded down to zero
+    assert_eq!(
+        accrued_rewards(
+            user_amount,
+            index / Uint256::from(3u128),
+            Decimal256::zero()
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+    // the third of the distributed rewards is paid out and nothing is left as dust
+    assert_eq!(claimed, Uint128::new(1u128));
+    assert_eq!(pool_remainder, Uint256::zero());
+    assert_eq!(user_remainder, Uint256::zero());
+
+    assert!(
+        reward_index_increase_with_remainder(reward, Uint128::zero(), Uint256::zero()).is_err()
+    );
+    assert!(accrued_rewards_with_remainder(
+        user_amount,
+        Decimal256::zero(),
+        index,
+        Uint256::zero()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_vault_manager_shares() {
+    let assets = [
+        native_asset("uluna".to_string(), Uint128::new(1000)),
+        native_asset("uusd".to_string(), Uint128::new(50000)),
+    ];
+    let price = Decimal::from_ratio(50u128, 1u128);
+    assert_eq!(compute_value(&assets, price).unwrap(), Uint128::new(100000));
+
+    // the first deposit mints one share per unit of value
+    assert_eq!(
+        compute_mint_shares(Uint128::new(100000), Uint128::zero(), Uint128::zero()).unwrap(),
+        Uint128::new(100000)
+    );
+    // the vault doubled its value, so new shares are half as many per unit
+    assert_eq!(
+        compute_mint_shares(
+            Uint128::new(1000),
+            Uint128::new(200000),
+            Uint128::new(100000)
+        )
+        .unwrap(),
+        Uint128::new(500)
+    );
+    assert_eq!(
+        compute_mint_shares(Uint128::new(1), Uint128::new(200000), Uint128::new(100000)),
+        Err(StdError::generic_err(
+            "The deposit is too small to mint shares"
+        ))
+    );
+
+    assert_eq!(
+        compute_withdrawn_assets(Uint128::new(333), Uint128::new(1000), &assets).unwrap(),
+        vec![
+            native_asset("uluna".to_string(), Uint128::new(333)),
+            native_asset("uusd".to_string(), Uint128::new(16650)),
+        ]
+    );
+    assert_eq!(
+        compute_withdrawn_assets(Uint128::new(1001), Uint128::new(1000), &assets),
+        Err(StdError::generic_err(
+            "Can't withdraw more than the total shares"
+        ))
+    );
+
+    // 10% fee on the gain above the high water mark
+    assert_eq!(
+        compute_performance_fee(
+            Decimal::percent(120),
+            Decimal::one(),
+            Uint128::new(100000),
+            1000
+        ),
+        Uint128::new(2000)
+    );
+    assert_eq!(
+        compute_performance_fee(
+            Decimal::percent(90),
+            Decimal::one(),
+            Uint128::new(100000),
+            1000
+        ),
+        Uint128::zero()
+    );
+    validate_performance_fee(2000).unwrap();
+    assert_eq!(
+        validate_performance_fee(2001),
+        Err(StdError::generic_err(
+            "The performance fee can't exceed 2000 bps"
+        ))
+    );
+
+    let strategy = Strategy::Range {
+        lower_price: Decimal::percent(90),
+        upper_price: Decimal::percent(110),
+    };
+    strategy.validate().unwrap();
+    assert!(!strategy.needs_rebalance(Decimal::one()));
+    assert!(strategy.needs_rebalance(Decimal::percent(111)));
+    assert!(!Strategy::FullRange {}.needs_rebalance(Decimal::percent(111)));
+    assert_eq!(
+        Strategy::Range {
+            lower_price: Decimal::one(),
+            upper_price: Decimal::one(),
+        }
+        .validate(),
+        Err(StdError::generic_err(
+            "The lower price must be positive and less than the upper price"
+        ))
+    );
+}
+
+#[test]
+fn test_vesting_claimable_at() {
+    let info = VestingInfo {
+        schedules: vec![
+            VestingSchedule {
+                start_point: VestingSchedulePoint {
+                    time: 100,
+                    amount: Uint128::new(100),
+                },
+                end_point: Some(VestingSchedulePoint {
+                    time: 200,
+                    amount: Uint128::new(1100),
+                }),
+                pauses: vec![],
+            },
+            VestingSchedule {
+                start_point: VestingSchedulePoint {
+                    time: 150,
+                    amount: Uint128::new(50),
+                },
+                end_point: None,
+                pauses: vec![],
+            },
+        ],
+        released_amount: Uint128::new(200),
+        released_amounts: vec![],
+        revocable: false,
+        revocation: None,
+    };
+
+    assert_eq!(info.schedules[0].total_amount(), Uint128::new(1100));
+    assert_eq!(info.schedules[1].total_amount(), Uint128::new(50));
+
+    assert_eq!(info.vested_amount(99).unwrap(), Uint128::zero());
+    assert_eq!(info.vested_amount(100).unwrap(), Uint128::new(100));
+    assert_eq!(info.vested_amount(149).unwrap(), Uint128::new(590));
+    assert_eq!(info.vested_amount(150).unwrap(), Uint128::new(650));
+    assert_eq!(info.vested_amount(200).unwrap(), Uint128::new(1150));
+    assert_eq!(info.vested_amount(1000).unwrap(), Uint128::new(1150));
+
+    assert_eq!(info.claimable_amount(100).unwrap(), Uint128::zero());
+    assert_eq!(info.claimable_amount(150).unwrap(), Uint128::new(450));
+}
+
+#[test]
+fn test_vesting_add_schedules() {
+    let schedule = |time: u64, amount: u128| VestingSchedule {
+        start_point: VestingSchedulePoint {
+            time,
+            amount: Uint128::new(amount),
+        },
+        end_point: None,
+        pauses: vec![],
+    };
+    let mut info = VestingInfo {
+        schedules: vec![schedule(100, 300)],
+        released_amount: Uint128::new(300),
+        released_amounts: vec![],
+        revocable: false,
+        revocation: None,
+    };
+
+    info.add_schedules(vec![schedule(200, 500), schedule(300, 100)])
+        .unwrap();
+    assert_eq!(info.schedules[0], schedule(100, 300));
+    assert_eq!(info.schedules.len(), 3);
+
+    info.released_amount = Uint128::new(400);
+    assert_eq!(
+        info.claimed_amounts(250).unwrap(),
+        vec![Uint128::new(300), Uint128::new(100), Uint128::zero()]
+    );
+
+    assert_eq!(
+        info.add_schedules(vec![schedule(400, 1); MAX_VESTING_SCHEDULES - 2]),
+        Err(StdError::generic_err(
+            "A vesting account can't have more than 16 schedules"
+        ))
+    );
+    assert_eq!(info.schedules.len(), 3);
+
+    // pauses can't be injected on registration
+    let paused = VestingSchedule {
+        pauses: vec![SchedulePause {
+            start: 500,
+            end: Some(400),
+            retroactive: false,
+        }],
+        ..schedule(400, 1)
+    };
+    assert_eq!(
+        info.add_schedules(vec![paused.clone()]),
+        Err(StdError::generic_err("New schedules can't have pauses"))
+    );
+    // an inconsistent pause doesn't make the vesting computation panic
+    assert_eq!(paused.vested_amount(1000).unwrap(), Uint128::new(1));
+}
+
+#[test]
+fn test_vesting_solvency() {
+    let mut info = VestingInfo {
+        schedules: vec![VestingSchedule {
+            start_point: VestingSchedulePoint {
+                time: 100,
+                amount: Uint128::zero(),
+            },
+            end_point: Some(VestingSchedulePoint {
+                time: 200,
+                amount: Uint128::new(1000),
+            }),
+            pauses: vec![],
+        }],
+        released_amount: Uint128::new(100),
+        released_amounts: vec![],
+        revocable: true,
+        revocation: None,
+    };
+    assert_eq!(info.outstanding_amount().unwrap(), Uint128::new(900));
+    // the unvested amount returned to the owner is not owed anymore
+    assert_eq!(info.revoke(150).unwrap(), Uint128::new(500));
+    assert_eq!(info.outstanding_amount().unwrap(), Uint128::new(400));
+
+    assert_solvency(Uint128::new(400), Uint128::new(600), Uint128::new(1000)).unwrap();
+    assert_eq!(
+        assert_solvency(Uint128::new(400), Uint128::new(601), Uint128::new(1000)),
+        Err(StdError::generic_err(
+            "The vesting contract is under-funded: 1001 tokens are required, but the balance is 1000"
+        ))
+    );
+
+    let solvency = SolvencyResponse::new(Uint128::new(400), Uint128::new(300));
+    assert_eq!(solvency.coverage_ratio, Decimal::percent(75));
+    assert_eq!(solvency.surplus, Uint128::zero());
+    assert_eq!(solvency.deficit, Uint128::new(100));
+    let solvency = SolvencyResponse::new(Uint128::zero(), Uint128::new(300));
+    assert_eq!(solvency.coverage_ratio, Decimal::one());
+    assert_eq!(solvency.surplus, Uint128::new(300));
+}
+
+#[test]
+fn test_vesting_receipt_token_id() {
+    let token_id = receipt_token_id("terra1account", 7);
+    assert_eq!(token_id, "terra1account/7");
+    assert_eq!(
+        parse_receipt_token_id(&token_id).unwrap(),
+        ("terra1account".to_string(), 7)
+    );
+    for token_id in ["terra1account", "terra1account/", "/7", "terra1account/x"] {
+        assert_eq!(
+            parse_receipt_token_id(token_id),
+            Err(StdError::generic_err(format!(
+                "Invalid receipt token id {}",
+                token_id
+            )))
+        );
+    }
+}
+
+#[test]
+fn test_vesting_receipt_claims() {
+    let schedule = |time: u64, amount: u128| VestingSchedule {
+        start_point: VestingSchedulePoint {
+            time,
+            amount: Uint128::new(amount),
+        },
+        end_point: None,
+        pauses: vec![],
+    };
+    let mut info = VestingInfo {
+        schedules: vec![schedule(100, 300), schedule(100, 500)],
+        released_amount: Uint128::zero(),
+        released_amounts: vec![],
+        revocable: false,
+        revocation: None,
+    };
+
+    // the holder of the second receipt claims first
+    info.release(&[1], Uint128::new(500), 200).unwrap();
+    assert_eq!(
+        info.claimable_amounts(200).unwrap(),
+        vec![Uint128::new(300), Uint128::zero()]
+    );
+    assert_eq!(
+        info.release(&[1], Uint128::new(1), 200),
+        Err(StdError::generic_err(
+            "The claim amount 1 exceeds the claimable amount 0"
+        ))
+    );
+    info.release(&[0], Uint128::new(300), 200).unwrap();
+    assert_eq!(
+        info.claimed_amounts(200).unwrap(),
+        vec![Uint128::new(300), Uint128::new(500)]
+    );
+    assert_eq!(info.released_amount, Uint128::new(800));
+    assert_eq!(
+        info.release(&[2], Uint128::new(1), 200),
+        Err(StdError::generic_err("Schedule 2 not found"))
+    );
+
+    // the amount released before the schedules were tracked is attributed in identifier order
+    info.released_amount = Uint128::new(400);
+    info.released_amounts = vec![];
+    info.release(&[1], Uint128::new(400), 200).unwrap();
+    assert_eq!(
+        info.released_amounts,
+        vec![Uint128::new(300), Uint128::new(500)]
+    );
+    assert!(info
+        .claimable_amounts(200)
+        .unwrap()
+        .iter()
+        .all(|amount| amount.is_zero()));
+}
+
+#[test]
+fn test_vesting_revoke() {
+    let mut info = VestingInfo {
+        schedules: vec![VestingSchedule {
+            start_point: VestingSchedulePoint {
+                time: 100,
+                amount: Uint128::zero(),
+            },
+            end_point: Some(VestingSchedulePoint {
+                time: 200,
+                amount: Uint128::new(1000),
+            }),
+            pauses: vec![],
+        }],
+        released_amount: Uint128::new(100),
+        released_amounts: vec![],
+        revocable: false,
+        revocation: None,
+    };
+    assert_eq!(
+        info.revoke(150),
+        Err(StdError::generic_err(
+            "The vesting account is not revocable"
+        ))
+    );
+
+    info.revocable = true;
+    assert_eq!(info.revoke(150).unwrap(), Uint128::new(500));
+    assert_eq!(
+        info.revocation,
+        Some(Revocation {
+            time: 150,
+            revoked_amount: Uint128::new(500),
+        })
+    );
+    // vesting stops at the revocation, the vested amount stays claimable
+    assert_eq!(info.vested_amount(1000).unwrap(), Uint128::new(500));
+    assert_eq!(info.claimable_amount(1000).unwrap(), Uint128::new(400));
+
+    assert_eq!(
+        info.revoke(160),
+        Err(StdError::generic_err(
+            "The vesting account is already revoked"
+        ))
+    );
+    assert_eq!(
+        info.add_schedules(vec![]),
+        Err(StdError::generic_err(
+            "Schedules can't be added to a revoked account"
+        ))
+    );
+}
+
+#[test]
+fn test_typed_swap_errors() {
+    let pool = Uint128::new(1_000_000);
+
+    assert_eq!(
+        compute_offer_amount(pool, pool, pool, Decimal::zero()).unwrap_err(),
+        SwapError::InsufficientLiquidity {
+            requested: pool,
+            available: pool,
+        }
+    );
+    // the commission is taken from the pool as well
+    assert_eq!(
+        compute_offer_amount(pool, pool, Uint128::new(999_000), Decimal::percent(1)).unwrap_err(),
+        SwapError::InsufficientLiquidity {
+            requested: Uint128::new(1_009_091),
+            available: pool,
+        }
+    );
+    assert_eq!(
+        compute_swap(pool, pool, Uint128::zero(), Decimal::zero()).unwrap_err(),
+        SwapError::ZeroAmount {}
+    );
+    assert_eq!(
+        compute_swap(Uint128::zero(), pool, pool, Decimal::zero()).unwrap_err(),
+        SwapError::PoolEmpty {}
+    );
+    assert_eq!(
+        compute_offer_amount(pool, Uint128::zero(), pool, Decimal::zero()).unwrap_err(),
+        SwapError::PoolEmpty {}
+    );
+    assert_eq!(
+        StdError::from(SwapError::PoolEmpty {}),
+        StdError::generic_err("The pool has no liquidity")
+    );
+
+    // the first deposit sets the requested price
+    let deposits = [Uint128::new(1_000), Uint128::new(2_010)];
+    assert!(assert_initial_price(deposits, Decimal::percent(200), None).is_ok());
+    assert_eq!(
+        assert_initial_price(deposits, Decimal::percent(190), None).unwrap_err(),
+        StdError::generic_err("The deposit sets the price 2.01, expected 1.9")
+    );
+    assert!(
+        assert_initial_price(deposits, Decimal::percent(190), Some(Decimal::percent(10))).is_ok()
+    );
+    assert!(assert_initial_price([pool, Uint128::zero()], Decimal::percent(200), None).is_err());
+    // a ratio above the Decimal range is rejected instead of overflowing
+    assert_eq!(
+        assert_initial_price([Uint128::new(1), Uint128::MAX], Decimal::percent(200), None)
+            .unwrap_err(),
+        StdError::generic_err(format!(
+            "The deposit sets the price {}, expected 2",
+            u128::MAX
+        ))
+    );
+
+    // an asymmetric first deposit seeds the pool at the price and refunds the excess
+    assert_eq!(
+        compute_initial_deposit(
+            [Uint128::new(1_000), Uint128::new(5_000)],
+            Decimal::percent(200)
+        )
+        .unwrap(),
+        (
+            [Uint128::new(1_000), Uint128::new(2_000)],
+            [Uint128::zero(), Uint128::new(3_000)]
+        )
+    );
+    assert_eq!(
+        compute_initial_deposit(
+            [Uint128::new(1_000), Uint128::new(500)],
+            Decimal::percent(200)
+        )
+        .unwrap(),
+        (
+            [Uint128::new(250), Uint128::new(500)],
+            [Uint128::new(750), Uint128::zero()]
+        )
+    );
+    assert_eq!(
+        compute_initial_deposit(
+            [Uint128::new(1_000), Uint128::zero()],
+            Decimal::percent(200)
+        )
+        .unwrap_err(),
+        StdError::generic_err("The first deposit is too small to set the initial price")
+    );
+    // the value of the first deposit at the price doesn't fit into a Uint128
+    let e30 = Uint128::new(1_000_000_000_000_000_000_000_000_000_000);
+    assert_eq!(
+        compute_initial_deposit([Uint128::MAX, e30], Decimal::percent(200)).unwrap(),
+        (
+            [e30.multiply_ratio(1u128, 2u128), e30],
+            [
+                Uint128::MAX - e30.multiply_ratio(1u128, 2u128),
+                Uint128::zero()
+            ]
+        )
+    );
+
+    let (return_amount, spread_amount, _) =
+        compute_swap(pool, pool, Uint128::new(10_000), Decimal::zero()).unwrap();
+    assert_eq!(
+        assert_max_spread(
+            None,
+            Decimal::percent(1),
+            Uint128::new(10_000),
+            return_amount,
+            spread_amount
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        assert_max_spread(
+            Some(Decimal::percent(50)),
+            Decimal::percent(1),
+            Uint128::new(10_000),
+            return_amount,
+            spread_amount
+        )
+        .unwrap_err(),
+        SwapError::MaxSpreadAssertion {
+            spread: Decimal::from_ratio(10_100u128, 20_000u128),
+            max_spread: Decimal::percent(1),
+        }
+    );
+
+    let err: StdError = SwapError::ZeroAmount {}.into();
+    assert_eq!(err, StdError::generic_err("The swap amount is zero"));
+}
+
+#[test]
+fn test_active_pools_limit() {
+    let active = vec![Addr::unchecked("lp0"), Addr::unchecked("lp1")];
+    let new = Addr::unchecked("lp2");
+
+    assert!(assert_active_pools_limit(&active, None, &new, &[]).is_ok());
+    // a zero limit set via UpdateConfig removes the limit
+    assert!(assert_active_pools_limit(&active, Some(0), &new, &[]).is_ok());
+    assert!(assert_active_pools_limit(&active, Some(3), &new, &[]).is_ok());
+    // updating an active pool doesn't change the count
+    assert!(assert_active_pools_limit(&active, Some(2), &active[0], &[]).is_ok());
+    assert_eq!(
+        assert_active_pools_limit(&active, Some(2), &new, &[]),
+        Err(StdError::generic_err(
+            "The number of active pools 3 exceeds the limit 2, specify the pools to evict"
+        ))
+    );
+    assert!(assert_active_pools_limit(&active, Some(2), &new, &[active[1].clone()]).is_ok());
+
+    assert_eq!(
+        assert_active_pools_limit(&active, Some(2), &new, &[Addr::unchecked("lp2")]),
+        Err(StdError::generic_err(
+            "Pool lp2 can't be activated and evicted at once"
+        ))
+    );
+    assert_eq!(
+        assert_active_pools_limit(&active, Some(2), &new, &[Addr::unchecked("lp3")]),
+        Err(StdError::generic_err(
+            "Pool lp3 is not active and can't be evicted"
+        ))
+    );
+    assert_eq!(
+        assert_active_pools_limit(
+            &active,
+            Some(2),
+            &new,
+            &[active[0].clone(), active[0].clone()]
+        ),
+        Err(StdError::generic_err("Pool lp0 is evicted more than once"))
+    );
+}
+
+#[test]
+fn test_staking_first_stake() {
+    assert_eq!(
+        compute_enter_shares(Uint128::new(999_999), Uint128::zero(), Uint128::zero()),
+        Err(StdError::generic_err(
+            "The first stake must be at least 1000000"
+        ))
+    );
+    assert_eq!(
+        compute_enter_shares(Uint128::new(1_000_000), Uint128::zero(), Uint128::zero()).unwrap(),
+        (Uint128::new(999_000), DEAD_SHARES)
+    );
+    // the dead shares can't be withdrawn
+    assert!(compute_leave_amount(
+        Uint128::new(1_000_000),
+        Uint128::new(1_000_000),
+        Uint128::new(1_000_000),
+        DEAD_SHARES
+    )
+    .is_err());
+    assert_eq!(
+        compute_leave_amount(
+            Uint128::new(999_000),
+            Uint128::new(1_000_000),
+            Uint128::new(1_000_000),
+            DEAD_SHARES
+        )
+        .unwrap(),
+        Uint128::new(999_000)
+    );
+    // a pool which had stakers before the migration holds no dead shares
+    assert_eq!(
+        compute_leave_amount(
+            Uint128::new(1_000_000),
+            Uint128::new(1_000_000),
+            Uint128::new(1_000_000),
+            Uint128::zero()
+        )
+        .unwrap(),
+        Uint128::new(1_000_000)
+    );
+    let snapshot = StakingSnapshot {
+        total_deposit: Uint128::new(2000),
+        total_shares: Uint128::new(1000),
+        dead_shares: Uint128::zero(),
+    };
+    let (amount, expected) = snapshot.leave(Uint128::new(1000)).unwrap();
+    assert_eq!(amount, Uint128::new(2000));
+    assert!(expected.total_shares.is_zero());
+    // the stake into the drained pool mints dead shares again
+    let (_, dead_shares, expected) = expected.enter(Uint128::new(1_000_000)).unwrap();
+    assert_eq!(dead_shares, DEAD_SHARES);
+    assert_eq!(expected.dead_shares, DEAD_SHARES);
+}
+
+#[test]
+fn test_stats_snapshot_timestamp() {
+    let day = DEFAULT_SNAPSHOT_INTERVAL;
+    assert_eq!(
+        get_snapshot_timestamp(None, day, day * 3 + 100).unwrap(),
+        day * 3
+    );
+    assert_eq!(
+        get_snapshot_timestamp(Some(day * 2), day, day * 3 + 100).unwrap(),
+        day * 3
+    );
+    assert_eq!(
+        get_snapshot_timestamp(Some(day * 3), day, day * 4 - 1),
+        Err(StdError::generic_err(format!(
+            "The next snapshot can be taken at {}",
+            day * 4
+        )))
+    );
+    assert_eq!(
+        get_snapshot_timestamp(None, 0, day),
+        Err(StdError::generic_err("Snapshot interval must be positive"))
+    );
+}
+
+#[test]
+fn test_staking_rate_alarm() {
+    assert_eq!(
+        compute_exchange_rate(Uint128::zero(), Uint128::zero()),
+        Decimal::one()
+    );
+    let rate = compute_exchange_rate(Uint128::new(1_200), Uint128::new(1_000));
+    assert_eq!(rate, Decimal::percent(120));
+
+    let max_change = Decimal::percent(5);
+    // regular fee inflow
+    let next = compute_exchange_rate(Uint128::new(1_250), Uint128::new(1_000));
+    assert_eq!(check_rate_change(rate, next, max_change), None);
+    // a donation moving the rate by 25%
+    let next = compute_exchange_rate(Uint128::new(1_500), Uint128::new(1_000));
+    assert_eq!(
+        check_rate_change(rate, next, max_change),
+        Some(Decimal::percent(25))
+    );
+    // the rate dropping is an alarm as well
+    let next = compute_exchange_rate(Uint128::new(1_080), Uint128::new(1_000));
+    assert_eq!(
+        check_rate_change(rate, next, max_change),
+        Some(Decimal::percent(10))
+    );
+}
+
+#[test]
+fn test_staking_voting_power() {
+    let balance = Uint128::new(100);
+    assert_eq!(
+        compute_voting_power(balance, Uint128::zero(), false).unwrap(),
+        balance
+    );
+    assert_eq!(
+        compute_voting_power(balance, Uint128::new(50), false).unwrap(),
+        Uint128::new(150)
+    );
+    // a delegating user keeps only the power delegated to it
+    assert_eq!(
+        compute_voting_power(balance, Uint128::new(50), true).unwrap(),
+        Uint128::new(50)
+    );
+}
+
+#[test]
+fn test_staking_lock_boost() {
+    assert_eq!(
+        compute_lock_boost(MAX_LOCK_DURATION).unwrap(),
+        MAX_LOCK_BOOST.parse::<Decimal>().unwrap()
+    );
+    assert_eq!(
+        compute_lock_boost(MAX_LOCK_DURATION / 2).unwrap(),
+        Decimal::percent(175)
+    );
+    assert!(compute_lock_boost(MIN_LOCK_DURATION).unwrap() > Decimal::one());
+    // the boost stays within the generator boost limit
+    validate_boost_multiplier(compute_lock_boost(MAX_LOCK_DURATION).unwrap()).unwrap();
+
+    for lock_duration in [MIN_LOCK_DURATION - 1, MAX_LOCK_DURATION + 1] {
+        assert_eq!(
+            compute_lock_boost(lock_duration),
+            Err(StdError::generic_err(
+                "The lock duration must be between 604800 and 63072000 seconds"
+            ))
+        );
+    }
+}
+
+#[test]
+fn test_staking_donation_attack() {
+    // the attacker makes the minimum first stake and donates ASTRO to inflate the exchange rate
+    let (attacker_shares, dead_shares) =
+        compute_enter_shares(Uint128::new(1_000_000), Uint128::zero(), Uint128::zero()).unwrap();
+    let total_shares = attacker_shares + dead_shares;
+    let donation = Uint128::new(1_000_000_000_000);
+    let total_deposit = Uint128::new(1_000_000) + donation;
+
+    // a victim staking less than the donation per share is rejected instead of minting zero shares
+    assert_eq!(
+        compute_enter_shares(Uint128::new(500_000), total_deposit, total_shares),
+        Err(StdError::generic_err(
+            "The stake is too small to mint any xASTRO"
+        ))
+    );
+
+    // a larger victim loses at most the value of one share
+    let victim_amount = Uint128::new(1_000_000_000);
+    let (victim_shares, victim_dead_shares) =
+        compute_enter_shares(victim_amount, total_deposit, total_shares).unwrap();
+    assert!(victim_dead_shares.is_zero());
+    let total_shares = total_shares + victim_shares;
+    let total_deposit = total_deposit + victim_amount;
+    let victim_value =
+        compute_leave_amount(victim_shares, total_deposit, total_shares, dead_shares).unwrap();
+    let share_value = total_deposit.multiply_ratio(1u128, total_shares.u128());
+    assert!(victim_amount - victim_value <= share_value);
+
+    // the attacker can't recover the part of the donation owned by the dead shares
+    let attacker_value =
+        compute_leave_amount(attacker_shares, total_deposit, total_shares, dead_shares).unwrap();
+    assert!(attacker_value < Uint128::new(1_000_000) + donation);
+}
+
+#[test]
+fn test_pair_key() {
+    let token = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0000"),
+    };
+    let native = |denom: &str| AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    };
+
+    assert_eq!(
+        pair_key(&[token.clone(), native("uusd")]),
+        pair_key(&[native("uusd"), token.clone()])
+    );
+    assert_eq!(
+        pair_key(&[native("uusd"), native("uluna")]),
+        pair_key(&[native("uLuna"), native("UUSD")])
+    );
+    assert_eq!(
+        pair_key(&[native("uusd"), native("uluna")]),
+        b"\x00\x05uluna\x00\x04uusd".to_vec()
+    );
+    // the length prefixes keep pairs with the same concatenated assets apart
+    assert_ne!(
+        pair_key(&[native("uatom"), native("uusdx")]),
+        pair_key(&[native("uatomu"), native("usdx")])
+    );
+    assert_ne!(
+        pair_key(&[token.clone(), native("uusd")]),
+        pair_key(&[token, native("uluna")])
+    );
+
+    assert!(assert_distinct_assets(&[native("uusd"), native("uluna")]).is_ok());
+    assert_eq!(
+        assert_distinct_assets(&[native("uusd"), native("UUSD")]),
+        Err(StdError::generic_err("Doubling assets in asset infos"))
+    );
+}
+
+#[test]
+fn test_max_spread_boundaries() {
+    assert_eq!(
+        resolve_max_spread(None, None).unwrap(),
+        Decimal::from_ratio(5u128, 1000u128)
+    );
+    assert_eq!(
+        resolve_max_spread(Some(Decimal::percent(50)), None).unwrap(),
+        Decimal::percent(50)
+    );
+    assert_eq!(
+        resolve_max_spread(Some(Decimal::percent(51)), None).unwrap_err(),
+        SwapError::Std(StdError::generic_err(
+            "Max spread 0.51 exceeds the allowed maximum 0.5"
+        ))
+    );
+    assert!(resolve_max_spread(Some(Decimal::percent(3)), Some(Decimal::percent(2))).is_err());
+    assert_eq!(
+        resolve_max_spread(Some(Decimal::percent(2)), Some(Decimal::percent(2))).unwrap(),
+        Decimal::percent(2)
+    );
+
+    // a spread equal to the maximum passes, one unit more fails
+    let max_spread = Decimal::percent(1);
+    assert_eq!(
+        assert_max_spread(
+            None,
+            max_spread,
+            Uint128::new(100),
+            Uint128::new(99),
+            Uint128::new(1)
+        ),
+        Ok(())
+    );
+    assert!(assert_max_spread(
+        None,
+        max_spread,
+        Uint128::new(100),
+        Uint128::new(98),
+        Uint128::new(2)
+    )
+    .is_err());
+
+    // with a belief price the spread is measured against the expected return
+    assert_eq!(
+        assert_max_spread(
+            Some(Decimal::percent(200)),
+            max_spread,
+            Uint128::new(200),
+            Uint128::new(99),
+            Uint128::zero()
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        assert_max_spread(
+            Some(Decimal::percent(200)),
+            max_spread,
+            Uint128::new(200),
+            Uint128::new(98),
+            Uint128::zero()
+        )
+        .unwrap_err(),
+        SwapError::MaxSpreadAssertion {
+            spread: Decimal::percent(2),
+            max_spread,
+        }
+    );
+    // returning more than expected is never a spread
+    assert_eq!(
+        assert_max_spread(
+            Some(Decimal::percent(200)),
+            Decimal::zero(),
+            Uint128::new(200),
+            Uint128::new(150),
+            Uint128::zero()
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_keeper_job_is_due() {
+    let reward = Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("astro0000"),
+        },
+        amount: Uint128::new(100),
+    };
+    let mut job = JobResponse {
+        id: 1,
+        params: JobParams {
+            name: "maker collect".to_string(),
+            contract: "maker0000".to_string(),
+            msg: to_binary(&"collect").unwrap(),
+            reward: reward.clone(),
+            interval: 3600,
+            condition: None,
+        },
+        funding: Asset {
+            amount: Uint128::new(150),
+            ..reward
+        },
+        last_performed: None,
+        executions: 0,
+    };
+
+    assert!(job.is_due(0));
+    job.last_performed = Some(1000);
+    assert!(!job.is_due(4599));
+    assert!(job.is_due(4600));
+    job.funding.amount = Uint128::new(99);
+    assert!(!job.is_due(4600));
+}
+
+#[test]
+fn test_boost_multiplier() {
+    assert!(validate_boost_multiplier(Decimal::one()).is_ok());
+    assert!(validate_boost_multiplier(Decimal::percent(250)).is_ok());
+    assert_eq!(
+        validate_boost_multiplier(Decimal::percent(251)),
+        Err(StdError::generic_err(
+            "Boost multiplier must be between 1 and 2.5"
+        ))
+    );
+    assert!(validate_boost_multiplier(Decimal::percent(99)).is_err());
+
+    assert_eq!(boosted_amount(Uint128::new(1000), None), Uint128::new(1000));
+    assert_eq!(
+        boosted_amount(Uint128::new(1000), Some(Decimal::percent(150))),
+        Uint128::new(1500)
+    );
+}
+
+#[test]
+fn test_paused_operations() {
+    let paused = PausedOperations::default();
+    assert!(paused.assert_allowed(PairOperation::Swap).is_ok());
+
+    // swaps are halted while withdrawals stay available
+    let paused = PausedOperations {
+        swap: true,
+        provide: true,
+        withdraw: false,
+        reference_price: None,
+    };
+    assert_eq!(
+        paused.assert_allowed(PairOperation::Swap),
+        Err(StdError::generic_err("Operation Swap is paused"))
+    );
+    assert!(paused.assert_allowed(PairOperation::Provide).is_err());
+    assert!(paused.assert_allowed(PairOperation::Withdraw).is_ok());
+}
+
+#[test]
+fn test_twap_order_slices() {
+    let params = OrderParams {
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "uluna".to_string(),
+        }],
+        slices: 3,
+        duration: 300,
+        oracle: "oracle0000".to_string(),
+        max_deviation: Decimal::percent(2),
+        receiver: None,
+    };
+    validate_order_params(&params).unwrap();
+    assert!(validate_order_params(&OrderParams {
+        slices: 0,
+        ..params.clone()
+    })
+    .is_err());
+    assert!(validate_order_params(&OrderParams {
+        duration: 2,
+        ..params.clone()
+    })
+    .is_err());
+    assert!(validate_order_params(&OrderParams {
+        max_deviation: Decimal::one(),
+        ..params.clone()
+    })
+    .is_err());
+
+    let mut order = OrderResponse {
+        order_id: 1,
+        creator: Addr::unchecked("creator"),
+        params,
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(1000),
+        },
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        start_time: 1000,
+        executed_slices: 0,
+        remaining_amount: Uint128::new(1000),
+        received_amount: Uint128::zero(),
+    };
+
+    let mut swapped = Uint128::zero();
+    for (slice, time) in [1000u64, 1100, 1200].iter().enumerate() {
+        assert_eq!(order.next_slice_time(), Some(*time), "slice {}", slice);
+        let amount = order.next_slice_amount();
+        swapped += amount;
+        order.remaining_amount -= amount;
+        order.executed_slices += 1;
+    }
+    assert_eq!(swapped, Uint128::new(1000));
+    assert_eq!(order.next_slice_time(), None);
+    assert_eq!(order.next_slice_amount(), Uint128::zero());
+
+    assert_eq!(
+        slice_minimum_receive(Uint128::new(1000), Decimal::percent(2)),
+        Uint128::new(980)
+    );
+}
+
+#[test]
+fn test_maker_distribution_throttle() {
+    let config = MakerConfigResponse {
+        owner: Addr::unchecked("owner"),
+        astro_token_contract: Addr::unchecked("astro0000"),
+        factory_contract: Addr::unchecked("factory0000"),
+        additional_factories: vec![],
+        staking_contract: Addr::unchecked("staking0000"),
+        governance_contract: None,
+        governance_percent: Uint64::zero(),
+        burn_percent: Uint64::zero(),
+        burned_astro: Uint128::zero(),
+        max_spread: Decimal::percent(5),
+        remainder_reward: Uint128::zero(),
+        pre_upgrade_astro_amount: Uint128::zero(),
+        remote_governance: None,
+        held_astro: Uint128::zero(),
+        min_distribution_interval: 3600,
+        min_distribution_amount: Uint128::new(1000),
+        last_distribution_time: 10_000,
+        insurance_fund: None,
+        insurance_percent: Uint64::zero(),
+        distribution_weights: vec![],
+        target_assets: vec![],
+        distribution_rate: None,
+        undistributed_astro: Uint128::new(10_000),
+        collect_cooldown: 0,
+        caller_reward_bps: 0,
+        last_collect_time: 0,
+        operator: None,
+    };
+
+    assert!(assert_distribution_allowed(&config, 13_600, Uint128::new(1000)).is_ok());
+    assert_eq!(
+        assert_distribution_allowed(&config, 13_599, Uint128::new(1000)),
+        Err(StdError::generic_err(
+            "ASTRO can't be distributed before 13600"
+        ))
+    );
+    assert_eq!(
+        assert_distribution_allowed(&config, 13_600, Uint128::new(999)),
+        Err(StdError::generic_err(
+            "The amount to distribute 999 is below the minimum 1000"
+        ))
+    );
+    assert_eq!(distributable_amount(&config, 13_600), Uint128::new(10_000));
+    let dripped = MakerConfigResponse {
+        distribution_rate: Some(Uint128::new(2)),
+        ..config.clone()
+    };
+    assert_eq!(distributable_amount(&dripped, 10_000), Uint128::zero());
+    assert_eq!(distributable_amount(&dripped, 13_600), Uint128::new(7_200));
+    assert_eq!(distributable_amount(&dripped, 20_000), Uint128::new(10_000));
+
+    // the status reports the thresholds and omits small balances
+    let status = maker_status(
+        &config,
+        13_600,
+        vec![
+            native_asset("uusd".to_string(), Uint128::new(500)),
+            native_asset("uluna".to_string(), Uint128::new(100)),
+            native_asset("ukrw".to_string(), Uint128::new(5_000)),
+        ],
+        Uint128::new(100),
+        |info| info != &native_asset_info("ukrw".to_string()),
+        true,
+    );
+    assert_eq!(
+        status.balances,
+        vec![
+            native_asset("uusd".to_string(), Uint128::new(500)),
+            native_asset("ukrw".to_string(), Uint128::new(5_000)),
+        ]
+    );
+    assert_eq!(
+        status.routable_assets,
+        vec![native_asset_info("uusd".to_string())]
+    );
+    assert_eq!(status.next_collect_time, 0);
+    assert_eq!(status.next_distribution_time, 13_600);
+    assert_eq!(status.distributable_astro, Uint128::new(10_000));
+    assert!(status.distribution_allowed);
+    assert!(
+        !maker_status(&config, 13_599, vec![], Uint128::zero(), |_| true, true)
+            .distribution_allowed
+    );
+    assert!(
+        !maker_status(&config, 13_600, vec![], Uint128::zero(), |_| true, false)
+            .distribution_allowed
+    );
+
+    // nothing to distribute even without thresholds
+    let config = MakerConfigResponse {
+        min_distribution_interval: 0,
+        min_distribution_amount: Uint128::zero(),
+        ..config
+    };
+    assert!(assert_distribution_allowed(&config, 10_000, Uint128::zero()).is_err());
+}
+
+#[test]
+fn test_offer_asset_commission() {
+    let pool = Uint128::new(1_000_000);
+
+    let (return_amount, spread_amount, commission_amount) =
+        compute_swap_offer_commission(pool, pool, Uint128::new(10_000), Decimal::percent(1))
+            .unwrap();
+    assert_eq!(commission_amount, Uint128::new(100));
+    assert_eq!(
+        (return_amount, spread_amount),
+        (Uint128::new(9_802), Uint128::new(98))
+    );
+    assert_eq!(
+        compute_swap(pool, pool, Uint128::new(9_900), Decimal::zero()).unwrap(),
+        (return_amount, spread_amount, Uint128::zero())
+    );
+
+    assert_eq!(
+        compute_swap_offer_commission(pool, pool, Uint128::new(1), Decimal::percent(100))
+            .unwrap_err(),
+        SwapError::ZeroAmount {}
+    );
+}
+
+#[test]
+fn test_pools_schedule() {
+    let pools = vec![
+        ("lp1".to_string(), Decimal::percent(60)),
+        ("lp2".to_string(), Decimal::zero()),
+    ];
+    let current = [Decimal::percent(30), Decimal::percent(20)];
+    let total = Decimal::percent(80);
+
+    assert!(
+        validate_pools_schedule(&pools, &current, total, 1000, 1000 + MIN_SCHEDULE_DELAY).is_ok()
+    );
+    assert_eq!(
+        validate_pools_schedule(&pools, &current, total, 1000, 999 + MIN_SCHEDULE_DELAY),
+        Err(StdError::generic_err(format!(
+            "Allocation point changes can't take effect before {}",
+            1000 + MIN_SCHEDULE_DELAY
+        )))
+    );
+    assert_eq!(
+        validate_pools_schedule(&[], &[], total, 1000, 1000 + MIN_SCHEDULE_DELAY),
+        Err(StdError::generic_err("No pools are scheduled"))
+    );
+
+    let pools = vec![
+        ("lp1".to_string(), Decimal::percent(30)),
+        ("lp1".to_string(), Decimal::percent(20)),
+    ];
+    assert_eq!(
+        validate_pools_schedule(&pools, &current, total, 1000, 1000 + MIN_SCHEDULE_DELAY),
+        Err(StdError::generic_err(
+            "Pool lp1 is scheduled more than once"
+        ))
+    );
+
+    let pools = vec![
+        ("lp1".to_string(), Decimal::percent(60)),
+        ("lp2".to_string(), Decimal::percent(41)),
+    ];
+    assert_eq!(
+        validate_pools_schedule(
+            &pools,
+            &[Decimal::zero(); 2],
+            Decimal::zero(),
+            1000,
+            1000 + MIN_SCHEDULE_DELAY
+        ),
+        Err(StdError::generic_err(
+            "The total allocation share 1.01 after the scheduled changes can't exceed 1"
+        ))
+    );
+
+    // the shares of the pools which are not listed are kept
+    let pools = vec![("lp1".to_string(), Decimal::percent(60))];
+    assert_eq!(
+        validate_pools_schedule(
+            &pools,
+            &[Decimal::percent(30)],
+            total,
+            1000,
+            1000 + MIN_SCHEDULE_DELAY
+        ),
+        Err(StdError::generic_err(
+            "The total allocation share 1.1 after the scheduled changes can't exceed 1"
+        ))
+    );
+    assert!(validate_pools_schedule(
+        &pools,
+        &[Decimal::percent(30)],
+        Decimal::percent(70),
+        1000,
+        1000 + MIN_SCHEDULE_DELAY
+    )
+    .is_ok());
+    // inconsistent current shares are rejected instead of panicking
+    assert!(validate_pools_schedule(
+        &pools,
+        &[Decimal::percent(90)],
+        total,
+        1000,
+        1000 + MIN_SCHEDULE_DELAY
+    )
+    .is_err());
+}
+
+#[test]
+fn test_generator_lp_token_validation() {
+    let deps = mock_dependencies(&[]);
+    let querier = deps.as_ref().querier;
+    let whitelist = [Addr::unchecked("external_lp")];
+
+    validate_lp_token(&querier, None, &whitelist[0], &whitelist).unwrap();
+    assert_eq!(
+        validate_lp_token(&querier, None, &Addr::unchecked("typo"), &whitelist),
+        Err(StdError::generic_err(
+            "typo is not whitelisted and the factory is not set"
+        ))
+    );
+}
+
+#[test]
+fn test_generator_proxy_harvest_due() {
+    // without an interval proxies can be harvested at any time
+    assert!(is_proxy_harvest_due(Some(1000), None, 1000));
+    // the first harvest of a pool is always allowed
+    assert!(is_proxy_harvest_due(None, Some(3600), 1000));
+    assert!(!is_proxy_harvest_due(Some(1000), Some(3600), 4599));
+    assert!(is_proxy_harvest_due(Some(1000), Some(3600), 4600));
+    assert!(!is_proxy_harvest_due(
+        Some(u64::MAX - 1),
+        Some(3600),
+        u64::MAX - 1
+    ));
+}
+
+#[test]
+fn test_generator_registration_deposit() {
+    assert_registration_deposit(None, &[]).unwrap();
+
+    let deposit = Coin::new(100_000000, "uusd");
+    assert_registration_deposit(Some(&deposit), &[Coin::new(100_000000, "uusd")]).unwrap();
+    assert_registration_deposit(
+        Some(&deposit),
+        &[Coin::new(1, "uluna"), Coin::new(150_000000, "uusd")],
+    )
+    .unwrap();
+    assert_eq!(
+        assert_registration_deposit(Some(&deposit), &[Coin::new(99_999999, "uusd")]),
+        Err(StdError::generic_err(
+            "A registration deposit of 100000000uusd is required"
+        ))
+    );
+    assert_eq!(
+        assert_registration_deposit(Some(&deposit), &[Coin::new(100_000000, "uluna")]),
+        Err(StdError::generic_err(
+            "A registration deposit of 100000000uusd is required"
+        ))
+    );
+}
+
+#[test]
+fn test_generator_deposit_caps() {
+    let caps = DepositCaps {
+        per_user: Some(Uint128::new(100)),
+        per_pool: Some(Uint128::new(1000)),
+    };
+    caps.assert_deposit_allowed(Uint128::new(100), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        caps.assert_deposit_allowed(Uint128::new(101), Uint128::new(500)),
+        Err(StdError::generic_err(
+            "The deposit exceeds the cap of 100 per user"
+        ))
+    );
+    assert_eq!(
+        caps.assert_deposit_allowed(Uint128::new(50), Uint128::new(1001)),
+        Err(StdError::generic_err(
+            "The deposit exceeds the pool cap of 1000"
+        ))
+    );
+
+    let caps = DepositCaps {
+        per_user: None,
+        per_pool: None,
+    };
+    caps.assert_deposit_allowed(Uint128::MAX, Uint128::MAX)
+        .unwrap();
+}
+
+#[test]
+fn test_generator_effective_stake() {
+    let stake =
+        effective_stake(Uint128::new(100), Decimal::percent(150), Uint128::new(600)).unwrap();
+    assert_eq!(stake.effective, Uint128::new(150));
+    assert_eq!(stake.max_effective, Uint128::new(250));
+    assert_eq!(stake.emission_share, Decimal::percent(25));
+
+    let stake = effective_stake(Uint128::zero(), Decimal::one(), Uint128::zero()).unwrap();
+    assert_eq!(stake.effective, Uint128::zero());
+    assert_eq!(stake.emission_share, Decimal::zero());
+}
+
+#[test]
+fn test_generator_simulate_rewards() {
+    assert_eq!(estimate_blocks(7 * 86400), 7 * 86400 * 1000 / BLOCK_TIME_MS);
+    assert_eq!(estimate_blocks(5), 5 * 1000 / BLOCK_TIME_MS);
+
+    let stake =
+        effective_stake(Uint128::new(100), Decimal::percent(150), Uint128::new(600)).unwrap();
+    let schedule = vec![EmissionPoint {
+        start_block: 150,
+        tokens_per_block: Uint128::new(50),
+    }];
+    // 50 blocks at the base rate, then 50 blocks at the scheduled rate
+    let emissions = get_emissions(Uint128::new(100), &schedule, 100, 200).unwrap();
+    assert_eq!(emissions, Uint128::new(7500));
+    assert_eq!(
+        project_rewards(
+            emissions,
+            Decimal::percent(10),
+            Decimal::percent(30),
+            &stake
+        ),
+        (Uint128::new(2500), Uint128::new(625))
+    );
+    assert_eq!(
+        project_rewards(emissions, Decimal::zero(), Decimal::zero(), &stake),
+        (Uint128::zero(), Uint128::zero())
+    );
+}
+
+#[test]
+fn test_generator_emission_schedule() {
+    let schedule = vec![
+        EmissionPoint {
+            start_block: 200,
+            tokens_per_block: Uint128::new(50),
+        },
+        EmissionPoint {
+            start_block: 300,
+            tokens_per_block: Uint128::new(20),
+        },
+    ];
+    validate_emission_schedule(&schedule, 100).unwrap();
+    assert_eq!(
+        validate_emission_schedule(&schedule, 200),
+        Err(StdError::generic_err(
+            "Emission point start block 200 must be greater than 200"
+        ))
+    );
+    let unordered = [schedule[1].clone(), schedule[0].clone()];
+    assert_eq!(
+        validate_emission_schedule(&unordered, 100),
+        Err(StdError::generic_err(
+            "Emission point start block 200 must be greater than 300"
+        ))
+    );
+
+    let base = Uint128::new(100);
+    assert_eq!(get_tokens_per_block(base, &schedule, 199), base);
+    assert_eq!(get_tokens_per_block(base, &schedule, 200), Uint128::new(50));
+    assert_eq!(
+        get_tokens_per_block(base, &schedule, 1000),
+        Uint128::new(20)
+    );
+
+    // within a single rate
+    assert_eq!(
+        get_emissions(base, &schedule, 100, 150).unwrap(),
+        Uint128::new(5000)
+    );
+    // across both boundaries: 100 * 100 + 100 * 50 + 50 * 20
+    assert_eq!(
+        get_emissions(base, &schedule, 100, 350).unwrap(),
+        Uint128::new(16000)
+    );
+    // starting exactly at a boundary
+    assert_eq!(
+        get_emissions(base, &schedule, 200, 300).unwrap(),
+        Uint128::new(5000)
+    );
+    assert_eq!(
+        get_emissions(base, &schedule, 350, 350).unwrap(),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_pool_activation_block() {
+    // pools without an activation block earn since their last reward update
+    assert_eq!(get_rewarded_blocks(100, 150, None), 50);
+    // nothing is earned before the activation block
+    assert_eq!(get_rewarded_blocks(100, 150, Some(200)), 0);
+    assert_eq!(get_rewarded_blocks(100, 200, Some(200)), 0);
+    // emissions start at the activation block, not at the creation of the pool
+    assert_eq!(get_rewarded_blocks(100, 250, Some(200)), 50);
+    assert_eq!(get_rewarded_blocks(220, 250, Some(200)), 30);
+
+    validate_activation_block(100, 101).unwrap();
+    assert_eq!(
+        validate_activation_block(100, 100),
+        Err(StdError::generic_err(
+            "The activation block must be after the current block 100"
+        ))
+    );
+}
+
+#[test]
+fn test_insurance_fund_payout() {
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let astro = AssetInfo::Token {
+        contract_addr: Addr::unchecked("astro0000"),
+    };
+    let available = vec![Asset {
+        info: uusd.clone(),
+        amount: Uint128::new(1000),
+    }];
+
+    let payout = vec![Asset {
+        info: uusd.clone(),
+        amount: Uint128::new(1000),
+    }];
+    assert!(assert_payout_covered(&available, &payout).is_ok());
+
+    let payout = vec![Asset {
+        info: uusd.clone(),
+        amount: Uint128::new(1001),
+    }];
+    assert_eq!(
+        assert_payout_covered(&available, &payout),
+        Err(StdError::generic_err(
+            "Insufficient fund balance of uusd: 1000 < 1001"
+        ))
+    );
+
+    let payout = vec![Asset {
+        info: astro,
+        amount: Uint128::new(1),
+    }];
+    assert_eq!(
+        assert_payout_covered(&available, &payout),
+        Err(StdError::generic_err(
+            "Insufficient fund balance of astro0000: 0 < 1"
+        ))
+    );
+
+    let payout = vec![
+        Asset {
+            info: uusd.clone(),
+            amount: Uint128::new(500),
+        },
+        Asset {
+            info: uusd.clone(),
+            amount: Uint128::new(500),
+        },
+    ];
+    assert_eq!(
+        assert_payout_covered(&available, &payout),
+        Err(StdError::generic_err("Duplicated payout asset uusd"))
+    );
+    assert!(assert_payout_covered(&available, &[]).is_err());
+
+    let mut payout = PayoutResponse {
+        id: 1,
+        recipient: Addr::unchecked("recipient"),
+        assets: available,
+        description: "exploit compensation".to_string(),
+        executable_at: 1000,
+        status: PayoutStatus::Pending {},
+    };
+    assert!(!payout.is_executable(999));
+    assert!(payout.is_executable(1000));
+    payout.status = PayoutStatus::Cancelled {};
+    assert!(!payout.is_executable(1000));
+}
+
+#[test]
+fn test_stable_imbalance_fee() {
+    let amp = 100;
+    let params = ImbalanceFeeParams {
+        fee_multiplier: Decimal::from_ratio(2u8, 1u8),
+        rebate_multiplier: Decimal::percent(50),
+    };
+    let fee = Decimal::permille(5);
+    let balanced = [Uint128::new(1_000_000), Uint128::new(1_000_000)];
+    let lopsided = [Uint128::new(1_500_000), Uint128::new(1_000_000)];
+
+    assert_eq!(compute_imbalance(amp, balanced).unwrap(), Decimal::zero());
+    assert!(compute_imbalance(amp, lopsided).unwrap() > Decimal::zero());
+
+    assert_eq!(
+        compute_imbalance_fee(amp, &params, fee, balanced, lopsided).unwrap(),
+        ImbalanceFee::Charge(Decimal::percent(1))
+    );
+    assert_eq!(
+        compute_imbalance_fee(
+            amp,
+            &params,
+            fee,
+            lopsided,
+            [Uint128::new(1_500_000), Uint128::new(1_400_000)]
+        )
+        .unwrap(),
+        ImbalanceFee::Rebate(Decimal::from_ratio(25u8, 10_000u16))
+    );
+    assert_eq!(
+        compute_imbalance_fee(
+            amp,
+            &params,
+            fee,
+            balanced,
+            [Uint128::new(2_000_000), Uint128::new(2_000_000)]
+        )
+        .unwrap(),
+        ImbalanceFee::None
+    );
+
+    assert!(params.validate().is_ok());
+    assert_eq!(
+        ImbalanceFeeParams {
+            rebate_multiplier: Decimal::percent(101),
+            ..params
+        }
+        .validate(),
+        Err(StdError::generic_err(
+            "Imbalance rebate multiplier can't exceed 1"
+        ))
+    );
+    assert!(ImbalanceFeeParams {
+        fee_multiplier: Decimal::from_ratio(11u8, 1u8),
+        ..params
+    }
+    .validate()
+    .is_err());
+}
+
+#[test]
+fn test_native_denom_validation() {
+    let deps = mock_dependencies(&[]);
+    let hash = "27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let ibc_denom = format!("ibc/{}", hash);
+    for denom in [
+        "uusd",
+        "uluna",
+        ibc_denom.as_str(),
+        "factory/terra1creator/token",
+        "gamm/pool/1",
+    ] {
+        validate_native_denom(&deps.api, denom).unwrap();
+        native_asset_info(denom.to_string())
+            .check(&deps.api)
+            .unwrap();
+    }
+
+    for (denom, reason) in [
+        ("us", "length must be between 3 and 128"),
+        (
+            "ibc/27394fb092d2eccd",
+            "the hash must be 64 uppercase hex characters",
+        ),
+        (
+            "factory/terra1creator",
+            "tokenfactory denoms must be factory/{creator}/{subdenom}",
+        ),
+        (
+            "factory/terra1creator/",
+            "the subdenom length must be between 1 and 44",
+        ),
+        (
+            "1usd",
+            "only letters, digits and /:._- are allowed, starting with a letter",
+        ),
+        (
+            "u usd",
+            "only letters, digits and /:._- are allowed, starting with a letter",
+        ),
+    ] {
+        assert_eq!(
+            validate_native_denom(&deps.api, denom),
+            Err(StdError::generic_err(format!(
+                "Invalid native denom {}: {}",
+                denom, reason
+            )))
+        );
+    }
+    assert_eq!(
+        validate_native_denom(&deps.api, "UUSD"),
+        Err(StdError::generic_err(
+            "Native token denom UUSD should be lowercase"
+        ))
+    );
+
+    assert_eq!(normalize_native_denom("UUSD"), "uusd");
+    assert_eq!(
+        normalize_native_denom(&format!("IBC/{}", hash.to_lowercase())),
+        ibc_denom
+    );
+    validate_native_denom(
+        &deps.api,
+        &normalize_native_denom(
+            "IBC/27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2",
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_restricted_denoms() {
+    assert!(is_restricted_denom("factory/terra1creator/token"));
+    assert!(!is_restricted_denom("uusd"));
+
+    let res = TransferAllowedResponse {
+        allowed: true,
+        reason: None,
+    };
+    assert!(res.assert_allowed("factory/creator/token").is_ok());
+
+    let res = TransferAllowedResponse {
+        allowed: false,
+        reason: Some("recipient is frozen".to_string()),
+    };
+    assert_eq!(
+        res.assert_allowed("factory/creator/token"),
+        Err(StdError::generic_err(
+            "Transfer of factory/creator/token is restricted: recipient is frozen"
+        ))
+    );
+    let res = TransferAllowedResponse {
+        allowed: false,
+        reason: None,
+    };
+    assert_eq!(
+        res.assert_allowed("factory/creator/token"),
+        Err(StdError::generic_err(
+            "Transfer of factory/creator/token is restricted: rejected by the registry"
+        ))
+    );
+
+    // unrestricted denoms are not probed
+    let deps = mock_dependencies(&[]);
+    let msg = native_asset("uusd".to_string(), Uint128::new(100))
+        .into_checked_msg(
+            &deps.as_ref().querier,
+            Some(Addr::unchecked("registry")),
+            Addr::unchecked("sender"),
+            Addr::unchecked("recipient"),
+        )
+        .unwrap();
+    assert_eq!(
+        msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: vec![Coin::new(100, "uusd")],
+        })
+    );
+}
+
+#[test]
+fn test_factory_batch_push_fees() {
+    let op = FactoryOp::UpdatePairConfig {
+        config: PairConfig {
+            code_id: 1,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 3333,
+            is_disabled: None,
+            reserves_checkpoint_interval: None,
+            max_allowed_spread: None,
+            commission_mode: CommissionMode::default(),
+            permissionless: false,
+            register_in_generator: false,
+            register_in_oracle: false,
+            lp_transfer_hook: false,
+            dynamic_fee_bounds: None,
+            tokenfactory_lp: false,
+        },
+        push_fees: false,
+    };
+    let json = String::from_utf8(to_binary(&op).unwrap().to_vec()).unwrap();
+    assert!(json.ends_with(r#","push_fees":false}}"#));
+
+    // batches built before the fee push was supported don't push fees
+    let legacy = json.replace(r#","push_fees":false"#, "");
+    assert_eq!(
+        from_binary::<FactoryOp>(&Binary::from(legacy.as_bytes())).unwrap(),
+        op
+    );
+}
+
+#[test]
+fn test_factory_config_export() {
+    let pair_config = PairConfig {
+        code_id: 1,
+        pair_type: PairType::Xyk {},
+        total_fee_bps: 30,
+        maker_fee_bps: 3333,
+        is_disabled: None,
+        reserves_checkpoint_interval: None,
+        max_allowed_spread: None,
+        commission_mode: CommissionMode::default(),
+        permissionless: false,
+        register_in_generator: false,
+        register_in_oracle: false,
+        lp_transfer_hook: false,
+        dynamic_fee_bounds: None,
+        tokenfactory_lp: false,
+    };
+    let export = ConfigExport {
+        version: CONFIG_EXPORT_VERSION,
+        pair_configs: vec![pair_config.clone()],
+        token_code_id: 2,
+        whitelist_code_id: 3,
+        fee_address: Some(Addr::unchecked("maker")),
+        generator_address: None,
+        volume_tracker: None,
+        guardian: None,
+        pair_creation_hooks: vec![Addr::unchecked("generator")],
+        oracle_factory: Some(Addr::unchecked("oracle_factory")),
+        creation_rate_limit: Some(CreationRateLimit {
+            cooldown: 60,
+            max_per_block: 2,
+        }),
+        deployers: vec![Addr::unchecked("deployer")],
+        fee_discount_router: Some(Addr::unchecked("router")),
+        max_fee_discount_bps: 5000,
+        pair_fee_overrides: vec![(
+            Addr::unchecked("pair0000"),
+            PairFees {
+                total_fee_bps: 10,
+                maker_fee_bps: 5000,
+            },
+        )],
+        frozen: true,
+    };
+
+    // the round trip keeps every field
+    let blob = to_binary(&export).unwrap();
+    assert_eq!(ConfigExport::from_blob(&blob).unwrap(), export);
+
+    let invalid = ConfigExport {
+        version: 1,
+        ..export.clone()
+    };
+    assert_eq!(
+        ConfigExport::from_blob(&to_binary(&invalid).unwrap()),
+        Err(StdError::generic_err("Unsupported config export version 1"))
+    );
+
+    let invalid = ConfigExport {
+        pair_fee_overrides: vec![(
+            Addr::unchecked("pair0000"),
+            PairFees {
+                total_fee_bps: 10_001,
+                maker_fee_bps: 0,
+            },
+        )],
+        ..export.clone()
+    };
+    assert_eq!(
+        ConfigExport::from_blob(&to_binary(&invalid).unwrap()),
+        Err(StdError::generic_err("Fee bps can't exceed 10000"))
+    );
+
+    let invalid = ConfigExport {
+        pair_configs: vec![pair_config.clone(), pair_config.clone()],
+        ..export.clone()
+    };
+    assert_eq!(
+        ConfigExport::from_blob(&to_binary(&invalid).unwrap()),
+        Err(StdError::generic_err(
+            "Duplicated pair config of pair type xyk"
+        ))
+    );
+
+    let invalid = ConfigExport {
+        pair_configs: vec![PairConfig {
+            total_fee_bps: 10_001,
+            ..pair_config
+        }],
+        ..export
+    };
+    assert_eq!(
+        ConfigExport::from_blob(&to_binary(&invalid).unwrap()),
+        Err(StdError::generic_err(
+            "Invalid pair config of pair type xyk"
+        ))
+    );
+}
+
+#[test]
+fn test_pair_registration_msgs() {
+    let pair_config = PairConfig {
+        code_id: 1,
+        pair_type: PairType::Xyk {},
+        total_fee_bps: 30,
+        maker_fee_bps: 3333,
+        is_disabled: None,
+        reserves_checkpoint_interval: None,
+        max_allowed_spread: None,
+        commission_mode: CommissionMode::default(),
+        permissionless: false,
+        register_in_generator: true,
+        register_in_oracle: false,
+        lp_transfer_hook: false,
+        dynamic_fee_bounds: None,
+        tokenfactory_lp: false,
+    };
+    let pair_info = PairInfo {
+        asset_infos: [
+            native_asset_info("uluna".to_string()),
+            native_asset_info("uusd".to_string()),
+        ],
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+        capabilities: vec![],
+        metadata: None,
+        official: false,
+    };
+    let generator = Addr::unchecked("generator");
+    let oracle_factory = Addr::unchecked("oracle_factory");
+
+    let msgs = pair_config
+        .registration_msgs(&pair_info, Some(&generator), Some(&oracle_factory))
+        .unwrap();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: generator.to_string(),
+            msg: to_binary(&GeneratorExecuteMsg::OnPairCreated {
+                pair_info: pair_info.clone(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let pair_config = PairConfig {
+        register_in_oracle: true,
+        lp_transfer_hook: false,
+        dynamic_fee_bounds: None,
+        ..pair_config
+    };
+    assert_eq!(
+        pair_config
+            .registration_msgs(&pair_info, Some(&generator), Some(&oracle_factory))
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        pair_config.registration_msgs(&pair_info, Some(&generator), None),
+        Err(StdError::generic_err(
+            "Pair config xyk registers pairs in the oracle factory, but it is not set"
+        ))
+    );
+}
+
+#[test]
+fn test_pair_fees_push() {
+    let pair_config = PairConfig {
+        code_id: 1,
+        pair_type: PairType::Xyk {},
+        total_fee_bps: 30,
+        maker_fee_bps: 3333,
+        is_disabled: None,
+        reserves_checkpoint_interval: None,
+        max_allowed_spread: None,
+        commission_mode: CommissionMode::default(),
+        permissionless: false,
+        register_in_generator: false,
+        register_in_oracle: false,
+        lp_transfer_hook: false,
+        dynamic_fee_bounds: None,
+        tokenfactory_lp: false,
+    };
+    let fees = pair_config.fees();
+    fees.validate().unwrap();
+    assert_eq!(fees.commission_rate(), Decimal::permille(3));
+    assert_eq!(
+        fees.maker_fee_rate(),
+        Decimal::from_ratio(3333u16, 10_000u16)
+    );
+
+    let fees = PairFees {
+        total_fee_bps: 10_001,
+        ..fees
+    };
+    assert_eq!(
+        fees.validate(),
+        Err(StdError::generic_err("Fee bps can't exceed 10000"))
+    );
+}
+
+#[test]
+fn test_factory_freeze() {
+    let create_pair = FactoryExecuteMsg::CreatePair {
+        pair_type: PairType::Xyk {},
+        asset_infos: [
+            native_asset_info("uusd".to_string()),
+            native_asset_info("uluna".to_string()),
+        ],
+        init_params: None,
+        metadata: None,
+    };
+    create_pair.assert_allowed_when_frozen(false).unwrap();
+    assert_eq!(
+        create_pair.assert_allowed_when_frozen(true),
+        Err(StdError::generic_err(
+            "The factory is frozen for a migration"
+        ))
+    );
+    assert!(FactoryExecuteMsg::ImportConfig {
+        blob: Binary::default()
+    }
+    .assert_allowed_when_frozen(true)
+    .is_err());
+
+    // the factory can be unfrozen and handed over while frozen
+    FactoryExecuteMsg::Unfreeze {}
+        .assert_allowed_when_frozen(true)
+        .unwrap();
+    FactoryExecuteMsg::ClaimOwnership {}
+        .assert_allowed_when_frozen(true)
+        .unwrap();
+}
+
+#[test]
+fn test_pair_creation_rate_limit() {
+    let limit = CreationRateLimit {
+        cooldown: 3600,
+        max_per_block: 2,
+    };
+    limit.assert_allowed(None, 0, 1000).unwrap();
+    limit.assert_allowed(Some(1000), 1, 4600).unwrap();
+    assert_eq!(
+        limit.assert_allowed(Some(1000), 0, 4599),
+        Err(StdError::generic_err(
+            "Pair creation is rate limited until 4600"
+        ))
+    );
+    assert_eq!(
+        limit.assert_allowed(None, 2, 1000),
+        Err(StdError::generic_err(
+            "At most 2 pairs can be created per block"
+        ))
+    );
+}
+
+#[test]
+fn test_permissionless_pair_creation() {
+    let pair_config = PairConfig {
+        code_id: 1,
+        pair_type: PairType::Xyk {},
+        total_fee_bps: 30,
+        maker_fee_bps: 3333,
+        is_disabled: None,
+        reserves_checkpoint_interval: None,
+        max_allowed_spread: None,
+        commission_mode: CommissionMode::default(),
+        permissionless: false,
+        register_in_generator: false,
+        register_in_oracle: false,
+        lp_transfer_hook: false,
+        dynamic_fee_bounds: None,
+        tokenfactory_lp: false,
+    };
+    pair_config.assert_creation_allowed(true).unwrap();
+    assert_eq!(
+        pair_config.assert_creation_allowed(false),
+        Err(StdError::generic_err("Only the owner can create xyk pairs"))
+    );
+
+    let pair_config = PairConfig {
+        permissionless: true,
+        ..pair_config
+    };
+    pair_config.assert_creation_allowed(false).unwrap();
+
+    let pair_config = PairConfig {
+        is_disabled: Some(true),
+        ..pair_config
+    };
+    for is_owner in [true, false] {
+        assert_eq!(
+            pair_config.assert_creation_allowed(is_owner),
+            Err(StdError::generic_err("Pair config xyk is disabled"))
+        );
+    }
+}
+
+#[test]
+fn test_maker_distribution_weights() {
+    let weights = vec![
+        ("staking".to_string(), Decimal::percent(50)),
+        ("governance".to_string(), Decimal::percent(30)),
+        ("treasury".to_string(), Decimal::percent(20)),
+    ];
+    assert!(validate_distribution_weights(&weights).is_ok());
+    assert!(validate_distribution_weights(&[]).is_ok());
+
+    assert_eq!(
+        validate_distribution_weights(&weights[..2]),
+        Err(StdError::generic_err(
+            "Distribution weights must sum to 1, got 0.8"
+        ))
+    );
+    assert_eq!(
+        validate_distribution_weights(&[
+            ("staking".to_string(), Decimal::percent(50)),
+            ("staking".to_string(), Decimal::percent(50)),
+        ]),
+        Err(StdError::generic_err(
+            "Duplicated distribution destination staking"
+        ))
+    );
+    assert_eq!(
+        validate_distribution_weights(&[
+            ("staking".to_string(), Decimal::one()),
+            ("governance".to_string(), Decimal::zero()),
+        ]),
+        Err(StdError::generic_err(
+            "The weight of governance must be positive"
+        ))
+    );
+
+    let weights: Vec<(Addr, Decimal)> = vec![
+        (Addr::unchecked("staking"), Decimal::from_ratio(1u8, 3u8)),
+        (Addr::unchecked("governance"), Decimal::from_ratio(1u8, 3u8)),
+        (Addr::unchecked("treasury"), Decimal::from_ratio(1u8, 3u8)),
+    ];
+    assert_eq!(
+        split_by_weights(Uint128::new(100), &weights),
+        vec![
+            (Addr::unchecked("staking"), Uint128::new(33)),
+            (Addr::unchecked("governance"), Uint128::new(33)),
+            (Addr::unchecked("treasury"), Uint128::new(34)),
+        ]
+    );
+}
+
+#[test]
+fn test_maker_buyback_tranches() {
+    let params = BuybackParams {
+        max_price_impact: Decimal::percent(1),
+        min_blocks_between_tranches: 1,
+    };
+    params.validate().unwrap();
+
+    // offering 1_000_000 * 0.01 / 0.99 moves the price of a 1_000_000 pool by 1%
+    let offer_pool = Uint128::new(1_000_000);
+    let tranche = params.next_tranche_amount(Uint128::new(100_000), offer_pool);
+    assert_eq!(tranche, Uint128::new(10_101));
+    assert!(Decimal::from_ratio(tranche, offer_pool + tranche) <= params.max_price_impact);
+    // the last tranche only swaps what is left
+    assert_eq!(
+        params.next_tranche_amount(Uint128::new(5_000), offer_pool),
+        Uint128::new(5_000)
+    );
+
+    for max_price_impact in [Decimal::zero(), Decimal::percent(11)] {
+        assert_eq!(
+            BuybackParams {
+                max_price_impact,
+                ..params.clone()
+            }
+            .validate(),
+            Err(StdError::generic_err(
+                "The tranche price impact must be positive and at most 0.1"
+            ))
+        );
+    }
+    assert_eq!(
+        BuybackParams {
+            min_blocks_between_tranches: 0,
+            ..params
+        }
+        .validate(),
+        Err(StdError::generic_err(
+            "Tranches must be at least one block apart"
+        ))
+    );
+}
+
+#[test]
+fn test_maker_native_swap_msg() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::new(1000000u128))],
+    );
+    let pair = Addr::unchecked("pair0000");
+    let max_spread = Decimal::percent(5);
+
+    // the tax is deducted from the attached coins
+    let msg = build_swap_msg(
+        &deps.as_ref().querier,
+        &pair,
+        native_asset("uusd".to_string(), Uint128::new(123123)),
+        max_spread,
+        None,
+    )
+    .unwrap();
+    let offer_asset = native_asset("uusd".to_string(), Uint128::new(UUSD_AFTER_TAX));
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: pair.to_string(),
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset,
+                belief_price: None,
+                max_spread: Some(max_spread),
+                to: None,
+                deadline: None,
+                fill_mode: FillMode::FillOrKill {},
+                fee_discount_bps: 0,
+            })
+            .unwrap(),
+            funds: vec![Coin::new(UUSD_AFTER_TAX, "uusd")],
+        })
+    );
+
+    // the gas token has no tax cap
+    let msg = build_swap_msg(
+        &deps.as_ref().querier,
+        &pair,
+        native_asset("uluna".to_string(), Uint128::new(1000)),
+        max_spread,
+        None,
+    )
+    .unwrap();
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => {
+            assert_eq!(funds, vec![Coin::new(1000, "uluna")])
+        }
+        _ => panic!("Unexpected message {:?}", msg),
+    }
+
+    let msg = build_swap_msg(
+        &deps.as_ref().querier,
+        &pair,
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(1000),
+        },
+        max_spread,
+        Some("recipient".to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount: Uint128::new(1000),
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: Some(max_spread),
+                    to: Some("recipient".to_string()),
+                    deadline: None,
+                    fill_mode: FillMode::FillOrKill {},
+                    fee_discount_bps: 0,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_maker_min_astro_received() {
+    assert_eq!(
+        assert_min_astro_received(Uint128::new(100), Uint128::new(1100), Uint128::new(1000))
+            .unwrap(),
+        Uint128::new(1000)
+    );
+    assert_eq!(
+        assert_min_astro_received(Uint128::new(100), Uint128::new(1099), Uint128::new(1000)),
+        Err(StdError::generic_err(
+            "Collect received 999 ASTRO, less than the minimum of 1000"
+        ))
+    );
+    assert_eq!(
+        assert_min_astro_received(Uint128::new(100), Uint128::new(50), Uint128::new(1)),
+        Err(StdError::generic_err(
+            "Collect received 0 ASTRO, less than the minimum of 1"
+        ))
+    );
+}
+
+#[test]
+fn test_maker_collect_incentive() {
+    assert_collect_cooldown(0, 0, 0).unwrap();
+    assert_collect_cooldown(1000, 3600, 4600).unwrap();
+    assert_eq!(
+        assert_collect_cooldown(1000, 3600, 4599),
+        Err(StdError::generic_err("The next collect is allowed at 4600"))
+    );
+
+    assert_eq!(
+        compute_caller_reward(Uint128::new(1_000_000), 50),
+        Uint128::new(5_000)
+    );
+    assert_eq!(
+        compute_caller_reward(Uint128::new(199), 50),
+        Uint128::zero()
+    );
+    validate_caller_reward_bps(100).unwrap();
+    assert_eq!(
+        validate_caller_reward_bps(101),
+        Err(StdError::generic_err("Caller reward can't exceed 100 bps"))
+    );
+}
+
+#[test]
+fn test_maker_target_assets() {
+    let astro = Addr::unchecked("astro");
+    let weights = vec![
+        ("staking".to_string(), Decimal::percent(50)),
+        ("community_pool".to_string(), Decimal::percent(30)),
+        ("dev_fund".to_string(), Decimal::percent(20)),
+    ];
+    let uusd = native_asset_info("uusd".to_string());
+    validate_target_assets(
+        &weights,
+        &[
+            ("community_pool".to_string(), uusd.clone()),
+            ("dev_fund".to_string(), uusd.clone()),
+        ],
+        &astro,
+    )
+    .unwrap();
+    validate_target_assets(&weights, &[], &astro).unwrap();
+
+    assert_eq!(
+        validate_target_assets(&weights, &[("treasury".to_string(), uusd.clone())], &astro),
+        Err(StdError::generic_err(
+            "treasury is not a distribution destination"
+        ))
+    );
+    assert_eq!(
+        validate_target_assets(
+            &weights,
+            &[
+                ("dev_fund".to_string(), uusd.clone()),
+                (
+                    "dev_fund".to_string(),
+                    native_asset_info("uluna".to_string())
+                ),
+            ],
+            &astro,
+        ),
+        Err(StdError::generic_err("Duplicated target asset of dev_fund"))
+    );
+    assert_eq!(
+        validate_target_assets(
+            &weights,
+            &[(
+                "staking".to_string(),
+                AssetInfo::Token {
+                    contract_addr: astro.clone(),
+                },
+            )],
+            &astro,
+        ),
+        Err(StdError::generic_err(
+            "The target asset of staking can't be ASTRO"
+        ))
+    );
+}
+
+#[test]
+fn test_pair_invariant_checks() {
+    let pools = [Uint128::new(1_000_000), Uint128::new(4_000_000)];
+    let total_share = Uint128::new(2_000_000);
+    let invariant = compute_xyk_invariant(pools).unwrap();
+    assert_eq!(invariant, Uint128::new(2_000_000));
+
+    // a swap keeps the supply and increases the invariant by the fee
+    let offer = Uint128::new(10_000);
+    let (return_amount, _, _) =
+        compute_swap(pools[0], pools[1], offer, Decimal::permille(3)).unwrap();
+    let swapped = [pools[0] + offer, pools[1] - return_amount];
+    assert_invariant_per_share(
+        invariant,
+        total_share,
+        compute_xyk_invariant(swapped).unwrap(),
+        total_share,
+    )
+    .unwrap();
+
+    // provides and withdrawals round in favor of the pool
+    let deposits = [Uint128::new(333), Uint128::new(1_337)];
+    let share = compute_share(pools, deposits, total_share).unwrap();
+    let provided = [pools[0] + deposits[0], pools[1] + deposits[1]];
+    assert_invariant_per_share(
+        invariant,
+        total_share,
+        compute_xyk_invariant(provided).unwrap(),
+        total_share + share,
+    )
+    .unwrap();
+    let withdrawn = compute_withdraw(pools, Uint128::new(777), total_share).unwrap();
+    let remaining = [pools[0] - withdrawn[0], pools[1] - withdrawn[1]];
+    assert_invariant_per_share(
+        invariant,
+        total_share,
+        compute_xyk_invariant(remaining).unwrap(),
+        total_share - Uint128::new(777),
+    )
+    .unwrap();
+
+    let d = compute_d(100, pools).unwrap();
+    let drained = [pools[0], pools[1] - Uint128::new(1_000)];
+    assert_eq!(
+        assert_invariant_per_share(
+            d,
+            total_share,
+            compute_d(100, drained).unwrap(),
+            total_share
+        ),
+        Err(StdError::generic_err(
+            "Invariant violation: the invariant per LP token decreased"
+        ))
+    );
+    assert_eq!(
+        assert_invariant_per_share(invariant, total_share, Uint128::zero(), total_share),
+        Err(StdError::generic_err(
+            "Invariant violation: LP supply 2000000 is inconsistent with the reserves"
+        ))
+    );
+}
+
+#[test]
+fn test_flash_swap_repayment() {
+    let pools = [Uint128::new(1_000_000), Uint128::new(1_000_000)];
+    let fee = Decimal::permille(3);
+    let borrowed = [Uint128::new(1_000), Uint128::zero()];
+
+    // repaying in the borrowed asset requires borrowed / (1 - fee)
+    assert_flash_swap_repaid(pools, [Uint128::new(1_000_004), pools[1]], borrowed, fee).unwrap();
+    assert_eq!(
+        assert_flash_swap_repaid(pools, [Uint128::new(1_000_003), pools[1]], borrowed, fee),
+        Err(StdError::generic_err(
+            "The flash swap was not repaid with the fee"
+        ))
+    );
+    assert!(
+        assert_flash_swap_repaid(pools, [Uint128::new(999_000), pools[1]], borrowed, fee).is_err()
+    );
+
+    // repaying in the other asset is a swap of the borrowed amount
+    assert_flash_swap_repaid(
+        pools,
+        [Uint128::new(999_000), Uint128::new(1_001_010)],
+        borrowed,
+        fee,
+    )
+    .unwrap();
+    assert!(assert_flash_swap_repaid(
+        pools,
+        [Uint128::new(999_000), Uint128::new(1_001_000)],
+        borrowed,
+        fee,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_batch_clearing() {
+    let pools = [Uint128::new(1_000_000), Uint128::new(1_000_000)];
+    let fee = Decimal::permille(3);
+
+    let (returns, commissions) =
+        compute_batch_clearing(pools, [Uint128::new(10_000), Uint128::new(4_000)], fee).unwrap();
+    let (swapped, _, _) =
+        compute_swap(pools[0], pools[1], Uint128::new(6_000), Decimal::zero()).unwrap();
+    assert_eq!(
+        commissions,
+        [(Uint128::new(4_000) + swapped) * fee, Uint128::new(12)]
+    );
+    assert_eq!(
+        returns,
+        [
+            Uint128::new(4_000) + swapped - commissions[0],
+            Uint128::new(3_988)
+        ]
+    );
+
+    // the net imbalance in the other direction
+    let (returns, _) = compute_batch_clearing(
+        pools,
+        [Uint128::new(4_000), Uint128::new(10_000)],
+        Decimal::zero(),
+    )
+    .unwrap();
+    assert_eq!(
+        returns,
+        [Uint128::new(4_000), Uint128::new(4_000) + swapped]
+    );
+
+    // fully matched orders don't move the pool
+    let (returns, _) = compute_batch_clearing(
+        pools,
+        [Uint128::new(5_000), Uint128::new(5_000)],
+        Decimal::zero(),
+    )
+    .unwrap();
+    assert_eq!(returns, [Uint128::new(5_000), Uint128::new(5_000)]);
+
+    assert!(compute_batch_clearing(
+        [Uint128::zero(), Uint128::new(1)],
+        [Uint128::new(1), Uint128::zero()],
+        fee
+    )
+    .is_err());
+}
+
+#[test]
+fn test_maker_bridge_routes() {
+    let native = |denom: &str| AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    };
+    let astro = AssetInfo::Token {
+        contract_addr: Addr::unchecked("astro0000"),
+    };
+    let pair = |asset_infos: [AssetInfo; 2], addr: &str| PairInfo {
+        asset_infos,
+        contract_addr: Addr::unchecked(addr),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+        capabilities: vec![],
+        metadata: None,
+        official: false,
+    };
+    let pairs = [
+        (
+            "ibc/axlusdcuusd".to_string(),
+            pair([native("ibc/axlusdc"), native("uusd")], "pair0000"),
+        ),
+        (
+            "uusduluna".to_string(),
+            pair([native("uusd"), native("uluna")], "pair0001"),
+        ),
+        (
+            "ulunaastro0000".to_string(),
+            pair([native("uluna"), astro.clone()], "pair0002"),
+        ),
+    ];
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_astroport_pairs(
+        &pairs
+            .iter()
+            .map(|(key, pair)| (key, pair))
+            .collect::<Vec<_>>(),
+    );
+    let factories = [Addr::unchecked("factory0000")];
+    let querier = deps.as_ref().querier;
+    let asset = native("ibc/axlusdc");
+
+    validate_bridge_route(
+        &querier,
+        &factories,
+        &asset,
+        &[native("uusd"), native("uluna"), astro.clone()],
+        &astro,
+    )
+    .unwrap();
+
+    assert_eq!(
+        validate_bridge_route(
+            &querier,
+            &factories,
+            &asset,
+            &[native("uusd"), native("uluna")],
+            &astro
+        ),
+        Err(StdError::generic_err(
+            "Bridge route of ibc/axlusdc must terminate in ASTRO"
+        ))
+    );
+    assert_eq!(
+        validate_bridge_route(
+            &querier,
+            &factories,
+            &asset,
+            &[native("uusd"), astro.clone()],
+            &astro
+        ),
+        Err(StdError::generic_err(
+            "No pair found for the bridge uusd -> astro0000"
+        ))
+    );
+    assert_eq!(
+        validate_bridge_route(
+            &querier,
+            &factories,
+            &asset,
+            &[native("uusd"), asset.clone(), astro.clone()],
+            &astro
+        ),
+        Err(StdError::generic_err(
+            "Bridge route of ibc/axlusdc visits ibc/axlusdc twice"
+        ))
+    );
+    assert!(validate_bridge_route(
+        &querier,
+        &factories,
+        &asset,
+        &[native("a"), native("b"), native("c"), astro.clone()],
+        &astro
+    )
+    .is_err());
+
+    let pair_info =
+        query_pair_in_factories(&querier, &factories, &[native("uluna"), astro.clone()]).unwrap();
+    assert_eq!(pair_info.contract_addr, Addr::unchecked("pair0002"));
+    assert_eq!(
+        query_pair_in_factories(&querier, &[], &[native("uluna"), astro.clone()]),
+        Err(StdError::generic_err(
+            "No pair found for the bridge uluna -> astro0000"
+        ))
+    );
+}
+
+#[test]
+fn test_fair_lp_value() {
+    let supply = Uint128::new(1_000_000);
+    let balanced = [Uint128::new(1_000_000), Uint128::new(1_000_000)];
+    // the same invariant after a manipulation of the reserves
+    let manipulated = [Uint128::new(4_000_000), Uint128::new(250_000)];
+
+    for pools in [balanced, manipulated].iter() {
+        assert_eq!(
+            fair_xyk_lp_value(*pools, Decimal::one(), supply, supply).unwrap(),
+            Uint128::new(2_000_000)
+        );
+    }
+    assert_eq!(
+        fair_xyk_lp_value(
+            balanced,
+            Decimal::percent(400),
+            Uint128::new(250_000),
+            supply
+        )
+        .unwrap(),
+        Uint128::new(1_000_000)
+    );
+    assert_eq!(
+        fair_xyk_lp_value(balanced, Decimal::one(), supply, Uint128::zero()).unwrap(),
+        Uint128::zero()
+    );
+    // the reserves of an 18 decimals pool don't overflow the invariant
+    let e30 = Uint128::new(1_000_000_000_000_000_000_000_000_000_000);
+    assert_eq!(
+        fair_xyk_lp_value([e30, e30], Decimal::percent(400), e30, e30).unwrap(),
+        Uint128::new(4_000_000_000_000_000_000_000_000_000_000)
+    );
+    assert_eq!(
+        fair_xyk_lp_value([e30, e30], Decimal::MAX, e30, e30),
+        Err(StdError::generic_err(
+            "The amount does not fit into Uint128"
+        ))
+    );
+
+    assert_eq!(
+        fair_stable_lp_value(100, balanced, Decimal::one(), supply, supply).unwrap(),
+        Uint128::new(2_000_000)
+    );
+    // a depegged asset values the invariant at its price
+    assert_eq!(
+        fair_stable_lp_value(
+            100,
+            balanced,
+            Decimal::percent(90),
+            Uint128::new(500_000),
+            supply
+        )
+        .unwrap(),
+        Uint128::new(900_000)
+    );
+}
+
+#[test]
+fn test_router_trade_hook() {
+    let res = CheckTradeResponse {
+        allowed: true,
+        reason: None,
+    };
+    assert!(res.assert_allowed().is_ok());
+
+    let res = CheckTradeResponse {
+        allowed: false,
+        reason: Some("sender is not screened".to_string()),
+    };
+    assert_eq!(
+        res.assert_allowed(),
+        Err(StdError::generic_err(
+            "Trade rejected by the pre-trade hook: sender is not screened"
+        ))
+    );
+}
+
+#[test]
+fn test_pair_partial_fill() {
+    let pool = Uint128::new(1_000_000);
+    let max_spread = Decimal::percent(1);
+    let assert_within_spread = |belief_price: Option<Decimal>, offer_amount: Uint128| {
+        let (return_amount, spread_amount, commission_amount) =
+            compute_swap(pool, pool, offer_amount, Decimal::zero()).unwrap();
+        assert_max_spread(
+            belief_price,
+            max_spread,
+            offer_amount,
+            return_amount + commission_amount,
+            spread_amount,
+        )
+    };
+
+    // an offer within the max spread is filled completely
+    assert_eq!(
+        compute_partial_fill_offer(pool, pool, Uint128::new(5000), None, max_spread).unwrap(),
+        Uint128::new(5000)
+    );
+
+    let filled =
+        compute_partial_fill_offer(pool, pool, Uint128::new(50000), None, max_spread).unwrap();
+    assert_eq!(filled, Uint128::new(10050));
+    assert_within_spread(None, filled).unwrap();
+    assert!(assert_within_spread(None, filled + Uint128::new(10)).is_err());
+
+    let belief_price = Some(Decimal::permille(1005));
+    let filled =
+        compute_partial_fill_offer(pool, pool, Uint128::new(50000), belief_price, max_spread)
+            .unwrap();
+    assert_eq!(filled, Uint128::new(15146));
+    assert_within_spread(belief_price, filled).unwrap();
+    assert!(assert_within_spread(belief_price, filled + Uint128::new(10)).is_err());
+
+    // nothing can be filled if the belief price is far off
+    assert_eq!(
+        compute_partial_fill_offer(
+            pool,
+            pool,
+            Uint128::new(50000),
+            Some(Decimal::percent(50)),
+            max_spread
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(FillMode::default(), FillMode::FillOrKill {});
+}
+
+#[test]
+fn test_swap_deadline() {
+    assert_deadline(None, 1000).unwrap();
+    assert_deadline(Some(1000), 1000).unwrap();
+    assert_eq!(
+        assert_deadline(Some(1000), 1001),
+        Err(StdError::generic_err(
+            "Transaction deadline 1000 has passed"
+        ))
+    );
+
+    // hook messages sent without a deadline keep working
+    let msg: PairCw20HookMsg =
+        from_binary(&Binary::from(br#"{"withdraw_liquidity":{}}"#.to_vec())).unwrap();
+    assert_eq!(msg, PairCw20HookMsg::WithdrawLiquidity { deadline: None });
+    let msg: PairCw20HookMsg = from_binary(&Binary::from(
+        br#"{"swap":{"belief_price":null,"max_spread":null,"to":null,"deadline":1000}}"#.to_vec(),
+    ))
+    .unwrap();
+    assert_eq!(
+        msg,
+        PairCw20HookMsg::Swap {
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            deadline: Some(1000),
+            fill_mode: FillMode::FillOrKill {},
+            fee_discount_bps: 0,
+        }
+    );
+    let msg: RouterCw20HookMsg = from_binary(&Binary::from(
+        br#"{"execute_swap_operations":{"operations":[],"minimum_receive":null,"to":null,"deadline":1000}}"#.to_vec(),
+    ))
+    .unwrap();
+    assert_eq!(
+        msg,
+        RouterCw20HookMsg::ExecuteSwapOperations {
+            operations: vec![],
+            minimum_receive: None,
+            to: None,
+            recipients: vec![],
+            allow_partial: false,
+            deadline: Some(1000),
+            client_id: None,
+        }
+    );
+}
+
+#[test]
+fn test_pair_retirement() {
+    let pair = Addr::unchecked("pair0000");
+    let successor_pair = Addr::unchecked("pair0001");
+    let retirement = RetirementInfo {
+        successor_pair: successor_pair.clone(),
+        surcharge_bps: 100,
+    };
+    retirement.validate(&pair).unwrap();
+    assert_eq!(
+        RetirementInfo {
+            successor_pair: successor_pair.clone(),
+            surcharge_bps: 501,
+        }
+        .validate(&pair),
+        Err(StdError::generic_err(
+            "The retirement surcharge can't exceed 500 bps"
+        ))
+    );
+    assert_eq!(
+        RetirementInfo {
+            successor_pair: pair.clone(),
+            surcharge_bps: 100,
+        }
+        .validate(&pair),
+        Err(StdError::generic_err("A pair can't be its own successor"))
+    );
+    assert_eq!(
+        retirement.compute_surcharge(Uint128::new(12345)),
+        Uint128::new(123)
+    );
+    assert_eq!(
+        retirement.assert_provide_allowed(),
+        Err(StdError::generic_err(
+            "The pair is retired, provide liquidity to pair0001 instead"
+        ))
+    );
+
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::new(1000000u128))],
+    );
+    let token = Addr::unchecked("asset0000");
+    let receiver = Addr::unchecked("addr0000");
+    let msgs = migrate_liquidity_msgs(
+        &deps.as_ref().querier,
+        &successor_pair,
+        [
+            native_asset("uusd".to_string(), Uint128::new(123123)),
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: token.clone(),
+                },
+                amount: Uint128::new(1000),
+            },
+        ],
+        &receiver,
+        None,
+        Some(true),
+    )
+    .unwrap();
+    assert_eq!(
+        msgs,
+        vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: successor_pair.to_string(),
+                    amount: Uint128::new(1000),
+                    expires: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: successor_pair.to_string(),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        native_asset("uusd".to_string(), Uint128::new(UUSD_AFTER_TAX)),
+                        Asset {
+                            info: AssetInfo::Token {
+                                contract_addr: token,
+                            },
+                            amount: Uint128::new(1000),
+                        },
+                    ],
+                    slippage_tolerance: None,
+                    auto_stake: Some(true),
+                    receiver: Some(receiver.to_string()),
+                    deadline: None,
+                    initial_price: None,
+                })
+                .unwrap(),
+                funds: vec![Coin::new(UUSD_AFTER_TAX, "uusd")],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_pair_apr() {
+    let tvl = Uint128::new(1_000_000);
+    // a week of fees worth 0.1% of the TVL
+    let fee_apr = compute_apr(Uint128::new(1_000), tvl, DEFAULT_APR_WINDOW).unwrap();
+    assert_eq!(fee_apr, Decimal::from_ratio(365u128, 7_000u128));
+    assert_eq!(
+        compute_apr(Uint128::new(100_000), tvl, SECONDS_PER_YEAR).unwrap(),
+        Decimal::percent(10)
+    );
+    assert_eq!(
+        compute_apr(Uint128::new(1_000), Uint128::zero(), DEFAULT_APR_WINDOW).unwrap(),
+        Decimal::zero()
+    );
+    assert_eq!(
+        compute_apr(Uint128::new(1_000), tvl, 0),
+        Err(StdError::generic_err("The APR window must be positive"))
+    );
+}
+
+#[test]
+fn test_router_partial_receipt() {
+    let operations = vec![
+        SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "uluna".to_string(),
+        },
+        SwapOperation::AstroSwap {
+            offer_asset_info: native_asset_info("uluna".to_string()),
+            ask_asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("astro"),
+            },
+        },
+    ];
+
+    let receipt =
+        SwapReceipt::partial(&operations, 1, Uint128::new(500), "max spread".to_string()).unwrap();
+    assert_eq!(receipt.completed_hops, 1);
+    assert_eq!(receipt.failed_hop, Some(1));
+    assert_eq!(
+        receipt.delivered,
+        native_asset("uluna".to_string(), Uint128::new(500))
+    );
+
+    // the offer is returned if the first hop fails
+    let receipt =
+        SwapReceipt::partial(&operations, 0, Uint128::new(100), "paused".to_string()).unwrap();
+    assert_eq!(receipt.completed_hops, 0);
+    assert_eq!(
+        receipt.delivered,
+        native_asset("uusd".to_string(), Uint128::new(100))
+    );
+
+    assert_eq!(
+        SwapReceipt::partial(&operations, 2, Uint128::zero(), String::new()),
+        Err(StdError::generic_err("Hop 2: the route has no such hop"))
+    );
+}
+
+#[test]
+fn test_router_response_data() {
+    let operations = vec![SwapOperation::NativeSwap {
+        offer_denom: "uusd".to_string(),
+        ask_denom: "uluna".to_string(),
+    }];
+    let refunds = vec![native_asset("uusd".to_string(), Uint128::new(10))];
+    let data = SwapResponseData::from_balances(
+        native_asset_info("uluna".to_string()),
+        Uint128::new(100),
+        Uint128::new(350),
+        vec![operations.clone()],
+        refunds.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        data,
+        SwapResponseData {
+            return_asset: native_asset("uluna".to_string(), Uint128::new(250)),
+            routes: vec![operations.clone()],
+            refunds,
+            receipt: None,
+        }
+    );
+    assert_eq!(
+        from_binary::<SwapResponseData>(&to_binary(&data).unwrap()).unwrap(),
+        data
+    );
+
+    assert!(SwapResponseData::from_balances(
+        native_asset_info("uluna".to_string()),
+        Uint128::new(100),
+        Uint128::new(99),
+        vec![operations],
+        vec![],
+    )
+    .is_err());
+}
+
+#[test]
+fn test_router_output_recipients() {
+    let recipients = vec![
+        ("alice".to_string(), Decimal::percent(70)),
+        ("bob".to_string(), Decimal::percent(30)),
+    ];
+    validate_output_recipients(None, &recipients).unwrap();
+    validate_output_recipients(Some("carol"), &[]).unwrap();
+    assert_eq!(
+        validate_output_recipients(Some("carol"), &recipients),
+        Err(StdError::generic_err(
+            "Either to or recipients can be set, but not both"
+        ))
+    );
+    assert_eq!(
+        validate_output_recipients(
+            None,
+            &[
+                ("alice".to_string(), Decimal::percent(70)),
+                ("alice".to_string(), Decimal::percent(30)),
+            ]
+        ),
+        Err(StdError::generic_err("Duplicated output recipient alice"))
+    );
+    assert_eq!(
+        validate_output_recipients(
+            None,
+            &[
+                ("alice".to_string(), Decimal::one()),
+                ("bob".to_string(), Decimal::zero()),
+            ]
+        ),
+        Err(StdError::generic_err("The weight of bob must be positive"))
+    );
+    assert_eq!(
+        validate_output_recipients(None, &[("alice".to_string(), Decimal::percent(70))]),
+        Err(StdError::generic_err(
+            "Output recipient weights must sum to 1, got 0.7"
+        ))
+    );
+
+    let parts = split_output(
+        &native_asset("uusd".to_string(), Uint128::new(1001)),
+        &[
+            (Addr::unchecked("alice"), Decimal::percent(70)),
+            (Addr::unchecked("bob"), Decimal::percent(30)),
+        ],
+    );
+    assert_eq!(
+        parts,
+        vec![
+            (
+                Addr::unchecked("alice"),
+                native_asset("uusd".to_string(), Uint128::new(700))
+            ),
+            (
+                Addr::unchecked("bob"),
+                native_asset("uusd".to_string(), Uint128::new(301))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_router_basket_validation() {
+    let ask = native_asset_info("uusd".to_string());
+    let dust = |denom: &str, amount: u128| native_asset(denom.to_string(), Uint128::new(amount));
+    validate_basket(&[dust("uluna", 10), dust("ukrw", 20)], &ask).unwrap();
+
+    assert_eq!(
+        validate_basket(&[], &ask),
+        Err(StdError::generic_err("The basket has no inputs"))
+    );
+    assert_eq!(
+        validate_basket(&[dust("uluna", 10), dust("uluna", 20)], &ask),
+        Err(StdError::generic_err("Basket input uluna is duplicated"))
+    );
+    assert_eq!(
+        validate_basket(&[dust("uusd", 10)], &ask),
+        Err(StdError::generic_err("Basket input uusd is the ask asset"))
+    );
+    assert_eq!(
+        validate_basket(&[dust("uluna", 0)], &ask),
+        Err(StdError::generic_err(
+            "Basket input uluna has a zero amount"
+        ))
+    );
+    let inputs: Vec<Asset> = (0..=MAX_BASKET_INPUTS)
+        .map(|i| dust(&format!("denom{}", i), 1))
+        .collect();
+    assert_eq!(
+        validate_basket(&inputs, &ask),
+        Err(StdError::generic_err(
+            "The basket can't have more than 10 inputs"
+        ))
+    );
+}
+
+#[test]
+fn test_router_split_routes() {
+    let astro = AssetInfo::Token {
+        contract_addr: Addr::unchecked("astro"),
+    };
+    let uusd = native_asset_info("uusd".to_string());
+    let uluna = native_asset_info("uluna".to_string());
+    let direct = vec![SwapOperation::AstroSwap {
+        offer_asset_info: uusd.clone(),
+        ask_asset_info: astro.clone(),
+    }];
+    let via_luna = vec![
+        SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "uluna".to_string(),
+        },
+        SwapOperation::AstroSwap {
+            offer_asset_info: uluna.clone(),
+            ask_asset_info: astro.clone(),
+        },
+    ];
+    let routes = vec![
+        SplitRoute {
+            weight: Decimal::from_ratio(1u128, 3u128),
+            operations: direct.clone(),
+        },
+        SplitRoute {
+            weight: Decimal::one() - Decimal::from_ratio(1u128, 3u128),
+            operations: via_luna.clone(),
+        },
+    ];
+    validate_split_routes(&routes, MAX_SWAP_OPERATIONS as u64, Some(5)).unwrap();
+    assert_eq!(
+        validate_split_routes(&routes, MAX_SWAP_OPERATIONS as u64, Some(4)),
+        Err(StdError::generic_err(
+            "Route 1: the routes exceed the complexity budget of 4"
+        ))
+    );
+
+    let parts = split_offer_amount(Uint128::new(100), &routes);
+    assert_eq!(parts, vec![Uint128::new(33), Uint128::new(67)]);
+
+    assert_eq!(
+        validate_split_routes(&[], MAX_SWAP_OPERATIONS as u64, None),
+        Err(StdError::generic_err("Must provide split routes"))
+    );
+
+    let err = validate_split_routes(
+        &[
+            SplitRoute {
+                weight: Decimal::percent(50),
+                operations: direct.clone(),
+            },
+            SplitRoute {
+                weight: Decimal::percent(40),
+                operations: via_luna.clone(),
+            },
+        ],
+        MAX_SWAP_OPERATIONS as u64,
+        None,
+    );
+    assert_eq!(
+        err,
+        Err(StdError::generic_err(
+            "Split route weights must sum to 1, got 0.9"
+        ))
+    );
+
+    let err = validate_split_routes(
+        &[
+            SplitRoute {
+                weight: Decimal::percent(50),
+                operations: direct.clone(),
+            },
+            SplitRoute {
+                weight: Decimal::percent(50),
+                operations: via_luna[..1].to_vec(),
+            },
+        ],
+        MAX_SWAP_OPERATIONS as u64,
+        None,
+    );
+    assert_eq!(
+        err,
+        Err(StdError::generic_err(
+            "Route 1: Hop 0: the route must end with the ask asset"
+        ))
+    );
+
+    let err = validate_split_routes(
+        &[
+            SplitRoute {
+                weight: Decimal::one(),
+                operations: direct,
+            },
+            SplitRoute {
+                weight: Decimal::zero(),
+                operations: via_luna,
+            },
+        ],
+        MAX_SWAP_OPERATIONS as u64,
+        None,
+    );
+    assert_eq!(
+        err,
+        Err(StdError::generic_err(
+            "Route 1: the weight must be positive"
+        ))
+    );
+}
+
+#[test]
+fn test_stable_reverse_simulation_and_amp_ramp() {
+    let amp = 100;
+    let pool = Uint128::new(1_000_000_000);
+    let fee = Decimal::permille(5);
+
+    for ask_amount in [1u128, 1_000, 1_000_000, 500_000_000].iter() {
+        let ask_amount = Uint128::new(*ask_amount);
+        let (offer_amount, _, commission_amount) =
+            compute_stable_offer_amount(amp, pool, pool, ask_amount, fee).unwrap();
+        let (return_amount, _, _) =
+            compute_stable_swap(amp, pool, pool, offer_amount, fee).unwrap();
+        assert!(return_amount >= ask_amount);
+        assert!(commission_amount >= ask_amount * fee);
+    }
+    assert_eq!(
+        compute_stable_offer_amount(amp, pool, pool, pool, Decimal::zero()).unwrap_err(),
+        SwapError::InsufficientLiquidity {
+            requested: pool,
+            available: pool,
+        }
+    );
+
+    assert_eq!(compute_current_amp(100, 1000, 200, 2000, 500), 100);
+    assert_eq!(compute_current_amp(100, 1000, 200, 2000, 1500), 150);
+    assert_eq!(compute_current_amp(200, 1000, 100, 2000, 1250), 175);
+    assert_eq!(compute_current_amp(100, 1000, 200, 2000, 3000), 200);
+
+    assert!(validate_amp_change(100, 1000, 0, MIN_AMP_CHANGING_TIME).is_ok());
+    assert_eq!(
+        validate_amp_change(100, 1001, 0, MIN_AMP_CHANGING_TIME),
+        Err(StdError::generic_err(
+            "Amp can't be changed by more than 10 times at once"
+        ))
+    );
+    assert_eq!(
+        validate_amp_change(100, 50, 0, MIN_AMP_CHANGING_TIME - 1),
+        Err(StdError::generic_err(
+            "Amp can't be changed faster than over 86400 seconds"
+        ))
+    );
+    assert!(validate_amp_change(100, 0, 0, MIN_AMP_CHANGING_TIME).is_err());
+}
+
+#[test]
+fn test_oracle_attestation() {
+    let pair = Addr::unchecked("pair0000");
+    let price = Decimal::from_ratio(3u128, 2u128);
+    let attestation = AttestationResponse::new(pair.clone(), price, 12345, 86400);
+    assert_eq!(attestation.commitment.len(), 32);
+    assert!(attestation.verify());
+    // the commitment is deterministic
+    assert_eq!(
+        attestation.commitment,
+        attestation_commitment(&pair, price, 12345, 86400)
+    );
+
+    // every field is committed to
+    let commitments = [
+        attestation_commitment(&Addr::unchecked("pair0001"), price, 12345, 86400),
+        attestation_commitment(&pair, Decimal::one(), 12345, 86400),
+        attestation_commitment(&pair, price, 12346, 86400),
+        attestation_commitment(&pair, price, 12345, 3600),
+    ];
+    for commitment in commitments.iter() {
+        assert_ne!(commitment, &attestation.commitment);
+    }
+
+    let mut tampered = attestation;
+    tampered.price = Decimal::one();
+    assert!(!tampered.verify());
+}
+
+#[test]
+fn test_oracle_twap() {
+    assert_eq!(
+        assert_update_allowed(1000, 1999, 1000),
+        Err(StdError::generic_err(
+            "Period not elapsed: 999 of 1000 seconds"
+        ))
+    );
+    assert_eq!(assert_update_allowed(1000, 2500, 1000), Ok(1500));
+
+    // a price of 1.5 scaled by 6 decimals for 100 seconds
+    assert_eq!(
+        compute_average_price(Uint128::new(150_000_000), Uint128::zero(), 100).unwrap(),
+        Decimal::percent(150)
+    );
+    // the cumulative price overflowed since the last snapshot
+    assert_eq!(
+        compute_average_price(
+            Uint128::new(49_999_999),
+            Uint128::MAX - Uint128::new(100_000_000),
+            100
+        )
+        .unwrap(),
+        Decimal::percent(150)
+    );
+    assert!(compute_average_price(Uint128::zero(), Uint128::zero(), 0).is_err());
+
+    assert_eq!(
+        compute_average_price_256(Uint128::new(150_000_000), Uint128::zero(), 100).unwrap(),
+        Decimal256::percent(150)
+    );
+    // prices which don't fit into a Decimal
+    assert_eq!(
+        compute_average_price_256(Uint128::MAX, Uint128::zero(), 1).unwrap(),
+        Decimal256::from_ratio(Uint128::MAX, 1_000_000u128)
+    );
+    assert!(compute_average_price_256(Uint128::zero(), Uint128::zero(), 0).is_err());
+}
+
+#[test]
+fn test_vesting_schedule_pause() {
+    let mut schedule = VestingSchedule {
+        start_point: VestingSchedulePoint {
+            time: 0,
+            amount: Uint128::zero(),
+        },
+        end_point: Some(VestingSchedulePoint {
+            time: 1000,
+            amount: Uint128::new(1000),
+        }),
+        pauses: vec![],
+    };
+
+    assert_eq!(
+        schedule.resume(100, false),
+        Err(StdError::generic_err("The schedule is not paused"))
+    );
+    schedule.pause(200).unwrap();
+    assert!(schedule.is_paused());
+    assert_eq!(
+        schedule.pause(300),
+        Err(StdError::generic_err("The schedule is already paused"))
+    );
+    // the vesting is frozen during the pause
+    assert_eq!(schedule.vested_amount(150).unwrap(), Uint128::new(150));
+    assert_eq!(schedule.vested_amount(500).unwrap(), Uint128::new(200));
+
+    let mut retroactive = schedule.clone();
+    retroactive.resume(500, true).unwrap();
+    assert!(!retroactive.is_paused());
+    assert_eq!(retroactive.vested_amount(500).unwrap(), Uint128::new(500));
+    assert_eq!(retroactive.vested_amount(1000).unwrap(), Uint128::new(1000));
+    // the history keeps the frozen amounts during the pause
+    assert_eq!(retroactive.vested_amount(400).unwrap(), Uint128::new(200));
+
+    schedule.resume(500, false).unwrap();
+    assert_eq!(schedule.vested_amount(500).unwrap(), Uint128::new(200));
+    assert_eq!(schedule.vested_amount(1000).unwrap(), Uint128::new(700));
+    assert_eq!(schedule.vested_amount(1300).unwrap(), Uint128::new(1000));
+    assert_eq!(
+        schedule.pauses,
+        vec![SchedulePause {
+            start: 200,
+            end: Some(500),
+            retroactive: false,
+        }]
+    );
+}
+
+#[test]
+fn test_lp_transfer_hook_msg() {
+    let hook_msg = TransferHookMsg::TransferNotification {
+        sender: "user0000".to_string(),
+        recipient: "user0001".to_string(),
+        amount: Uint128::new(100),
+    };
+    let msg = hook_msg
+        .into_cosmos_msg("generator0000".to_string())
+        .unwrap();
+    let binary = match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) => {
+            assert_eq!(contract_addr, "generator0000");
+            assert!(funds.is_empty());
+            msg
+        }
+        _ => panic!("Unexpected message"),
+    };
+    assert_eq!(
+        from_binary::<GeneratorExecuteMsg>(&binary).unwrap(),
+        GeneratorExecuteMsg::TransferNotification {
+            sender: "user0000".to_string(),
+            recipient: "user0001".to_string(),
+            amount: Uint128::new(100),
+        }
+    );
+}
+
+#[test]
+fn test_router_recent_executions() {
+    assert_eq!(recent_execution_ids(0, None, None), 0..0);
+    assert_eq!(recent_execution_ids(5, None, None), 0..5);
+    assert_eq!(recent_execution_ids(5, Some(2), None), 3..5);
+    assert_eq!(recent_execution_ids(5, Some(10), None), 5..5);
+    assert_eq!(recent_execution_ids(50, None, Some(100)), 0..30);
+
+    // overwritten executions are skipped
+    assert_eq!(recent_execution_ids(250, None, Some(10)), 150..160);
+    assert_eq!(recent_execution_ids(250, Some(20), Some(10)), 150..160);
+    assert_eq!(recent_execution_ids(250, Some(240), None), 241..250);
+    assert_eq!(execution_slot(250), execution_slot(150));
+    assert_eq!(execution_slot(MAX_RECENT_EXECUTIONS - 1), 99);
+
+    let route = vec![SwapOperation::NativeSwap {
+        offer_denom: "uusd".to_string(),
+        ask_denom: "uluna".to_string(),
+    }];
+    let reversed = vec![SwapOperation::NativeSwap {
+        offer_denom: "uluna".to_string(),
+        ask_denom: "uusd".to_string(),
+    }];
+    let hash = route_hash(std::slice::from_ref(&route)).unwrap();
+    assert_eq!(hash.len(), 32);
+    assert_eq!(hash, route_hash(std::slice::from_ref(&route)).unwrap());
+    assert_ne!(hash, route_hash(std::slice::from_ref(&reversed)).unwrap());
+    assert_ne!(hash, route_hash(&[route, reversed]).unwrap());
+}
+
+#[test]
+fn test_router_client_ids() {
+    let alice = Addr::unchecked("alice0000");
+    let bob = Addr::unchecked("bob0000");
+    const CLIENT_IDS: Map<(&Addr, &str), u64> = Map::new("client_ids");
+    let mut storage = MockStorage::new();
+
+    register_client_id(&mut storage, CLIENT_IDS, &alice, "swap-1", 100).unwrap();
+    assert_eq!(
+        register_client_id(&mut storage, CLIENT_IDS, &alice, "swap-1", 150).unwrap_err(),
+        RouterError::DuplicateClientId {
+            client_id: "swap-1".to_string(),
+            height: 100
+        }
+    );
+    assert_eq!(
+        StdError::from(
+            register_client_id(&mut storage, CLIENT_IDS, &alice, "swap-1", 150).unwrap_err()
+        ),
+        StdError::generic_err("Swap with client id swap-1 was already submitted at height 100")
+    );
+    // ids are scoped by caller
+    register_client_id(&mut storage, CLIENT_IDS, &bob, "swap-1", 150).unwrap();
+    // the id can be reused once the window passed
+    register_client_id(
+        &mut storage,
+        CLIENT_IDS,
+        &alice,
+        "swap-1",
+        100 + CLIENT_ID_WINDOW_BLOCKS,
+    )
+    .unwrap();
+    assert_eq!(
+        CLIENT_IDS.load(&storage, (&alice, "swap-1")).unwrap(),
+        100 + CLIENT_ID_WINDOW_BLOCKS
+    );
+
+    assert!(register_client_id(&mut storage, CLIENT_IDS, &alice, "", 300).is_err());
+    assert!(register_client_id(
+        &mut storage,
+        CLIENT_IDS,
+        &alice,
+        &"x".repeat(MAX_CLIENT_ID_LENGTH + 1),
+        300
+    )
+    .is_err());
+
+    // many ids of one caller don't evict the ids of another
+    for i in 0..1000 {
+        register_client_id(&mut storage, CLIENT_IDS, &alice, &i.to_string(), 1000).unwrap();
+    }
+    assert_eq!(
+        register_client_id(&mut storage, CLIENT_IDS, &bob, "swap-1", 200).unwrap_err(),
+        RouterError::DuplicateClientId {
+            client_id: "swap-1".to_string(),
+            height: 150
+        }
+    );
+}
+
+#[test]
+fn test_maker_operator() {
+    let owner = Addr::unchecked("owner0000");
+    let operator = Addr::unchecked("operator0000");
+    let update_bridges = MakerExecuteMsg::UpdateBridges {
+        add: None,
+        remove: Some(vec![native_asset_info("uluna".to_string())]),
+        add_routes: None,
+    };
+    let update_collect_settings = MakerExecuteMsg::UpdateCollectSettings {
+        collect_cooldown: Some(60),
+        caller_reward_bps: None,
+    };
+    let set_buyback = MakerExecuteMsg::SetBuyback { params: None };
+
+    for msg in [&update_bridges, &update_collect_settings, &set_buyback] {
+        msg.assert_owner_or_operator(&owner, &owner, Some(&operator))
+            .unwrap();
+    }
+    update_bridges
+        .assert_owner_or_operator(&operator, &owner, Some(&operator))
+        .unwrap();
+    update_collect_settings
+        .assert_owner_or_operator(&operator, &owner, Some(&operator))
+        .unwrap();
+    assert_eq!(
+        set_buyback
+            .assert_owner_or_operator(&operator, &owner, Some(&operator))
+            .unwrap_err(),
+        StdError::generic_err("Unauthorized")
+    );
+    assert!(update_bridges
+        .assert_owner_or_operator(&operator, &owner, None)
+        .is_err());
+}
+
+#[test]
+fn test_stable_dynamic_fee() {
+    let params = DynamicFeeParams {
+        target_ratio: Decimal::one(),
+        sensitivity: Decimal::from_ratio(100u8, 1u8),
+    };
+    params.validate().unwrap();
+    assert!(DynamicFeeParams {
+        target_ratio: Decimal::zero(),
+        ..params
+    }
+    .validate()
+    .is_err());
+    assert!(DynamicFeeParams {
+        sensitivity: Decimal::from_ratio(1001u16, 1u8),
+        ..params
+    }
+    .validate()
+    .is_err());
+
+    let bounds = DynamicFeeBounds {
+        min_fee_bps: 1,
+        max_fee_bps: 50,
+    };
+    bounds.validate().unwrap();
+    assert!(DynamicFeeBounds {
+        min_fee_bps: 60,
+        max_fee_bps: 50,
+    }
+    .validate()
+    .is_err());
+
+    let balanced = [Uint128::new(1_000_000), Uint128::new(1_000_000)];
+    let skewed = [Uint128::new(1_001_000), Uint128::new(999_001)];
+    assert_eq!(
+        compute_peg_deviation(balanced, Decimal::one()).unwrap(),
+        Decimal::zero()
+    );
+    assert_eq!(
+        compute_peg_deviation(skewed, Decimal::one()).unwrap(),
+        Decimal::from_ratio(2_000_998_997_999_001u128, 1_000_000_000_000_000_000u128)
+    );
+
+    let base_fee = Decimal::from_ratio(5u8, 10_000u16);
+    // worsening the peg costs more
+    assert_eq!(
+        compute_dynamic_fee(base_fee, &params, &bounds, balanced, skewed).unwrap(),
+        Decimal::from_ratio(600_049_949_899_950u128, 1_000_000_000_000_000_000u128)
+    );
+    // restoring the peg costs less
+    assert_eq!(
+        compute_dynamic_fee(base_fee, &params, &bounds, skewed, balanced).unwrap(),
+        Decimal::from_ratio(399_950_050_100_050u128, 1_000_000_000_000_000_000u128)
+    );
+    // the fee is clamped to the bounds
+    let depegged = [Uint128::new(1_100_000), Uint128::new(901_000)];
+    assert_eq!(
+        compute_dynamic_fee(base_fee, &params, &bounds, balanced, depegged).unwrap(),
+        Decimal::from_ratio(50u8, 10_000u16)
+    );
+    assert_eq!(
+        compute_dynamic_fee(base_fee, &params, &bounds, depegged, balanced).unwrap(),
+        Decimal::from_ratio(1u8, 10_000u16)
+    );
+}
+
+#[test]
+fn test_generator_pool_hooks() {
+    let hooks = vec![Addr::unchecked("points0000"), Addr::unchecked("tax0000")];
+    validate_pool_hooks(&hooks).unwrap();
+    assert_eq!(
+        validate_pool_hooks(&[hooks[0].clone(), hooks[0].clone()]).unwrap_err(),
+        StdError::generic_err("Duplicated pool hook points0000")
+    );
+    let too_many: Vec<Addr> = (0..=MAX_POOL_HOOKS)
+        .map(|i| Addr::unchecked(format!("hook{}", i)))
+        .collect();
+    assert!(validate_pool_hooks(&too_many).is_err());
+
+    let hook_msg = PoolHookMsg::PoolDeposit {
+        lp_token: Addr::unchecked("lp0000"),
+        user: Addr::unchecked("user0000"),
+        amount: Uint128::new(100),
+    };
+    let msgs = pool_hook_msgs(&hooks, &hook_msg).unwrap();
+    assert_eq!(msgs.len(), 2);
+    for (msg, hook) in msgs.iter().zip(hooks.iter()) {
+        assert_eq!(msg.id, POOL_HOOK_REPLY_ID);
+        assert_eq!(msg.gas_limit, Some(POOL_HOOK_GAS_LIMIT));
+        assert_eq!(msg.reply_on, ReplyOn::Error);
+        assert_eq!(
+            msg.msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: hook.to_string(),
+                msg: to_binary(&hook_msg).unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+    assert!(pool_hook_msgs(&[], &hook_msg).unwrap().is_empty());
+}
+
+#[test]
+fn test_factory_effective_fee() {
+    let pair_config = PairConfig {
+        code_id: 1,
+        pair_type: PairType::Stable {},
+        total_fee_bps: 5,
+        maker_fee_bps: 5000,
+        is_disabled: None,
+        reserves_checkpoint_interval: None,
+        max_allowed_spread: None,
+        commission_mode: CommissionMode::default(),
+        permissionless: false,
+        register_in_generator: false,
+        register_in_oracle: false,
+        lp_transfer_hook: false,
+        dynamic_fee_bounds: None,
+        tokenfactory_lp: false,
+    };
+    let pair = Addr::unchecked("pair0000");
+
+    let res = EffectiveFeeResponse::resolve(pair.clone(), &pair_config, None);
+    assert_eq!(res.pair_type, PairType::Stable {});
+    assert_eq!(res.fees, pair_config.fees());
+    assert_eq!(res.source, FeeSource::PairConfig {});
+
+    let fee_override = PairFees {
+        total_fee_bps: 1,
+        maker_fee_bps: 0,
+    };
+    let res = EffectiveFeeResponse::resolve(pair.clone(), &pair_config, Some(fee_override));
+    assert_eq!(res.pair_contract, pair);
+    assert_eq!(res.fees, fee_override);
+    assert_eq!(res.source, FeeSource::Override {});
+}
+
+#[test]
+fn test_staking_two_step_accounting() {
+    // 1000 ASTRO sent with the Enter hook on top of a pool of 2000 ASTRO and 1000 xASTRO
+    let snapshot = StakingSnapshot::before_enter(
+        Uint128::new(3000),
+        Uint128::new(1000),
+        Uint128::new(1000),
+        Uint128::zero(),
+    )
+    .unwrap();
+    assert_eq!(snapshot.total_deposit, Uint128::new(2000));
+
+    let (shares, dead_shares, expected) = snapshot.enter(Uint128::new(1000)).unwrap();
+    assert_eq!(shares, Uint128::new(500));
+    assert!(dead_shares.is_zero());
+    assert_eq!(
+        expected,
+        StakingSnapshot {
+            total_deposit: Uint128::new(3000),
+            total_shares: Uint128::new(1500),
+            dead_shares: Uint128::zero(),
+        }
+    );
+    expected
+        .assert_post_conditions(Uint128::new(3000), Uint128::new(1500))
+        .unwrap();
+    // donations during the dispatch are fine
+    expected
+        .assert_post_conditions(Uint128::new(3100), Uint128::new(1500))
+        .unwrap();
+    // a reentrant mint is not
+    assert_eq!(
+        expected
+            .assert_post_conditions(Uint128::new(3000), Uint128::new(1600))
+            .unwrap_err(),
+        StdError::generic_err("The xASTRO supply is 1600, expected 1500")
+    );
+
+    let (amount, expected) = expected.leave(Uint128::new(300)).unwrap();
+    assert_eq!(amount, Uint128::new(600));
+    assert_eq!(
+        expected,
+        StakingSnapshot {
+            total_deposit: Uint128::new(2400),
+            total_shares: Uint128::new(1200),
+            dead_shares: Uint128::zero(),
+        }
+    );
+    assert_eq!(
+        expected
+            .assert_post_conditions(Uint128::new(2300), Uint128::new(1200))
+            .unwrap_err(),
+        StdError::generic_err("The ASTRO balance is 2300, expected at least 2400")
+    );
+}
+
+#[test]
+fn test_airdrop_merkle_proof() {
+    let hash_pair = |a: [u8; 32], b: [u8; 32]| -> [u8; 32] {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        let mut hasher = Sha256::new();
+        hasher.update(first);
+        hasher.update(second);
+        hasher.finalize().into()
+    };
+    let leaves = [
+        merkle_leaf("user0000", Uint128::new(100)),
+        merkle_leaf("user0001", Uint128::new(200)),
+        merkle_leaf("user0002", Uint128::new(300)),
+    ];
+    let left = hash_pair(leaves[0], leaves[1]);
+    let root = hex::encode(hash_pair(left, leaves[2]));
+    // the fixed width amount keeps leaves with the same concatenation apart
+    assert_ne!(
+        merkle_leaf("user1", Uint128::new(23)),
+        merkle_leaf("user12", Uint128::new(3))
+    );
+
+    let proof = vec![hex::encode(leaves[1]), hex::encode(leaves[2])];
+    verify_merkle_proof(&root, "user0000", Uint128::new(100), &proof).unwrap();
+    verify_merkle_proof(&root, "user0002", Uint128::new(300), &[hex::encode(left)]).unwrap();
+    assert_eq!(
+        verify_merkle_proof(&root, "user0000", Uint128::new(101), &proof).unwrap_err(),
+        StdError::generic_err("Invalid merkle proof")
+    );
+    assert!(
+        verify_merkle_proof(&root, "user0000", Uint128::new(100), &["zz".to_string()]).is_err()
+    );
+    assert!(verify_merkle_proof("abcd", "user0000", Uint128::new(100), &proof).is_err());
+
+    let mut campaign = CampaignResponse {
+        id: 1,
+        merkle_root: root,
+        expiry: 1000,
+        total_amount: Uint128::new(600),
+        claimed_amount: Uint128::new(500),
+        clawed_back: false,
+    };
+    campaign.assert_claimable(Uint128::new(100), 1000).unwrap();
+    assert!(campaign.assert_claimable(Uint128::new(101), 1000).is_err());
+    assert_eq!(
+        campaign
+            .assert_claimable(Uint128::new(100), 1001)
+            .unwrap_err(),
+        StdError::generic_err("Campaign 1 expired at 1000")
+    );
+    assert!(campaign.clawback_amount(1000).is_err());
+    assert_eq!(campaign.clawback_amount(1001).unwrap(), Uint128::new(100));
+    campaign.clawed_back = true;
+    assert!(campaign.clawback_amount(1001).is_err());
+    assert!(campaign.assert_claimable(Uint128::new(1), 900).is_err());
+
+    let msg = claim_and_stake_msg(
+        &Addr::unchecked("astro0000"),
+        &Addr::unchecked("staking0000"),
+        &Addr::unchecked("user0000"),
+        Uint128::new(100),
+    )
+    .unwrap();
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "astro0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking0000".to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&StakingCw20HookMsg::Enter {
+                    lock_duration: None,
+                    receiver: Some("user0000".to_string()),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_pair_fee_history() {
+    let fees = |maker_fee_bps: u16| PairFees {
+        total_fee_bps: 30,
+        maker_fee_bps,
+    };
+    let mut history = vec![];
+    assert!(!record_fee_change(
+        &mut history,
+        FeeChange {
+            height: 1,
+            old: fees(3333),
+            new: fees(3333),
+        }
+    ));
+    assert!(history.is_empty());
+
+    for height in 0..MAX_FEE_HISTORY as u64 + 5 {
+        assert!(record_fee_change(
+            &mut history,
+            FeeChange {
+                height,
+                old: fees(height as u16),
+                new: fees(height as u16 + 1),
+            }
+        ));
+    }
+    assert_eq!(history.len(), MAX_FEE_HISTORY);
+    assert_eq!(history[0].height, 5);
+    assert_eq!(
+        history[MAX_FEE_HISTORY - 1].height,
+        MAX_FEE_HISTORY as u64 + 4
+    );
+
+    let event = history[0].to_event();
+    assert_eq!(event.ty, FEE_CHANGE_EVENT);
+    assert_eq!(
+        event
+            .attributes
+            .iter()
+            .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("height", "5"),
+            ("old_total_fee_bps", "30"),
+            ("old_maker_fee_bps", "5"),
+            ("new_total_fee_bps", "30"),
+            ("new_maker_fee_bps", "6"),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenfactory_creation_fee() {
+    let coin = [b"\x0a\x05uluna".to_vec(), b"\x12\x0810000000".to_vec()].concat();
+    // the creation fee followed by the gas consumed by the creation
+    let params = [vec![0x0a, coin.len() as u8], coin, vec![0x10, 0xe8, 0x07]].concat();
+    let response = [vec![0x0a, params.len() as u8], params].concat();
+
+    let params = TokenFactoryParams::decode(&response).unwrap();
+    assert_eq!(
+        params.denom_creation_fee,
+        vec![Coin::new(10_000_000, "uluna")]
+    );
+    assert_eq!(
+        TokenFactoryParams::decode(&[]).unwrap(),
+        TokenFactoryParams::default()
+    );
+    assert!(TokenFactoryParams::decode(&response[..response.len() - 1]).is_err());
+
+    assert_eq!(
+        tokenfactory_creation_funds(
+            &[Coin::new(10_000_000, "uluna"), Coin::new(5, "uusd")],
+            &params
+        )
+        .unwrap(),
+        vec![Coin::new(10_000_000, "uluna")]
+    );
+    assert_eq!(
+        tokenfactory_creation_funds(&[Coin::new(1_000, "uluna")], &params).unwrap_err(),
+        StdError::generic_err(
+            "Creating a tokenfactory LP token requires a fee of 10000000uluna, provided 1000uluna"
+        )
+    );
+    assert!(
+        tokenfactory_creation_funds(&[], &TokenFactoryParams::default())
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_generator_legacy_migration() {
+    let legacy = Addr::unchecked("legacy_generator0000");
+    let user = Addr::unchecked("user0000");
+    let positions = vec![
+        (Addr::unchecked("lp_token0000"), Uint128::new(100)),
+        (Addr::unchecked("lp_token0001"), Uint128::zero()),
+        (Addr::unchecked("lp_token0002"), Uint128::new(5)),
+    ];
+
+    assert_eq!(
+        legacy_release_msg(&legacy, &user, &positions).unwrap(),
+        Some(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: legacy.to_string(),
+            msg: to_binary(&GeneratorExecuteMsg::ReleaseToSuccessor {
+                user: user.to_string(),
+                lp_tokens: vec!["lp_token0000".to_string(), "lp_token0002".to_string()],
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+    assert_eq!(
+        legacy_release_msg(&legacy, &user, &positions[1..2]).unwrap(),
+        None
+    );
+    assert_eq!(legacy_release_msg(&legacy, &user, &[]).unwrap(), None);
+}
+
+#[test]
+fn test_sudo_msgs() {
+    let sudo = FactorySudoMsg::UpdateConfig {
+        token_code_id: Some(2),
+        fee_address: None,
+        generator_address: Some("generator0000".to_string()),
+        whitelist_code_id: None,
+        volume_tracker: None,
+        guardian: Some(UpdateAddr::Remove {}),
+        oracle_factory: None,
+    };
+    assert_eq!(
+        sudo.into_execute_msg(),
+        FactoryExecuteMsg::UpdateConfig {
+            token_code_id: Some(2),
+            fee_address: None,
+            generator_address: Some("generator0000".to_string()),
+            whitelist_code_id: None,
+            volume_tracker: None,
+            guardian: Some(UpdateAddr::Remove {}),
+            oracle_factory: None,
+        }
+    );
+
+    let sudo: MakerSudoMsg = from_binary(&Binary::from(
+        br#"{"update_config":{"max_spread":"0.1","collect_cooldown":60}}"#.as_ref(),
+    ))
+    .unwrap();
+    match sudo.into_execute_msg() {
+        MakerExecuteMsg::UpdateConfig {
+            max_spread,
+            collect_cooldown,
+            operator,
+            ..
+        } => {
+            assert_eq!(max_spread, Some(Decimal::percent(10)));
+            assert_eq!(collect_cooldown, Some(60));
+            assert_eq!(operator, None);
+        }
+        msg => panic!("unexpected message {:?}", msg),
+    }
+}
+
+#[test]
+fn test_pair_refund_report() {
+    let receiver = Addr::unchecked("user0000");
+    let token = AssetInfo::Token {
+        contract_addr: Addr::unchecked("token0000"),
+    };
+    let report = RefundReport::new(
+        vec![
+            native_asset("uusd".to_string(), Uint128::new(100)),
+            Asset {
+                info: token.clone(),
+                amount: Uint128::zero(),
+            },
+        ],
+        receiver.clone(),
+        RefundReason::ExcessProvide {},
+    )
+    .unwrap();
+    assert_eq!(
+        report.refund_assets,
+        vec![native_asset("uusd".to_string(), Uint128::new(100))]
+    );
+    assert_eq!(
+        report.attributes(),
+        vec![
+            attr("refund_assets", "100uusd"),
+            attr("refund_receiver", "user0000"),
+            attr("refund_reason", "excess_provide"),
+        ]
+    );
+    let data = to_binary(&report).unwrap();
+    assert_eq!(from_binary::<RefundReport>(&data).unwrap(), report);
+
+    let report = RefundReport::new(
+        vec![
+            native_asset("uusd".to_string(), Uint128::new(1)),
+            Asset {
+                info: token,
+                amount: Uint128::new(2),
+            },
+        ],
+        receiver.clone(),
+        RefundReason::PartialFill {},
+    )
+    .unwrap();
+    assert_eq!(
+        report.attributes()[0],
+        attr("refund_assets", "1uusd,2token0000")
+    );
+
+    for reason in [
+        RefundReason::ExcessProvide {},
+        RefundReason::PartialFill {},
+        RefundReason::BatchSpreadViolation {},
+        RefundReason::ExpiredDeposit {},
+        RefundReason::FailedHook {},
+    ] {
+        assert_eq!(
+            RefundReport::new(vec![], receiver.clone(), reason.clone()),
+            None
+        );
+        assert_eq!(
+            to_binary(&reason).unwrap(),
+            Binary::from(format!("{{\"{}\":{{}}}}", reason).as_bytes())
+        );
+    }
+}
+
+#[test]
+fn test_router_fee_discount() {
+    let config = FeeDiscountConfig {
+        token: "xastro0000".to_string(),
+        tiers: vec![
+            FeeDiscountTier {
+                min_balance: Uint128::new(1_000),
+                discount_bps: 1_000,
+            },
+            FeeDiscountTier {
+                min_balance: Uint128::new(10_000),
+                discount_bps: 2_500,
+            },
+        ],
+    };
+    config.validate().unwrap();
+    assert_eq!(config.discount_bps(Uint128::new(999)), 0);
+    assert_eq!(config.discount_bps(Uint128::new(1_000)), 1_000);
+    assert_eq!(config.discount_bps(Uint128::new(50_000)), 2_500);
+
+    let mut unordered = config.clone();
+    unordered.tiers.reverse();
+    assert_eq!(
+        unordered.validate().unwrap_err(),
+        StdError::generic_err("Fee discount tiers must be in ascending order")
+    );
+    let mut too_high = config.clone();
+    too_high.tiers[1].discount_bps = 10_001;
+    assert!(too_high.validate().is_err());
+    let empty = FeeDiscountConfig {
+        tiers: vec![],
+        ..config
+    };
+    assert!(empty.validate().is_err());
+
+    // the pair caps the discount of the router
+    let fees = PairFees {
+        total_fee_bps: 30,
+        maker_fee_bps: 3333,
+    };
+    assert_eq!(
+        fees.discounted_commission_rate(0, 5_000),
+        fees.commission_rate()
+    );
+    assert_eq!(
+        fees.discounted_commission_rate(2_500, 5_000),
+        Decimal::from_ratio(225u128, 100_000u128)
+    );
+    assert_eq!(
+        fees.discounted_commission_rate(10_000, 5_000),
+        Decimal::from_ratio(15u128, 10_000u128)
+    );
+    assert_eq!(
+        fees.discounted_commission_rate(2_500, 0),
+        fees.commission_rate()
+    );
+}
+
+#[test]
+fn test_maker_fee_sources() {
+    let maker = Addr::unchecked("maker0000");
+    let sources: Vec<Addr> = (0..MAX_FEE_SOURCES)
+        .map(|i| Addr::unchecked(format!("source{}", i)))
+        .collect();
+
+    assert!(assert_fee_source_registrable(&sources[..1], &sources[1]).is_ok());
+    assert_eq!(
+        assert_fee_source_registrable(&sources[..1], &sources[0]).unwrap_err(),
+        StdError::generic_err("Fee source source0 is already registered")
+    );
+    assert!(assert_fee_source_registrable(&sources, &Addr::unchecked("lending0000")).is_err());
+
+    let msgs = fee_source_withdraw_msgs(&sources[..2], &maker).unwrap();
+    assert_eq!(msgs.len(), 2);
+    assert_eq!(
+        msgs[1],
+        SubMsg {
+            id: FEE_SOURCE_REPLY_ID,
+            msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "source1".to_string(),
+                msg: to_binary(&FeeSourceExecuteMsg::WithdrawFees {
+                    to: maker.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            gas_limit: Some(FEE_SOURCE_GAS_LIMIT),
+            reply_on: ReplyOn::Error,
+        }
+    );
+    assert!(fee_source_withdraw_msgs(&[], &maker).unwrap().is_empty());
+}
+
+#[test]
+fn test_oracle_min_liquidity() {
+    let min_liquidity = [Uint128::new(1_000), Uint128::new(500)];
+
+    assert!(assert_price_reliable(
+        [Uint128::new(1_000), Uint128::new(500)],
+        Some(min_liquidity)
+    )
+    .is_ok());
+    assert!(assert_price_reliable([Uint128::zero(), Uint128::zero()], None).is_ok());
+    let err = assert_price_reliable(
+        [Uint128::new(5_000), Uint128::new(499)],
+        Some(min_liquidity),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        OracleError::PriceUnreliable {
+            reserves: [Uint128::new(5_000), Uint128::new(499)],
+            min_liquidity,
+        }
+    );
+    assert_eq!(
+        StdError::from(err),
+        StdError::generic_err(
+            "Price is unreliable: the reserves 5000, 499 are below the minimum liquidity 1000, 500"
+        )
+    );
+
+    let event = invalidation_event(
+        &Addr::unchecked("pair0000"),
+        [Uint128::new(5_000), Uint128::new(499)],
+    );
+    assert_eq!(event.ty, INVALIDATION_EVENT);
+    assert_eq!(event.attributes[2].value, "499");
+}
+
+#[test]
+fn test_staking_reward_epochs() {
+    // the fee distributor epochs aren't aligned to the unix epoch
+    let start_time = 1_000_000;
+    let epoch_start = get_epoch_start(start_time, 10);
+    assert_eq!(reward_epoch(start_time, epoch_start).unwrap(), 10);
+    assert_eq!(reward_epoch(start_time, epoch_start - 1).unwrap(), 9);
+    // a reward epoch is the fee distributor epoch with the same number
+    for time in [
+        start_time,
+        epoch_start - 1,
+        epoch_start,
+        epoch_start + EPOCH_LENGTH,
+    ] {
+        assert_eq!(
+            reward_epoch(start_time, time).unwrap(),
+            get_epoch(start_time, time).unwrap()
+        );
+    }
+    assert!(reward_epoch(start_time, start_time - 1).is_err());
+
+    // only finished epochs can be claimed
+    assert_eq!(
+        claimable_epochs(None, 8, start_time, epoch_start, None),
+        8..10
+    );
+    assert_eq!(
+        claimable_epochs(Some(8), 5, start_time, epoch_start, None),
+        9..10
+    );
+    assert_eq!(
+        claimable_epochs(Some(9), 5, start_time, epoch_start, None),
+        10..10
+    );
+    assert_eq!(
+        claimable_epochs(None, 0, start_time, epoch_start, Some(3)),
+        0..3
+    );
+    assert_eq!(
+        claimable_epochs(None, 12, start_time, epoch_start, None),
+        10..10
+    );
+    assert_eq!(
+        claimable_epochs(None, 0, start_time, start_time - 1, None),
+        0..0
+    );
+
+    let mut assets = vec![];
+    add_reward_asset(
+        &mut assets,
+        native_asset("uusd".to_string(), Uint128::new(300)),
+    )
+    .unwrap();
+    add_reward_asset(
+        &mut assets,
+        native_asset("uusd".to_string(), Uint128::new(700)),
+    )
+    .unwrap();
+    add_reward_asset(
+        &mut assets,
+        native_asset("uluna".to_string(), Uint128::new(1)),
+    )
+    .unwrap();
+    assert_eq!(
+        assets,
+        vec![
+            native_asset("uusd".to_string(), Uint128::new(1_000)),
+            native_asset("uluna".to_string(), Uint128::new(1)),
+        ]
+    );
+    assert!(add_reward_asset(
+        &mut assets,
+        native_asset("ukrw".to_string(), Uint128::zero())
+    )
+    .is_err());
+    let mut full: Vec<Asset> = (0..MAX_REWARD_ASSETS)
+        .map(|i| native_asset(format!("denom{}", i), Uint128::new(1)))
+        .collect();
+    assert!(
+        add_reward_asset(&mut full, native_asset("ukrw".to_string(), Uint128::new(1))).is_err()
+    );
+    add_reward_asset(
+        &mut full,
+        native_asset("denom0".to_string(), Uint128::new(1)),
+    )
+    .unwrap();
+
+    let epoch = RewardEpochResponse {
+        epoch: 9,
+        total_shares: Uint128::new(4_000),
+        assets,
+    };
+    assert_eq!(
+        epoch.user_rewards(Uint128::new(1_000)),
+        vec![native_asset("uusd".to_string(), Uint128::new(250))]
+    );
+    assert!(epoch.user_rewards(Uint128::zero()).is_empty());
+    let empty = RewardEpochResponse {
+        total_shares: Uint128::zero(),
+        ..epoch
+    };
+    assert!(empty.user_rewards(Uint128::new(1_000)).is_empty());
+}
+
+#[test]
+fn test_address_validation() {
+    let deps = mock_dependencies(&[]);
+    let api = deps.as_ref().api;
+
+    assert_eq!(
+        addr_field_validate(api, "owner", "owner0000").unwrap(),
+        Addr::unchecked("owner0000")
+    );
+    assert_eq!(
+        addr_field_validate(api, "owner", "Owner0000").unwrap_err(),
+        StdError::generic_err(
+            "Invalid owner: Generic error: Address Owner0000 should be lowercase"
+        )
+    );
+    assert_eq!(addr_opt_validate(api, "guardian", &None).unwrap(), None);
+    assert_eq!(
+        addr_opt_validate(api, "guardian", &Some("guardian0000".to_string())).unwrap(),
+        Some(Addr::unchecked("guardian0000"))
+    );
+    assert_eq!(
+        validate_addresses(
+            api,
+            "proxies",
+            &["proxy0000".to_string(), "PROXY0001".to_string()]
+        )
+        .unwrap_err(),
+        StdError::generic_err(
+            "Invalid proxies[1]: Generic error: Address PROXY0001 should be lowercase"
+        )
+    );
+    assert_eq!(
+        validate_addresses(api, "proxies", &["proxy0000".to_string()]).unwrap(),
+        vec![Addr::unchecked("proxy0000")]
+    );
+
+    assert_eq!(
+        UpdateAddr::Set("tracker0000".to_string())
+            .validate(api, "volume_tracker")
+            .unwrap(),
+        Some(Addr::unchecked("tracker0000"))
+    );
+    assert_eq!(
+        UpdateAddr::Remove {}
+            .validate(api, "volume_tracker")
+            .unwrap(),
+        None
+    );
+    assert!(UpdateAddr::Set("Tracker".to_string())
+        .validate(api, "volume_tracker")
+        .is_err());
+}
+
+#[test]
+fn test_pair_mev_capture() {
+    let params = MevCaptureParams {
+        capture_ratio: Decimal::percent(50),
+        min_profit: Uint128::new(5),
+    };
+    params.validate().unwrap();
+    for capture_ratio in [Decimal::zero(), Decimal::percent(101)] {
+        assert!(MevCaptureParams {
+            capture_ratio,
+            ..params.clone()
+        }
+        .validate()
+        .is_err());
+    }
+
+    // the front-run buys 50 of the second asset for 100 of the first one
+    let mut flow = TraderFlow::default();
+    flow.record_swap(10, 0, Uint128::new(100), Uint128::new(50))
+        .unwrap();
+    assert_eq!(flow.offered, [Uint128::new(100), Uint128::zero()]);
+    assert_eq!(flow.returned, [Uint128::zero(), Uint128::new(50)]);
+
+    // another swap in the same direction is not a round trip
+    assert_eq!(
+        flow.round_trip_fee(&params, 10, 0, Uint128::new(100), Uint128::new(45))
+            .unwrap(),
+        Uint128::zero()
+    );
+    // the back-run sells everything back for 110, the profit is 10
+    assert_eq!(
+        flow.round_trip_fee(&params, 10, 1, Uint128::new(50), Uint128::new(110))
+            .unwrap(),
+        Uint128::new(5)
+    );
+    // only the bought amount is matched, 50 of 100 return 120 for a cost of 100
+    assert_eq!(
+        flow.round_trip_fee(&params, 10, 1, Uint128::new(100), Uint128::new(240))
+            .unwrap(),
+        Uint128::new(10)
+    );
+    // a loss or a profit below the minimum is not charged
+    assert_eq!(
+        flow.round_trip_fee(&params, 10, 1, Uint128::new(50), Uint128::new(90))
+            .unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        flow.round_trip_fee(&params, 10, 1, Uint128::new(50), Uint128::new(104))
+            .unwrap(),
+        Uint128::zero()
+    );
+    // swaps of later blocks are not matched
+    assert_eq!(
+        flow.round_trip_fee(&params, 11, 1, Uint128::new(50), Uint128::new(110))
+            .unwrap(),
+        Uint128::zero()
+    );
+
+    flow.record_swap(11, 1, Uint128::new(50), Uint128::new(110))
+        .unwrap();
+    assert_eq!(
+        flow,
+        TraderFlow {
+            height: 11,
+            offered: [Uint128::zero(), Uint128::new(50)],
+            returned: [Uint128::new(110), Uint128::zero()],
+        }
+    );
+}
+
+#[test]
+fn test_compat_router_path() {
+    let uusd = native_asset_info("uusd".to_string());
+    let astro = token_asset_info(Addr::unchecked("astro"));
+    let uluna = native_asset_info("uluna".to_string());
+
+    assert_eq!(
+        path_to_operations(&[native_asset_info("uusd".to_string())]).unwrap_err(),
+        StdError::generic_err("The path must contain at least two assets")
+    );
+    assert_eq!(
+        path_to_operations(&[uusd.clone(), astro.clone(), astro.clone()]).unwrap_err(),
+        StdError::generic_err("Path index 2: the asset astro is repeated")
+    );
+    let operations = path_to_operations(&[uusd.clone(), astro.clone(), uluna.clone()]).unwrap();
+    assert_eq!(
+        operations,
+        vec![
+            SwapOperation::AstroSwap {
+                offer_asset_info: uusd.clone(),
+                ask_asset_info: astro.clone(),
+            },
+            SwapOperation::AstroSwap {
+                offer_asset_info: astro.clone(),
+                ask_asset_info: uluna.clone(),
+            },
+        ]
+    );
+
+    let router = Addr::unchecked("router");
+    let to = Addr::unchecked("recipient");
+    let path = [uusd.clone(), astro.clone(), uluna.clone()];
+    assert!(router_swap_msg(
+        &router,
+        native_asset("uluna".to_string(), Uint128::new(100)),
+        &path,
+        Uint128::new(90),
+        &to,
+        1000
+    )
+    .is_err());
+    // an empty path is rejected instead of panicking
+    assert_eq!(
+        router_swap_msg(
+            &router,
+            native_asset("uusd".to_string(), Uint128::new(100)),
+            &[],
+            Uint128::new(90),
+            &to,
+            1000
+        )
+        .unwrap_err(),
+        StdError::generic_err("The path must contain at least two assets")
+    );
+    assert_eq!(
+        router_swap_msg(
+            &router,
+            native_asset("uusd".to_string(), Uint128::new(100)),
+            &path,
+            Uint128::new(90),
+            &to,
+            1000
+        )
+        .unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: operations.clone(),
+                minimum_receive: Some(Uint128::new(90)),
+                to: Some(to.clone()),
+                recipients: vec![],
+                allow_partial: false,
+                deadline: Some(1000),
+                client_id: None,
+            })
+            .unwrap(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100),
+            }],
+        })
+    );
+    let path = [astro.clone(), uluna.clone()];
+    assert_eq!(
+        router_swap_msg(
+            &router,
+            token_asset(Addr::unchecked("astro"), Uint128::new(100)),
+            &path,
+            Uint128::new(90),
+            &to,
+            1000
+        )
+        .unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "astro".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "router".to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations {
+                    operations: path_to_operations(&path).unwrap(),
+                    minimum_receive: Some(Uint128::new(90)),
+                    to: Some("recipient".to_string()),
+                    recipients: vec![],
+                    allow_partial: false,
+                    deadline: Some(1000),
+                    client_id: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_generator_alloc_shares() {
+    assert_eq!(
+        pool_emission_share(Decimal::percent(20), Decimal::percent(80)),
+        Decimal::percent(25)
+    );
+    assert_eq!(
+        pool_emission_share(Decimal::percent(20), Decimal::zero()),
+        Decimal::zero()
+    );
+
+    let total =
+        update_total_alloc_point(Decimal::zero(), Decimal::zero(), Decimal::percent(70)).unwrap();
+    assert_eq!(total, Decimal::percent(70));
+    assert_eq!(
+        update_total_alloc_point(total, Decimal::zero(), Decimal::percent(31)).unwrap_err(),
+        StdError::generic_err("The total allocation share 1.01 can't exceed 1")
+    );
+    // replacing the share of a pool only counts the difference
+    assert_eq!(
+        update_total_alloc_point(total, Decimal::percent(70), Decimal::one()).unwrap(),
+        Decimal::one()
+    );
+    assert_eq!(
+        update_total_alloc_point(total, Decimal::percent(70), Decimal::zero()).unwrap(),
+        Decimal::zero()
+    );
+    assert!(update_total_alloc_point(total, Decimal::percent(80), Decimal::zero()).is_err());
+    // callers still sending integer allocation points get a hint to convert them
+    assert_eq!(
+        update_total_alloc_point(
+            Decimal::zero(),
+            Decimal::zero(),
+            Decimal::from_ratio(100u128, 1u128)
+        )
+        .unwrap_err(),
+        StdError::generic_err(
+            "The allocation share 100 can't exceed 1, integer allocation points must be converted to shares"
+        )
+    );
+
+    // legacy integer allocation points keep their split
+    assert_eq!(
+        normalize_alloc_points(&[
+            Decimal::from_ratio(100u128, 1u128),
+            Decimal::from_ratio(300u128, 1u128)
+        ])
+        .unwrap(),
+        vec![Decimal::percent(25), Decimal::percent(75)]
+    );
+}
+
+#[test]
+fn test_maker_unset_governance() {
+    let mut config = MakerConfigResponse {
+        owner: Addr::unchecked("owner"),
+        astro_token_contract: Addr::unchecked("astro0000"),
+        factory_contract: Addr::unchecked("factory0000"),
+        additional_factories: vec![],
+        staking_contract: Addr::unchecked("staking0000"),
+        governance_contract: None,
+        governance_percent: Uint64::new(20),
+        burn_percent: Uint64::zero(),
+        burned_astro: Uint128::zero(),
+        max_spread: Decimal::percent(5),
+        remainder_reward: Uint128::zero(),
+        pre_upgrade_astro_amount: Uint128::zero(),
+        remote_governance: None,
+        held_astro: Uint128::zero(),
+        min_distribution_interval: 0,
+        min_distribution_amount: Uint128::zero(),
+        last_distribution_time: 0,
+        insurance_fund: None,
+        insurance_percent: Uint64::zero(),
+        distribution_weights: vec![],
+        target_assets: vec![],
+        distribution_rate: None,
+        undistributed_astro: Uint128::new(500),
+        collect_cooldown: 0,
+        caller_reward_bps: 0,
+        last_collect_time: 0,
+        operator: None,
+    };
+    // a percent stored before the governance was removed is not applied
+    assert_eq!(config.effective_governance_percent(), Uint64::zero());
+
+    assert!(validate_governance(None, false, Uint64::zero()).is_ok());
+    assert_eq!(
+        validate_governance(None, false, Uint64::new(20)).unwrap_err(),
+        StdError::generic_err(
+            "The governance percent must be zero while the governance is not set"
+        )
+    );
+    assert_eq!(
+        validate_governance(None, true, Uint64::new(101)).unwrap_err(),
+        StdError::generic_err("The governance percent can't exceed 100")
+    );
+    assert!(validate_governance(None, true, Uint64::new(100)).is_ok());
+
+    // the ASTRO collected while the governance was unset is distributed to the staking first
+    assert_eq!(
+        validate_governance(Some(&config), true, Uint64::new(20)).unwrap_err(),
+        StdError::generic_err(
+            "Distribute the 500 undistributed ASTRO before setting the governance"
+        )
+    );
+    config.undistributed_astro = Uint128::zero();
+    assert!(validate_governance(Some(&config), true, Uint64::new(20)).is_ok());
+
+    config.governance_contract = Some(Addr::unchecked("governance0000"));
+    config.undistributed_astro = Uint128::new(500);
+    assert_eq!(config.effective_governance_percent(), Uint64::new(20));
+    assert!(validate_governance(Some(&config), true, Uint64::new(30)).is_ok());
+    // removing the governance requires zeroing the percent in the same update
+    assert!(validate_governance(Some(&config), false, Uint64::new(20)).is_err());
+    assert!(validate_governance(Some(&config), false, Uint64::zero()).is_ok());
+}
+
+#[test]
+fn test_max_offer_within_spread() {
+    let pool = Uint128::new(1_000_000);
+    let max_spread = Decimal::percent(1);
+    let within_spread = |stable: bool, offer_amount: Uint128| {
+        let (return_amount, spread_amount, commission_amount) = if stable {
+            compute_stable_swap(100, pool, pool, offer_amount, Decimal::permille(3)).unwrap()
+        } else {
+            compute_swap(pool, pool, offer_amount, Decimal::permille(3)).unwrap()
+        };
+        assert_max_spread(
+            None,
+            max_spread,
+            offer_amount,
+            return_amount + commission_amount,
+            spread_amount,
+        )
+        .is_ok()
+    };
+
+    let max_offer = compute_max_offer_within_spread(pool, pool, max_spread).unwrap();
+    assert_eq!(max_offer, Uint128::new(10050));
+    assert!(within_spread(false, max_offer));
+    assert!(!within_spread(false, max_offer + Uint128::new(1)));
+
+    let max_offer = compute_stable_max_offer_within_spread(100, pool, pool, max_spread).unwrap();
+    assert!(max_offer > Uint128::new(10050));
+    assert!(within_spread(true, max_offer));
+    assert!(!within_spread(true, max_offer + Uint128::new(1)));
+
+    assert_eq!(
+        compute_max_offer_within_spread(pool, pool, Decimal::one()).unwrap_err(),
+        SwapError::Std(StdError::generic_err("The max spread must be below 1"))
+    );
+    assert_eq!(
+        compute_stable_max_offer_within_spread(100, Uint128::zero(), pool, max_spread).unwrap_err(),
+        SwapError::PoolEmpty {}
+    );
+}
+
+#[test]
+fn test_router_route_messages() {
+    let astro = token_asset_info(Addr::unchecked("astro"));
+    let operations = vec![
+        SwapOperation::NativeSwap {
+            offer_denom: "uluna".to_string(),
+            ask_denom: "uusd".to_string(),
+        },
+        SwapOperation::AstroSwap {
+            offer_asset_info: native_asset_info("uusd".to_string()),
+            ask_asset_info: astro.clone(),
+        },
+        SwapOperation::AstroSwap {
+            offer_asset_info: astro,
+            ask_asset_info: token_asset_info(Addr::unchecked("wluna")),
+        },
+        SwapOperation::Unwrap {
+            cw20: Addr::unchecked("wluna"),
+            denom: "uluna".to_string(),
+        },
+    ];
+
+    let messages = RouteMessages::new(&operations);
+    assert_eq!(
+        messages,
+        RouteMessages {
+            // four callbacks, the assertion and the native offer of the first pair
+            wasm_executes: 6,
+            // the outputs of both pairs, the token offer of the second pair and the unwrap
+            cw20_transfers: 4,
+            bank_sends: 1,
+            native_swaps: 1,
+        }
+    );
+    assert_eq!(messages.total(), 12);
+    assert_eq!(RouteMessages::new(&[]).total(), 1);
+}
+
+#[test]
+fn test_generator_paused_pool() {
+    // not paused
+    assert_eq!(get_paused_blocks(100, 150, None, None), (50, 0));
+    // paused after the last update, the blocks since the pause are skipped
+    assert_eq!(get_paused_blocks(100, 150, None, Some(120)), (20, 30));
+    // paused before the last update, e.g. after a deposit of the paused pool
+    assert_eq!(get_paused_blocks(130, 150, None, Some(120)), (0, 20));
+    // a pool which is not active yet skips nothing
+    assert_eq!(get_paused_blocks(100, 150, Some(200), Some(120)), (0, 0));
+    assert_eq!(get_paused_blocks(100, 150, Some(130), Some(140)), (10, 10));
+
+    let astro = Addr::unchecked("astro0000");
+    let vesting = Addr::unchecked("vesting0000");
+    assert_eq!(
+        PausedEmissions::default(),
+        PausedEmissions::ReturnToVesting {}
+    );
+    assert_eq!(
+        PausedEmissions::Burn {}
+            .msg(&astro, &vesting, Uint128::zero())
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        PausedEmissions::Burn {}
+            .msg(&astro, &vesting, Uint128::new(10))
+            .unwrap(),
+        Some(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "astro0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(10)
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+    assert_eq!(
+        PausedEmissions::ReturnToVesting {}
+            .msg(&astro, &vesting, Uint128::new(10))
+            .unwrap(),
+        Some(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "astro0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "vesting0000".to_string(),
+                amount: Uint128::new(10)
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+}
+
+#[test]
+fn test_vesting_aggregate_unlocks() {
+    let schedule = |start: u64, start_amount: u128, end: Option<(u64, u128)>| VestingInfo {
+        schedules: vec![VestingSchedule {
+            start_point: VestingSchedulePoint {
+                time: start,
+                amount: Uint128::new(start_amount),
+            },
+            end_point: end.map(|(time, amount)| VestingSchedulePoint {
+                time,
+                amount: Uint128::new(amount),
+            }),
+            pauses: vec![],
+        }],
+        released_amount: Uint128::zero(),
+        released_amounts: vec![],
+        revocable: false,
+        revocation: None,
+    };
+
+    let mut buckets = UnlockBucket::split(50, 250, 4).unwrap();
+    assert_eq!(
+        buckets.iter().map(|b| (b.start, b.end)).collect::<Vec<_>>(),
+        vec![(50, 100), (100, 150), (150, 200), (200, 250)]
+    );
+    UnlockBucket::add_unlocks(&mut buckets, &schedule(100, 100, Some((200, 1100)))).unwrap();
+    UnlockBucket::add_unlocks(&mut buckets, &schedule(150, 50, None)).unwrap();
+    assert_eq!(
+        buckets.iter().map(|b| b.amount.u128()).collect::<Vec<_>>(),
+        vec![100, 550, 500, 0]
+    );
+
+    // the last bucket takes the remainder of the period
+    let buckets = UnlockBucket::split(0, 10, 3).unwrap();
+    assert_eq!(buckets[2].start, 6);
+    assert_eq!(buckets[2].end, 10);
+
+    assert!(UnlockBucket::split(10, 10, 1).is_err());
+    assert!(UnlockBucket::split(0, 10, 0).is_err());
+    assert!(UnlockBucket::split(0, 1000, MAX_UNLOCK_BUCKETS + 1).is_err());
+    assert_eq!(
+        UnlockBucket::split(0, 10, 11).unwrap_err(),
+        StdError::generic_err("A bucket must be at least one second long")
+    );
+}
+
+#[test]
+fn test_stakers_snapshot() {
+    assert_eq!(stakers_snapshot_limit(None), 30);
+    assert_eq!(stakers_snapshot_limit(Some(5)), 5);
+    assert_eq!(
+        stakers_snapshot_limit(Some(1000)),
+        MAX_STAKERS_SNAPSHOT_LIMIT as usize
+    );
+
+    let balances = vec![
+        (Addr::unchecked("alice"), Uint128::new(100)),
+        (Addr::unchecked("bob"), Uint128::zero()),
+        (Addr::unchecked("carol"), Uint128::new(50)),
+    ];
+    let page = StakersSnapshotResponse::new(10, 1000, balances.clone(), 3);
+    assert_eq!(
+        page.stakers,
+        vec![
+            StakerBalance {
+                address: Addr::unchecked("alice"),
+                balance: Uint128::new(100),
+            },
+            StakerBalance {
+                address: Addr::unchecked("carol"),
+                balance: Uint128::new(50),
+            },
+        ]
+    );
+    // a full page continues after the last read holder, even if its balance is zero
+    assert_eq!(page.next_start_after, Some(Addr::unchecked("carol")));
+    assert_eq!(
+        StakersSnapshotResponse::new(10, 1000, balances[..2].to_vec(), 2).next_start_after,
+        Some(Addr::unchecked("bob"))
+    );
+    assert_eq!(
+        StakersSnapshotResponse::new(10, 1000, balances, 5).next_start_after,
+        None
+    );
+}
+
+#[test]
+fn test_factory_sync_pair() {
+    let registered = PairInfo {
+        asset_infos: [
+            token_asset_info(Addr::unchecked("asset0000")),
+            native_asset_info("uusd".to_string()),
+        ],
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+        capabilities: vec![],
+        metadata: None,
+        official: true,
+    };
+
+    // the order of the asset infos doesn't matter
+    let mut actual = PairInfo {
+        asset_infos: [
+            native_asset_info("uusd".to_string()),
+            token_asset_info(Addr::unchecked("asset0000")),
+        ],
+        official: false,
+        ..registered.clone()
+    };
+    assert!(diverged_pair_fields(&registered, &actual).is_empty());
+    assert_eq!(sync_pair_info(&registered, actual.clone()).unwrap(), None);
+
+    // assets with the same concatenation are still a divergence
+    let shifted = |first: &str, second: &str| PairInfo {
+        asset_infos: [
+            native_asset_info(first.to_string()),
+            native_asset_info(second.to_string()),
+        ],
+        ..registered.clone()
+    };
+    assert_eq!(
+        diverged_pair_fields(&shifted("uatom", "uusdx"), &shifted("uatomu", "usdx")),
+        vec!["asset_infos".to_string()]
+    );
+
+    actual.liquidity_token = Addr::unchecked("liquidity0001");
+    actual.pair_type = PairType::Stable {};
+    actual.capabilities = vec![CAPABILITY_FLASH_LOAN.to_string()];
+    assert_eq!(
+        diverged_pair_fields(&registered, &actual),
+        vec!["liquidity_token".to_string(), "pair_type".to_string()]
+    );
+    assert_eq!(
+        sync_pair_info(&registered, actual.clone()).unwrap(),
+        Some(PairInfo {
+            asset_infos: actual.asset_infos.clone(),
+            liquidity_token: Addr::unchecked("liquidity0001"),
+            pair_type: PairType::Stable {},
+            capabilities: vec![CAPABILITY_FLASH_LOAN.to_string()],
+            ..registered.clone()
+        })
+    );
+
+    let moved = PairInfo {
+        contract_addr: Addr::unchecked("pair0001"),
+        ..actual.clone()
+    };
+    assert_eq!(
+        sync_pair_info(&registered, moved).unwrap_err(),
+        StdError::generic_err("The pair pair0000 reported the address pair0001")
+    );
+    let doubled = PairInfo {
+        asset_infos: [
+            native_asset_info("uusd".to_string()),
+            native_asset_info("uusd".to_string()),
+        ],
+        ..actual
+    };
+    assert!(sync_pair_info(&registered, doubled).is_err());
+}
+
+#[test]
+fn test_cw20_hook_versioning() {
+    let token = Addr::unchecked("asset0000");
+    let hook = PairCw20HookMsg::WithdrawLiquidity {
+        deadline: Some(100),
+    };
+
+    // unversioned messages are still accepted
+    assert_eq!(
+        parse_hook_msg::<PairCw20HookMsg>(&token, &to_binary(&hook).unwrap()).unwrap(),
+        hook
+    );
+    let envelope = HookEnvelope {
+        version: HOOK_VERSION,
+        msg: to_binary(&hook).unwrap(),
+    };
+    assert_eq!(
+        parse_hook_msg::<PairCw20HookMsg>(&token, &to_binary(&envelope).unwrap()).unwrap(),
+        hook
+    );
+
+    let envelope = HookEnvelope {
+        version: HOOK_VERSION + 1,
+        ..envelope
+    };
+    assert_eq!(
+        parse_hook_msg::<PairCw20HookMsg>(&token, &to_binary(&envelope).unwrap()).unwrap_err(),
+        HookError::UnsupportedVersion {
+            token: token.clone(),
+            version: HOOK_VERSION + 1,
+            expected: HOOK_VERSION,
+        }
+    );
+    let err =
+        parse_hook_msg::<PairCw20HookMsg>(&token, &Binary::from(br#"{"unknown":{}}"#)).unwrap_err();
+    assert!(
+        matches!(err, HookError::UnknownMessage { ref token, .. } if token.as_str() == "asset0000")
+    );
+    assert!(StdError::from(err)
+        .to_string()
+        .contains("Unknown hook message sent by token asset0000"));
+
+    let rejection = HookRejection {
+        token: token.clone(),
+        sender: Addr::unchecked("user0000"),
+        amount: Uint128::new(100),
+        reason: "unknown variant".to_string(),
+        height: 10,
+    };
+    assert_eq!(
+        rejection.refund_msg().unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "user0000".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    let mut rejected = RejectedHooksResponse::default();
+    rejected.record(rejection.clone());
+    rejected.record(HookRejection {
+        height: 11,
+        ..rejection
+    });
+    assert_eq!(rejected.count, 2);
+    assert_eq!(rejected.last_rejection.unwrap().height, 11);
+}
+
+#[test]
+fn test_generator_user_portfolio() {
+    let position = |lp_token: &str, amount: u128, pending: u128| PortfolioPosition {
+        deposit: UserDepositInfo {
+            lp_token: Addr::unchecked(lp_token),
+            amount: Uint128::from(amount),
+            pending: Uint128::from(pending),
+            pending_on_proxy: None,
+            proxy_reward_token: None,
+            reward_beneficiary: None,
+        },
+        boost_multiplier: Decimal::one(),
+        boosted_amount: Uint128::from(amount),
+    };
+
+    // a full page skips the emptied deposit and points to the next page
+    let res = UserPortfolioResponse::new(
+        vec![
+            position("lp0", 100, 10),
+            position("lp1", 0, 5),
+            position("lp2", 50, 20),
+        ],
+        3,
+    )
+    .unwrap();
+    assert_eq!(
+        res.positions,
+        vec![position("lp0", 100, 10), position("lp2", 50, 20)]
+    );
+    assert_eq!(res.total_pending, Uint128::from(30u128));
+    assert_eq!(res.next_start_after, Some(Addr::unchecked("lp2")));
+
+    // the last page has no next page
+    let res = UserPortfolioResponse::new(vec![position("lp3", 0, 0)], 3).unwrap();
+    assert_eq!(res.positions, vec![]);
+    assert_eq!(res.total_pending, Uint128::zero());
+    assert_eq!(res.next_start_after, None);
+}
+
+#[test]
+fn test_maker_insurance_percent() {
+    assert!(validate_insurance_percent(Uint64::zero()).is_ok());
+    assert!(validate_insurance_percent(Uint64::new(100)).is_ok());
+    assert_eq!(
+        validate_insurance_percent(Uint64::new(101)).unwrap_err(),
+        StdError::generic_err("The insurance percent can't exceed 100")
+    );
+}
+
+#[test]
+fn test_maker_burn_percent() {
+    let err = StdError::generic_err(
+        "The burn percent together with the governance and the insurance percents can't exceed 100",
+    );
+    assert!(validate_burn_percent(Uint64::new(40), Uint64::new(60), Uint64::zero()).is_ok());
+    assert!(validate_burn_percent(Uint64::new(40), Uint64::new(40), Uint64::new(20)).is_ok());
+    assert_eq!(
+        validate_burn_percent(Uint64::new(40), Uint64::new(61), Uint64::zero()).unwrap_err(),
+        err
+    );
+    // the insurance share counts towards the limit
+    assert_eq!(
+        validate_burn_percent(Uint64::new(40), Uint64::new(40), Uint64::new(21)).unwrap_err(),
+        err
+    );
+    assert_eq!(
+        validate_burn_percent(Uint64::zero(), Uint64::new(1), Uint64::new(u64::MAX)).unwrap_err(),
+        err
+    );
+
+    let mut config = MakerConfigResponse {
+        owner: Addr::unchecked("owner"),
+        astro_token_contract: Addr::unchecked("astro0000"),
+        factory_contract: Addr::unchecked("factory0000"),
+        additional_factories: vec![],
+        staking_contract: Addr::unchecked("staking0000"),
+        governance_contract: None,
+        governance_percent: Uint64::zero(),
+        burn_percent: Uint64::zero(),
+        burned_astro: Uint128::zero(),
+        max_spread: Decimal::percent(5),
+        remainder_reward: Uint128::zero(),
+        pre_upgrade_astro_amount: Uint128::zero(),
+        remote_governance: None,
+        held_astro: Uint128::zero(),
+        min_distribution_interval: 0,
+        min_distribution_amount: Uint128::zero(),
+        last_distribution_time: 0,
+        insurance_fund: None,
+        insurance_percent: Uint64::zero(),
+        distribution_weights: vec![],
+        target_assets: vec![],
+        distribution_rate: None,
+        undistributed_astro: Uint128::zero(),
+        collect_cooldown: 0,
+        caller_reward_bps: 0,
+        last_collect_time: 0,
+        operator: None,
+    };
+    // nothing is burned by default
+    assert_eq!(config.burn_msg(Uint128::from(1000u128)).unwrap(), None);
+
+    // the burned amounts accumulate
+    config.burn_percent = Uint64::new(10);
+    let (burned, msg) = config.burn_msg(Uint128::from(1000u128)).unwrap().unwrap();
+    assert_eq!(burned, Uint128::from(100u128));
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "astro0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(100u128)
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    config.burn_msg(Uint128::from(555u128)).unwrap();
+    assert_eq!(config.burned_astro, Uint128::from(155u128));
+}
+
+#[test]
+fn test_pair_paused_oracle() {
+    let pools = [Uint128::from(1000u128), Uint128::from(2000u128)];
+    let last = [Uint128::from(10u128), Uint128::from(20u128)];
+    let mut paused = PausedOperations::default();
+    assert!(!paused.is_paused());
+
+    // the pool price is recorded while swaps are allowed
+    assert_eq!(
+        accumulate_prices(&paused, pools, last, 10).unwrap(),
+        [Uint128::from(20_000_010u128), Uint128::from(5_000_020u128)]
+    );
+    // the prices are frozen with empty reserves
+    assert_eq!(
+        accumulate_prices(&paused, [Uint128::zero(), pools[1]], last, 10).unwrap(),
+        last
+    );
+
+    // the reference price requires paused swaps
+    paused.reference_price = Some(Decimal::percent(400));
+    assert_eq!(
+        paused.validate().unwrap_err(),
+        StdError::generic_err("The reference price can only be set while swaps are paused")
+    );
+    paused.swap = true;
+    assert!(paused.is_paused());
+    assert!(paused.validate().is_ok());
+    assert_eq!(
+        accumulate_prices(&paused, pools, last, 10).unwrap(),
+        [Uint128::from(40_000_010u128), Uint128::from(2_500_020u128)]
+    );
+
+    // the prices are frozen without a reference price
+    paused.reference_price = None;
+    assert_eq!(accumulate_prices(&paused, pools, last, 10).unwrap(), last);
+
+    // price increases beyond u128 wrap around instead of panicking
+    let unpaused = PausedOperations::default();
+    let skewed = [Uint128::new(1), Uint128::MAX];
+    assert_eq!(
+        accumulate_prices(&unpaused, skewed, last, 10).unwrap(),
+        // 10 * 1e6 * u128::MAX wraps to 2^128 - 1e7
+        [
+            last[0].wrapping_add(Uint128::new(u128::MAX - 9_999_999)),
+            last[1]
+        ]
+    );
+
+    paused.reference_price = Some(Decimal::zero());
+    assert_eq!(
+        paused.validate().unwrap_err(),
+        StdError::generic_err("The reference price must be greater than zero")
+    );
+}
+
+#[test]
+fn test_stable_invariant_cache() {
+    let pools = [Uint128::new(1_000_000), Uint128::new(2_000_000)];
+    let cache = InvariantCache::load_or_compute(None, 100, pools).unwrap();
+    assert_eq!(cache.d, compute_d(100, pools).unwrap());
+
+    // the invariant is reused for the same state in any order
+    let stale = InvariantCache {
+        d: Uint128::new(1),
+        ..cache.clone()
+    };
+    assert_eq!(
+        InvariantCache::load_or_compute(Some(&stale), 100, [pools[1], pools[0]]).unwrap(),
+        stale
+    );
+    // and recomputed once the reserves or the amplification change
+    assert_eq!(
+        InvariantCache::load_or_compute(Some(&stale), 200, pools)
+            .unwrap()
+            .d,
+        compute_d(200, pools).unwrap()
+    );
+    let new_pools = [Uint128::new(1_000_100), Uint128::new(2_000_000)];
+    assert_eq!(
+        InvariantCache::load_or_compute(Some(&stale), 100, new_pools).unwrap(),
+        InvariantCache {
+            amp: 100,
+            pools: new_pools,
+            d: compute_d(100, new_pools).unwrap(),
+        }
+    );
+
+    // several offers share one invariant
+    for offer_amount in [100u128, 10_000, 500_000] {
+        assert_eq!(
+            compute_stable_swap_with_d(
+                100,
+                pools[0],
+                pools[1],
+                Uint128::new(offer_amount),
+                Decimal::permille(3),
+                cache.d
+            )
+            .unwrap(),
+            compute_stable_swap(
+                100,
+                pools[0],
+                pools[1],
+                Uint128::new(offer_amount),
+                Decimal::permille(3)
+            )
+            .unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_generator_orphan_proxy_rewards() {
+    // rewards received while nothing is staked become orphaned
+    let (index_increase, orphaned) =
+        distribute_proxy_rewards(Uint128::new(100), Uint128::zero(), Uint128::new(20)).unwrap();
+    assert_eq!(index_increase, Decimal256::zero());
+    assert_eq!(orphaned, Uint128::new(120));
+
+    // and are kept apart once LP tokens are staked again
+    let (index_increase, orphaned) =
+        distribute_proxy_rewards(Uint128::new(100), Uint128::new(50), orphaned).unwrap();
+    assert_eq!(
+        index_increase,
+        reward_index_increase(Uint128::new(100), Uint128::new(50)).unwrap()
+    );
+    assert_eq!(orphaned, Uint128::new(120));
+}
+
+#[test]
+fn test_generator_reward_beneficiary() {
+    let owner = Addr::unchecked("vault0000");
+
+    // the rewards go to the owner of the deposit by default
+    assert_eq!(
+        RewardBeneficiaryResponse::new(owner.clone(), None),
+        RewardBeneficiaryResponse {
+            owner: owner.clone(),
+            beneficiary: owner.clone(),
+        }
+    );
+    // a beneficiary overrides the recipient but not the owner
+    assert_eq!(
+        RewardBeneficiaryResponse::new(owner.clone(), Some(Addr::unchecked("user0000"))),
+        RewardBeneficiaryResponse {
+            owner,
+            beneficiary: Addr::unchecked("user0000"),
+        }
+    );
+
+    // unsetting the beneficiary is sent as null
+    assert_eq!(
+        from_binary::<GeneratorExecuteMsg>(&Binary::from(
+            br#"{"set_reward_beneficiary":{"lp_token":"lp0000","beneficiary":null}}"#.to_vec()
+        ))
+        .unwrap(),
+        GeneratorExecuteMsg::SetRewardBeneficiary {
+            lp_token: "lp0000".to_string(),
+            beneficiary: None,
+        }
+    );
+}
@@ -1,7 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use crate::factory::UpdateAddr;
+use cosmwasm_std::{to_binary, CosmosMsg, ReplyOn, StdError, StdResult, SubMsg, Uint128, WasmMsg};
 use cw20::{Cw20Coin, MinterResponse};
 
 /// ## Description
@@ -19,13 +20,91 @@ pub struct InstantiateMsg {
     pub initial_balances: Vec<Cw20Coin>,
     /// the controls configs of type [`MinterResponse`]
     pub mint: Option<MinterResponse>,
+    /// the contract which is notified with a [`TransferHookMsg`] when tokens change owner
+    #[serde(default)]
+    pub transfer_hook: Option<String>,
+}
+
+/// The gas limit of a transfer hook call, so the hook can't make token transfers run out of gas
+pub const TRANSFER_HOOK_GAS_LIMIT: u64 = 200_000;
+/// The reply identifier of transfer hook calls, their failures are ignored
+pub const TRANSFER_HOOK_REPLY_ID: u64 = 1;
+
+/// ## Description
+/// This structure describes the message sent to the transfer hook of a token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferHookMsg {
+    /// ## Description
+    /// Notifies that `amount` tokens were moved from `sender` to `recipient`
+    TransferNotification {
+        /// the previous owner of the tokens
+        sender: String,
+        /// the new owner of the tokens
+        recipient: String,
+        /// the amount of moved tokens
+        amount: Uint128,
+    },
+    /// ## Description
+    /// Notifies that `amount` tokens were minted to `recipient`
+    MintNotification {
+        /// the owner of the minted tokens
+        recipient: String,
+        /// the amount of minted tokens
+        amount: Uint128,
+    },
+    /// ## Description
+    /// Notifies that `amount` tokens of `owner` were burned
+    BurnNotification {
+        /// the previous owner of the burned tokens
+        owner: String,
+        /// the amount of burned tokens
+        amount: Uint128,
+    },
+}
+
+impl TransferHookMsg {
+    /// ## Description
+    /// Returns a [`CosmosMsg`] which sends the notification to the transfer hook.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **hook** is the contract which receives the notification.
+    pub fn into_cosmos_msg(self, hook: String) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: hook,
+            msg: to_binary(&self)?,
+            funds: vec![],
+        }))
+    }
+
+    /// ## Description
+    /// Returns a [`SubMsg`] which sends the notification to the transfer hook with
+    /// [`TRANSFER_HOOK_GAS_LIMIT`] and replies with [`TRANSFER_HOOK_REPLY_ID`] on error only, so a
+    /// failing hook doesn't revert the token operation.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **hook** is the contract which receives the notification.
+    pub fn into_sub_msg(self, hook: String) -> StdResult<SubMsg> {
+        Ok(SubMsg {
+            id: TRANSFER_HOOK_REPLY_ID,
+            msg: self.into_cosmos_msg(hook)?,
+            gas_limit: Some(TRANSFER_HOOK_GAS_LIMIT),
+            reply_on: ReplyOn::Error,
+        })
+    }
 }
 
 /// ## Description
 /// This structure describes a migration message.
-/// We currently take no arguments for migrations.
 #[derive(Serialize, Deserialize, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// updates or removes the contract notified when tokens change owner, e.g. after the
+    /// generator moved to a new address
+    #[serde(default)]
+    pub transfer_hook: Option<UpdateAddr>,
+}
 
 impl InstantiateMsg {
     pub fn get_cap(&self) -> Option<Uint128> {
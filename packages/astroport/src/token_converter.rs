@@ -0,0 +1,127 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the CW20 ASTRO token which is converted
+    pub old_astro_token: String,
+    /// the tokenfactory denom of the new ASTRO
+    pub new_astro_denom: String,
+    /// the timestamp until which the new ASTRO can be converted back to the CW20 ASTRO
+    pub reverse_grace_period_end: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`] and converts the received CW20 ASTRO 1:1
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Converts the attached new ASTRO back to the CW20 ASTRO 1:1. Only available during the
+    /// reverse grace period
+    Revert {
+        /// the recipient of the CW20 ASTRO
+        receiver: Option<String>,
+    },
+    /// ## Description
+    /// Pauses or resumes conversions
+    /// ## Executor
+    /// Only owner can execute it
+    SetPaused { paused: bool },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the timestamp until which the new ASTRO can be converted back
+        reverse_grace_period_end: Option<u64>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Converts the received CW20 ASTRO to the new ASTRO
+    Convert {
+        /// the recipient of the new ASTRO
+        receiver: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns the conversion statistics in a [`ConversionStatsResponse`] object
+    ConversionStats {},
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the CW20 ASTRO token which is converted
+    pub old_astro_token: Addr,
+    /// the tokenfactory denom of the new ASTRO
+    pub new_astro_denom: String,
+    /// the timestamp until which the new ASTRO can be converted back
+    pub reverse_grace_period_end: Option<u64>,
+    /// whether conversions are paused
+    pub paused: bool,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConversionStatsResponse {
+    /// the total amount of CW20 ASTRO converted to the new ASTRO
+    pub total_converted: Uint128,
+    /// the total amount of new ASTRO converted back to the CW20 ASTRO
+    pub total_reverted: Uint128,
+    /// the amount of CW20 ASTRO held in escrow
+    pub escrowed: Uint128,
+    /// the number of conversions
+    pub conversions: u64,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,226 @@
+use crate::asset::{Asset, AssetInfo};
+use crate::router::SwapOperation;
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of slices of an order
+pub const MAX_SLICES: u32 = 100;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// contract address that used for controls settings
+    pub owner: String,
+    /// the router contract used to swap the slices
+    pub router: String,
+    /// the share of each slice paid to the keeper who executes it
+    pub keeper_fee: Decimal,
+}
+
+/// ## Description
+/// This structure describes the parameters of a streaming swap order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderParams {
+    /// the route used to swap each slice
+    pub operations: Vec<SwapOperation>,
+    /// the number of slices, at most [`MAX_SLICES`]
+    pub slices: u32,
+    /// the time window in seconds over which the slices are executed
+    pub duration: u64,
+    /// the oracle which returns the average amount of the ask asset for the offer asset
+    pub oracle: String,
+    /// the maximum deviation of a slice return from the oracle amount
+    pub max_deviation: Decimal,
+    /// the recipient of the ask asset
+    pub receiver: Option<String>,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
+    /// template.
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Creates an order which swaps the attached native tokens
+    CreateOrder { params: OrderParams },
+    /// ## Description
+    /// Swaps the next slice of an order if it is due and pays the keeper fee to the sender
+    ExecuteSlice { order_id: u64 },
+    /// ## Description
+    /// Cancels an order and returns the remaining offer amount and the received ask amount
+    /// ## Executor
+    /// Only the creator of the order can execute it
+    CancelOrder { order_id: u64 },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the router contract used to swap the slices
+        router: Option<String>,
+        /// the share of each slice paid to the keeper
+        keeper_fee: Option<Decimal>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Creates an order which swaps the received tokens
+    CreateOrder { params: OrderParams },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns an order in a [`OrderResponse`] object
+    Order { order_id: u64 },
+    /// Returns the orders of a creator in a [`OrdersResponse`] object
+    Orders {
+        creator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// contract address that used for controls settings
+    pub owner: Addr,
+    /// the router contract used to swap the slices
+    pub router: Addr,
+    /// the share of each slice paid to the keeper
+    pub keeper_fee: Decimal,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderResponse {
+    /// the order identifier
+    pub order_id: u64,
+    /// the creator of the order
+    pub creator: Addr,
+    /// the parameters of the order
+    pub params: OrderParams,
+    /// the total offer asset of the order
+    pub offer_asset: Asset,
+    /// the ask asset info
+    pub ask_asset_info: AssetInfo,
+    /// the time the order was created
+    pub start_time: u64,
+    /// the number of executed slices
+    pub executed_slices: u32,
+    /// the offer amount which was not swapped yet
+    pub remaining_amount: Uint128,
+    /// the ask amount received so far
+    pub received_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrdersResponse {
+    pub orders: Vec<OrderResponse>,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns an [`Err`] if the parameters of an order are invalid.
+/// ## Params
+/// * **params** is the object of type [`OrderParams`].
+pub fn validate_order_params(params: &OrderParams) -> StdResult<()> {
+    if params.slices == 0 || params.slices > MAX_SLICES {
+        return Err(StdError::generic_err(format!(
+            "The number of slices must be between 1 and {}",
+            MAX_SLICES
+        )));
+    }
+    if params.duration < params.slices as u64 {
+        return Err(StdError::generic_err(
+            "The duration must be at least one second per slice",
+        ));
+    }
+    if params.max_deviation >= Decimal::one() {
+        return Err(StdError::generic_err(
+            "The maximum deviation must be less than one",
+        ));
+    }
+    Ok(())
+}
+
+impl OrderResponse {
+    /// ## Description
+    /// Returns the time at which the next slice can be executed, [`None`] if all slices are executed.
+    pub fn next_slice_time(&self) -> Option<u64> {
+        if self.executed_slices >= self.params.slices {
+            return None;
+        }
+        Some(
+            self.start_time
+                + self.params.duration * self.executed_slices as u64 / self.params.slices as u64,
+        )
+    }
+
+    /// ## Description
+    /// Returns the offer amount of the next slice. The last slice swaps the whole remainder.
+    pub fn next_slice_amount(&self) -> Uint128 {
+        let remaining_slices = self.params.slices.saturating_sub(self.executed_slices);
+        if remaining_slices == 0 {
+            return Uint128::zero();
+        }
+        self.remaining_amount
+            .multiply_ratio(1u128, remaining_slices as u128)
+    }
+}
+
+/// ## Description
+/// Returns the minimum amount a slice must return so that it stays within the maximum deviation
+/// from the oracle amount.
+/// ## Params
+/// * **oracle_amount** is the average ask amount returned by the oracle for the slice.
+///
+/// * **max_deviation** is the maximum deviation of the order.
+pub fn slice_minimum_receive(oracle_amount: Uint128, max_deviation: Decimal) -> Uint128 {
+    oracle_amount * (Decimal::one() - max_deviation)
+}
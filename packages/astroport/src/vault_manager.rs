@@ -0,0 +1,398 @@
+use crate::asset::Asset;
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum performance fee in bps
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 2000;
+/// The maximum number of deposits or withdrawals processed by a single [`ExecuteMsg::ProcessQueues`]
+pub const MAX_PROCESS_QUEUE_LIMIT: u32 = 30;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance contract which approves strategies and controls settings
+    pub owner: String,
+    /// the operator which manages the positions within the approved strategies
+    pub manager: String,
+    /// the pair contract whose liquidity is managed by the vault
+    pub pair: String,
+    /// the code identifier of the share token
+    pub share_token_code_id: u64,
+    /// the maker contract which receives the performance fee
+    pub maker: String,
+    /// the fee charged on the gains of the vault in bps, at most [`MAX_PERFORMANCE_FEE_BPS`]
+    pub performance_fee_bps: u16,
+}
+
+/// ## Description
+/// This enum describes the strategies the manager can run.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    /// Provides the liquidity over the full price range
+    FullRange {},
+    /// Concentrates the liquidity within a price range which is rebalanced once the price
+    /// leaves it
+    Range {
+        /// the lowest price of the first asset in the second one
+        lower_price: Decimal,
+        /// the highest price of the first asset in the second one
+        upper_price: Decimal,
+    },
+}
+
+impl Strategy {
+    /// ## Description
+    /// Returns an [`Err`] if the price range of the strategy is empty.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn validate(&self) -> StdResult<()> {
+        if let Strategy::Range {
+            lower_price,
+            upper_price,
+        } = self
+        {
+            if lower_price.is_zero() || lower_price >= upper_price {
+                return Err(StdError::generic_err(
+                    "The lower price must be positive and less than the upper price",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns true if the position of the strategy has to be rebalanced at the current price.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **price** is the current price of the first asset in the second one.
+    pub fn needs_rebalance(&self, price: Decimal) -> bool {
+        match self {
+            Strategy::FullRange {} => false,
+            Strategy::Range {
+                lower_price,
+                upper_price,
+            } => price < *lower_price || price > *upper_price,
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// ## Description
+    /// Queues a deposit of the pair assets. Shares are minted when the deposit queue is processed
+    Deposit {
+        /// the assets to deposit
+        assets: [Asset; 2],
+        /// the receiver of the shares, the sender if not set
+        receiver: Option<String>,
+    },
+    /// ## Description
+    /// Cancels the queued deposit of the sender and returns the assets
+    CancelDeposit {},
+    /// ## Description
+    /// Mints shares for the queued deposits and pays out the queued withdrawals at the current
+    /// share price, at most [`MAX_PROCESS_QUEUE_LIMIT`] of each
+    /// ## Executor
+    /// Only manager can execute it
+    ProcessQueues { limit: Option<u32> },
+    /// ## Description
+    /// Withdraws the position and provides it again according to the specified approved strategy
+    /// ## Executor
+    /// Only manager can execute it
+    Rebalance {
+        /// the identifier of the approved strategy
+        strategy_id: u64,
+        /// the maximum spread of the swaps which rebalance the assets
+        max_spread: Option<Decimal>,
+    },
+    /// ## Description
+    /// Claims the fees earned by the position, charges the performance fee and provides the
+    /// rest as liquidity
+    /// ## Executor
+    /// Only manager can execute it
+    Compound {},
+    /// ## Description
+    /// Approves a strategy the manager can run
+    /// ## Executor
+    /// Only owner can execute it
+    ApproveStrategy { strategy: Strategy },
+    /// ## Description
+    /// Revokes an approved strategy. The active strategy can't be revoked
+    /// ## Executor
+    /// Only owner can execute it
+    RevokeStrategy { strategy_id: u64 },
+    /// ## Description
+    /// Updates general settings
+    /// ## Executor
+    /// Only owner can execute it
+    UpdateConfig {
+        /// the operator which manages the positions
+        manager: Option<String>,
+        /// the maker contract which receives the performance fee
+        maker: Option<String>,
+        /// the fee charged on the gains of the vault in bps
+        performance_fee_bps: Option<u16>,
+    },
+    /// ## Description
+    /// Creates a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ProposeNewOwner {
+        /// a new ownership
+        owner: String,
+        /// the validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// ## Description
+    /// Removes a request to change ownership
+    /// ## Executor
+    /// Only owner can execute it
+    DropOwnershipProposal {},
+    /// ## Description
+    /// Approves ownership
+    /// ## Executor
+    /// Only owner can execute it
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Queues a withdrawal of the received shares. The assets are sent when the withdrawal queue
+    /// is processed
+    QueueWithdrawal {
+        /// the receiver of the assets, the sender if not set
+        receiver: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Returns the position and the share accounting of the vault in a [`VaultStateResponse`] object
+    VaultState {},
+    /// Returns the approved strategies in a [`StrategiesResponse`] object
+    Strategies {},
+    /// Returns the queued deposits in a [`QueuedDepositsResponse`] object
+    QueuedDeposits {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the queued withdrawals in a [`QueuedWithdrawalsResponse`] object
+    QueuedWithdrawals {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the shares and the queued operations of a user in a [`UserPositionResponse`] object
+    UserPosition { user: String },
+}
+
+/// ## Description
+/// A custom struct for each query response that returns controls settings of contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// the governance contract which approves strategies and controls settings
+    pub owner: Addr,
+    /// the operator which manages the positions
+    pub manager: Addr,
+    /// the pair contract whose liquidity is managed by the vault
+    pub pair: Addr,
+    /// the share token
+    pub share_token: Addr,
+    /// the maker contract which receives the performance fee
+    pub maker: Addr,
+    /// the fee charged on the gains of the vault in bps
+    pub performance_fee_bps: u16,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VaultStateResponse {
+    /// the assets held by the vault including its position
+    pub assets: [Asset; 2],
+    /// the total supply of the share token
+    pub total_shares: Uint128,
+    /// the value of one share in the second asset of the pair
+    pub share_price: Decimal,
+    /// the highest share price the performance fee was charged at
+    pub high_water_mark: Decimal,
+    /// the identifier of the active strategy, [`None`] before the first rebalance
+    pub active_strategy: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes an approved strategy.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategyInfo {
+    /// the identifier of the strategy
+    pub id: u64,
+    /// the strategy
+    pub strategy: Strategy,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategiesResponse {
+    pub strategies: Vec<StrategyInfo>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedDepositsResponse {
+    /// the receivers of the shares and the deposited assets
+    pub deposits: Vec<(Addr, [Asset; 2])>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedWithdrawalsResponse {
+    /// the receivers of the assets and the withdrawn shares
+    pub withdrawals: Vec<(Addr, Uint128)>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserPositionResponse {
+    /// the shares of the user
+    pub shares: Uint128,
+    /// the assets redeemable for the shares, see [`compute_withdrawn_assets`]
+    pub assets: Vec<Asset>,
+    /// the queued deposit of the user
+    pub queued_deposit: Option<[Asset; 2]>,
+    /// the shares of the queued withdrawal of the user
+    pub queued_withdrawal: Uint128,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+/// ## Description
+/// Returns an [`Err`] if the performance fee exceeds [`MAX_PERFORMANCE_FEE_BPS`].
+/// ## Params
+/// * **performance_fee_bps** is the fee charged on the gains of the vault in bps.
+pub fn validate_performance_fee(performance_fee_bps: u16) -> StdResult<()> {
+    if performance_fee_bps > MAX_PERFORMANCE_FEE_BPS {
+        return Err(StdError::generic_err(format!(
+            "The performance fee can't exceed {} bps",
+            MAX_PERFORMANCE_FEE_BPS
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns the value of the pair assets in the second asset.
+/// ## Params
+/// * **assets** are the assets of the pair in the pair order.
+///
+/// * **price** is the price of the first asset in the second one.
+pub fn compute_value(assets: &[Asset; 2], price: Decimal) -> StdResult<Uint128> {
+    Ok((assets[0].amount * price).checked_add(assets[1].amount)?)
+}
+
+/// ## Description
+/// Returns the shares minted for a deposit. The first deposit mints one share per unit of value,
+/// later deposits mint shares in proportion to the value of the vault.
+/// ## Params
+/// * **deposit_value** is the value of the deposit.
+///
+/// * **total_value** is the value of the vault before the deposit.
+///
+/// * **total_shares** is the total supply of the share token.
+pub fn compute_mint_shares(
+    deposit_value: Uint128,
+    total_value: Uint128,
+    total_shares: Uint128,
+) -> StdResult<Uint128> {
+    let shares = if total_shares.is_zero() {
+        deposit_value
+    } else if total_value.is_zero() {
+        return Err(StdError::generic_err(
+            "The vault has shares, but no value to deposit into",
+        ));
+    } else {
+        deposit_value.multiply_ratio(total_shares, total_value)
+    };
+    if shares.is_zero() {
+        return Err(StdError::generic_err(
+            "The deposit is too small to mint shares",
+        ));
+    }
+    Ok(shares)
+}
+
+/// ## Description
+/// Returns the assets paid out for withdrawn shares, rounded down in favour of the vault.
+/// ## Params
+/// * **shares** is the amount of withdrawn shares.
+///
+/// * **total_shares** is the total supply of the share token.
+///
+/// * **assets** are the assets held by the vault.
+pub fn compute_withdrawn_assets(
+    shares: Uint128,
+    total_shares: Uint128,
+    assets: &[Asset],
+) -> StdResult<Vec<Asset>> {
+    if shares > total_shares {
+        return Err(StdError::generic_err(
+            "Can't withdraw more than the total shares",
+        ));
+    }
+    Ok(assets
+        .iter()
+        .map(|asset| Asset {
+            info: asset.info.clone(),
+            amount: asset.amount.multiply_ratio(shares, total_shares),
+        })
+        .collect())
+}
+
+/// ## Description
+/// Returns the performance fee in the second asset of the pair. The fee is charged only on the
+/// gain of the share price above the high water mark.
+/// ## Params
+/// * **share_price** is the current value of one share.
+///
+/// * **high_water_mark** is the highest share price the fee was charged at.
+///
+/// * **total_shares** is the total supply of the share token.
+///
+/// * **performance_fee_bps** is the fee charged on the gains of the vault in bps.
+pub fn compute_performance_fee(
+    share_price: Decimal,
+    high_water_mark: Decimal,
+    total_shares: Uint128,
+    performance_fee_bps: u16,
+) -> Uint128 {
+    if share_price <= high_water_mark {
+        return Uint128::zero();
+    }
+    let gain = total_shares * share_price - total_shares * high_water_mark;
+    gain.multiply_ratio(performance_fee_bps, 10_000u16)
+}
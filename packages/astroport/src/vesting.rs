@@ -1,9 +1,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Order, Uint128};
+use cosmwasm_std::{Addr, Decimal, Order, StdError, StdResult, Uint128};
 use cw20::Cw20ReceiveMsg;
 
+/// The maximum number of schedules of a single vesting account
+pub const MAX_VESTING_SCHEDULES: usize = 16;
+
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -12,6 +15,35 @@ pub struct InstantiateMsg {
     pub owner: String,
     /// the token address
     pub token_addr: String,
+    /// the CW721 contract minting transferable receipts of schedules, the vesting contract must be
+    /// its minter. Schedules are soulbound if not set
+    pub receipt_nft: Option<String>,
+}
+
+/// ## Description
+/// Returns the identifier of the receipt NFT of a vesting schedule.
+/// ## Params
+/// * **account** is the vesting account the schedule was created for.
+///
+/// * **schedule_id** is the schedule identifier.
+pub fn receipt_token_id(account: &str, schedule_id: u64) -> String {
+    format!("{}/{}", account, schedule_id)
+}
+
+/// ## Description
+/// Returns the vesting account and the schedule identifier of a receipt NFT identifier created
+/// by [`receipt_token_id`].
+/// ## Params
+/// * **token_id** is the identifier of the receipt NFT.
+pub fn parse_receipt_token_id(token_id: &str) -> StdResult<(String, u64)> {
+    token_id
+        .rsplit_once('/')
+        .and_then(|(account, schedule_id)| {
+            let schedule_id = schedule_id.parse().ok()?;
+            Some((account.to_string(), schedule_id))
+        })
+        .filter(|(account, _)| !account.is_empty())
+        .ok_or_else(|| StdError::generic_err(format!("Invalid receipt token id {}", token_id)))
 }
 
 /// ## Description
@@ -19,7 +51,8 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    /// Claims the amount from Vesting for transfer to the recipient
+    /// Claims the amount from Vesting for transfer to the recipient. The schedules with a receipt
+    /// NFT can only be claimed with [`ExecuteMsg::ClaimReceipt`]
     Claim {
         /// the recipient of claim
         recipient: Option<String>,
@@ -30,6 +63,68 @@ pub enum ExecuteMsg {
     /// template.
     Receive(Cw20ReceiveMsg),
     /// ## Description
+    /// Registers vesting accounts funded by the surplus of the contract balance over the
+    /// registered obligations, see [`assert_solvency`]
+    /// ## Executor
+    /// Only owner can execute it
+    RegisterVestingAccounts {
+        vesting_accounts: Vec<VestingAccount>,
+    },
+    /// ## Description
+    /// Freezes the vesting of a single schedule of an account, e.g. during a dispute
+    /// ## Executor
+    /// Only owner can execute it
+    PauseSchedule {
+        /// the vesting account
+        account: String,
+        /// the schedule identifier
+        schedule_id: u64,
+    },
+    /// ## Description
+    /// Resumes the vesting of a paused schedule. With `retroactive` the schedule vests as if it
+    /// was never paused, otherwise its remaining vesting is postponed by the paused period
+    /// ## Executor
+    /// Only owner can execute it
+    ResumeSchedule {
+        /// the vesting account
+        account: String,
+        /// the schedule identifier
+        schedule_id: u64,
+        /// whether the amount accrued during the pause is released
+        retroactive: bool,
+    },
+    /// ## Description
+    /// Revokes a revocable vesting account. The schedules stop vesting at the current time, the
+    /// vested amount stays claimable by the account and the unvested amount is sent to the owner
+    /// ## Executor
+    /// Only owner can execute it
+    Revoke {
+        /// the vesting account
+        account: String,
+    },
+    /// ## Description
+    /// Claims the vested amount of the schedule represented by a receipt NFT. Once a receipt was
+    /// minted for a schedule, only the holder of the receipt can claim it, and the claim is
+    /// booked against that schedule only, see [`VestingInfo::release`]
+    /// ## Executor
+    /// Only the owner of the receipt NFT can execute it
+    ClaimReceipt {
+        /// the identifier of the receipt NFT, see [`receipt_token_id`]
+        token_id: String,
+        /// the recipient of claim
+        recipient: Option<String>,
+        /// the amount of claim
+        amount: Option<Uint128>,
+    },
+    /// ## Description
+    /// Sets or removes the CW721 contract minting receipts. Existing receipts stay valid
+    /// ## Executor
+    /// Only owner can execute it
+    SetReceiptNft {
+        /// the CW721 contract, [`None`] keeps new schedules soulbound
+        receipt_nft: Option<String>,
+    },
+    /// ## Description
     /// Creates a request to change ownership
     /// ## Executor
     /// Only owner can execute it
@@ -59,6 +154,14 @@ pub struct VestingAccount {
     pub address: String,
     /// the schedules of account
     pub schedules: Vec<VestingSchedule>,
+    /// whether the owner can revoke the account, ignored when schedules are appended to an
+    /// existing account
+    #[serde(default)]
+    pub revocable: bool,
+    /// whether a transferable receipt NFT is minted for each new schedule, requires the
+    /// receipt NFT contract to be set
+    #[serde(default)]
+    pub mint_receipts: bool,
 }
 
 /// ## Description
@@ -69,6 +172,25 @@ pub struct VestingInfo {
     pub schedules: Vec<VestingSchedule>,
     /// the released amount
     pub released_amount: Uint128,
+    /// the released amount of each schedule by identifier, see [`VestingInfo::claimed_amounts`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub released_amounts: Vec<Uint128>,
+    /// whether the owner can revoke the account
+    #[serde(default)]
+    pub revocable: bool,
+    /// the revocation of the account, set only by [`ExecuteMsg::Revoke`]
+    #[serde(default)]
+    pub revocation: Option<Revocation>,
+}
+
+/// ## Description
+/// This structure describes the revocation of a vesting account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Revocation {
+    /// the time the account was revoked, the schedules don't vest after it
+    pub time: u64,
+    /// the unvested amount returned to the owner
+    pub revoked_amount: Uint128,
 }
 
 /// ## Description
@@ -79,6 +201,336 @@ pub struct VestingSchedule {
     pub start_point: VestingSchedulePoint,
     /// the end point of schedule
     pub end_point: Option<VestingSchedulePoint>,
+    /// the pauses of the schedule in chronological order, set only by [`ExecuteMsg::PauseSchedule`],
+    /// schedules registered with pauses are rejected by [`VestingInfo::add_schedules`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pauses: Vec<SchedulePause>,
+}
+
+/// ## Description
+/// This structure describes a pause of a vesting schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchedulePause {
+    /// the time the schedule was paused
+    pub start: u64,
+    /// the time the schedule was resumed, [`None`] while the schedule is paused
+    pub end: Option<u64>,
+    /// whether the amount accrued during the pause was released on resume
+    pub retroactive: bool,
+}
+
+impl VestingInfo {
+    /// ## Description
+    /// Returns the total amount vested by all schedules at the specified time.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the timestamp at which the vested amount is calculated.
+    pub fn vested_amount(&self, time: u64) -> StdResult<Uint128> {
+        let time = self.vesting_time(time);
+        self.schedules
+            .iter()
+            .try_fold(Uint128::zero(), |acc, schedule| {
+                Ok(acc.checked_add(schedule.vested_amount(time)?)?)
+            })
+    }
+
+    /// ## Description
+    /// Returns the amount which can be claimed at the specified time.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the timestamp at which the claimable amount is calculated.
+    pub fn claimable_amount(&self, time: u64) -> StdResult<Uint128> {
+        Ok(self
+            .vested_amount(time)?
+            .saturating_sub(self.released_amount))
+    }
+
+    /// ## Description
+    /// Appends new schedules to the account. The identifier of a schedule is its index, so the
+    /// identifiers of existing schedules never change. Returns an [`Err`] if the account would
+    /// have more than [`MAX_VESTING_SCHEDULES`] schedules or a new schedule has pauses, as they
+    /// are set only by [`ExecuteMsg::PauseSchedule`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **schedules** are the schedules to add.
+    pub fn add_schedules(&mut self, schedules: Vec<VestingSchedule>) -> StdResult<()> {
+        if self.revocation.is_some() {
+            return Err(StdError::generic_err(
+                "Schedules can't be added to a revoked account",
+            ));
+        }
+        if self.schedules.len() + schedules.len() > MAX_VESTING_SCHEDULES {
+            return Err(StdError::generic_err(format!(
+                "A vesting account can't have more than {} schedules",
+                MAX_VESTING_SCHEDULES
+            )));
+        }
+        if schedules.iter().any(|schedule| !schedule.pauses.is_empty()) {
+            return Err(StdError::generic_err("New schedules can't have pauses"));
+        }
+        self.schedules.extend(schedules);
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the amount the contract still owes to the account, i.e. the total amount of the
+    /// schedules minus the released and the revoked amounts.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    pub fn outstanding_amount(&self) -> StdResult<Uint128> {
+        let total_amount = self
+            .schedules
+            .iter()
+            .try_fold(Uint128::zero(), |acc, schedule| {
+                acc.checked_add(schedule.total_amount())
+            })?;
+        let revoked_amount = self
+            .revocation
+            .as_ref()
+            .map(|revocation| revocation.revoked_amount)
+            .unwrap_or_default();
+        Ok(total_amount
+            .saturating_sub(self.released_amount)
+            .saturating_sub(revoked_amount))
+    }
+
+    /// ## Description
+    /// Revokes the account and returns the unvested amount which is returned to the owner.
+    /// Returns an [`Err`] if the account is not revocable or was already revoked.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn revoke(&mut self, time: u64) -> StdResult<Uint128> {
+        if !self.revocable {
+            return Err(StdError::generic_err(
+                "The vesting account is not revocable",
+            ));
+        }
+        if self.revocation.is_some() {
+            return Err(StdError::generic_err(
+                "The vesting account is already revoked",
+            ));
+        }
+        let total_amount = self
+            .schedules
+            .iter()
+            .try_fold(Uint128::zero(), |acc, schedule| {
+                acc.checked_add(schedule.total_amount())
+            })?;
+        let revoked_amount = total_amount.checked_sub(self.vested_amount(time)?)?;
+        self.revocation = Some(Revocation {
+            time,
+            revoked_amount,
+        });
+        Ok(revoked_amount)
+    }
+
+    /// ## Description
+    /// Returns the claimed amount of each schedule. The released amounts are booked per schedule
+    /// by [`VestingInfo::release`]. The amount released before they were tracked is attributed
+    /// to the schedules in the order of their identifiers, up to the amount each one has vested.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn claimed_amounts(&self, time: u64) -> StdResult<Vec<Uint128>> {
+        let time = self.vesting_time(time);
+        let booked = self
+            .released_amounts
+            .iter()
+            .try_fold(Uint128::zero(), |acc, amount| acc.checked_add(*amount))?;
+        let mut unattributed = self.released_amount.saturating_sub(booked);
+        self.schedules
+            .iter()
+            .enumerate()
+            .map(|(id, schedule)| {
+                let released = self.released_amounts.get(id).copied().unwrap_or_default();
+                let legacy = std::cmp::min(
+                    schedule.vested_amount(time)?.saturating_sub(released),
+                    unattributed,
+                );
+                unattributed -= legacy;
+                Ok(released + legacy)
+            })
+            .collect()
+    }
+
+    /// ## Description
+    /// Returns the amount of each schedule which can be claimed at the specified time.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the timestamp at which the claimable amounts are calculated.
+    pub fn claimable_amounts(&self, time: u64) -> StdResult<Vec<Uint128>> {
+        let claimed_amounts = self.claimed_amounts(time)?;
+        let vesting_time = self.vesting_time(time);
+        self.schedules
+            .iter()
+            .zip(claimed_amounts)
+            .map(|(schedule, claimed)| {
+                Ok(schedule
+                    .vested_amount(vesting_time)?
+                    .saturating_sub(claimed))
+            })
+            .collect()
+    }
+
+    /// ## Description
+    /// Books a released amount against the specified schedules in the given order, each up to
+    /// its claimable amount. [`ExecuteMsg::ClaimReceipt`] releases only the schedule of the
+    /// receipt, [`ExecuteMsg::Claim`] only the schedules without a receipt. Returns an [`Err`]
+    /// if the schedules can't cover the amount or a schedule doesn't exist.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **schedule_ids** are the identifiers of the released schedules.
+    ///
+    /// * **amount** is the released amount.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn release(&mut self, schedule_ids: &[u64], amount: Uint128, time: u64) -> StdResult<()> {
+        let claimable_amounts = self.claimable_amounts(time)?;
+        // the amounts attributed to the schedules are booked, so later releases can't move them
+        self.released_amounts = self.claimed_amounts(time)?;
+        let mut remaining = amount;
+        for schedule_id in schedule_ids {
+            let claimable = claimable_amounts
+                .get(*schedule_id as usize)
+                .copied()
+                .ok_or_else(|| {
+                    StdError::generic_err(format!("Schedule {} not found", schedule_id))
+                })?;
+            let released = std::cmp::min(claimable, remaining);
+            self.released_amounts[*schedule_id as usize] += released;
+            remaining -= released;
+        }
+        if !remaining.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "The claim amount {} exceeds the claimable amount {}",
+                amount,
+                amount - remaining
+            )));
+        }
+        self.released_amount = self.released_amount.checked_add(amount)?;
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the time the schedules vest at, the revocation time for revoked accounts.
+    fn vesting_time(&self, time: u64) -> u64 {
+        match &self.revocation {
+            Some(revocation) => std::cmp::min(time, revocation.time),
+            None => time,
+        }
+    }
+}
+
+impl VestingSchedule {
+    /// ## Description
+    /// Returns the amount vested by the schedule at the specified time. The amount of the start
+    /// point is unlocked at the start time, the rest is unlocked linearly until the end point.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the timestamp at which the vested amount is calculated.
+    pub fn vested_amount(&self, time: u64) -> StdResult<Uint128> {
+        let time = self.effective_time(time);
+        if time < self.start_point.time {
+            return Ok(Uint128::zero());
+        }
+
+        match &self.end_point {
+            Some(end_point) if time < end_point.time => {
+                let unlocked = end_point
+                    .amount
+                    .checked_sub(self.start_point.amount)?
+                    .multiply_ratio(
+                        time - self.start_point.time,
+                        end_point.time - self.start_point.time,
+                    );
+                Ok(self.start_point.amount.checked_add(unlocked)?)
+            }
+            Some(end_point) => Ok(end_point.amount),
+            None => Ok(self.start_point.amount),
+        }
+    }
+
+    /// ## Description
+    /// Returns the time on the schedule curve at the specified time. Vesting is frozen during
+    /// pauses and the curve is postponed by the pauses which were not resumed retroactively.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    fn effective_time(&self, time: u64) -> u64 {
+        self.pauses
+            .iter()
+            .filter(|pause| time > pause.start)
+            .fold(time, |effective, pause| {
+                let paused = match pause.end {
+                    Some(end) if time >= end && pause.retroactive => 0,
+                    Some(end) if time >= end => end.saturating_sub(pause.start),
+                    _ => time.saturating_sub(pause.start),
+                };
+                effective.saturating_sub(paused)
+            })
+    }
+
+    /// ## Description
+    /// Returns true if the schedule is paused.
+    pub fn is_paused(&self) -> bool {
+        matches!(self.pauses.last(), Some(pause) if pause.end.is_none())
+    }
+
+    /// ## Description
+    /// Pauses the schedule. Returns an [`Err`] if the schedule is already paused.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    pub fn pause(&mut self, time: u64) -> StdResult<()> {
+        if self.is_paused() {
+            return Err(StdError::generic_err("The schedule is already paused"));
+        }
+        self.pauses.push(SchedulePause {
+            start: time,
+            end: None,
+            retroactive: false,
+        });
+        Ok(())
+    }
+
+    /// ## Description
+    /// Resumes the schedule. Returns an [`Err`] if the schedule is not paused.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **time** is the current timestamp.
+    ///
+    /// * **retroactive** is whether the amount accrued during the pause is released.
+    pub fn resume(&mut self, time: u64, retroactive: bool) -> StdResult<()> {
+        match self.pauses.last_mut() {
+            Some(pause) if pause.end.is_none() => {
+                pause.end = Some(time);
+                pause.retroactive = retroactive;
+                Ok(())
+            }
+            _ => Err(StdError::generic_err("The schedule is not paused")),
+        }
+    }
+
+    /// ## Description
+    /// Returns the total amount of the schedule.
+    pub fn total_amount(&self) -> Uint128 {
+        self.end_point
+            .as_ref()
+            .map(|end_point| end_point.amount)
+            .unwrap_or(self.start_point.amount)
+    }
 }
 
 /// ## Description
@@ -117,6 +569,245 @@ pub enum QueryMsg {
         address: String,
     },
     Timestamp {},
+    /// ## Description
+    /// Returns the vested and claimable amounts of the account at the specified time in the
+    /// [`ClaimableAtResponse`] object.
+    ClaimableAt {
+        address: String,
+        timestamp: u64,
+    },
+    /// ## Description
+    /// Returns the schedules of the account and the amounts vested by each of them in the
+    /// [`ScheduleBreakdownResponse`] object.
+    ScheduleBreakdown {
+        address: String,
+    },
+    /// ## Description
+    /// Returns every schedule of the account with its identifier and claimed amount in the
+    /// [`VestingSchedulesResponse`] object.
+    VestingSchedules {
+        address: String,
+    },
+    /// ## Description
+    /// Returns the complete pause history of a schedule in the [`SchedulePausesResponse`] object.
+    SchedulePauses {
+        address: String,
+        schedule_id: u64,
+    },
+    /// ## Description
+    /// Returns the vested and revoked amounts of the account in the [`RevocationInfoResponse`]
+    /// object.
+    RevocationInfo {
+        address: String,
+    },
+    /// ## Description
+    /// Returns the schedule represented by a receipt NFT in the [`ReceiptResponse`] object.
+    Receipt {
+        token_id: String,
+    },
+    /// ## Description
+    /// Returns the registered obligations and how much of them the token balance covers in the
+    /// [`SolvencyResponse`] object.
+    Solvency {},
+    /// ## Description
+    /// Returns the total amount unlocked by all vesting accounts per time bucket between `from`
+    /// and `to` in the [`AggregateUnlocksResponse`] object, see [`UnlockBucket::split`].
+    AggregateUnlocks {
+        from: u64,
+        to: u64,
+        /// the number of buckets, at most [`MAX_UNLOCK_BUCKETS`]
+        buckets: u32,
+    },
+}
+
+/// The maximum number of buckets of the [`QueryMsg::AggregateUnlocks`] query
+pub const MAX_UNLOCK_BUCKETS: u32 = 100;
+
+/// ## Description
+/// This structure describes the amount unlocked after the start and until the end of a bucket.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnlockBucket {
+    /// the start of the bucket, exclusive
+    pub start: u64,
+    /// the end of the bucket, inclusive
+    pub end: u64,
+    /// the unlocked amount
+    pub amount: Uint128,
+}
+
+impl UnlockBucket {
+    /// ## Description
+    /// Splits the period into buckets of equal length with zero amounts, the last bucket ends at
+    /// `to`. Returns an [`Err`] if the period is empty or the number of buckets is zero, above
+    /// [`MAX_UNLOCK_BUCKETS`] or above the length of the period.
+    /// ## Params
+    /// * **from** is the start of the period.
+    ///
+    /// * **to** is the end of the period.
+    ///
+    /// * **buckets** is the number of buckets.
+    pub fn split(from: u64, to: u64, buckets: u32) -> StdResult<Vec<UnlockBucket>> {
+        if from >= to {
+            return Err(StdError::generic_err(
+                "The end of the period must be after its start",
+            ));
+        }
+        if buckets == 0 || buckets > MAX_UNLOCK_BUCKETS {
+            return Err(StdError::generic_err(format!(
+                "The number of buckets must be within [1, {}]",
+                MAX_UNLOCK_BUCKETS
+            )));
+        }
+        let length = (to - from) / buckets as u64;
+        if length == 0 {
+            return Err(StdError::generic_err(
+                "A bucket must be at least one second long",
+            ));
+        }
+        Ok((0..buckets as u64)
+            .map(|i| UnlockBucket {
+                start: from + i * length,
+                end: if i + 1 == buckets as u64 {
+                    to
+                } else {
+                    from + (i + 1) * length
+                },
+                amount: Uint128::zero(),
+            })
+            .collect())
+    }
+
+    /// ## Description
+    /// Adds the amounts unlocked by an account to the buckets.
+    /// ## Params
+    /// * **buckets** are the buckets returned by [`UnlockBucket::split`].
+    ///
+    /// * **info** is the vesting information of the account.
+    pub fn add_unlocks(buckets: &mut [UnlockBucket], info: &VestingInfo) -> StdResult<()> {
+        for bucket in buckets.iter_mut() {
+            let unlocked = info
+                .vested_amount(bucket.end)?
+                .checked_sub(info.vested_amount(bucket.start)?)?;
+            bucket.amount = bucket.amount.checked_add(unlocked)?;
+        }
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregateUnlocksResponse {
+    /// the buckets in chronological order
+    pub buckets: Vec<UnlockBucket>,
+    /// the total amount unlocked during the period
+    pub total_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    /// the amount owed to all vesting accounts, see [`VestingInfo::outstanding_amount`]
+    pub obligations: Uint128,
+    /// the token balance of the contract
+    pub balance: Uint128,
+    /// the balance divided by the obligations, one if there are no obligations
+    pub coverage_ratio: Decimal,
+    /// the balance which is not owed to any account
+    pub surplus: Uint128,
+    /// the obligations which are not covered by the balance
+    pub deficit: Uint128,
+}
+
+impl SolvencyResponse {
+    /// ## Description
+    /// Creates the solvency report of the contract.
+    /// ## Params
+    /// * **obligations** is the amount owed to all vesting accounts.
+    ///
+    /// * **balance** is the token balance of the contract.
+    pub fn new(obligations: Uint128, balance: Uint128) -> Self {
+        let coverage_ratio = if obligations.is_zero() {
+            Decimal::one()
+        } else {
+            Decimal::from_ratio(balance, obligations)
+        };
+        SolvencyResponse {
+            obligations,
+            balance,
+            coverage_ratio,
+            surplus: balance.saturating_sub(obligations),
+            deficit: obligations.saturating_sub(balance),
+        }
+    }
+}
+
+/// ## Description
+/// Returns an [`Err`] if registering new vesting accounts would over-commit the contract, i.e.
+/// the obligations including the new accounts would exceed the token balance.
+/// ## Params
+/// * **obligations** is the amount owed to the registered vesting accounts.
+///
+/// * **new_obligations** is the total amount of the schedules to register.
+///
+/// * **balance** is the token balance of the contract including the received tokens.
+pub fn assert_solvency(
+    obligations: Uint128,
+    new_obligations: Uint128,
+    balance: Uint128,
+) -> StdResult<()> {
+    let required = obligations.checked_add(new_obligations)?;
+    if required > balance {
+        return Err(StdError::generic_err(format!(
+            "The vesting contract is under-funded: {} tokens are required, but the balance is {}",
+            required, balance
+        )));
+    }
+    Ok(())
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReceiptResponse {
+    /// the identifier of the receipt NFT
+    pub token_id: String,
+    /// the vesting account the schedule was created for
+    pub account: Addr,
+    /// the schedule identifier
+    pub schedule_id: u64,
+    /// the amount vested by the schedule and not claimed yet, see
+    /// [`VestingInfo::claimable_amounts`]
+    pub claimable_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RevocationInfoResponse {
+    /// the account address
+    pub address: Addr,
+    /// whether the owner can revoke the account
+    pub revocable: bool,
+    /// the time the account was revoked, [`None`] if it was not revoked
+    pub revoked_at: Option<u64>,
+    /// the amount vested at the current time, or at the revocation time if revoked
+    pub vested_amount: Uint128,
+    /// the unvested amount returned to the owner
+    pub revoked_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchedulePausesResponse {
+    /// the account address
+    pub address: Addr,
+    /// the schedule identifier
+    pub schedule_id: u64,
+    /// the pauses of the schedule in chronological order
+    pub pauses: Vec<SchedulePause>,
 }
 
 /// ## Description
@@ -127,6 +818,9 @@ pub struct ConfigResponse {
     pub owner: Addr,
     /// the token address
     pub token_addr: Addr,
+    /// the CW721 contract minting transferable receipts of schedules
+    #[serde(default)]
+    pub receipt_nft: Option<Addr>,
 }
 
 /// ## Description
@@ -147,6 +841,77 @@ pub struct VestingAccountsResponse {
     pub vesting_accounts: Vec<VestingAccountResponse>,
 }
 
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableAtResponse {
+    /// the account address
+    pub address: Addr,
+    /// the timestamp of the calculation
+    pub timestamp: u64,
+    /// the amount vested by the timestamp
+    pub vested_amount: Uint128,
+    /// the amount which can be claimed at the timestamp
+    pub claimable_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a vesting schedule together with the amount it has vested.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingScheduleBreakdown {
+    /// the points of the schedule curve
+    pub schedule: VestingSchedule,
+    /// the total amount of the schedule
+    pub total_amount: Uint128,
+    /// the amount vested by the schedule at the current time
+    pub vested_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduleBreakdownResponse {
+    /// the account address
+    pub address: Addr,
+    /// the schedules of the account
+    pub schedules: Vec<VestingScheduleBreakdown>,
+    /// the total amount of all schedules
+    pub total_amount: Uint128,
+    /// the amount vested at the current time
+    pub vested_amount: Uint128,
+    /// the amount already claimed
+    pub released_amount: Uint128,
+    /// the amount which can be claimed at the current time
+    pub claimable_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a single vesting schedule of an account.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingScheduleResponse {
+    /// the schedule identifier
+    pub id: u64,
+    /// the schedule
+    pub schedule: VestingSchedule,
+    /// the amount vested by the schedule at the current time
+    pub vested_amount: Uint128,
+    /// the amount of the schedule already claimed
+    pub claimed_amount: Uint128,
+    /// whether the schedule is paused
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedulesResponse {
+    /// the account address
+    pub address: Addr,
+    /// the schedules of the account
+    pub schedules: Vec<VestingScheduleResponse>,
+}
+
 /// ## Description
 /// This enum describes the type of sort
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -183,8 +948,12 @@ pub struct MigrateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    /// Register vesting accounts
+    /// Register vesting accounts. The schedules of an existing account are appended to its
+    /// current schedules, at most [`MAX_VESTING_SCHEDULES`] per account. The received tokens
+    /// together with the surplus of the balance must cover the new schedules, see [`assert_solvency`]
     RegisterVestingAccounts {
         vesting_accounts: Vec<VestingAccount>,
     },
+    /// Adds the received tokens to the balance covering the registered obligations
+    TopUp {},
 }
@@ -0,0 +1,321 @@
+//! Property tests asserting the invariants of the AMM math in [`astroport::math`].
+
+use astroport::math::{
+    compute_d, compute_offer_amount, compute_share, compute_stable_swap, compute_swap,
+    compute_withdraw,
+};
+use cosmwasm_std::{Decimal, Uint128, Uint256};
+use proptest::prelude::*;
+
+const MIN_POOL: u128 = 1_000_000;
+const MAX_POOL: u128 = 1_000_000_000_000_000_000;
+
+fn pool() -> impl Strategy<Value = Uint128> {
+    (MIN_POOL..MAX_POOL).prop_map(Uint128::new)
+}
+
+fn amount() -> impl Strategy<Value = Uint128> {
+    (1u128..MAX_POOL / 10).prop_map(Uint128::new)
+}
+
+fn commission_rate() -> impl Strategy<Value = Decimal> {
+    (0u64..1000u64).prop_map(Decimal::permille)
+}
+
+fn k(x: Uint128, y: Uint128) -> Uint256 {
+    Uint256::from(x) * Uint256::from(y)
+}
+
+/// An operation of a sequence applied to an xyk pool
+#[derive(Clone, Debug)]
+enum Op {
+    /// swaps `amount` of the first asset if `reverse` is false, of the second one otherwise
+    Swap { reverse: bool, amount: Uint128 },
+    /// provides the amounts of both assets
+    Provide { amounts: [Uint128; 2] },
+    /// withdraws the percent of the LP token supply
+    Withdraw { percent: u64 },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (any::<bool>(), amount()).prop_map(|(reverse, amount)| Op::Swap { reverse, amount }),
+        (amount(), amount()).prop_map(|(a, b)| Op::Provide { amounts: [a, b] }),
+        (1u64..100u64).prop_map(|percent| Op::Withdraw { percent }),
+    ]
+}
+
+/// The state of an xyk pool
+#[derive(Clone, Debug)]
+struct Pool {
+    pools: [Uint128; 2],
+    total_share: Uint128,
+}
+
+impl Pool {
+    /// Applies the operation, ignoring the operations rejected by the math
+    fn apply(&mut self, op: &Op, rate: Decimal) {
+        match op {
+            Op::Swap { reverse, amount } => {
+                let (offer, ask) = if *reverse { (1, 0) } else { (0, 1) };
+                if let Ok((return_amount, _, _)) =
+                    compute_swap(self.pools[offer], self.pools[ask], *amount, rate)
+                {
+                    self.pools[offer] += *amount;
+                    self.pools[ask] -= return_amount;
+                }
+            }
+            Op::Provide { amounts } => {
+                let share = compute_share(self.pools, *amounts, self.total_share).unwrap();
+                self.pools = [self.pools[0] + amounts[0], self.pools[1] + amounts[1]];
+                self.total_share += share;
+            }
+            Op::Withdraw { percent } => {
+                let share = self.total_share.multiply_ratio(*percent, 100u64);
+                let withdrawn = compute_withdraw(self.pools, share, self.total_share).unwrap();
+                self.pools = [self.pools[0] - withdrawn[0], self.pools[1] - withdrawn[1]];
+                self.total_share -= share;
+            }
+        }
+    }
+
+    /// Returns true if the invariant per LP share squared didn't decrease since `before`
+    fn value_per_share_kept(&self, before: &Pool) -> bool {
+        let ts = Uint256::from(self.total_share);
+        let ts_before = Uint256::from(before.total_share);
+        k(self.pools[0], self.pools[1]) * ts_before * ts_before
+            >= k(before.pools[0], before.pools[1]) * ts * ts
+    }
+}
+
+proptest! {
+    #[test]
+    fn xyk_sequences_never_dilute_shares(
+        pool_0 in pool(),
+        pool_1 in pool(),
+        total_share in pool(),
+        ops in prop::collection::vec(op(), 1..20),
+        rate in commission_rate(),
+    ) {
+        let mut state = Pool {
+            pools: [pool_0, pool_1],
+            total_share,
+        };
+        for op in ops.iter() {
+            let before = state.clone();
+            state.apply(op, rate);
+            prop_assume!(!state.total_share.is_zero());
+            // no operation moves value away from the remaining LP shares
+            prop_assert!(state.value_per_share_kept(&before), "{:?} diluted {:?}", op, before);
+        }
+    }
+
+    #[test]
+    fn xyk_sequence_round_trip_yields_no_profit(
+        pool_0 in pool(),
+        pool_1 in pool(),
+        total_share in pool(),
+        ops in prop::collection::vec(op(), 0..10),
+        deposit in (amount(), amount()),
+        rate in commission_rate(),
+    ) {
+        let mut state = Pool {
+            pools: [pool_0, pool_1],
+            total_share,
+        };
+        for op in ops.iter() {
+            state.apply(op, rate);
+        }
+        prop_assume!(!state.total_share.is_zero());
+
+        // providing and withdrawing the minted share at any point returns at most the deposit
+        let deposit = [deposit.0, deposit.1];
+        let share = compute_share(state.pools, deposit, state.total_share).unwrap();
+        let withdrawn = compute_withdraw(
+            [state.pools[0] + deposit[0], state.pools[1] + deposit[1]],
+            share,
+            state.total_share + share,
+        ).unwrap();
+        prop_assert!(withdrawn[0] <= deposit[0]);
+        prop_assert!(withdrawn[1] <= deposit[1]);
+    }
+
+    #[test]
+    fn stable_swap_sequences_never_decrease_invariant(
+        amp in 1u64..1000u64,
+        offer_pool in pool(),
+        swaps in prop::collection::vec((any::<bool>(), amount()), 1..10),
+        rate in commission_rate(),
+    ) {
+        let mut pools = [offer_pool, offer_pool];
+        let mut d = compute_d(amp, pools).unwrap();
+        for (reverse, amount) in swaps {
+            let (offer, ask) = if reverse { (1, 0) } else { (0, 1) };
+            if let Ok((return_amount, _, _)) =
+                compute_stable_swap(amp, pools[offer], pools[ask], amount, rate)
+            {
+                pools[offer] += amount;
+                pools[ask] -= return_amount;
+                let new_d = compute_d(amp, pools).unwrap();
+                prop_assert!(new_d >= d);
+                d = new_d;
+            }
+        }
+    }
+
+    #[test]
+    fn stable_math_rejects_zero_amp(
+        offer_pool in pool(),
+        offer_amount in amount(),
+        rate in commission_rate(),
+    ) {
+        prop_assert!(compute_d(0, [offer_pool, offer_pool]).is_err());
+        prop_assert!(compute_stable_swap(0, offer_pool, offer_pool, offer_amount, rate).is_err());
+    }
+
+    #[test]
+    fn xyk_swap_never_decreases_invariant(
+        offer_pool in pool(),
+        ask_pool in pool(),
+        offer_amount in amount(),
+        rate in commission_rate(),
+    ) {
+        let (return_amount, _, _) = compute_swap(offer_pool, ask_pool, offer_amount, rate).unwrap();
+        prop_assert!(return_amount < ask_pool);
+        prop_assert!(
+            k(offer_pool + offer_amount, ask_pool - return_amount) >= k(offer_pool, ask_pool)
+        );
+    }
+
+    #[test]
+    fn xyk_swap_fee_accounting_closes(
+        offer_pool in pool(),
+        ask_pool in pool(),
+        offer_amount in amount(),
+        rate in commission_rate(),
+    ) {
+        let (return_amount, spread_amount, commission_amount) =
+            compute_swap(offer_pool, ask_pool, offer_amount, rate).unwrap();
+        let before_commission = return_amount + commission_amount;
+        prop_assert_eq!(commission_amount, before_commission * rate);
+
+        // the spot price output splits exactly into the return, the commission and the spread
+        let spot_output = offer_amount.multiply_ratio(ask_pool, offer_pool);
+        prop_assert_eq!(
+            spread_amount,
+            spot_output.saturating_sub(before_commission)
+        );
+    }
+
+    #[test]
+    fn xyk_round_trip_yields_no_profit(
+        offer_pool in pool(),
+        ask_pool in pool(),
+        offer_amount in amount(),
+        rate in commission_rate(),
+    ) {
+        let (return_amount, _, commission_amount) =
+            compute_swap(offer_pool, ask_pool, offer_amount, rate).unwrap();
+        prop_assume!(!return_amount.is_zero());
+
+        let new_offer_pool = offer_pool + offer_amount;
+        let new_ask_pool = ask_pool - return_amount;
+        let (round_trip_amount, _, _) = compute_swap(
+            new_ask_pool,
+            new_offer_pool,
+            return_amount,
+            rate,
+        ).unwrap();
+        // swapping the output back never returns more than the original offer
+        prop_assert!(round_trip_amount <= offer_amount);
+        // and the commission of a nonzero fee rate makes the round trip a strict loss
+        if !commission_amount.is_zero() {
+            prop_assert!(round_trip_amount < offer_amount);
+        }
+    }
+
+    #[test]
+    fn xyk_reverse_simulation_covers_ask_amount(
+        offer_pool in pool(),
+        ask_pool in pool(),
+        ask_amount in amount(),
+        rate in (0u64..500u64).prop_map(Decimal::permille),
+    ) {
+        if let Ok((offer_amount, _, _)) = compute_offer_amount(offer_pool, ask_pool, ask_amount, rate) {
+            let (return_amount, _, _) = compute_swap(offer_pool, ask_pool, offer_amount, rate).unwrap();
+            prop_assert!(return_amount >= ask_amount);
+        }
+    }
+
+    #[test]
+    fn provide_then_withdraw_yields_no_profit(
+        pool_0 in pool(),
+        pool_1 in pool(),
+        deposit_0 in amount(),
+        deposit_1 in amount(),
+        total_share in pool(),
+    ) {
+        let share = compute_share([pool_0, pool_1], [deposit_0, deposit_1], total_share).unwrap();
+        let withdrawn = compute_withdraw(
+            [pool_0 + deposit_0, pool_1 + deposit_1],
+            share,
+            total_share + share,
+        ).unwrap();
+        prop_assert!(withdrawn[0] <= deposit_0);
+        prop_assert!(withdrawn[1] <= deposit_1);
+    }
+
+    #[test]
+    fn share_is_monotonic_in_deposit(
+        pool_0 in pool(),
+        pool_1 in pool(),
+        deposit_0 in amount(),
+        deposit_1 in amount(),
+        extra in amount(),
+        total_share in pool(),
+    ) {
+        let pools = [pool_0, pool_1];
+        let share = compute_share(pools, [deposit_0, deposit_1], total_share).unwrap();
+        let bigger_share =
+            compute_share(pools, [deposit_0 + extra, deposit_1 + extra], total_share).unwrap();
+        prop_assert!(bigger_share >= share);
+    }
+
+    #[test]
+    fn stable_swap_never_decreases_invariant(
+        amp in 1u64..1000u64,
+        offer_pool in pool(),
+        offer_amount in amount(),
+        rate in commission_rate(),
+    ) {
+        // stable pools are balanced within an order of magnitude
+        let ask_pool = offer_pool + offer_pool.multiply_ratio(3u128, 7u128);
+        let d = compute_d(amp, [offer_pool, ask_pool]).unwrap();
+        let (return_amount, _, _) =
+            compute_stable_swap(amp, offer_pool, ask_pool, offer_amount, rate).unwrap();
+        let new_d = compute_d(amp, [offer_pool + offer_amount, ask_pool - return_amount]).unwrap();
+        prop_assert!(new_d >= d);
+    }
+
+    #[test]
+    fn stable_round_trip_yields_no_profit(
+        amp in 1u64..1000u64,
+        offer_pool in pool(),
+        offer_amount in amount(),
+        rate in commission_rate(),
+    ) {
+        let ask_pool = offer_pool;
+        let (return_amount, _, _) =
+            compute_stable_swap(amp, offer_pool, ask_pool, offer_amount, rate).unwrap();
+        prop_assume!(!return_amount.is_zero());
+
+        let (round_trip_amount, _, _) = compute_stable_swap(
+            amp,
+            ask_pool - return_amount,
+            offer_pool + offer_amount,
+            return_amount,
+            rate,
+        ).unwrap();
+        prop_assert!(round_trip_amount <= offer_amount);
+    }
+}
@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+
+set -e
+set -o pipefail
+
+projectPath=$(cd "$(dirname "${0}")" && cd ../ && pwd)
+
+# The maximum size of a contract accepted by the chains, in bytes
+maxWasmSize=${MAX_WASM_SIZE:-819200}
+
+cd "$projectPath/artifacts"
+
+# The reference checksums of the release are committed, the artifacts must reproduce them
+if [[ ! -f checksums.txt ]]; then
+  echo "artifacts/checksums.txt is missing, commit the reference checksums of the release"
+  exit 1
+fi
+
+# With --rebuild the contracts are built again and compared with the reference, the optimizer
+# overwrites checksums.txt so the reference is kept aside
+if [[ "${1:-}" == "--rebuild" ]]; then
+  reference=$(mktemp)
+  trap 'cp "$reference" checksums.txt && rm -f "$reference"' EXIT
+  cp checksums.txt "$reference"
+  (cd "$projectPath" && ./build_release.sh)
+  sha256sum -c "$reference"
+else
+  sha256sum -c checksums.txt
+fi
+
+for wasm in *.wasm; do
+  size=$(wc -c < "$wasm")
+  if [[ "$size" -gt "$maxWasmSize" ]]; then
+    echo "$wasm is $size bytes, more than the limit of $maxWasmSize bytes"
+    exit 1
+  fi
+done
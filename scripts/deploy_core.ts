@@ -140,6 +140,7 @@ async function uploadAndInitRouter(terra: LCDClient, wallet: any) {
             join(ARTIFACTS_PATH, 'astroport_router.wasm'),
             {
                 astroport_factory: network.factoryAddress,
+                owner: network.multisigAddress,
             },
         )
         network.routerAddress = resp.shift()
@@ -116,7 +116,8 @@ async function main() {
 
             let resp = await deployContract(terra, wallet, network.multisigAddress, join(ARTIFACTS_PATH, 'astroport_oracle.wasm'), {
                 factory_contract: network.factoryAddress,
-                asset_infos: pool.assetInfos
+                asset_infos: pool.assetInfos,
+                owner: network.multisigAddress
             })
             network[pool_oracle_key] = resp.shift();
 